@@ -1,4 +1,9 @@
-use std::{fmt, ops::Range, sync::Arc};
+use std::{
+    fmt::{self, Display},
+    ops::Range,
+    str::FromStr,
+    sync::Arc,
+};
 
 use crate::{
     builtins::{self, bool},
@@ -30,6 +35,62 @@ impl ModuleKind {
     }
 }
 
+/// A version of the language. Each edition may introduce changes which would break programs
+/// written for an earlier one, so projects declare which edition they're written for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Edition {
+    /// The language as first released. Projects which don't declare an edition use this one.
+    V2022,
+    /// `assert` is no longer a synonym of `expect`.
+    V2023,
+}
+
+impl Default for Edition {
+    fn default() -> Self {
+        Edition::V2022
+    }
+}
+
+impl Edition {
+    pub const SUPPORTED: [Edition; 2] = [Edition::V2022, Edition::V2023];
+
+    /// The most recent edition, for new projects.
+    pub fn latest() -> Self {
+        Edition::V2023
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Edition::V2022 => "2022",
+            Edition::V2023 => "2023",
+        }
+    }
+}
+
+impl FromStr for Edition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Edition::SUPPORTED
+            .into_iter()
+            .find(|edition| edition.as_str() == s)
+            .ok_or_else(|| {
+                format!(
+                    "Unsupported edition '{s}', supported editions are: {}.",
+                    Edition::SUPPORTED
+                        .map(|edition| edition.as_str())
+                        .join(", ")
+                )
+            })
+    }
+}
+
+impl Display for Edition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Module<Info, Definitions> {
     pub name: String,
@@ -49,7 +110,9 @@ impl<Info, Definitions> Module<Info, Definitions> {
     }
 }
 
-impl UntypedModule {
+impl<Info, T, Expr, ConstantRecordTag, PackageName>
+    Module<Info, Definition<T, Expr, ConstantRecordTag, PackageName>>
+{
     pub fn dependencies(&self) -> Vec<(String, Span)> {
         self.definitions()
             .flat_map(|def| {
@@ -80,6 +143,11 @@ pub struct Function<T, Expr> {
     pub return_annotation: Option<Annotation>,
     pub return_type: T,
     pub end_position: usize,
+    /// Whether a test is expected to fail, as declared with `fail` after its arguments.
+    /// Always false for functions.
+    pub can_error: bool,
+    /// The `describe` block a test is declared in, if any. Always none for functions.
+    pub group: Option<String>,
 }
 
 pub type TypedTypeAlias = TypeAlias<Arc<Type>>;
@@ -89,6 +157,100 @@ impl TypedFunction {
     pub fn test_hint(&self) -> Option<(BinOp, Box<TypedExpr>, Box<TypedExpr>)> {
         do_test_hint(&self.body)
     }
+
+    /// Whether a test checks something: it ends with a comparison (see [`Self::test_hint`]),
+    /// compares values somewhere along the way, or `expect`s a pattern. Tests expected to fail
+    /// always assert something.
+    pub fn has_assertion(&self) -> bool {
+        self.can_error || self.test_hint().is_some() || contains_comparison(&self.body)
+    }
+}
+
+fn is_comparison(name: &BinOp) -> bool {
+    !matches!(
+        name,
+        BinOp::And
+            | BinOp::Or
+            | BinOp::AddInt
+            | BinOp::SubInt
+            | BinOp::MultInt
+            | BinOp::DivInt
+            | BinOp::ModInt
+    )
+}
+
+fn contains_comparison(expr: &TypedExpr) -> bool {
+    match expr {
+        TypedExpr::BinOp {
+            name, left, right, ..
+        } => is_comparison(name) || contains_comparison(left) || contains_comparison(right),
+        TypedExpr::Sequence { expressions, .. } | TypedExpr::Pipeline { expressions, .. } => {
+            expressions.iter().any(contains_comparison)
+        }
+        TypedExpr::Fn { body, .. } => contains_comparison(body),
+        TypedExpr::List { elements, tail, .. } => {
+            elements.iter().any(contains_comparison)
+                || tail.as_deref().map_or(false, contains_comparison)
+        }
+        TypedExpr::Call { fun, args, .. } => {
+            contains_comparison(fun) || args.iter().any(|arg| contains_comparison(&arg.value))
+        }
+        TypedExpr::Assignment { value, kind, .. } => {
+            matches!(kind, AssignmentKind::Expect) || contains_comparison(value)
+        }
+        TypedExpr::Trace { then, .. } => contains_comparison(then),
+        TypedExpr::When {
+            subjects, clauses, ..
+        } => {
+            subjects.iter().any(contains_comparison)
+                || clauses.iter().any(|clause| {
+                    clause
+                        .guard
+                        .as_ref()
+                        .map_or(false, guard_contains_comparison)
+                        || contains_comparison(&clause.then)
+                })
+        }
+        TypedExpr::If {
+            branches,
+            final_else,
+            ..
+        } => {
+            branches.iter().any(|branch| {
+                contains_comparison(&branch.condition) || contains_comparison(&branch.body)
+            }) || contains_comparison(final_else)
+        }
+        TypedExpr::RecordAccess { record, .. } => contains_comparison(record),
+        TypedExpr::Tuple { elems, .. } => elems.iter().any(contains_comparison),
+        TypedExpr::TupleIndex { tuple, .. } => contains_comparison(tuple),
+        TypedExpr::RecordUpdate { spread, args, .. } => {
+            contains_comparison(spread) || args.iter().any(|arg| contains_comparison(&arg.value))
+        }
+        TypedExpr::UnOp { value, .. } => contains_comparison(value),
+        TypedExpr::Int { .. }
+        | TypedExpr::String { .. }
+        | TypedExpr::ByteArray { .. }
+        | TypedExpr::Var { .. }
+        | TypedExpr::ModuleSelect { .. }
+        | TypedExpr::Todo { .. }
+        | TypedExpr::ErrorTerm { .. } => false,
+    }
+}
+
+fn guard_contains_comparison<A, B>(guard: &ClauseGuard<A, B>) -> bool {
+    match guard {
+        ClauseGuard::Equals { .. }
+        | ClauseGuard::NotEquals { .. }
+        | ClauseGuard::GtInt { .. }
+        | ClauseGuard::GtEqInt { .. }
+        | ClauseGuard::LtInt { .. }
+        | ClauseGuard::LtEqInt { .. } => true,
+        ClauseGuard::Not { value, .. } => guard_contains_comparison(value),
+        ClauseGuard::Or { left, right, .. } | ClauseGuard::And { left, right, .. } => {
+            guard_contains_comparison(left) || guard_contains_comparison(right)
+        }
+        ClauseGuard::Var { .. } | ClauseGuard::Constant(_) => false,
+    }
 }
 
 pub fn do_test_hint(body: &TypedExpr) -> Option<(BinOp, Box<TypedExpr>, Box<TypedExpr>)> {
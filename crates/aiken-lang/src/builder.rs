@@ -1,4 +1,7 @@
-use std::{cell::RefCell, rc::Rc, sync::Arc};
+use std::{
+    rc::Rc,
+    sync::{Arc, RwLock},
+};
 
 use indexmap::{IndexMap, IndexSet};
 use itertools::Itertools;
@@ -1145,7 +1148,7 @@ pub fn find_generics_to_replace(tipo: &mut Arc<Type>, generic_types: &IndexMap<u
                 *tipo = t.into();
             }
             Type::Var { tipo: var_tipo } => {
-                let var_type = var_tipo.as_ref().borrow().clone();
+                let var_type = var_tipo.read().unwrap().clone();
                 let var_tipo = match var_type {
                     TypeVar::Unbound { .. } => todo!(),
                     TypeVar::Link { tipo } => {
@@ -1157,7 +1160,7 @@ pub fn find_generics_to_replace(tipo: &mut Arc<Type>, generic_types: &IndexMap<u
                 };
 
                 let t = Type::Var {
-                    tipo: RefCell::from(TypeVar::Link { tipo: var_tipo }).into(),
+                    tipo: RwLock::from(TypeVar::Link { tipo: var_tipo }).into(),
                 };
                 *tipo = t.into()
             }
@@ -1917,7 +1920,7 @@ pub fn lookup_data_type_by_tipo(
             data_types.get(&data_type_key).map(|item| (*item).clone())
         }
         Type::Var { tipo } => {
-            if let TypeVar::Link { tipo } = &*tipo.borrow() {
+            if let TypeVar::Link { tipo } = &*tipo.read().unwrap() {
                 lookup_data_type_by_tipo(data_types, tipo)
             } else {
                 None
@@ -2000,7 +2003,7 @@ pub fn replace_opaque_type(t: &mut Arc<Type>, data_types: IndexMap<DataTypeKey,
                 .into();
             }
             Type::Var { tipo } => {
-                if let TypeVar::Link { tipo } = &*tipo.borrow() {
+                if let TypeVar::Link { tipo } = &*tipo.read().unwrap() {
                     let mut new_type = tipo.clone();
                     replace_opaque_type(&mut new_type, data_types.clone());
                     *t = new_type;
@@ -9,7 +9,10 @@ use crate::{
     IdGenerator,
 };
 use indexmap::IndexMap;
-use std::{cell::RefCell, collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
 use strum::IntoEnumIterator;
 use uplc::builtins::DefaultFunction;
 
@@ -590,6 +593,9 @@ pub fn from_default_function(
         DefaultFunction::SndPair => None,
         DefaultFunction::ChooseList => None,
         DefaultFunction::MkCons => None,
+        // Only available from PlutusV3 onwards, which validators can't target yet.
+        DefaultFunction::Keccak_256 => None,
+        DefaultFunction::Blake2b_224 => None,
     };
 
     info.map(|(tipo, arity)| {
@@ -638,6 +644,8 @@ pub fn prelude_functions(id_gen: &IdGenerator) -> IndexMap<FunctionAccessKey, Ty
             return_annotation: None,
             return_type: bool(),
             end_position: 0,
+            can_error: false,
+            group: None,
             body: TypedExpr::UnOp {
                 location: Span::empty(),
                 tipo: bool(),
@@ -698,6 +706,8 @@ pub fn prelude_functions(id_gen: &IdGenerator) -> IndexMap<FunctionAccessKey, Ty
             return_annotation: None,
             return_type: a_var,
             end_position: 0,
+            can_error: false,
+            group: None,
         },
     );
 
@@ -755,6 +765,8 @@ pub fn prelude_functions(id_gen: &IdGenerator) -> IndexMap<FunctionAccessKey, Ty
             return_annotation: None,
             return_type: a_var,
             end_position: 0,
+            can_error: false,
+            group: None,
         },
     );
 
@@ -868,6 +880,8 @@ pub fn prelude_functions(id_gen: &IdGenerator) -> IndexMap<FunctionAccessKey, Ty
             return_annotation: None,
             return_type,
             end_position: 0,
+            can_error: false,
+            group: None,
         },
     );
 
@@ -999,13 +1013,13 @@ pub fn function(args: Vec<Arc<Type>>, ret: Arc<Type>) -> Arc<Type> {
 }
 
 pub fn generic_var(id: u64) -> Arc<Type> {
-    let tipo = Arc::new(RefCell::new(TypeVar::Generic { id }));
+    let tipo = Arc::new(RwLock::new(TypeVar::Generic { id }));
 
     Arc::new(Type::Var { tipo })
 }
 
 pub fn unbound_var(id: u64) -> Arc<Type> {
-    let tipo = Arc::new(RefCell::new(TypeVar::Unbound { id }));
+    let tipo = Arc::new(RwLock::new(TypeVar::Unbound { id }));
 
     Arc::new(Type::Var { tipo })
 }
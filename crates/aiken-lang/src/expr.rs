@@ -4,12 +4,14 @@ use vec1::Vec1;
 
 use crate::{
     ast::{
-        Annotation, Arg, AssignmentKind, BinOp, CallArg, Clause, DefinitionLocation, IfBranch,
-        Pattern, RecordUpdateSpread, Span, TodoKind, TypedRecordUpdateArg, UnOp,
+        Annotation, Arg, AssignmentKind, BinOp, CallArg, Clause, ClauseGuard, DefinitionLocation,
+        IfBranch, Pattern, RecordUpdateSpread, Span, TodoKind, TypedRecordUpdateArg, UnOp,
         UntypedRecordUpdateArg,
     },
     builtins::void,
-    tipo::{ModuleValueConstructor, PatternConstructor, Type, ValueConstructor},
+    tipo::{
+        ModuleValueConstructor, PatternConstructor, Type, ValueConstructor, ValueConstructorVariant,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -318,6 +320,89 @@ impl TypedExpr {
             | Self::RecordUpdate { location, .. } => *location,
         }
     }
+
+    /// Whether the expression refers, anywhere, to the local variable `name` introduced at
+    /// `defined_at` (e.g. a function argument). Clause guards only know variables by name, so
+    /// any variable called `name` in a guard counts.
+    pub fn references(&self, name: &str, defined_at: Span) -> bool {
+        let refers = |expr: &Self| expr.references(name, defined_at);
+
+        match self {
+            Self::Var {
+                constructor:
+                    ValueConstructor {
+                        variant: ValueConstructorVariant::LocalVariable { location },
+                        ..
+                    },
+                name: var,
+                ..
+            } => var == name && *location == defined_at,
+            Self::Int { .. }
+            | Self::String { .. }
+            | Self::ByteArray { .. }
+            | Self::Var { .. }
+            | Self::ModuleSelect { .. }
+            | Self::Todo { .. }
+            | Self::ErrorTerm { .. } => false,
+            Self::Sequence { expressions, .. } | Self::Pipeline { expressions, .. } => {
+                expressions.iter().any(refers)
+            }
+            Self::Fn { body, .. } => refers(body),
+            Self::List { elements, tail, .. } => {
+                elements.iter().any(refers) || tail.iter().any(|tail| refers(tail))
+            }
+            Self::Call { fun, args, .. } => {
+                refers(fun) || args.iter().any(|arg| refers(&arg.value))
+            }
+            Self::BinOp { left, right, .. } => refers(left) || refers(right),
+            Self::Assignment { value, .. } => refers(value),
+            Self::Trace { then, .. } => refers(then),
+            Self::When {
+                subjects, clauses, ..
+            } => {
+                subjects.iter().any(refers)
+                    || clauses.iter().any(|clause| {
+                        refers(&clause.then)
+                            || clause.guard.iter().any(|guard| guard_mentions(guard, name))
+                    })
+            }
+            Self::If {
+                branches,
+                final_else,
+                ..
+            } => {
+                branches
+                    .iter()
+                    .any(|branch| refers(&branch.condition) || refers(&branch.body))
+                    || refers(final_else)
+            }
+            Self::RecordAccess { record, .. } => refers(record),
+            Self::Tuple { elems, .. } => elems.iter().any(refers),
+            Self::TupleIndex { tuple, .. } => refers(tuple),
+            Self::RecordUpdate { spread, args, .. } => {
+                refers(spread) || args.iter().any(|arg| refers(&arg.value))
+            }
+            Self::UnOp { value, .. } => refers(value),
+        }
+    }
+}
+
+fn guard_mentions<A, B>(guard: &ClauseGuard<A, B>, name: &str) -> bool {
+    match guard {
+        ClauseGuard::Var { name: var, .. } => var == name,
+        ClauseGuard::Constant(_) => false,
+        ClauseGuard::Not { value, .. } => guard_mentions(value, name),
+        ClauseGuard::Equals { left, right, .. }
+        | ClauseGuard::NotEquals { left, right, .. }
+        | ClauseGuard::GtInt { left, right, .. }
+        | ClauseGuard::GtEqInt { left, right, .. }
+        | ClauseGuard::LtInt { left, right, .. }
+        | ClauseGuard::LtEqInt { left, right, .. }
+        | ClauseGuard::Or { left, right, .. }
+        | ClauseGuard::And { left, right, .. } => {
+            guard_mentions(left, name) || guard_mentions(right, name)
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
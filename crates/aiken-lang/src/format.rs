@@ -128,6 +128,7 @@ impl<'comments> Formatter<'comments> {
         let mut has_declarations = false;
         let mut imports = Vec::new();
         let mut declarations = Vec::with_capacity(definitions.len());
+        let mut group: Option<(&'a str, Vec<Document<'a>>)> = None;
 
         for def in definitions {
             let start = def.location().start;
@@ -148,13 +149,39 @@ impl<'comments> Formatter<'comments> {
 
                     let comments = self.pop_comments(start);
 
-                    let declaration = self.documented_definition(def);
+                    let declaration = commented(self.documented_definition(def), comments);
 
-                    declarations.push(commented(declaration, comments))
+                    // Consecutive tests of a same group go back in a single `describe` block.
+                    let def_group = match def {
+                        Definition::Test(Function {
+                            group: Some(group), ..
+                        }) => Some(group.as_str()),
+                        _ => None,
+                    };
+
+                    match (&mut group, def_group) {
+                        (Some((current, tests)), Some(def_group)) if *current == def_group => {
+                            tests.push(declaration)
+                        }
+                        _ => {
+                            if let Some((name, tests)) = group.take() {
+                                declarations.push(describe(name, tests));
+                            }
+
+                            match def_group {
+                                Some(def_group) => group = Some((def_group, vec![declaration])),
+                                None => declarations.push(declaration),
+                            }
+                        }
+                    }
                 }
             }
         }
 
+        if let Some((name, tests)) = group.take() {
+            declarations.push(describe(name, tests));
+        }
+
         let imports = join(
             imports
                 .into_iter()
@@ -233,6 +260,7 @@ impl<'comments> Formatter<'comments> {
                 return_annotation,
                 body,
                 *end_position,
+                false,
             ),
 
             Definition::Test(Function {
@@ -240,8 +268,18 @@ impl<'comments> Formatter<'comments> {
                 arguments: args,
                 body,
                 end_position,
+                can_error,
                 ..
-            }) => self.definition_fn(&false, "test", name, args, &None, body, *end_position),
+            }) => self.definition_fn(
+                &false,
+                "test",
+                name,
+                args,
+                &None,
+                body,
+                *end_position,
+                *can_error,
+            ),
 
             Definition::TypeAlias(TypeAlias {
                 alias,
@@ -518,6 +556,7 @@ impl<'comments> Formatter<'comments> {
         return_annotation: &'a Option<Annotation>,
         body: &'a UntypedExpr,
         end_location: usize,
+        can_error: bool,
     ) -> Document<'a> {
         // Fn name and args
         let head = pub_(*public)
@@ -533,6 +572,12 @@ impl<'comments> Formatter<'comments> {
         }
         .group();
 
+        let head = if can_error {
+            head.append(" fail")
+        } else {
+            head
+        };
+
         // Format body
         let body = self.expr(body);
 
@@ -1803,6 +1848,13 @@ fn printed_comments<'a, 'comments>(
     }
 }
 
+fn describe<'a>(group: &'a str, tests: Vec<Document<'a>>) -> Document<'a> {
+    docvec!["describe \"", group, "\" {"]
+        .append(line().append(join(tests, lines(2))).nest(INDENT))
+        .append(line())
+        .append("}")
+}
+
 fn commented<'a, 'comments>(
     doc: Document<'a>,
     comments: impl IntoIterator<Item = Option<&'comments str>>,
@@ -7,7 +7,7 @@ pub mod lexer;
 pub mod token;
 
 use crate::{
-    ast::{self, BinOp, Span, TodoKind, UnOp, UntypedDefinition, CAPTURE_VARIABLE},
+    ast::{self, BinOp, Edition, Span, TodoKind, UnOp, UntypedDefinition, CAPTURE_VARIABLE},
     expr,
 };
 
@@ -18,12 +18,21 @@ use token::Token;
 pub fn module(
     src: &str,
     kind: ast::ModuleKind,
+) -> Result<(ast::UntypedModule, ModuleExtra), Vec<ParseError>> {
+    module_with_edition(src, kind, Edition::default())
+}
+
+/// Like `module`, but following the rules of the given edition of the language.
+pub fn module_with_edition(
+    src: &str,
+    kind: ast::ModuleKind,
+    edition: Edition,
 ) -> Result<(ast::UntypedModule, ModuleExtra), Vec<ParseError>> {
     let len = src.chars().count();
 
     let span = |i| Span::new((), i..i + 1);
 
-    let tokens = lexer::lexer().parse(chumsky::Stream::from_iter(
+    let tokens = lexer::lexer(edition).parse(chumsky::Stream::from_iter(
         span(len),
         src.chars().enumerate().map(|(i, c)| (c, span(i))),
     ))?;
@@ -80,14 +89,16 @@ pub fn module(
 
 fn module_parser() -> impl Parser<Token, Vec<UntypedDefinition>, Error = ParseError> {
     choice((
-        import_parser(),
-        data_parser(),
-        type_alias_parser(),
-        fn_parser(),
-        test_parser(),
-        constant_parser(),
+        import_parser().map(|definition| vec![definition]),
+        data_parser().map(|definition| vec![definition]),
+        type_alias_parser().map(|definition| vec![definition]),
+        fn_parser().map(|definition| vec![definition]),
+        test_parser().map(|definition| vec![definition]),
+        describe_parser(),
+        constant_parser().map(|definition| vec![definition]),
     ))
     .repeated()
+    .flatten()
     .then_ignore(end())
 }
 
@@ -268,6 +279,8 @@ pub fn fn_parser() -> impl Parser<Token, ast::UntypedDefinition, Error = ParseEr
                             .unwrap_or_else(|| args_span.end),
                     },
                     end_position: span.end - 1,
+                    can_error: false,
+                    group: None,
                     name,
                     public: opt_pub.is_some(),
                     return_annotation,
@@ -280,17 +293,22 @@ pub fn fn_parser() -> impl Parser<Token, ast::UntypedDefinition, Error = ParseEr
 pub fn test_parser() -> impl Parser<Token, ast::UntypedDefinition, Error = ParseError> {
     just(Token::Test)
         .ignore_then(select! {Token::Name {name} => name})
-        .then_ignore(just(Token::LeftParen))
-        .then_ignore(just(Token::RightParen))
+        .then(
+            fn_param_parser()
+                .separated_by(just(Token::Comma))
+                .allow_trailing()
+                .delimited_by(just(Token::LeftParen), just(Token::RightParen)),
+        )
         .map_with_span(|name, span| (name, span))
+        .then(select! {Token::Name {name} if name == "fail" => ()}.or_not())
         .then(
             expr_seq_parser()
                 .or_not()
                 .delimited_by(just(Token::LeftBrace), just(Token::RightBrace)),
         )
-        .map_with_span(|((name, span_end), body), span| {
+        .map_with_span(|((((name, arguments), span_end), fail), body), span| {
             ast::UntypedDefinition::Test(ast::Function {
-                arguments: vec![],
+                arguments,
                 body: body.unwrap_or(expr::UntypedExpr::Todo {
                     kind: TodoKind::EmptyFunction,
                     location: span,
@@ -299,6 +317,8 @@ pub fn test_parser() -> impl Parser<Token, ast::UntypedDefinition, Error = Parse
                 doc: None,
                 location: span_end,
                 end_position: span.end - 1,
+                can_error: fail.is_some(),
+                group: None,
                 name,
                 public: false,
                 return_annotation: None,
@@ -307,6 +327,31 @@ pub fn test_parser() -> impl Parser<Token, ast::UntypedDefinition, Error = Parse
         })
 }
 
+/// A group of tests, as in `describe "minting" { test ... }`.
+pub fn describe_parser() -> impl Parser<Token, Vec<ast::UntypedDefinition>, Error = ParseError> {
+    select! {Token::Name {name} if name == "describe" => ()}
+        .ignore_then(select! {Token::String {value} => value})
+        .then(
+            test_parser()
+                .repeated()
+                .delimited_by(just(Token::LeftBrace), just(Token::RightBrace)),
+        )
+        .map(|(group, tests)| {
+            tests
+                .into_iter()
+                .map(|test| match test {
+                    ast::UntypedDefinition::Test(f) => {
+                        ast::UntypedDefinition::Test(ast::Function {
+                            group: Some(group.clone()),
+                            ..f
+                        })
+                    }
+                    definition => definition,
+                })
+                .collect()
+        })
+}
+
 fn constant_parser() -> impl Parser<Token, ast::UntypedDefinition, Error = ParseError> {
     pub_parser()
         .or_not()
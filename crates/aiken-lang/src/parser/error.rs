@@ -1,4 +1,7 @@
-use crate::{ast::Span, parser::token::Token};
+use crate::{
+    ast::{Edition, Span},
+    parser::token::Token,
+};
 use indoc::formatdoc;
 use miette::Diagnostic;
 use owo_colors::OwoColorize;
@@ -46,6 +49,25 @@ impl ParseError {
         }
     }
 
+    pub fn removed_keyword(
+        span: Span,
+        keyword: &'static str,
+        replacement: &'static str,
+        edition: Edition,
+    ) -> Self {
+        Self {
+            kind: ErrorKind::RemovedKeyword {
+                keyword,
+                replacement,
+                edition,
+            },
+            span,
+            while_parsing: None,
+            expected: HashSet::new(),
+            label: None,
+        }
+    }
+
     pub fn malformed_base16_string_literal(span: Span) -> Self {
         Self {
             kind: ErrorKind::MalformedBase16StringLiteral,
@@ -145,6 +167,13 @@ pub enum ErrorKind {
         , bad = "✖️".red()
     }))]
     InvalidWhenClause,
+    #[error("I found '{}', which isn't a keyword anymore as of edition {edition}.", keyword.purple())]
+    #[diagnostic(help("Use '{}' instead, which means the same. Running 'aiken fmt' replaces it for you.", replacement.yellow()))]
+    RemovedKeyword {
+        keyword: &'static str,
+        replacement: &'static str,
+        edition: Edition,
+    },
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Diagnostic, thiserror::Error)]
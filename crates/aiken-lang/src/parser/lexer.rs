@@ -1,12 +1,12 @@
 use chumsky::prelude::*;
 
-use crate::ast::Span;
+use crate::ast::{Edition, Span};
 
 use ordinal::Ordinal;
 
 use super::{error::ParseError, token::Token};
 
-pub fn lexer() -> impl Parser<char, Vec<(Token, Span)>, Error = ParseError> {
+pub fn lexer(edition: Edition) -> impl Parser<char, Vec<(Token, Span)>, Error = ParseError> {
     let int = text::int(10).map(|value| Token::Int { value });
 
     let ordinal = text::int(10)
@@ -83,44 +83,53 @@ pub fn lexer() -> impl Parser<char, Vec<(Token, Span)>, Error = ParseError> {
         .map(|value| Token::String { value })
         .labelled("string");
 
-    let keyword = text::ident().map(|s: String| match s.as_str() {
-        "trace" => Token::Trace,
-        "error" => Token::ErrorTerm,
-        "as" => Token::As,
-        "assert" => Token::Expect,
-        "expect" => Token::Expect,
-        "const" => Token::Const,
-        "fn" => Token::Fn,
-        "test" => Token::Test,
-        "if" => Token::If,
-        "else" => Token::Else,
-        "is" => Token::Is,
-        "let" => Token::Let,
-        "opaque" => Token::Opaque,
-        "pub" => Token::Pub,
-        "use" => Token::Use,
-        "todo" => Token::Todo,
-        "type" => Token::Type,
-        "when" => Token::When,
-        _ => {
-            if s.chars().next().map_or(false, |c| c.is_uppercase()) {
-                Token::UpName {
-                    // TODO: do not allow _ in upname
-                    name: s,
-                }
-            } else if s.starts_with('_') {
-                Token::DiscardName {
-                    // TODO: do not allow uppercase letters in discard name
-                    name: s,
-                }
-            } else {
-                Token::Name {
-                    // TODO: do not allow uppercase letters in name
-                    name: s,
+    let keyword = text::ident()
+        .validate(move |s: String, span, emit| {
+            if s == "assert" && edition >= Edition::V2023 {
+                emit(ParseError::removed_keyword(
+                    span, "assert", "expect", edition,
+                ))
+            }
+            s
+        })
+        .map(|s: String| match s.as_str() {
+            "trace" => Token::Trace,
+            "error" => Token::ErrorTerm,
+            "as" => Token::As,
+            "assert" => Token::Expect,
+            "expect" => Token::Expect,
+            "const" => Token::Const,
+            "fn" => Token::Fn,
+            "test" => Token::Test,
+            "if" => Token::If,
+            "else" => Token::Else,
+            "is" => Token::Is,
+            "let" => Token::Let,
+            "opaque" => Token::Opaque,
+            "pub" => Token::Pub,
+            "use" => Token::Use,
+            "todo" => Token::Todo,
+            "type" => Token::Type,
+            "when" => Token::When,
+            _ => {
+                if s.chars().next().map_or(false, |c| c.is_uppercase()) {
+                    Token::UpName {
+                        // TODO: do not allow _ in upname
+                        name: s,
+                    }
+                } else if s.starts_with('_') {
+                    Token::DiscardName {
+                        // TODO: do not allow uppercase letters in discard name
+                        name: s,
+                    }
+                } else {
+                    Token::Name {
+                        // TODO: do not allow uppercase letters in name
+                        name: s,
+                    }
                 }
             }
-        }
-    });
+        });
 
     let module_comments = just("////").ignore_then(
         take_until(text::newline().rewind())
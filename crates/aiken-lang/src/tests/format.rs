@@ -305,3 +305,49 @@ fn test_format_bytearray_literals() {
 
     assert_fmt(src, expected);
 }
+
+#[test]
+fn test_format_describe() {
+    let src = indoc! {r#"
+        describe "minting" {
+        // Comes first
+        test foo() { True }
+        test bar() fail { error("nope") }
+        }
+        test baz() { True }
+    "#};
+
+    let expected = indoc! {r#"
+        describe "minting" {
+          // Comes first
+          test foo() {
+            True
+          }
+
+          test bar() fail {
+            error("nope")
+          }
+        }
+
+        test baz() {
+          True
+        }
+    "#};
+
+    assert_fmt(src, expected);
+}
+
+#[test]
+fn test_format_test_expected_to_fail() {
+    let src = indoc! {r#"
+        test foo() fail { error("nope") }
+    "#};
+
+    let expected = indoc! {r#"
+        test foo() fail {
+          error("nope")
+        }
+    "#};
+
+    assert_fmt(src, expected);
+}
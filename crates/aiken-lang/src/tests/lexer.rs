@@ -1,6 +1,10 @@
 use chumsky::prelude::*;
 
-use crate::{ast::Span, parser::lexer, parser::token::Token};
+use crate::{
+    ast::{Edition, Span},
+    parser::lexer,
+    parser::token::Token,
+};
 
 #[test]
 fn tokens() {
@@ -10,7 +14,7 @@ fn tokens() {
     let span = |i| Span::new((), i..i + 1);
 
     assert_eq!(
-        lexer::lexer()
+        lexer::lexer(Edition::default())
             .parse(chumsky::Stream::from_iter(
                 span(len),
                 code.chars().enumerate().map(|(i, c)| (c, span(i))),
@@ -320,6 +320,8 @@ fn empty_function() {
             return_annotation: None,
             return_type: (),
             end_position: 14,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -391,6 +393,8 @@ fn expect() {
             return_annotation: None,
             return_type: (),
             end_position: 70,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -440,6 +444,8 @@ fn plus_binop() {
             }),
             return_type: (),
             end_position: 35,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -503,6 +509,8 @@ fn pipeline() {
             return_annotation: None,
             return_type: (),
             end_position: 63,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -600,6 +608,8 @@ fn if_expression() {
             return_annotation: None,
             return_type: (),
             end_position: 107,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -728,6 +738,8 @@ fn let_bindings() {
             return_annotation: None,
             return_type: (),
             end_position: 122,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -819,6 +831,8 @@ fn block() {
             return_annotation: None,
             return_type: (),
             end_position: 67,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -965,6 +979,8 @@ fn when() {
             return_annotation: None,
             return_type: (),
             end_position: 139,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -1058,6 +1074,8 @@ fn anonymous_function() {
             }),
             return_type: (),
             end_position: 84,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -1103,6 +1121,8 @@ fn field_access() {
             return_annotation: None,
             return_type: (),
             end_position: 34,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -1264,6 +1284,8 @@ fn call() {
             return_annotation: None,
             return_type: (),
             end_position: 109,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -1355,6 +1377,8 @@ fn record_update() {
             }),
             return_type: (),
             end_position: 89,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -1411,6 +1435,8 @@ fn record_create_labeled() {
             return_annotation: None,
             return_type: (),
             end_position: 54,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -1471,6 +1497,8 @@ fn record_create_labeled_with_field_access() {
             return_annotation: None,
             return_type: (),
             end_position: 66,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -1523,6 +1551,8 @@ fn record_create_unlabeled() {
             return_annotation: None,
             return_type: (),
             end_position: 40,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -1626,6 +1656,8 @@ fn parse_tuple() {
             return_annotation: None,
             return_type: (),
             end_position: 86,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -1692,6 +1724,8 @@ fn parse_tuple2() {
             return_annotation: None,
             return_type: (),
             end_position: 39,
+            can_error: false,
+            group: None,
         })],
     );
 }
@@ -1765,6 +1799,8 @@ fn base16_bytearray_literals() {
                 return_annotation: None,
                 return_type: (),
                 end_position: 81,
+                can_error: false,
+                group: None,
             }),
         ],
     )
@@ -1792,6 +1828,8 @@ fn function_def() {
             return_annotation: None,
             return_type: (),
             end_position: 10,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -1839,6 +1877,8 @@ fn function_invoke() {
             return_annotation: None,
             return_type: (),
             end_position: 29,
+            can_error: false,
+            group: None,
         })],
     )
 }
@@ -1900,6 +1940,8 @@ fn function_ambiguous_sequence() {
                 return_annotation: None,
                 return_type: (),
                 end_position: 34,
+                can_error: false,
+                group: None,
             }),
             ast::UntypedDefinition::Fn(Function {
                 arguments: vec![],
@@ -1932,6 +1974,8 @@ fn function_ambiguous_sequence() {
                 return_annotation: None,
                 return_type: (),
                 end_position: 71,
+                can_error: false,
+                group: None,
             }),
             ast::UntypedDefinition::Fn(Function {
                 arguments: vec![],
@@ -1963,6 +2007,8 @@ fn function_ambiguous_sequence() {
                 return_annotation: None,
                 return_type: (),
                 end_position: 104,
+                can_error: false,
+                group: None,
             }),
             ast::UntypedDefinition::Fn(Function {
                 arguments: vec![],
@@ -2022,6 +2068,8 @@ fn function_ambiguous_sequence() {
                 return_annotation: None,
                 return_type: (),
                 end_position: 154,
+                can_error: false,
+                group: None,
             }),
         ],
     )
@@ -2114,6 +2162,8 @@ fn tuple_pattern() {
             return_annotation: None,
             return_type: (),
             end_position: 50,
+            can_error: false,
+            group: None,
         })],
     );
 }
@@ -2224,6 +2274,8 @@ fn subtraction_vs_negate() {
             return_annotation: None,
             return_type: (),
             end_position: 62,
+            can_error: false,
+            group: None,
         })],
     );
 }
@@ -2488,6 +2540,8 @@ fn clause_guards() {
             return_annotation: None,
             return_type: (),
             end_position: 251,
+            can_error: false,
+            group: None,
         })],
     );
 }
@@ -2583,6 +2637,35 @@ fn scope_logical_expression() {
             return_annotation: None,
             return_type: (),
             end_position: 62,
+            can_error: false,
+            group: None,
         })],
     )
 }
+
+#[test]
+fn assert_removed_in_edition_2023() {
+    let code = indoc! {r#"
+        fn foo(d: Data) {
+          assert x: Int = d
+          x
+        }
+    "#};
+
+    assert!(parser::module_with_edition(code, ast::ModuleKind::Lib, ast::Edition::V2022).is_ok());
+
+    let errors =
+        parser::module_with_edition(code, ast::ModuleKind::Lib, ast::Edition::V2023).unwrap_err();
+
+    assert!(matches!(
+        &errors[..],
+        [parser::error::ParseError {
+            kind: parser::error::ErrorKind::RemovedKeyword {
+                keyword: "assert",
+                replacement: "expect",
+                edition: ast::Edition::V2023,
+            },
+            ..
+        }]
+    ));
+}
@@ -1,4 +1,8 @@
-use std::{cell::RefCell, collections::HashMap, ops::Deref, sync::Arc};
+use std::{
+    collections::HashMap,
+    ops::Deref,
+    sync::{Arc, RwLock},
+};
 
 use uplc::{ast::Type as UplcType, builtins::DefaultFunction};
 
@@ -19,7 +23,7 @@ mod pattern;
 mod pipe;
 pub mod pretty;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub enum Type {
     /// A nominal (named) type such as `Int`, `Float`, or a programmer defined
     /// custom type such as `Person`. The type can take other types as
@@ -46,7 +50,7 @@ pub enum Type {
     /// A type variable. See the contained `TypeVar` enum for more information.
     ///
     Var {
-        tipo: Arc<RefCell<TypeVar>>,
+        tipo: Arc<RwLock<TypeVar>>,
     },
     // /// A tuple is an ordered collection of 0 or more values, each of which
     // /// can have a different type, so the `tuple` type is the sum of all the
@@ -57,6 +61,49 @@ pub enum Type {
     },
 }
 
+impl PartialEq for Type {
+    fn eq(&self, other: &Type) -> bool {
+        match (self, other) {
+            (
+                Type::App {
+                    public,
+                    module,
+                    name,
+                    args,
+                },
+                Type::App {
+                    public: other_public,
+                    module: other_module,
+                    name: other_name,
+                    args: other_args,
+                },
+            ) => {
+                public == other_public
+                    && module == other_module
+                    && name == other_name
+                    && args == other_args
+            }
+
+            (
+                Type::Fn { args, ret },
+                Type::Fn {
+                    args: other_args,
+                    ret: other_ret,
+                },
+            ) => args == other_args && ret == other_ret,
+
+            (Type::Var { tipo }, Type::Var { tipo: other_tipo }) => {
+                Arc::ptr_eq(tipo, other_tipo)
+                    || *tipo.read().unwrap() == *other_tipo.read().unwrap()
+            }
+
+            (Type::Tuple { elems }, Type::Tuple { elems: other_elems }) => elems == other_elems,
+
+            _ => false,
+        }
+    }
+}
+
 impl Type {
     pub fn is_result_constructor(&self) -> bool {
         match self {
@@ -70,7 +117,7 @@ impl Type {
     }
 
     pub fn is_unbound(&self) -> bool {
-        matches!(self, Self::Var { tipo } if tipo.borrow().is_unbound())
+        matches!(self, Self::Var { tipo } if tipo.read().unwrap().is_unbound())
     }
 
     pub fn is_function(&self) -> bool {
@@ -94,7 +141,7 @@ impl Type {
     pub fn is_void(&self) -> bool {
         match self {
             Self::App { module, name, .. } if "Void" == name && module.is_empty() => true,
-            Self::Var { tipo } => tipo.borrow().is_void(),
+            Self::Var { tipo } => tipo.read().unwrap().is_void(),
             _ => false,
         }
     }
@@ -102,7 +149,7 @@ impl Type {
     pub fn is_bool(&self) -> bool {
         match self {
             Self::App { module, name, .. } if "Bool" == name && module.is_empty() => true,
-            Self::Var { tipo } => tipo.borrow().is_bool(),
+            Self::Var { tipo } => tipo.read().unwrap().is_bool(),
             _ => false,
         }
     }
@@ -110,7 +157,7 @@ impl Type {
     pub fn is_int(&self) -> bool {
         match self {
             Self::App { module, name, .. } if "Int" == name && module.is_empty() => true,
-            Self::Var { tipo } => tipo.borrow().is_int(),
+            Self::Var { tipo } => tipo.read().unwrap().is_int(),
             _ => false,
         }
     }
@@ -118,7 +165,7 @@ impl Type {
     pub fn is_bytearray(&self) -> bool {
         match self {
             Self::App { module, name, .. } if "ByteArray" == name && module.is_empty() => true,
-            Self::Var { tipo } => tipo.borrow().is_bytearray(),
+            Self::Var { tipo } => tipo.read().unwrap().is_bytearray(),
             _ => false,
         }
     }
@@ -126,7 +173,7 @@ impl Type {
     pub fn is_string(&self) -> bool {
         match self {
             Self::App { module, name, .. } if "String" == name && module.is_empty() => true,
-            Self::Var { tipo } => tipo.borrow().is_string(),
+            Self::Var { tipo } => tipo.read().unwrap().is_string(),
             _ => false,
         }
     }
@@ -134,7 +181,7 @@ impl Type {
     pub fn is_list(&self) -> bool {
         match self {
             Self::App { module, name, .. } if "List" == name && module.is_empty() => true,
-            Self::Var { tipo } => tipo.borrow().is_list(),
+            Self::Var { tipo } => tipo.read().unwrap().is_list(),
             _ => false,
         }
     }
@@ -142,7 +189,7 @@ impl Type {
     pub fn is_option(&self) -> bool {
         match self {
             Self::App { module, name, .. } if "Option" == name && module.is_empty() => true,
-            Self::Var { tipo } => tipo.borrow().is_option(),
+            Self::Var { tipo } => tipo.read().unwrap().is_option(),
             _ => false,
         }
     }
@@ -155,19 +202,22 @@ impl Type {
                 if let Type::Tuple { elems } = &*args[0] {
                     elems.len() == 2
                 } else if let Type::Var { tipo } = &*args[0] {
-                    matches!(tipo.borrow().get_uplc_type(), Some(UplcType::Pair(_, _)))
+                    matches!(
+                        tipo.read().unwrap().get_uplc_type(),
+                        Some(UplcType::Pair(_, _))
+                    )
                 } else {
                     false
                 }
             }
-            Self::Var { tipo } => tipo.borrow().is_map(),
+            Self::Var { tipo } => tipo.read().unwrap().is_map(),
             _ => false,
         }
     }
 
     pub fn is_tuple(&self) -> bool {
         match self {
-            Type::Var { tipo } => tipo.borrow().is_tuple(),
+            Type::Var { tipo } => tipo.read().unwrap().is_tuple(),
             Type::Tuple { .. } => true,
             _ => false,
         }
@@ -176,7 +226,7 @@ impl Type {
     pub fn is_data(&self) -> bool {
         match self {
             Self::App { module, name, .. } => "Data" == name && module.is_empty(),
-            Self::Var { tipo } => tipo.borrow().is_data(),
+            Self::Var { tipo } => tipo.read().unwrap().is_data(),
             _ => false,
         }
     }
@@ -191,7 +241,7 @@ impl Type {
                 is_a_generic
             }
 
-            Type::Var { tipo } => tipo.borrow().is_generic(),
+            Type::Var { tipo } => tipo.read().unwrap().is_generic(),
             Type::Tuple { elems } => {
                 let mut is_a_generic = false;
                 for elem in elems {
@@ -213,14 +263,14 @@ impl Type {
         match self {
             Self::Fn { args, .. } => Some(args.clone()),
             Self::App { args, .. } => Some(args.clone()),
-            Self::Var { tipo } => tipo.borrow().arg_types(),
+            Self::Var { tipo } => tipo.read().unwrap().arg_types(),
             _ => None,
         }
     }
 
     pub fn get_generic(&self) -> Option<u64> {
         match self {
-            Type::Var { tipo } => tipo.borrow().get_generic(),
+            Type::Var { tipo } => tipo.read().unwrap().get_generic(),
             _ => None,
         }
     }
@@ -229,13 +279,13 @@ impl Type {
         if self.is_list() {
             match self {
                 Self::App { args, .. } => args.clone(),
-                Self::Var { tipo } => tipo.borrow().get_inner_type(),
+                Self::Var { tipo } => tipo.read().unwrap().get_inner_type(),
                 _ => vec![],
             }
         } else if self.is_tuple() {
             match self {
                 Self::Tuple { elems } => elems.to_vec(),
-                Self::Var { tipo } => tipo.borrow().get_inner_type(),
+                Self::Var { tipo } => tipo.read().unwrap().get_inner_type(),
                 _ => vec![],
             }
         } else if matches!(self.get_uplc_type(), UplcType::Data) {
@@ -246,7 +296,7 @@ impl Type {
                     args.push(ret.clone());
                     args
                 }
-                Type::Var { tipo } => tipo.borrow().get_inner_type(),
+                Type::Var { tipo } => tipo.read().unwrap().get_inner_type(),
                 _ => unreachable!(),
             }
         } else {
@@ -276,7 +326,7 @@ impl Type {
                         UplcType::List(UplcType::Data.into())
                     }
                 }
-                Self::Var { tipo } => tipo.borrow().get_uplc_type().unwrap(),
+                Self::Var { tipo } => tipo.read().unwrap().get_uplc_type().unwrap(),
                 _ => todo!(),
             }
         } else {
@@ -311,7 +361,7 @@ impl Type {
             }
 
             Self::Var { tipo } => {
-                let args: Vec<_> = match tipo.borrow().deref() {
+                let args: Vec<_> = match tipo.read().unwrap().deref() {
                     TypeVar::Link { tipo } => {
                         return tipo.get_app_args(public, module, name, arity, environment);
                     }
@@ -325,7 +375,7 @@ impl Type {
 
                 // We are an unbound type variable! So convert us to a type link
                 // to the desired type.
-                *tipo.borrow_mut() = TypeVar::Link {
+                *tipo.write().unwrap() = TypeVar::Link {
                     tipo: Arc::new(Self::App {
                         name: name.to_string(),
                         module: module.to_owned(),
@@ -351,7 +401,7 @@ impl Type {
                 .find_private_type()
                 .or_else(|| args.iter().find_map(|t| t.find_private_type())),
 
-            Self::Var { tipo, .. } => match tipo.borrow().deref() {
+            Self::Var { tipo, .. } => match tipo.read().unwrap().deref() {
                 TypeVar::Unbound { .. } => None,
 
                 TypeVar::Generic { .. } => None,
@@ -511,7 +561,7 @@ impl TypeVar {
             Self::Link { tipo } => tipo.get_inner_types(),
             var => {
                 vec![Type::Var {
-                    tipo: RefCell::new(var.clone()).into(),
+                    tipo: RwLock::new(var.clone()).into(),
                 }
                 .into()]
             }
@@ -111,7 +111,7 @@ impl<'a> Environment<'a> {
         call_location: Span,
     ) -> Result<(Vec<Arc<Type>>, Arc<Type>), Error> {
         if let Type::Var { tipo } = tipo.deref() {
-            let new_value = match tipo.borrow().deref() {
+            let new_value = match tipo.read().unwrap().deref() {
                 TypeVar::Link { tipo, .. } => {
                     return self.match_fun_type(tipo.clone(), arity, fn_location, call_location);
                 }
@@ -128,7 +128,7 @@ impl<'a> Environment<'a> {
             };
 
             if let Some((args, ret)) = new_value {
-                *tipo.borrow_mut() = TypeVar::Link {
+                *tipo.write().unwrap() = TypeVar::Link {
                     tipo: function(args.clone(), ret.clone()),
                 };
 
@@ -196,6 +196,8 @@ impl<'a> Environment<'a> {
                 return_annotation,
                 return_type,
                 end_position,
+                can_error,
+                group,
             }) => {
                 // Lookup the inferred function information
                 let function = self
@@ -240,6 +242,8 @@ impl<'a> Environment<'a> {
                     return_type,
                     body,
                     end_position,
+                    can_error,
+                    group,
                 })
             }
 
@@ -484,6 +488,30 @@ impl<'a> Environment<'a> {
             .insert(type_name, constructors);
     }
 
+    /// Warn when a local variable about to be introduced at `location` shadows another one
+    /// already in scope. Discarded names, which include the arguments generated for function
+    /// captures, are never reported.
+    pub fn warn_on_shadowing(&mut self, name: &str, location: Span) {
+        if name.starts_with('_') {
+            return;
+        }
+
+        if let Some(ValueConstructor {
+            variant:
+                ValueConstructorVariant::LocalVariable {
+                    location: shadowed_at,
+                },
+            ..
+        }) = self.scope.get(name)
+        {
+            self.warnings.push(Warning::ImplicitShadowing {
+                name: name.to_string(),
+                location,
+                shadowed_at: *shadowed_at,
+            });
+        }
+    }
+
     /// Insert a variable in the current scope.
     pub fn insert_variable(
         &mut self,
@@ -528,7 +556,7 @@ impl<'a> Environment<'a> {
             }
 
             Type::Var { tipo } => {
-                match tipo.borrow().deref() {
+                match tipo.read().unwrap().deref() {
                     TypeVar::Link { tipo } => return self.instantiate(tipo.clone(), ids, hydrator),
 
                     TypeVar::Unbound { .. } => return Arc::new(Type::Var { tipo: tipo.clone() }),
@@ -1073,10 +1101,27 @@ impl<'a> Environment<'a> {
                 }
             }
 
-            Definition::Test(Function { name, location, .. }) => {
+            Definition::Test(Function {
+                name,
+                arguments: args,
+                location,
+                ..
+            }) => {
                 assert_unique_value_name(names, name, location)?;
-                hydrators.insert(name.clone(), Hydrator::new());
-                let arg_types = vec![];
+
+                let mut hydrator = Hydrator::new();
+
+                hydrator.permit_holes(true);
+
+                let mut arg_types = Vec::new();
+
+                for arg in args {
+                    let tipo = hydrator.type_from_option_annotation(&arg.annotation, self)?;
+
+                    arg_types.push(tipo);
+                }
+
+                hydrators.insert(name.clone(), hydrator);
                 let return_type = builtins::bool();
                 self.insert_variable(
                     name.clone(),
@@ -1084,7 +1129,7 @@ impl<'a> Environment<'a> {
                         name: name.clone(),
                         field_map: None,
                         module: module_name.to_owned(),
-                        arity: 0,
+                        arity: args.len(),
                         location: *location,
                         builtin: None,
                     },
@@ -1230,7 +1275,7 @@ impl<'a> Environment<'a> {
 
         // Collapse right hand side type links. Left hand side will be collapsed in the next block.
         if let Type::Var { tipo } = t2.deref() {
-            if let TypeVar::Link { tipo } = tipo.borrow().deref() {
+            if let TypeVar::Link { tipo } = tipo.read().unwrap().deref() {
                 return self.unify(t1, tipo.clone(), location, allow_cast);
             }
         }
@@ -1242,7 +1287,7 @@ impl<'a> Environment<'a> {
                 Link,
             }
 
-            let action = match tipo.borrow().deref() {
+            let action = match tipo.read().unwrap().deref() {
                 TypeVar::Link { tipo } => Action::Unify(tipo.clone()),
 
                 TypeVar::Unbound { id } => {
@@ -1252,8 +1297,8 @@ impl<'a> Environment<'a> {
 
                 TypeVar::Generic { id } => {
                     if let Type::Var { tipo } = t2.deref() {
-                        if tipo.borrow().is_unbound() {
-                            *tipo.borrow_mut() = TypeVar::Generic { id: *id };
+                        if tipo.read().unwrap().is_unbound() {
+                            *tipo.write().unwrap() = TypeVar::Generic { id: *id };
                             return Ok(());
                         }
                     }
@@ -1263,7 +1308,7 @@ impl<'a> Environment<'a> {
 
             return match action {
                 Action::Link => {
-                    *tipo.borrow_mut() = TypeVar::Link { tipo: t2 };
+                    *tipo.write().unwrap() = TypeVar::Link { tipo: t2 };
                     Ok(())
                 }
 
@@ -1496,7 +1541,7 @@ pub enum EntityKind {
 /// While traversing the type tree.
 fn unify_unbound_type(tipo: Arc<Type>, own_id: u64, location: Span) -> Result<(), Error> {
     if let Type::Var { tipo } = tipo.deref() {
-        let new_value = match tipo.borrow().deref() {
+        let new_value = match tipo.read().unwrap().deref() {
             TypeVar::Link { tipo, .. } => {
                 return unify_unbound_type(tipo.clone(), own_id, location)
             }
@@ -1513,7 +1558,7 @@ fn unify_unbound_type(tipo: Arc<Type>, own_id: u64, location: Span) -> Result<()
         };
 
         if let Some(t) = new_value {
-            *tipo.borrow_mut() = t;
+            *tipo.write().unwrap() = t;
         }
         return Ok(());
     }
@@ -1627,7 +1672,7 @@ pub(super) fn assert_no_labeled_arguments<A>(args: &[CallArg<A>]) -> Option<(Spa
 
 pub(super) fn collapse_links(t: Arc<Type>) -> Arc<Type> {
     if let Type::Var { tipo } = t.deref() {
-        if let TypeVar::Link { tipo } = tipo.borrow().deref() {
+        if let TypeVar::Link { tipo } = tipo.read().unwrap().deref() {
             return tipo.clone();
         }
     }
@@ -1690,7 +1735,7 @@ fn get_compatible_record_fields<A>(
 #[allow(clippy::only_used_in_recursion)]
 pub(crate) fn generalise(t: Arc<Type>, ctx_level: usize) -> Arc<Type> {
     match t.deref() {
-        Type::Var { tipo } => match tipo.borrow().deref() {
+        Type::Var { tipo } => match tipo.read().unwrap().deref() {
             TypeVar::Unbound { id } => generic_var(*id),
             TypeVar::Link { tipo } => generalise(tipo.clone(), ctx_level),
             TypeVar::Generic { .. } => Arc::new(Type::Var { tipo: tipo.clone() }),
@@ -335,6 +335,17 @@ Perhaps, try the following:
         given: usize,
     },
 
+    #[error("I found a test taking {} arguments, but tests take at most one.\n", given.purple())]
+    #[diagnostic(code("arity::test"))]
+    #[diagnostic(help(r#"A test either takes no argument, or a single argument of type {type_Data}. In the latter case, the test is a property: it runs against many randomly generated values and fails on the first one that makes it fail."#
+        , type_Data = "Data".green()
+    ))]
+    IncorrectTestArity {
+        #[label]
+        location: Span,
+        given: usize,
+    },
+
     #[error(
       "I realized the module '{}' contains the keyword '{}', which is forbidden.\n",
       name.purple(),
@@ -1061,6 +1072,19 @@ pub enum Warning {
         location: Span,
     },
 
+    #[error("I found a variable '{}' shadowing another one.\n", name.purple())]
+    #[diagnostic(help(
+        "The outer variable can't be used past this point. If that's intended, you can ignore this warning; otherwise, you might want to rename one of them."
+    ))]
+    #[diagnostic(code("shadowing"))]
+    ImplicitShadowing {
+        name: String,
+        #[label("shadowing")]
+        location: Span,
+        #[label("shadowed")]
+        shadowed_at: Span,
+    },
+
     #[error(
         "I realized the following expression returned a result that is implicitly discarded.\n"
     )]
@@ -1114,6 +1138,17 @@ pub enum Warning {
         sample: UntypedExpr,
     },
 
+    #[error("I found a test that doesn't assert anything: '{}'.\n", name.purple())]
+    #[diagnostic(help(
+        "A test passes as soon as it returns True. Try ending it with a comparison such as {} so that it actually checks something.", "left == right".yellow()
+    ))]
+    #[diagnostic(code("test::no_assertion"))]
+    TestWithoutAssertion {
+        name: String,
+        #[label("asserts nothing")]
+        location: Span,
+    },
+
     #[error("I found a todo left in the code.\n")]
     #[diagnostic(help("You probably want to replace that one with real code... eventually."))]
     #[diagnostic(code("todo"))]
@@ -1151,7 +1186,7 @@ pub enum Warning {
     #[diagnostic(help(
         "No big deal, but you might want to remove it to get rid of that warning."
     ))]
-    #[diagnostic(code("unused:import::value"))]
+    #[diagnostic(code("unused::import::value"))]
     UnusedImportedValue {
         #[label]
         location: Span,
@@ -1655,6 +1655,8 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             for (arg, t) in args.iter().zip(args.iter().map(|arg| arg.tipo.clone())) {
                 match &arg.arg_name {
                     ArgName::Named { name, .. } => {
+                        body_typer.environment.warn_on_shadowing(name, arg.location);
+
                         body_typer.environment.insert_variable(
                             name.to_string(),
                             ValueConstructorVariant::LocalVariable {
@@ -158,6 +158,8 @@ fn infer_definition(
             body,
             return_annotation,
             end_position,
+            can_error,
+            group,
             ..
         }) => {
             if public && kind.is_validator() {
@@ -245,14 +247,28 @@ fn infer_definition(
                     .expect("Could not find return type for fn"),
                 body,
                 end_position,
+                can_error,
+                group,
             }))
         }
 
         Definition::Test(f) => {
+            if f.arguments.len() > 1 {
+                return Err(Error::IncorrectTestArity {
+                    location: f.location,
+                    given: f.arguments.len(),
+                });
+            }
+
             if let Definition::Fn(f) =
                 infer_definition(Definition::Fn(f), module_name, hydrators, environment, kind)?
             {
                 environment.unify(f.return_type.clone(), builtins::bool(), f.location, false)?;
+
+                for arg in &f.arguments {
+                    environment.unify(arg.tipo.clone(), builtins::data(), arg.location, false)?;
+                }
+
                 Ok(Definition::Test(f))
             } else {
                 unreachable!("test defintion inferred as something else than a function?")
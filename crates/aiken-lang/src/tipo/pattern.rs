@@ -66,6 +66,8 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                 // have the same variables.
                 self.initial_pattern_vars.insert(name.to_string());
 
+                self.environment.warn_on_shadowing(name, location);
+
                 // And now insert the variable for use in the code that comes
                 // after the pattern.
                 self.environment.insert_variable(
@@ -73,7 +73,7 @@ impl Printer {
                 .append(") ->")
                 .append(break_("", " ").append(self.print(ret)).nest(INDENT).group()),
 
-            Type::Var { tipo: typ, .. } => self.type_var_doc(&typ.borrow()),
+            Type::Var { tipo: typ, .. } => self.type_var_doc(&typ.read().unwrap()),
 
             Type::Tuple { elems, .. } => self.args_to_aiken_doc(elems).surround("(", ")"),
         }
@@ -169,7 +169,7 @@ fn qualify_type_name(module: &String, typ_name: &str) -> Document<'static> {
 
 #[cfg(test)]
 mod test {
-    use std::cell::RefCell;
+    use std::sync::RwLock;
 
     use pretty_assertions::assert_eq;
 
@@ -327,7 +327,7 @@ mod test {
         );
         assert_string!(
             Type::Var {
-                tipo: Arc::new(RefCell::new(TypeVar::Link {
+                tipo: Arc::new(RwLock::new(TypeVar::Link {
                     tipo: Arc::new(Type::App {
                         args: vec![],
                         module: "whatever".to_string(),
@@ -340,17 +340,17 @@ mod test {
         );
         assert_string!(
             Type::Var {
-                tipo: Arc::new(RefCell::new(TypeVar::Unbound { id: 2231 })),
+                tipo: Arc::new(RwLock::new(TypeVar::Unbound { id: 2231 })),
             },
             "a",
         );
         assert_string!(
             function(
                 vec![Arc::new(Type::Var {
-                    tipo: Arc::new(RefCell::new(TypeVar::Unbound { id: 78 })),
+                    tipo: Arc::new(RwLock::new(TypeVar::Unbound { id: 78 })),
                 })],
                 Arc::new(Type::Var {
-                    tipo: Arc::new(RefCell::new(TypeVar::Unbound { id: 2 })),
+                    tipo: Arc::new(RwLock::new(TypeVar::Unbound { id: 2 })),
                 }),
             ),
             "fn(a) -> b",
@@ -358,10 +358,10 @@ mod test {
         assert_string!(
             function(
                 vec![Arc::new(Type::Var {
-                    tipo: Arc::new(RefCell::new(TypeVar::Generic { id: 78 })),
+                    tipo: Arc::new(RwLock::new(TypeVar::Generic { id: 78 })),
                 })],
                 Arc::new(Type::Var {
-                    tipo: Arc::new(RefCell::new(TypeVar::Generic { id: 2 })),
+                    tipo: Arc::new(RwLock::new(TypeVar::Generic { id: 2 })),
                 }),
             ),
             "fn(a) -> b",
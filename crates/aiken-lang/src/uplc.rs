@@ -50,6 +50,9 @@ pub struct CodeGenerator<'a> {
     needs_field_access: bool,
     used_data_assert_on_list: bool,
     zero_arg_functions: IndexMap<FunctionAccessKey, Vec<Air>>,
+    prune_unused_lets: bool,
+    inline_single_use: bool,
+    filter_traces: bool,
 }
 
 impl<'a> CodeGenerator<'a> {
@@ -67,9 +70,32 @@ impl<'a> CodeGenerator<'a> {
             needs_field_access: false,
             used_data_assert_on_list: false,
             zero_arg_functions: IndexMap::new(),
+            prune_unused_lets: false,
+            inline_single_use: false,
+            filter_traces: false,
         }
     }
 
+    /// Remove bindings the generated programs never use, such as unused helpers. Off by
+    /// default.
+    pub fn prune_unused_lets(mut self, prune_unused_lets: bool) -> Self {
+        self.prune_unused_lets = prune_unused_lets;
+        self
+    }
+
+    /// Inline bindings the generated programs use only once. Off by default.
+    pub fn inline_single_use(mut self, inline_single_use: bool) -> Self {
+        self.inline_single_use = inline_single_use;
+        self
+    }
+
+    /// Compile `trace` expressions to what they're followed by, leaving no logs in the
+    /// generated programs. Off by default.
+    pub fn filter_traces(mut self, filter_traces: bool) -> Self {
+        self.filter_traces = filter_traces;
+        self
+    }
+
     pub fn reset(&mut self) {
         self.needs_field_access = false;
         self.used_data_assert_on_list = false;
@@ -123,6 +149,14 @@ impl<'a> CodeGenerator<'a> {
 
         program = aiken_optimize_and_intern(program);
 
+        if self.prune_unused_lets {
+            program.term = program.term.prune_unused_lets();
+        }
+
+        if self.inline_single_use {
+            program.term = program.term.inline_single_use();
+        }
+
         // This is very important to call here.
         // If this isn't done, re-using the same instance
         // of the generator will result in free unique errors
@@ -635,6 +669,9 @@ impl<'a> CodeGenerator<'a> {
 
                 ir_stack.append(&mut elems_air);
             }
+            TypedExpr::Trace { then, .. } if self.filter_traces => {
+                self.build_ir(then, ir_stack, scope);
+            }
             TypedExpr::Trace {
                 tipo, then, text, ..
             } => {
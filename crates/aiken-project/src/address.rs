@@ -0,0 +1,70 @@
+use crate::{blueprint, error::Error};
+use pallas::ledger::addresses::{Address, Pointer, ShelleyDelegationPart, StakePayload};
+
+/// What a validator's address delegates its stake to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Delegation {
+    /// No delegation at all, i.e. an enterprise address.
+    Enterprise,
+    /// The credential of a stake address, given in hex or bech32.
+    StakeAddress(String),
+    /// A stake registration certificate, located by the slot, the index of its transaction
+    /// in the block and its own index in the transaction.
+    Pointer {
+        slot: u64,
+        tx_index: u64,
+        cert_index: u64,
+    },
+}
+
+impl Delegation {
+    /// Largest slot a pointer may refer to. The ledger only keeps 32 bits of it.
+    pub const MAX_POINTER_SLOT: u64 = u32::MAX as u64;
+
+    /// Largest transaction or certificate index a pointer may refer to. The ledger only
+    /// keeps 16 bits of them.
+    pub const MAX_POINTER_INDEX: u64 = u16::MAX as u64;
+
+    pub fn to_delegation_part(&self) -> Result<ShelleyDelegationPart, Error> {
+        match self {
+            Delegation::Enterprise => Ok(ShelleyDelegationPart::Null),
+            Delegation::StakeAddress(s) => {
+                let stake_address = Address::from_hex(s)
+                    .or_else(|_| Address::from_bech32(s))
+                    .map_err(|error| Error::MalformedStakeAddress { error: Some(error) })
+                    .and_then(|addr| match addr {
+                        Address::Stake(addr) => Ok(addr),
+                        _ => Err(Error::MalformedStakeAddress { error: None }),
+                    })?;
+
+                Ok(match stake_address.payload() {
+                    StakePayload::Stake(key) => ShelleyDelegationPart::Key(*key),
+                    StakePayload::Script(script) => ShelleyDelegationPart::Script(*script),
+                })
+            }
+            Delegation::Pointer {
+                slot,
+                tx_index,
+                cert_index,
+            } => {
+                for (field, value, max) in [
+                    ("slot", *slot, Self::MAX_POINTER_SLOT),
+                    ("transaction index", *tx_index, Self::MAX_POINTER_INDEX),
+                    ("certificate index", *cert_index, Self::MAX_POINTER_INDEX),
+                ] {
+                    if value > max {
+                        return Err(
+                            blueprint::error::Error::InvalidPointer { field, value, max }.into(),
+                        );
+                    }
+                }
+
+                Ok(ShelleyDelegationPart::Pointer(Pointer::new(
+                    *slot,
+                    *tx_index,
+                    *cert_index,
+                )))
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::schema;
+use super::{schema, validator::Purpose};
 use crate::module::CheckedModule;
 use aiken_lang::{
     ast::{Span, TypedFunction},
@@ -55,14 +55,61 @@ pub enum Error {
     #[diagnostic(help("Did you forget to {build} the project?", build = "build".purple().bold()))]
     InvalidOrMissingFile,
 
+    #[error("I found an invalid blueprint: {reason}{}.", if path.is_empty() { String::new() } else { format!(" at {}", path.purple()) })]
+    #[diagnostic(code("aiken::blueprint::invalid::file"))]
+    #[diagnostic(help("The blueprint must follow the structure described in CIP-0057. If you edited it by hand, you may want to {build} the project again instead.", build = "build".purple().bold()))]
+    InvalidBlueprint { path: String, reason: String },
+
     #[error("I didn't find any parameters to apply in the given validator.")]
     #[diagnostic(code("aiken::blueprint::apply::no_parameters"))]
     NoParametersToApply,
 
+    #[error("I was given {} parameter(s) to apply but the validator only has {} left.", format!("{given}").purple(), format!("{expected}").purple())]
+    #[diagnostic(code("aiken::blueprint::apply::too_many_parameters"))]
+    TooManyParameters { expected: usize, given: usize },
+
+    #[error("I was given {} parameter(s) to apply to the {} validator {} but it has {} left.", format!("{given}").purple(), purpose.purple(), title.purple().bold(), format!("{expected}").purple())]
+    #[diagnostic(code("aiken::blueprint::apply::wrong_number_of_parameters"))]
+    #[diagnostic(help(
+        "A parameters spec must list all the parameters left of each validator it mentions."
+    ))]
+    WrongNumberOfParameters {
+        title: String,
+        purpose: Purpose,
+        expected: usize,
+        given: usize,
+    },
+
+    #[error("I couldn't make sense of the parameters spec: {reason}.")]
+    #[diagnostic(code("aiken::blueprint::apply::invalid_spec"))]
+    #[diagnostic(help(r#"A parameters spec is a list of validators along with their parameters, as Plutus data in the detailed JSON schema, for example:
+
+[{{ "title": "foo", "purpose": "spend", "parameters": [{{ "int": 42 }}] }}]
+
+The purpose may be left out when there's only one validator with that title."#))]
+    InvalidParametersSpec { reason: String },
+
+    #[error("The compiled code of the {} validator {} doesn't look like a validator: {reason}.", purpose.purple(), title.purple().bold())]
+    #[diagnostic(code("aiken::blueprint::invalid::program"))]
+    #[diagnostic(help("A validator should always be a function of its arguments. This is most likely a bug in the code generator; please report it along with the validator's source."))]
+    MalformedValidator {
+        title: String,
+        purpose: Purpose,
+        reason: String,
+    },
+
     #[error("I couldn't compute the address of the given validator because it's parameterized by {} parameter(s)!", format!("{n}").purple())]
     #[diagnostic(code("aiken::blueprint::address::parameterized"))]
     #[diagnostic(help("I can only compute addresses of validators that are fully applied. For example, a {keyword_spend} validator must have exactly 3 arguments: a datum, a redeemer and a context. If it has more, they need to be provided beforehand and applied directly in the validator. Applying parameters change the validator's compiled code, and thus the address.\n\nThis is why I need you to apply parmeters first.", keyword_spend = "spend".purple()))]
     ParameterizedValidator { n: usize },
+
+    #[error("I couldn't build a pointer with a {field} of {}, since it can't be larger than {}.", value.to_string().purple(), max.to_string().purple())]
+    #[diagnostic(code("aiken::blueprint::address::pointer"))]
+    InvalidPointer {
+        field: &'static str,
+        value: u64,
+        max: u64,
+    },
 }
 
 pub fn assert_return_bool(module: &CheckedModule, def: &TypedFunction) -> Result<(), Error> {
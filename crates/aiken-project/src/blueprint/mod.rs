@@ -25,6 +25,16 @@ pub struct Preamble {
     pub license: Option<String>,
 }
 
+/// Parameters to apply to one of the validators of a blueprint, as listed in a parameters
+/// spec file. Parameters are Plutus data in the detailed JSON schema.
+#[derive(Debug, PartialEq, Clone, serde::Deserialize)]
+pub struct ParametersSpec {
+    pub title: String,
+    #[serde(default)]
+    pub purpose: Option<Purpose>,
+    pub parameters: Vec<serde_json::Value>,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum LookupResult<'a, T> {
     One(&'a T),
@@ -51,6 +61,63 @@ impl Blueprint<Schema> {
             validators: validators?,
         })
     }
+
+    /// Check that every validator decodes back and reduces to a lambda. This evaluates every
+    /// validator, so it's kept separate from [`Blueprint::new`] and only run on request.
+    pub fn verify(&self) -> Result<(), Error> {
+        self.validators.iter().try_for_each(Validator::verify)
+    }
+
+    /// TypeScript declarations of the datum, redeemer and parameters of every validator,
+    /// named after the validator and its purpose (e.g. `FooSpendDatum`).
+    pub fn to_typescript(&self) -> String {
+        let mut declarations = Vec::new();
+
+        for validator in &self.validators {
+            let prefix = format!(
+                "{}{}",
+                pascal_case(&validator.title),
+                pascal_case(&validator.purpose.to_string())
+            );
+
+            if let Some(datum) = &validator.datum {
+                declarations.push(
+                    datum
+                        .annotated
+                        .to_typescript_declaration(&format!("{prefix}Datum")),
+                );
+            }
+
+            declarations.push(
+                validator
+                    .redeemer
+                    .annotated
+                    .to_typescript_declaration(&format!("{prefix}Redeemer")),
+            );
+
+            for (ix, parameter) in validator.parameters.iter().enumerate() {
+                declarations.push(
+                    parameter
+                        .annotated
+                        .to_typescript_declaration(&format!("{prefix}Parameter{ix}")),
+                );
+            }
+        }
+
+        declarations.join("\n")
+    }
+}
+
+fn pascal_case(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
 }
 
 impl<T> Blueprint<T>
@@ -106,6 +173,203 @@ where
     }
 }
 
+impl Blueprint<serde_json::Value> {
+    /// Decode a blueprint as found in a `plutus.json` file. Its structure is checked against
+    /// CIP-0057 first, so that a malformed (e.g. hand-edited) blueprint is reported along with
+    /// the path of the offending field rather than with a bare deserialization error.
+    pub fn from_json(value: serde_json::Value) -> Result<Self, Error> {
+        let blueprint = expect_object(&value, "")?;
+
+        let preamble = expect_field(blueprint, "", "preamble")?;
+        let preamble = expect_object(preamble, "preamble")?;
+        for field in ["title", "version"] {
+            expect_string(
+                expect_field(preamble, "preamble", field)?,
+                &format!("preamble.{field}"),
+            )?;
+        }
+        for field in ["description", "license"] {
+            if let Some(value) = preamble.get(field) {
+                expect_string(value, &format!("preamble.{field}"))?;
+            }
+        }
+
+        let validators = expect_field(blueprint, "", "validators")?;
+        for (index, validator) in expect_array(validators, "validators")?.iter().enumerate() {
+            check_validator(validator, &format!("validators[{index}]"))?;
+        }
+
+        serde_json::from_value(value).map_err(|error| Error::InvalidBlueprint {
+            path: "".to_string(),
+            reason: error.to_string(),
+        })
+    }
+}
+
+fn check_validator(value: &serde_json::Value, path: &str) -> Result<(), Error> {
+    let validator = expect_object(value, path)?;
+
+    expect_string(
+        expect_field(validator, path, "title")?,
+        &format!("{path}.title"),
+    )?;
+
+    let purpose = expect_field(validator, path, "purpose")?;
+    let purpose_path = format!("{path}.purpose");
+    if !["spend", "mint", "withdraw", "publish"].contains(&expect_string(purpose, &purpose_path)?) {
+        return Err(Error::InvalidBlueprint {
+            path: purpose_path,
+            reason: "expected one of 'spend', 'mint', 'withdraw' or 'publish'".to_string(),
+        });
+    }
+
+    if let Some(datum) = validator.get("datum") {
+        check_annotated_schema(datum, &format!("{path}.datum"))?;
+    }
+
+    check_annotated_schema(
+        expect_field(validator, path, "redeemer")?,
+        &format!("{path}.redeemer"),
+    )?;
+
+    if let Some(parameters) = validator.get("parameters") {
+        let parameters_path = format!("{path}.parameters");
+        for (index, parameter) in expect_array(parameters, &parameters_path)?
+            .iter()
+            .enumerate()
+        {
+            check_annotated_schema(parameter, &format!("{parameters_path}[{index}]"))?;
+        }
+    }
+
+    let code_path = format!("{path}.compiledCode");
+    let code = expect_string(expect_field(validator, path, "compiledCode")?, &code_path)?;
+    if hex::decode(code).is_err() {
+        return Err(Error::InvalidBlueprint {
+            path: code_path,
+            reason: "expected a hex-encoded string".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn check_annotated_schema(value: &serde_json::Value, path: &str) -> Result<(), Error> {
+    let annotated = expect_object(value, path)?;
+
+    for field in ["title", "description"] {
+        if let Some(value) = annotated.get(field) {
+            expect_string(value, &format!("{path}.{field}"))?;
+        }
+    }
+
+    check_schema(value, path)
+}
+
+fn check_schema(value: &serde_json::Value, path: &str) -> Result<(), Error> {
+    let schema = expect_object(value, path)?;
+
+    if let Some(constructors) = schema.get("anyOf") {
+        let constructors_path = format!("{path}.anyOf");
+        for (index, constructor) in expect_array(constructors, &constructors_path)?
+            .iter()
+            .enumerate()
+        {
+            check_annotated_schema(constructor, &format!("{constructors_path}[{index}]"))?;
+        }
+        return Ok(());
+    }
+
+    let data_type = match schema.get("dataType") {
+        Some(data_type) => expect_string(data_type, &format!("{path}.dataType"))?,
+        // Opaque data, or a reference to a definition.
+        None => return Ok(()),
+    };
+
+    let sub_schemas: &[&str] = match data_type {
+        "integer" | "bytes" | "#unit" | "#boolean" | "#integer" | "#bytes" | "#string" => &[],
+        "list" | "#list" => &["items"],
+        "map" => &["keys", "values"],
+        "#pair" => &["left", "right"],
+        "constructor" => {
+            let index_path = format!("{path}.index");
+            if !expect_field(schema, path, "index")?.is_u64() {
+                return Err(Error::InvalidBlueprint {
+                    path: index_path,
+                    reason: "expected a non-negative integer".to_string(),
+                });
+            }
+
+            let fields_path = format!("{path}.fields");
+            for (index, field) in expect_array(expect_field(schema, path, "fields")?, &fields_path)?
+                .iter()
+                .enumerate()
+            {
+                check_annotated_schema(field, &format!("{fields_path}[{index}]"))?;
+            }
+
+            &[]
+        }
+        unknown => {
+            return Err(Error::InvalidBlueprint {
+                path: format!("{path}.dataType"),
+                reason: format!("unknown data type '{unknown}'"),
+            })
+        }
+    };
+
+    for field in sub_schemas {
+        check_annotated_schema(
+            expect_field(schema, path, field)?,
+            &format!("{path}.{field}"),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn expect_field<'a>(
+    object: &'a serde_json::Map<String, serde_json::Value>,
+    path: &str,
+    field: &str,
+) -> Result<&'a serde_json::Value, Error> {
+    object.get(field).ok_or_else(|| Error::InvalidBlueprint {
+        path: if path.is_empty() {
+            field.to_string()
+        } else {
+            format!("{path}.{field}")
+        },
+        reason: "missing required field".to_string(),
+    })
+}
+
+fn expect_object<'a>(
+    value: &'a serde_json::Value,
+    path: &str,
+) -> Result<&'a serde_json::Map<String, serde_json::Value>, Error> {
+    value.as_object().ok_or_else(|| Error::InvalidBlueprint {
+        path: path.to_string(),
+        reason: "expected an object".to_string(),
+    })
+}
+
+fn expect_array<'a>(
+    value: &'a serde_json::Value,
+    path: &str,
+) -> Result<&'a Vec<serde_json::Value>, Error> {
+    value.as_array().ok_or_else(|| Error::InvalidBlueprint {
+        path: path.to_string(),
+        reason: "expected an array".to_string(),
+    })
+}
+
+fn expect_string<'a>(value: &'a serde_json::Value, path: &str) -> Result<&'a str, Error> {
+    value.as_str().ok_or_else(|| Error::InvalidBlueprint {
+        path: path.to_string(),
+        reason: "expected a string".to_string(),
+    })
+}
+
 impl Display for Blueprint<Schema> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let s = serde_json::to_string_pretty(self).map_err(|_| fmt::Error)?;
@@ -180,4 +444,68 @@ mod test {
             }),
         );
     }
+
+    #[test]
+    fn from_json_missing_compiled_code() {
+        let blueprint = json!({
+            "preamble": {
+                "title": "Foo",
+                "version": "1.0.0"
+            },
+            "validators": [
+                {
+                    "title": "foo",
+                    "purpose": "spend",
+                    "datum": { "dataType": "integer" },
+                    "redeemer": { "dataType": "bytes" },
+                    "compiledCode": "581d0100003232322225333004"
+                },
+                {
+                    "title": "bar",
+                    "purpose": "mint",
+                    "redeemer": {
+                        "anyOf": [
+                            {
+                                "dataType": "constructor",
+                                "index": 0,
+                                "fields": [{ "dataType": "integer" }]
+                            }
+                        ]
+                    }
+                }
+            ]
+        });
+
+        assert!(matches!(
+            Blueprint::from_json(blueprint),
+            Err(Error::InvalidBlueprint { path, reason })
+                if path == "validators[1].compiledCode" && reason == "missing required field"
+        ));
+    }
+
+    #[test]
+    fn from_json_invalid_schema() {
+        let blueprint = json!({
+            "preamble": {
+                "title": "Foo",
+                "version": "1.0.0"
+            },
+            "validators": [
+                {
+                    "title": "foo",
+                    "purpose": "mint",
+                    "redeemer": {
+                        "dataType": "list",
+                        "items": { "dataType": "float" }
+                    },
+                    "compiledCode": "00"
+                }
+            ]
+        });
+
+        assert!(matches!(
+            Blueprint::from_json(blueprint),
+            Err(Error::InvalidBlueprint { path, .. }) if path == "validators[0].redeemer.items.dataType"
+        ));
+    }
 }
@@ -15,6 +15,14 @@ use std::{
     fmt::{self, Display},
     sync::Arc,
 };
+use uplc::{
+    machine::runtime::{convert_constr_to_tag, ANY_TAG},
+    prng::Prng,
+    BigInt, Constr, KeyValuePairs, PlutusData,
+};
+
+/// Maximum number of elements in sampled lists and maps.
+pub const MAX_SAMPLE_LENGTH: u64 = 4;
 
 #[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Annotated<T> {
@@ -227,7 +235,7 @@ impl Annotated<Schema> {
                     annotated,
                 })
             }
-            Type::Var { tipo } => match tipo.borrow().deref() {
+            Type::Var { tipo } => match tipo.read().unwrap().deref() {
                 TypeVar::Link { tipo } => Annotated::from_type(modules, tipo, type_parameters),
                 TypeVar::Generic { id } => {
                     let tipo = type_parameters
@@ -333,6 +341,220 @@ impl Data {
     }
 }
 
+impl Schema {
+    /// Upper bound, in bytes, of the CBOR serialisation of any value of this schema, or
+    /// `None` when values can be arbitrarily large. Only Plutus data have a serialised
+    /// form (e.g. as datums), so primitive UPLC schemas are considered unbounded.
+    pub fn max_size(&self) -> Option<usize> {
+        match self {
+            Schema::Data(Some(data)) => data.max_size(),
+            _ => None,
+        }
+    }
+}
+
+impl Data {
+    /// Upper bound, in bytes, of the CBOR serialisation of any value of this schema, or
+    /// `None` when values can be arbitrarily large. Integers are unbounded too, since
+    /// they may be serialised as big numbers.
+    pub fn max_size(&self) -> Option<usize> {
+        match self {
+            Data::Integer | Data::Bytes | Data::List(_) | Data::Map(..) => None,
+            Data::AnyOf(constructors) => constructors
+                .iter()
+                .map(|constructor| constructor.annotated.max_size())
+                .try_fold(0, |max, size| size.map(|size| max.max(size))),
+        }
+    }
+}
+
+impl Constructor {
+    /// Size of the constructor's tag and fields, following the encoding of `Constr` in
+    /// Plutus data: compact tags for indexes below 128, and an explicit index otherwise.
+    /// Fields are an indefinite-length array, unless there's none.
+    pub fn max_size(&self) -> Option<usize> {
+        let tag = match self.index {
+            0..=6 => 2,
+            7..=127 => 3,
+            index => 3 + cbor_uint_size(index),
+        };
+
+        let fields = if self.fields.is_empty() {
+            1
+        } else {
+            self.fields
+                .iter()
+                .map(|field| field.annotated.max_size())
+                .sum::<Option<usize>>()?
+                + 2
+        };
+
+        Some(tag + fields)
+    }
+}
+
+impl Schema {
+    /// A random value of this schema, to be used as a datum or redeemer. Primitive UPLC
+    /// schemas can't be represented as Plutus data, so any data is drawn for those.
+    pub fn sample(&self, prng: &mut Prng) -> PlutusData {
+        match self {
+            Schema::Data(Some(data)) => data.sample(prng),
+            _ => prng.data(),
+        }
+    }
+}
+
+impl Data {
+    /// A random value of this schema. Lists and maps have at most [`MAX_SAMPLE_LENGTH`]
+    /// elements, so that values stay reasonably small.
+    pub fn sample(&self, prng: &mut Prng) -> PlutusData {
+        match self {
+            Data::Integer => PlutusData::BigInt(BigInt::Int(prng.integer().into())),
+            Data::Bytes => PlutusData::BoundedBytes(prng.bytes().into()),
+            Data::List(items) => PlutusData::Array(
+                (0..prng.below(MAX_SAMPLE_LENGTH + 1))
+                    .map(|_| items.sample(prng))
+                    .collect(),
+            ),
+            Data::Map(keys, values) => PlutusData::Map(KeyValuePairs::Def(
+                (0..prng.below(MAX_SAMPLE_LENGTH + 1))
+                    .map(|_| (keys.sample(prng), values.sample(prng)))
+                    .collect(),
+            )),
+            Data::AnyOf(constructors) => {
+                let constructor = &constructors[prng.below(constructors.len() as u64) as usize];
+
+                constructor.annotated.sample(prng)
+            }
+        }
+    }
+}
+
+impl Constructor {
+    pub fn sample(&self, prng: &mut Prng) -> PlutusData {
+        let index = self.index as u64;
+
+        PlutusData::Constr(Constr {
+            tag: convert_constr_to_tag(index).unwrap_or(ANY_TAG),
+            any_constructor: convert_constr_to_tag(index).map_or(Some(index), |_| None),
+            fields: self
+                .fields
+                .iter()
+                .map(|field| field.annotated.sample(prng))
+                .collect(),
+        })
+    }
+}
+
+fn cbor_uint_size(n: usize) -> usize {
+    match n {
+        0..=23 => 1,
+        24..=0xff => 2,
+        0x100..=0xffff => 3,
+        0x10000..=0xffff_ffff => 5,
+        _ => 9,
+    }
+}
+
+impl Schema {
+    /// TypeScript type of the values this schema describes, as handled off-chain: integers
+    /// are `bigint`s, byte arrays hex-encoded `string`s, and constructors objects carrying
+    /// their index and fields.
+    pub fn to_typescript(&self) -> String {
+        match self {
+            Schema::Unit => "null".to_string(),
+            Schema::Boolean => "boolean".to_string(),
+            Schema::Integer => "bigint".to_string(),
+            Schema::Bytes | Schema::String => "string".to_string(),
+            Schema::Pair(left, right) => {
+                format!("[{}, {}]", left.to_typescript(), right.to_typescript())
+            }
+            Schema::List(elems) => format!(
+                "[{}]",
+                elems
+                    .iter()
+                    .map(Data::to_typescript)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Schema::Data(None) => "unknown".to_string(),
+            Schema::Data(Some(data)) => data.to_typescript(),
+        }
+    }
+
+    /// An exported TypeScript declaration of the schema's type: an interface for a single
+    /// constructor, and a type alias otherwise.
+    pub fn to_typescript_declaration(&self, name: &str) -> String {
+        match self {
+            Schema::Data(Some(Data::AnyOf(constructors))) if constructors.len() == 1 => {
+                let constructor = &constructors[0].annotated;
+                format!(
+                    "export interface {name} {{\n  constructor: {};\n  fields: {};\n}}\n",
+                    constructor.index,
+                    constructor.fields_to_typescript()
+                )
+            }
+            _ => format!("export type {name} = {};\n", self.to_typescript()),
+        }
+    }
+}
+
+impl Data {
+    pub fn to_typescript(&self) -> String {
+        match self {
+            Data::Integer => "bigint".to_string(),
+            Data::Bytes => "string".to_string(),
+            Data::List(items) => format!("Array<{}>", items.to_typescript()),
+            Data::Map(keys, values) => {
+                format!("Map<{}, {}>", keys.to_typescript(), values.to_typescript())
+            }
+            Data::AnyOf(constructors) => constructors
+                .iter()
+                .map(|constructor| constructor.annotated.to_typescript())
+                .collect::<Vec<_>>()
+                .join(" | "),
+        }
+    }
+}
+
+impl Constructor {
+    pub fn to_typescript(&self) -> String {
+        format!(
+            "{{ constructor: {}; fields: {} }}",
+            self.index,
+            self.fields_to_typescript()
+        )
+    }
+
+    /// Fields as a tuple, labelled with the fields' names when they all have one.
+    fn fields_to_typescript(&self) -> String {
+        let labels = self
+            .fields
+            .iter()
+            .map(|field| {
+                // Unlabelled fields may still be titled after their type, which starts with an
+                // uppercase letter.
+                field
+                    .title
+                    .as_ref()
+                    .filter(|title| title.starts_with(|c: char| c.is_ascii_lowercase()))
+            })
+            .collect::<Option<Vec<_>>>();
+
+        let fields = self
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(ix, field)| match &labels {
+                Some(labels) => format!("{}: {}", labels[ix], field.annotated.to_typescript()),
+                None => field.annotated.to_typescript(),
+            })
+            .collect::<Vec<_>>();
+
+        format!("[{}]", fields.join(", "))
+    }
+}
+
 impl Default for Schema {
     fn default() -> Self {
         Schema::Unit
@@ -559,7 +781,7 @@ fn collect_type_parameters<'a>(
 
     for (index, generic) in generics.iter().enumerate() {
         match &**generic {
-            Type::Var { tipo } => match *tipo.borrow() {
+            Type::Var { tipo } => match *tipo.read().unwrap() {
                 TypeVar::Generic { id } => {
                     type_parameters.insert(id, applications.get(index).unwrap());
                 }
@@ -784,4 +1006,145 @@ pub mod test {
             }),
         )
     }
+
+    fn constr(index: u64, fields: Vec<uplc::PlutusData>) -> uplc::PlutusData {
+        let (tag, any_constructor) = match index {
+            0..=6 => (121 + index, None),
+            7..=127 => (1280 + index - 7, None),
+            _ => (102, Some(index)),
+        };
+
+        uplc::PlutusData::Constr(uplc::Constr {
+            tag,
+            any_constructor,
+            fields,
+        })
+    }
+
+    fn cbor_size(data: &uplc::PlutusData) -> usize {
+        uplc::plutus_data_to_bytes(data).unwrap().len()
+    }
+
+    #[test]
+    fn max_size_constructors() {
+        let unit = |index| {
+            Annotated::from(Data::AnyOf(vec![Constructor {
+                index,
+                fields: vec![],
+            }
+            .into()]))
+        };
+
+        for index in [0, 6, 7, 127, 128, 1000] {
+            let schema = unit(index);
+            assert_eq!(
+                schema.annotated.max_size(),
+                Some(cbor_size(&constr(index as u64, vec![]))),
+                "index {index}"
+            );
+        }
+
+        let schema = Schema::Data(Some(Data::AnyOf(vec![
+            Constructor {
+                index: 0,
+                fields: vec![],
+            }
+            .into(),
+            Constructor {
+                index: 1,
+                fields: vec![unit(0), unit(200)],
+            }
+            .into(),
+        ])));
+
+        assert_eq!(
+            schema.max_size(),
+            Some(cbor_size(&constr(
+                1,
+                vec![constr(0, vec![]), constr(200, vec![])]
+            )))
+        );
+    }
+
+    #[test]
+    fn max_size_unbounded() {
+        assert_eq!(Schema::Data(None).max_size(), None);
+        assert_eq!(Schema::Data(Some(Data::Bytes)).max_size(), None);
+        assert_eq!(Schema::Data(Some(Data::Integer)).max_size(), None);
+        assert_eq!(
+            Schema::Data(Some(Data::List(Box::new(Data::Integer)))).max_size(),
+            None
+        );
+
+        let schema = Schema::Data(Some(Data::AnyOf(vec![
+            Constructor {
+                index: 0,
+                fields: vec![],
+            }
+            .into(),
+            Constructor {
+                index: 1,
+                fields: vec![Data::Bytes.into()],
+            }
+            .into(),
+        ])));
+
+        assert_eq!(schema.max_size(), None);
+    }
+
+    #[test]
+    fn typescript_constructor_with_fields() {
+        let schema = Schema::Data(Some(Data::AnyOf(vec![Annotated {
+            title: Some("Datum".to_string()),
+            description: None,
+            annotated: Constructor {
+                index: 0,
+                fields: vec![
+                    Annotated {
+                        title: Some("owner".to_string()),
+                        description: None,
+                        annotated: Data::Bytes,
+                    },
+                    Annotated {
+                        title: Some("amounts".to_string()),
+                        description: None,
+                        annotated: Data::Map(Box::new(Data::Bytes), Box::new(Data::Integer)),
+                    },
+                ],
+            },
+        }])));
+
+        assert_eq!(
+            schema.to_typescript_declaration("Datum"),
+            "export interface Datum {\n  constructor: 0;\n  fields: [owner: string, amounts: Map<string, bigint>];\n}\n"
+        );
+    }
+
+    #[test]
+    fn typescript_union() {
+        let schema = Schema::Data(Some(Data::AnyOf(vec![
+            Annotated {
+                title: Some("Some".to_string()),
+                description: None,
+                annotated: Constructor {
+                    index: 0,
+                    fields: vec![Annotated {
+                        title: Some("Optional".to_string()),
+                        description: None,
+                        annotated: Data::List(Box::new(Data::Integer)),
+                    }],
+                },
+            },
+            Constructor {
+                index: 1,
+                fields: vec![],
+            }
+            .into(),
+        ])));
+
+        assert_eq!(
+            schema.to_typescript_declaration("Redeemer"),
+            "export type Redeemer = { constructor: 0; fields: [Array<bigint>] } | { constructor: 1; fields: [] };\n"
+        );
+    }
 }
@@ -10,7 +10,13 @@ use std::{
     collections::HashMap,
     fmt::{self, Display},
 };
-use uplc::ast::{DeBruijn, Program, Term};
+use uplc::{
+    ast::{DeBruijn, Program, Term},
+    machine::cost_model::ExBudget,
+    prng::Prng,
+    tx::property::Spend,
+    PlutusData,
+};
 
 #[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Validator<T> {
@@ -123,6 +129,30 @@ impl Validator<Schema> {
                 .unwrap(),
         })
     }
+
+    /// Spending transactions for this validator, with a datum and a redeemer drawn from their
+    /// schemas. Any data is drawn when there's no datum schema. `expect_success` tells whether
+    /// the validator ought to accept a given datum and redeemer.
+    pub fn spend_generator<E>(
+        &self,
+        expect_success: E,
+    ) -> Spend<'_, impl FnMut(&mut Prng) -> (PlutusData, PlutusData) + '_, E>
+    where
+        E: Fn(&PlutusData, &PlutusData) -> bool,
+    {
+        Spend::new(
+            &self.program,
+            move |prng: &mut Prng| {
+                let datum = match &self.datum {
+                    Some(datum) => datum.annotated.sample(prng),
+                    None => prng.data(),
+                };
+
+                (datum, self.redeemer.annotated.sample(prng))
+            },
+            expect_success,
+        )
+    }
 }
 
 impl<T> Validator<T>
@@ -142,6 +172,49 @@ where
             }
         }
     }
+
+    /// Apply arguments to the validator's parameters, in order. Nothing is applied when there
+    /// are more arguments than parameters left.
+    pub fn apply_all(self, args: &[Term<DeBruijn>]) -> Result<Self, Error> {
+        let remaining = self.parameters.len();
+
+        if args.len() > remaining {
+            return Err(Error::TooManyParameters {
+                expected: remaining,
+                given: args.len(),
+            });
+        }
+
+        args.iter()
+            .try_fold(self, |validator, arg| validator.apply(arg))
+    }
+
+    /// Sanity-check the compiled code: it must survive a flat roundtrip and reduce to a
+    /// lambda, since a validator is always applied to at least its redeemer and context.
+    pub fn verify(&self) -> Result<(), Error> {
+        let malformed = |reason: String| Error::MalformedValidator {
+            title: self.title.clone(),
+            purpose: self.purpose.clone(),
+            reason,
+        };
+
+        let bytes = self
+            .program
+            .to_flat()
+            .map_err(|e| malformed(format!("it can't be encoded ({e})")))?;
+
+        let program = Program::<DeBruijn>::from_flat(&bytes)
+            .map_err(|e| malformed(format!("it can't be decoded ({e})")))?;
+
+        match program.eval(ExBudget::default()).0 {
+            Ok(Term::Lambda { .. }) => Ok(()),
+            Ok(term) => Err(malformed(format!(
+                "it reduces to {} instead of a lambda",
+                term.to_pretty()
+            ))),
+            Err(e) => Err(malformed(format!("it fails to evaluate ({e})"))),
+        }
+    }
 }
 
 impl Purpose {
@@ -192,8 +265,18 @@ mod test {
     };
     use assert_json_diff::assert_json_eq;
     use indexmap::IndexMap;
+    use pallas::ledger::primitives::babbage::Redeemer;
+    use pallas_traverse::{Era, MultiEraTx};
     use serde_json::{self, json};
     use std::{collections::HashMap, path::PathBuf};
+    use uplc::{
+        ast::Constant,
+        tx::{
+            property::{self, Counterexample, Settings},
+            script_context::SlotConfig,
+        },
+        BigInt,
+    };
 
     // TODO: Possible refactor this out of the module and have it used by `Project`. The idea would
     // be to make this struct below the actual project, and wrap it in another metadata struct
@@ -300,6 +383,186 @@ mod test {
         assert_json_eq!(serde_json::to_value(&validator).unwrap(), json);
     }
 
+    #[test]
+    fn validator_size() {
+        let mut project = TestProject::new();
+
+        let modules = CheckedModules::singleton(project.check(project.parse(
+            r#"
+            fn mint(redeemer: Data, ctx: Data) {
+                True
+            }
+            "#,
+        )));
+
+        let mut generator = modules.new_generator(
+            &project.functions,
+            &project.data_types,
+            &project.module_types,
+        );
+
+        let (validator, def) = modules.validators().next().unwrap();
+
+        let validator =
+            Validator::from_checked_module(&modules, &mut generator, validator, def).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&validator).unwrap()["size"],
+            json!(validator.program.to_cbor().unwrap().len())
+        );
+    }
+
+    #[test]
+    fn validator_verify() {
+        let mut project = TestProject::new();
+
+        let modules = CheckedModules::singleton(project.check(project.parse(
+            r#"
+            fn mint(redeemer: Data, ctx: Data) {
+                True
+            }
+            "#,
+        )));
+
+        let mut generator = modules.new_generator(
+            &project.functions,
+            &project.data_types,
+            &project.module_types,
+        );
+
+        let (validator, def) = modules.validators().next().unwrap();
+
+        let validator =
+            Validator::from_checked_module(&modules, &mut generator, validator, def).unwrap();
+
+        assert!(validator.verify().is_ok());
+
+        let constant = Validator {
+            program: Program {
+                version: (1, 0, 0),
+                term: Term::Constant(Constant::Bool(true).into()),
+            },
+            ..validator
+        };
+
+        assert!(matches!(
+            constant.verify(),
+            Err(Error::MalformedValidator { title, .. }) if title == "test_module"
+        ));
+    }
+
+    fn spend_property(source_code: &str, expect_success: fn(i64) -> bool) -> Vec<Counterexample> {
+        let mut project = TestProject::new();
+
+        let modules = CheckedModules::singleton(project.check(project.parse(source_code)));
+
+        let mut generator = modules.new_generator(
+            &project.functions,
+            &project.data_types,
+            &project.module_types,
+        );
+
+        let (validator, def) = modules.validators().next().unwrap();
+
+        let validator =
+            Validator::from_checked_module(&modules, &mut generator, validator, def).unwrap();
+
+        let settings = Settings {
+            cost_mdls: None,
+            initial_budget: None,
+            slot_config: &SlotConfig::default(),
+        };
+
+        let mut spend = validator.spend_generator(|_, redeemer| match redeemer {
+            PlutusData::BigInt(BigInt::Int(n)) => expect_success(i128::from(*n) as i64),
+            _ => false,
+        });
+
+        property::check(&mut spend, 0, 50, &settings)
+    }
+
+    #[test]
+    fn validator_spend_property() {
+        let accepting = r#"
+            fn spend(datum: Int, redeemer: Int, ctx: Data) {
+                True
+            }
+            "#;
+
+        assert!(spend_property(accepting, |_| true).is_empty());
+
+        let rejecting = r#"
+            fn spend(datum: Int, redeemer: Int, ctx: Data) {
+                redeemer >= 0
+            }
+            "#;
+
+        assert!(spend_property(rejecting, |redeemer| redeemer >= 0).is_empty());
+
+        let counterexamples = spend_property(rejecting, |_| true);
+
+        assert!(!counterexamples.is_empty());
+
+        for counterexample in counterexamples {
+            assert!(counterexample.error.is_some());
+
+            let tx = MultiEraTx::decode(Era::Babbage, &counterexample.case.tx).unwrap();
+
+            let redeemer = &tx.as_babbage().unwrap().transaction_witness_set.redeemer;
+
+            assert!(matches!(
+                redeemer.as_deref(),
+                Some([Redeemer { data: PlutusData::BigInt(BigInt::Int(n)), .. }]) if i128::from(*n) < 0
+            ));
+        }
+    }
+
+    #[test]
+    fn validator_apply_all() {
+        let mut project = TestProject::new();
+
+        let modules = CheckedModules::singleton(project.check(project.parse(
+            r#"
+            fn mint(utxo_ref: Int, redeemer: Data, ctx: Data) {
+                True
+            }
+            "#,
+        )));
+
+        let mut generator = modules.new_generator(
+            &project.functions,
+            &project.data_types,
+            &project.module_types,
+        );
+
+        let (validator, def) = modules.validators().next().unwrap();
+
+        let validator =
+            Validator::from_checked_module(&modules, &mut generator, validator, def).unwrap();
+
+        let arg = Term::Constant(Constant::Integer(42.into()).into());
+
+        assert!(matches!(
+            validator.clone().apply_all(&[arg.clone(), arg.clone()]),
+            Err(Error::TooManyParameters {
+                expected: 1,
+                given: 2
+            })
+        ));
+
+        let applied = validator.apply_all(std::slice::from_ref(&arg)).unwrap();
+
+        assert!(applied.parameters.is_empty());
+
+        assert!(matches!(
+            applied.apply_all(&[arg]),
+            Err(Error::TooManyParameters {
+                expected: 0,
+                given: 1
+            })
+        ));
+    }
+
     #[test]
     fn validator_mint_basic() {
         assert_validator(
@@ -316,6 +579,7 @@ mod test {
                 "title": "Data",
                 "description": "Any Plutus data."
               },
+              "size": 61,
               "compiledCode": "583b010000323232323232322253330054a22930b180080091129998030010a4c26600a6002600e0046660060066010004002ae695cdaab9f5742ae881"
             }),
         );
@@ -342,11 +606,53 @@ mod test {
                 "title": "Data",
                 "description": "Any Plutus data."
               },
+              "size": 66,
               "compiledCode": "5840010000323232323232322322253330074a22930b1bad0013001001222533300600214984cc014c004c01c008ccc00c00cc0200080055cd2b9b5573eae855d101"
             }),
         );
     }
 
+    #[test]
+    fn validator_mint_optional_redeemer() {
+        assert_validator(
+            r#"
+            fn mint(redeemer: Option<Int>, ctx: Data) {
+                True
+            }
+            "#,
+            json!({
+              "title": "test_module",
+              "purpose": "mint",
+              "hash": "afddc16c18e7d8de379fb9aad39b3d1b5afd27603e5ebac818432a72",
+              "redeemer": {
+                "title": "Optional",
+                "anyOf": [
+                  {
+                    "title": "Some",
+                    "description": "An optional value.",
+                    "dataType": "constructor",
+                    "index": 0,
+                    "fields": [
+                      {
+                        "dataType": "integer"
+                      }
+                    ]
+                  },
+                  {
+                    "title": "None",
+                    "description": "Nothing.",
+                    "dataType": "constructor",
+                    "index": 1,
+                    "fields": []
+                  }
+                ]
+              },
+              "size": 61,
+              "compiledCode": "583b010000323232323232322253330054a22930b180080091129998030010a4c26600a6002600e0046660060066010004002ae695cdaab9f5742ae881"
+            }),
+        );
+    }
+
     #[test]
     fn validator_spend() {
         assert_validator(
@@ -455,6 +761,7 @@ mod test {
                   }
                 ]
               },
+              "size": 61,
               "compiledCode": "583b0100003232323232323222253330064a22930b180080091129998030010a4c26600a6002600e0046660060066010004002ae695cdaab9f5742ae89"
             }),
         );
@@ -484,6 +791,7 @@ mod test {
               "redeemer": {
                 "dataType": "#string"
               },
+              "size": 87,
               "compiledCode": "585501000032323232323232232232253330084a22930b1b99375c002646466ec0c024008c024004c024004dd6000980080091129998030010a4c26600a6002600e0046660060066010004002ae695cdaab9f5742ae881"
             }),
         )
@@ -520,6 +828,7 @@ mod test {
                 "title": "Data",
                 "description": "Any Plutus data."
               },
+              "size": 66,
               "compiledCode": "5840010000323232323232322322253330074a22930b1bac0013001001222533300600214984cc014c004c01c008ccc00c00cc0200080055cd2b9b5573eae855d101"
             }),
         )
@@ -591,6 +900,7 @@ mod test {
                       }
                     ]
                   },
+                  "size": 61,
                   "compiledCode": "583b010000323232323232322253330054a22930b180080091129998030010a4c26600a6002600e0046660060066010004002ae695cdaab9f5742ae881"
                 }
             ),
@@ -638,6 +948,7 @@ mod test {
                       }
                     ]
                   },
+                  "size": 61,
                   "compiledCode": "583b010000323232323232322253330054a22930b180080091129998030010a4c26600a6002600e0046660060066010004002ae695cdaab9f5742ae881"
                 }
             ),
@@ -673,6 +984,7 @@ mod test {
                       "dataType": "integer"
                     }
                   },
+                  "size": 61,
                   "compiledCode": "583b010000323232323232322253330054a22930b180080091129998030010a4c26600a6002600e0046660060066010004002ae695cdaab9f5742ae881"
                 }
             ),
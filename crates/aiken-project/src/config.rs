@@ -1,19 +1,40 @@
 use crate::{package_name::PackageName, Error};
-use aiken_lang::ast::Span;
+use aiken_lang::ast::{Edition, Span};
 use miette::NamedSource;
 use serde::{Deserialize, Serialize};
-use std::{fmt::Display, fs, io, path::Path};
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
 
 #[derive(Deserialize, Serialize)]
 pub struct Config {
     pub name: PackageName,
     pub version: String,
+    /// Edition of the language the project is written for.
+    #[serde(default, with = "edition")]
+    pub edition: Edition,
     pub license: Option<String>,
     #[serde(default)]
     pub description: String,
     pub repository: Option<Repository>,
     #[serde(default)]
     pub dependencies: Vec<Dependency>,
+    /// Additional directories, relative to the project's root, in which to look for
+    /// dependencies before falling back to `build/packages`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub search_paths: Vec<PathBuf>,
+    /// Remove bindings that generated validators and tests never use, to make them smaller.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub prune_unused_lets: bool,
+    /// Inline bindings that generated validators and tests use only once.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub inline_single_use: bool,
+    /// Check that every built validator decodes back and reduces to a lambda. This evaluates
+    /// each validator once, hence off by default.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub verify_validators: bool,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -38,6 +59,21 @@ pub struct Dependency {
     pub source: Platform,
 }
 
+mod edition {
+    use aiken_lang::ast::Edition;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(edition: &Edition, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(edition.as_str())
+    }
+
+    pub fn deserialize<'a, D: Deserializer<'a>>(deserializer: D) -> Result<Edition, D::Error> {
+        let s = String::deserialize(deserializer)?;
+
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
 impl Display for Platform {
     fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::result::Result<(), ::std::fmt::Error> {
         match *self {
@@ -53,6 +89,7 @@ impl Config {
         Config {
             name: name.clone(),
             version: "0.0.0".to_string(),
+            edition: Edition::latest(),
             license: Some("Apache-2.0".to_string()),
             description: format!("Aiken contracts for project '{name}'"),
             repository: Some(Repository {
@@ -68,6 +105,10 @@ impl Config {
                 version: "main".to_string(),
                 source: Platform::Github,
             }],
+            search_paths: vec![],
+            prune_unused_lets: false,
+            inline_single_use: false,
+            verify_validators: false,
         }
     }
 
@@ -1,9 +1,15 @@
-use std::{collections::HashSet, fs, path::Path};
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
 
 use aiken_lang::ast::Span;
 use miette::NamedSource;
+use pallas::crypto::hash::Hasher;
 use serde::{Deserialize, Serialize};
 use tokio::time::Instant;
+use walkdir::WalkDir;
 
 use crate::{
     config::{Config, Dependency},
@@ -164,7 +170,7 @@ where
 
     let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio");
 
-    let (manifest, changed) = Manifest::load(
+    let (mut manifest, changed) = Manifest::load(
         runtime.handle().clone(),
         event_listener,
         config,
@@ -181,23 +187,139 @@ where
         &local,
         project_name,
         root_path,
+        config,
         event_listener,
     ))?;
 
-    if changed {
+    let locked = verify_integrity(&mut manifest, root_path, config)?;
+
+    if changed || locked {
         manifest.save(root_path)?;
     }
 
-    LocalPackages::from(&manifest).save(root_path)?;
+    let mut local = LocalPackages::from(&manifest);
+
+    local
+        .packages
+        .retain(|dep| vendored_package(root_path, config, &dep.name).is_none());
+
+    local.save(root_path)?;
 
     Ok(manifest)
 }
 
+/// Check the sources of each downloaded package against the hash recorded in the manifest,
+/// recording it for packages which don't have one yet. Returns whether any was recorded.
+/// Vendored packages are part of the project's own sources, and so aren't checked.
+fn verify_integrity(
+    manifest: &mut Manifest,
+    root_path: &Path,
+    config: &Config,
+) -> Result<bool, Error> {
+    let mut recorded = false;
+
+    for package in manifest.packages.iter_mut() {
+        if package.name == config.name
+            || vendored_package(root_path, config, &package.name).is_some()
+        {
+            continue;
+        }
+
+        let path = root_path.join(paths::build_deps_package(&package.name));
+
+        if !path.is_dir() {
+            continue;
+        }
+
+        let integrity = package_integrity(&path)?;
+
+        match &package.integrity {
+            Some(expected) if expected != &integrity => {
+                return Err(Error::PackageIntegrityMismatch {
+                    package: package.clone(),
+                    expected: expected.clone(),
+                    found: integrity,
+                })
+            }
+            Some(_) => (),
+            None => {
+                package.integrity = Some(integrity);
+                recorded = true;
+            }
+        }
+    }
+
+    Ok(recorded)
+}
+
+/// A blake2b-256 hash of a package's sources: every file in the package, along with its
+/// path relative to the package, in path order.
+pub fn package_integrity(package_path: &Path) -> Result<String, Error> {
+    let mut hasher = Hasher::<256>::new();
+
+    for entry in WalkDir::new(package_path).sort_by_file_name() {
+        let entry = entry.map_err(|error| Error::FileIo {
+            path: package_path.to_path_buf(),
+            error: error.into(),
+        })?;
+
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path();
+
+        let relative_path = path
+            .strip_prefix(package_path)
+            .unwrap_or(path)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let contents = fs::read(path).map_err(|error| Error::FileIo {
+            path: path.to_path_buf(),
+            error,
+        })?;
+
+        hasher.input(relative_path.as_bytes());
+        hasher.input(&[0]);
+        hasher.input(&(contents.len() as u64).to_be_bytes());
+        hasher.input(&contents);
+    }
+
+    Ok(hasher.finalize().to_string())
+}
+
+/// Locate the sources of a dependency. Packages vendored in one of the project's
+/// `search_paths` take precedence over the ones downloaded in `build/packages`.
+pub fn package_path(root_path: &Path, config: &Config, package_name: &PackageName) -> PathBuf {
+    vendored_package(root_path, config, package_name)
+        .unwrap_or_else(|| root_path.join(paths::build_deps_package(package_name)))
+}
+
+fn vendored_package(
+    root_path: &Path,
+    config: &Config,
+    package_name: &PackageName,
+) -> Option<PathBuf> {
+    config
+        .search_paths
+        .iter()
+        .map(|search_path| {
+            root_path
+                .join(search_path)
+                .join(paths::package_dir(package_name))
+        })
+        .find(|path| path.is_dir())
+}
+
 async fn fetch_missing_packages<T>(
     manifest: &Manifest,
     local: &LocalPackages,
     project_name: PackageName,
     root_path: &Path,
+    config: &Config,
     event_listener: &T,
 ) -> Result<(), Error>
 where
@@ -208,6 +330,7 @@ where
     let mut missing = local
         .missing_local_packages(manifest, &project_name)
         .into_iter()
+        .filter(|package| vendored_package(root_path, config, &package.name).is_none())
         .map(|package| {
             count += 1;
             package
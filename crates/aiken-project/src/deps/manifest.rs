@@ -94,12 +94,16 @@ pub struct Package {
     pub version: String,
     pub requirements: Vec<String>,
     pub source: Platform,
+    /// A hash of the package's sources, recorded the first time the package is used and
+    /// checked every time after that. See [`super::package_integrity`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<String>,
 }
 
 fn resolve_versions<T>(
     _runtime: tokio::runtime::Handle,
     config: &Config,
-    _manifest: Option<&Manifest>,
+    manifest: Option<&Manifest>,
     event_listener: &T,
 ) -> Result<Manifest, Error>
 where
@@ -129,6 +133,16 @@ where
                 version: dep.version.clone(),
                 requirements: vec![],
                 source: dep.source,
+                // Packages that didn't change are still expected to have the same sources.
+                integrity: manifest.and_then(|manifest| {
+                    manifest
+                        .packages
+                        .iter()
+                        .find(|p| {
+                            p.name == dep.name && p.version == dep.version && p.source == dep.source
+                        })
+                        .and_then(|p| p.integrity.clone())
+                }),
             })
             .collect(),
         requirements: config.dependencies.clone(),
@@ -13,14 +13,20 @@ use pulldown_cmark as markdown;
 use serde::Serialize;
 use serde_json as json;
 use std::{
-    path::{Path, PathBuf},
+    collections::{hash_map::DefaultHasher, HashSet},
+    fs,
+    hash::{Hash, Hasher},
+    io,
+    path::{Component, Path, PathBuf},
     sync::Arc,
-    time::{Duration, SystemTime},
 };
 
 const MAX_COLUMNS: isize = 999;
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Name of the file, under the docs' destination, listing the files generated by the last run.
+const MANIFEST: &str = ".aiken-docs";
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct DocFile {
     pub path: PathBuf,
@@ -43,7 +49,7 @@ struct ModuleTemplate<'a> {
     constants: Vec<DocConstant>,
     documentation: String,
     source: &'a DocLink,
-    timestamp: String,
+    fingerprint: String,
 }
 
 impl<'a> ModuleTemplate<'a> {
@@ -67,7 +73,7 @@ struct PageTemplate<'a> {
     modules: &'a Vec<DocLink>,
     content: String,
     source: &'a DocLink,
-    timestamp: &'a str,
+    fingerprint: &'a str,
 }
 
 impl<'a> PageTemplate<'a> {
@@ -92,9 +98,16 @@ impl DocLink {
 ///
 /// The documentation is built using template files located at the root of this crate.
 /// With the documentation, we also build a client-side search index to ease navigation
-/// across multiple modules.
-pub fn generate_all(root: &Path, config: &Config, modules: Vec<&CheckedModule>) -> Vec<DocFile> {
-    let timestamp = new_timestamp();
+/// across multiple modules, as well as a lightweight `search-index.json` listing every
+/// documented symbol and where to find it.
+pub fn generate_all(
+    root: &Path,
+    config: &Config,
+    mut modules: Vec<&CheckedModule>,
+) -> Vec<DocFile> {
+    modules.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let fingerprint = fingerprint(&modules);
     let (modules_prefix, modules_links) = generate_modules_links(&modules);
 
     let source = match &config.repository {
@@ -114,39 +127,110 @@ pub fn generate_all(root: &Path, config: &Config, modules: Vec<&CheckedModule>)
 
     let mut output_files: Vec<DocFile> = vec![];
     let mut search_indexes: Vec<SearchIndex> = vec![];
+    let mut search_symbols: Vec<SearchSymbol> = vec![];
 
     for module in &modules {
-        let (indexes, file) = generate_module(
+        let (indexes, symbols, file) = generate_module(
             config,
             module,
             (&modules_prefix, &modules_links),
             &source,
-            &timestamp,
+            &fingerprint,
         );
         search_indexes.extend(indexes);
+        search_symbols.extend(symbols);
         output_files.push(file);
     }
 
     output_files.extend(generate_static_assets(search_indexes));
+    output_files.push(generate_search_symbols(search_symbols));
     output_files.push(generate_readme(
         root,
         config,
         (&modules_prefix, &modules_links),
         &source,
-        &timestamp,
+        &fingerprint,
     ));
 
     output_files
 }
 
+/// Write doc files under `destination`, leaving alone those whose content is already up to
+/// date so that regenerating docs only touches what changed. Files generated by a previous
+/// run but no longer part of `files`, e.g. the page of a module since removed or renamed, are
+/// deleted; anything else found under `destination` is left untouched. Returns the paths of
+/// the files written, relative to `destination`.
+pub fn write_all(destination: &Path, files: Vec<DocFile>) -> io::Result<Vec<PathBuf>> {
+    let manifest = destination.join(MANIFEST);
+
+    let previous = fs::read_to_string(&manifest).unwrap_or_default();
+
+    let expected: HashSet<&Path> = files.iter().map(|file| file.path.as_path()).collect();
+
+    for stale in previous
+        .lines()
+        .map(Path::new)
+        // Only ever delete below the destination, whatever the manifest says.
+        .filter(|path| path.components().all(|c| matches!(c, Component::Normal(_))))
+        .filter(|path| !expected.contains(path))
+    {
+        let path = destination.join(stale);
+
+        if path.is_file() {
+            fs::remove_file(&path)?;
+        }
+
+        // Remove the directories the stale file leaves empty, up to the destination.
+        for dir in path.ancestors().skip(1) {
+            if dir == destination {
+                break;
+            }
+
+            if fs::read_dir(dir).map_or(true, |mut entries| entries.next().is_some()) {
+                break;
+            }
+
+            fs::remove_dir(dir)?;
+        }
+    }
+
+    let listing = files
+        .iter()
+        .map(|file| format!("{}\n", file.path.to_string_lossy()))
+        .collect::<String>();
+
+    let mut updated = Vec::new();
+
+    for file in files {
+        let path = destination.join(&file.path);
+
+        if fs::read(&path).ok().as_deref() == Some(file.content.as_bytes()) {
+            continue;
+        }
+
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, file.content)?;
+
+        updated.push(file.path);
+    }
+
+    if previous != listing {
+        fs::create_dir_all(destination)?;
+        fs::write(&manifest, listing)?;
+    }
+
+    Ok(updated)
+}
+
 fn generate_module(
     config: &Config,
     module: &CheckedModule,
     (modules_prefix, modules): (&str, &Vec<DocLink>),
     source: &DocLink,
-    timestamp: &Duration,
-) -> (Vec<SearchIndex>, DocFile) {
+    fingerprint: &str,
+) -> (Vec<SearchIndex>, Vec<SearchSymbol>, DocFile) {
     let mut search_indexes = vec![];
+    let mut search_symbols = vec![];
 
     // Functions
     let functions: Vec<DocFunction> = module
@@ -156,9 +240,10 @@ fn generate_module(
         .flat_map(DocFunction::from_definition)
         .sorted()
         .collect();
-    functions
-        .iter()
-        .for_each(|function| search_indexes.push(SearchIndex::from_function(module, function)));
+    functions.iter().for_each(|function| {
+        search_indexes.push(SearchIndex::from_function(module, function));
+        search_symbols.push(SearchSymbol::new(module, &function.name, "function"));
+    });
 
     // Types
     let types: Vec<DocType> = module
@@ -168,9 +253,10 @@ fn generate_module(
         .flat_map(DocType::from_definition)
         .sorted()
         .collect();
-    types
-        .iter()
-        .for_each(|type_info| search_indexes.push(SearchIndex::from_type(module, type_info)));
+    types.iter().for_each(|type_info| {
+        search_indexes.push(SearchIndex::from_type(module, type_info));
+        search_symbols.push(SearchSymbol::new(module, &type_info.name, "type"));
+    });
 
     // Constants
     let constants: Vec<DocConstant> = module
@@ -180,9 +266,10 @@ fn generate_module(
         .flat_map(DocConstant::from_definition)
         .sorted()
         .collect();
-    constants
-        .iter()
-        .for_each(|constant| search_indexes.push(SearchIndex::from_constant(module, constant)));
+    constants.iter().for_each(|constant| {
+        search_indexes.push(SearchIndex::from_constant(module, constant));
+        search_symbols.push(SearchSymbol::new(module, &constant.name, "constant"));
+    });
 
     // Module
     search_indexes.push(SearchIndex::from_module(module));
@@ -200,11 +287,12 @@ fn generate_module(
         types,
         constants,
         source,
-        timestamp: timestamp.as_secs().to_string(),
+        fingerprint: fingerprint.to_string(),
     };
 
     (
         search_indexes,
+        search_symbols,
         DocFile {
             path: PathBuf::from(format!("{}.html", module.module_name)),
             content: module
@@ -214,6 +302,60 @@ fn generate_module(
     )
 }
 
+/// How many of the public definitions of some modules come with a doc comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocCoverage {
+    pub total: usize,
+    /// Public definitions without any doc comment, as `module.name`.
+    pub undocumented: Vec<String>,
+}
+
+impl DocCoverage {
+    pub fn documented(&self) -> usize {
+        self.total - self.undocumented.len()
+    }
+
+    /// Share of documented definitions, in percent. Modules without any public definition
+    /// are fully covered.
+    pub fn percentage(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.documented() as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+pub fn coverage(modules: &[&CheckedModule]) -> DocCoverage {
+    let mut total = 0;
+    let mut undocumented = vec![];
+
+    for module in modules.iter().sorted_by(|a, b| a.name.cmp(&b.name)) {
+        for def in module.ast.definitions() {
+            let (name, doc) = match def {
+                Definition::Fn(func_def) if func_def.public => (&func_def.name, &func_def.doc),
+                Definition::ModuleConstant(const_def) if const_def.public => {
+                    (&const_def.name, &const_def.doc)
+                }
+                Definition::TypeAlias(info) if info.public => (&info.alias, &info.doc),
+                Definition::DataType(info) if info.public => (&info.name, &info.doc),
+                _ => continue,
+            };
+
+            total += 1;
+
+            if doc.as_deref().unwrap_or_default().trim().is_empty() {
+                undocumented.push(format!("{}.{}", module.name, name));
+            }
+        }
+    }
+
+    DocCoverage {
+        total,
+        undocumented,
+    }
+}
+
 fn generate_static_assets(search_indexes: Vec<SearchIndex>) -> Vec<DocFile> {
     let mut assets: Vec<DocFile> = vec![];
 
@@ -269,12 +411,21 @@ fn generate_static_assets(search_indexes: Vec<SearchIndex>) -> Vec<DocFile> {
     assets
 }
 
+fn generate_search_symbols(mut search_symbols: Vec<SearchSymbol>) -> DocFile {
+    search_symbols.sort();
+
+    DocFile {
+        path: PathBuf::from("search-index.json"),
+        content: json::to_string(&search_symbols).expect("search symbols serialization"),
+    }
+}
+
 fn generate_readme(
     root: &Path,
     config: &Config,
     (modules_prefix, modules): (&str, &Vec<DocLink>),
     source: &DocLink,
-    timestamp: &Duration,
+    fingerprint: &str,
 ) -> DocFile {
     let path = PathBuf::from("index.html");
 
@@ -290,7 +441,7 @@ fn generate_readme(
         project_version: &config.version.to_string(),
         content: render_markdown(&content),
         source,
-        timestamp: &timestamp.as_secs().to_string(),
+        fingerprint,
     };
 
     DocFile {
@@ -383,8 +534,32 @@ impl SearchIndex {
     }
 }
 
+/// An entry of `search-index.json`. Unlike [`SearchIndex`], it carries no documentation
+/// so that the whole index stays small enough to ship to the browser.
+#[derive(Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+struct SearchSymbol {
+    module: String,
+    name: String,
+    kind: &'static str,
+    url: String,
+}
+
+impl SearchSymbol {
+    fn new(module: &CheckedModule, name: &str, kind: &'static str) -> Self {
+        SearchSymbol {
+            module: module.name.to_string(),
+            name: name.to_string(),
+            kind,
+            url: format!("{}.html#{}", module.name, name),
+        }
+    }
+}
+
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
 struct DocFunction {
+    /// Where the definition starts in its module. Coming first, definitions are sorted by
+    /// it, and then by name.
+    location: usize,
     name: String,
     signature: String,
     documentation: String,
@@ -396,6 +571,7 @@ impl DocFunction {
     fn from_definition(def: &TypedDefinition) -> Option<Self> {
         match def {
             Definition::Fn(func_def) if func_def.public => Some(DocFunction {
+                location: func_def.location.start,
                 name: func_def.name.clone(),
                 documentation: func_def
                     .doc
@@ -420,6 +596,7 @@ impl DocFunction {
 
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
 struct DocConstant {
+    location: usize,
     name: String,
     definition: String,
     documentation: String,
@@ -431,6 +608,7 @@ impl DocConstant {
     fn from_definition(def: &TypedDefinition) -> Option<Self> {
         match def {
             Definition::ModuleConstant(const_def) if const_def.public => Some(DocConstant {
+                location: const_def.location.start,
                 name: const_def.name.clone(),
                 documentation: const_def
                     .doc
@@ -450,6 +628,7 @@ impl DocConstant {
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
 struct DocType {
+    location: usize,
     name: String,
     definition: String,
     documentation: String,
@@ -464,6 +643,7 @@ impl DocType {
     fn from_definition(def: &TypedDefinition) -> Option<Self> {
         match def {
             Definition::TypeAlias(info) if info.public => Some(DocType {
+                location: info.location.start,
                 name: info.alias.clone(),
                 definition: format::Formatter::new()
                     .docs_type_alias(&info.alias, &info.parameters, &info.annotation)
@@ -477,6 +657,7 @@ impl DocType {
             }),
 
             Definition::DataType(info) if info.public && !info.opaque => Some(DocType {
+                location: info.location.start,
                 name: info.name.clone(),
                 definition: format::Formatter::new()
                     .docs_data_type(
@@ -499,6 +680,7 @@ impl DocType {
             }),
 
             Definition::DataType(info) if info.public && info.opaque => Some(DocType {
+                location: info.location.start,
                 name: info.name.clone(),
                 definition: format::Formatter::new()
                     .docs_opaque_data_type(&info.name, &info.parameters, &info.location)
@@ -518,6 +700,7 @@ impl DocType {
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
 struct DocTypeConstructor {
+    name: String,
     definition: String,
     documentation: String,
     raw_documentation: String,
@@ -526,6 +709,7 @@ struct DocTypeConstructor {
 impl DocTypeConstructor {
     fn from_record_constructor(constructor: &RecordConstructor<Arc<Type>>) -> Self {
         DocTypeConstructor {
+            name: constructor.name.clone(),
             definition: format::Formatter::new()
                 .docs_record_constructor(constructor)
                 .to_pretty_string(80),
@@ -568,10 +752,20 @@ fn escape_html_contents(indexes: Vec<SearchIndex>) -> Vec<SearchIndex> {
         .collect::<Vec<SearchIndex>>()
 }
 
-fn new_timestamp() -> Duration {
-    SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .expect("get current timestamp")
+/// Identifies the sources documentation is generated from, so that browsers reload scripts
+/// once these change. Unlike a timestamp, generating the same documentation twice gives the
+/// exact same output.
+fn fingerprint(modules: &[&CheckedModule]) -> String {
+    let mut hasher = DefaultHasher::new();
+
+    VERSION.hash(&mut hasher);
+
+    for module in modules {
+        module.name.hash(&mut hasher);
+        module.code.hash(&mut hasher);
+    }
+
+    format!("{:x}", hasher.finish())
 }
 
 fn find_modules_prefix(modules: &[DocLink]) -> String {
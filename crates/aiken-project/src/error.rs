@@ -3,7 +3,7 @@ use crate::{
     deps::manifest::Package,
     package_name::PackageName,
     pretty,
-    script::EvalHint,
+    script::{Counterexample, EvalHint, FailureReason},
 };
 use aiken_lang::{
     ast::{BinOp, Span},
@@ -20,7 +20,11 @@ use std::{
     ops::Deref,
     path::{Path, PathBuf},
 };
-use uplc::machine::cost_model::ExBudget;
+use uplc::{
+    ast::{NamedDeBruijn, Program},
+    machine::cost_model::ExBudget,
+    PlutusData,
+};
 use zip::result::ZipError;
 
 #[allow(dead_code)]
@@ -69,6 +73,8 @@ pub enum Error {
     #[error("I couldn't find any 'aiken.toml' manifest in {path}.")]
     MissingManifest { path: PathBuf },
 
+    /// The modules of the cycle in import order, e.g. `a`, `b`, `a` when `a` imports `b`
+    /// which imports `a`.
     #[error("I just found a cycle in module hierarchy!")]
     ImportCycle { modules: Vec<String> },
 
@@ -112,13 +118,18 @@ pub enum Error {
         named: Box<NamedSource>,
     },
 
-    #[error("{name} failed{}", if *verbose { format!("\n{src}") } else { String::new() } )]
+    #[error("{name} failed: {reason}{}", if *verbose { format!("\n{src}") } else { String::new() } )]
     TestFailure {
         name: String,
         path: PathBuf,
         verbose: bool,
         src: String,
+        /// The source of the test's module, to point at the operands of a failed comparison.
+        named: NamedSource,
         evaluation_hint: Option<EvalHint>,
+        can_error: bool,
+        reason: FailureReason,
+        counterexample: Option<Counterexample>,
     },
 
     #[error(
@@ -129,6 +140,18 @@ pub enum Error {
     )]
     UnknownPackageVersion { package: Package },
 
+    #[error(
+        "I found that the sources of {}/{} {} don't match the ones recorded in aiken.lock",
+        package.name.owner,
+        package.name.repo,
+        package.version
+    )]
+    PackageIntegrityMismatch {
+        package: Package,
+        expected: String,
+        found: String,
+    },
+
     #[error("I couldn't parse the provided stake address.")]
     MalformedStakeAddress {
         error: Option<pallas::ledger::addresses::Error>,
@@ -143,6 +166,13 @@ pub enum Error {
     MoreThanOneValidatorFound {
         known_validators: Vec<(String, validator::Purpose)>,
     },
+
+    #[error("Only {coverage:.1}% of the public definitions are documented, below the required {threshold}%.")]
+    InsufficientDocCoverage {
+        coverage: f64,
+        threshold: f64,
+        undocumented: Vec<String>,
+    },
 }
 
 impl Error {
@@ -226,10 +256,12 @@ impl Error {
             Error::ZipExtract(_) => None,
             Error::JoinError(_) => None,
             Error::UnknownPackageVersion { .. } => None,
+            Error::PackageIntegrityMismatch { .. } => None,
             Error::Json { .. } => None,
             Error::MalformedStakeAddress { .. } => None,
             Error::NoValidatorNotFound { .. } => None,
             Error::MoreThanOneValidatorFound { .. } => None,
+            Error::InsufficientDocCoverage { .. } => None,
         }
     }
 
@@ -253,10 +285,12 @@ impl Error {
             Error::ZipExtract(_) => None,
             Error::JoinError(_) => None,
             Error::UnknownPackageVersion { .. } => None,
+            Error::PackageIntegrityMismatch { .. } => None,
             Error::Json { .. } => None,
             Error::MalformedStakeAddress { .. } => None,
             Error::NoValidatorNotFound { .. } => None,
             Error::MoreThanOneValidatorFound { .. } => None,
+            Error::InsufficientDocCoverage { .. } => None,
         }
     }
 }
@@ -308,10 +342,12 @@ impl Diagnostic for Error {
             Error::ZipExtract(_) => None,
             Error::JoinError(_) => None,
             Error::UnknownPackageVersion { .. } => Some(Box::new("aiken::packages::resolve")),
+            Error::PackageIntegrityMismatch { .. } => Some(Box::new("aiken::packages::integrity")),
             Error::Json { .. } => None,
             Error::MalformedStakeAddress { .. } => None,
             Error::NoValidatorNotFound { .. } => None,
             Error::MoreThanOneValidatorFound { .. } => None,
+            Error::InsufficientDocCoverage { .. } => Some(Box::new("aiken::docs::coverage")),
         }
     }
 
@@ -325,8 +361,8 @@ impl Diagnostic for Error {
             Error::FileIo { error, .. } => Some(Box::new(format!("{error}"))),
             Error::Blueprint(e) => e.help(),
             Error::ImportCycle { modules } => Some(Box::new(format!(
-                "Try moving the shared code to a separate module that the others can depend on\n- {}",
-                modules.join("\n- ")
+                "Try moving the shared code to a separate module that the others can depend on\n{}",
+                modules.join(" -> ")
             ))),
             Error::List(_) => None,
             Error::Parse { error, .. } => error.kind.help(),
@@ -337,38 +373,26 @@ impl Diagnostic for Error {
             Error::Format { .. } => None,
             Error::ValidatorMustReturnBool { .. } => Some(Box::new("Try annotating the validator's return type with Bool")),
             Error::WrongValidatorArity { .. } => Some(Box::new("Validators require a minimum number of arguments please add the missing arguments.\nIf you don't need one of the required arguments use an underscore `_datum`.")),
+            Error::TestFailure { counterexample: Some(Counterexample { seed, value }), evaluation_hint, .. } => {
+                let msg = format!(
+                    "Falsified by the following value (as CBOR), generated with seed {seed}:\n\n{}",
+                    uplc::plutus_data_to_bytes(value).map(hex::encode).unwrap_or_default()
+                );
+                Some(Box::new(match evaluation_hint.as_ref().and_then(|hint| explain_hint(hint, Some(value))) {
+                    Some(explanation) => format!("{msg}\n\n{explanation}"),
+                    None => msg,
+                }))
+            },
+            Error::TestFailure { reason: FailureReason::UnexpectedSuccess, .. } => Some(Box::new("This test is marked as `fail`, yet it succeeded.")),
             Error::TestFailure { evaluation_hint, .. }  =>{
-                match evaluation_hint {
-                    None => None,
-                    Some(hint) => {
-                        let budget = ExBudget { mem: i64::MAX, cpu: i64::MAX, };
-                        let left = pretty::boxed("left", &match hint.left.eval(budget) {
-                            (Ok(term), _, _) => format!("{term}"),
-                            (Err(err), _, _) => format!("{err}"),
-                        });
-                        let right = pretty::boxed("right", &match hint.right.eval(budget) {
-                            (Ok(term), _, _) => format!("{term}"),
-                            (Err(err), _, _) => format!("{err}"),
-                        });
-                        let msg = match hint.bin_op {
-                            BinOp::And => Some(format!("{left}\n\nand\n\n{right}\n\nshould both be true.")),
-                            BinOp::Or => Some(format!("{left}\n\nor\n\n{right}\n\nshould be true.")),
-                            BinOp::Eq => Some(format!("{left}\n\nshould be equal to\n\n{right}")),
-                            BinOp::NotEq => Some(format!("{left}\n\nshould not be equal to\n\n{right}")),
-                            BinOp::LtInt => Some(format!("{left}\n\nshould be lower than\n\n{right}")),
-                            BinOp::LtEqInt => Some(format!("{left}\n\nshould be lower than or equal to\n\n{right}")),
-                            BinOp::GtEqInt => Some(format!("{left}\n\nshould be greater than\n\n{right}")),
-                            BinOp::GtInt => Some(format!("{left}\n\nshould be greater than or equal to\n\n{right}")),
-                            _ => None
-                        }?;
-                        Some(Box::new(msg))
-                    }
-                }
+                let msg = explain_hint(evaluation_hint.as_ref()?, None)?;
+                Some(Box::new(msg))
             },
             Error::Http(_) => None,
             Error::ZipExtract(_) => None,
             Error::JoinError(_) => None,
             Error::UnknownPackageVersion{..} => Some(Box::new("Perhaps, double-check the package repository and version?")),
+            Error::PackageIntegrityMismatch { expected, found, .. } => Some(Box::new(format!("The package was recorded with the hash {expected}, but its sources now hash to {found}. If the change is expected, for example because the version is a branch which moved, remove the package's `integrity` from aiken.lock to record the new one.\n\nOtherwise, remove build/packages to download the package again."))),
             Error::Json(error) => Some(Box::new(format!("{error}"))),
             Error::MalformedStakeAddress { error } => Some(Box::new(format!("A stake address must be provided either as a base16-encoded string, or as a bech32-encoded string with the 'stake' or 'stake_test' prefix.{hint}", hint = match error {
                 Some(error) => format!("\n\nHere's the error I encountered: {error}"),
@@ -386,6 +410,12 @@ impl Diagnostic for Error {
                     known_validators.iter().map(|(name, purpose)| format!("→ {name} (purpose = {purpose})", name = name.purple().bold(), purpose = purpose.bright_blue())).collect::<Vec<String>>().join("\n")
                 )))
             },
+            Error::InsufficientDocCoverage { undocumented, .. } => {
+                Some(Box::new(format!(
+                    "Here are the public definitions I found without any documentation:\n\n{}",
+                    undocumented.iter().map(|name| format!("→ {}", name.purple().bold())).collect::<Vec<String>>().join("\n")
+                )))
+            },
         }
     }
 
@@ -416,15 +446,27 @@ impl Diagnostic for Error {
             Error::WrongValidatorArity { location, .. } => Some(Box::new(
                 vec![LabeledSpan::new_with_span(None, *location)].into_iter(),
             )),
+            Error::TestFailure {
+                evaluation_hint: Some(hint),
+                ..
+            } => Some(Box::new(
+                vec![
+                    LabeledSpan::new_with_span(Some("left".to_string()), hint.left_location),
+                    LabeledSpan::new_with_span(Some("right".to_string()), hint.right_location),
+                ]
+                .into_iter(),
+            )),
             Error::TestFailure { .. } => None,
             Error::Http(_) => None,
             Error::ZipExtract(_) => None,
             Error::JoinError(_) => None,
             Error::UnknownPackageVersion { .. } => None,
+            Error::PackageIntegrityMismatch { .. } => None,
             Error::Json { .. } => None,
             Error::MalformedStakeAddress { .. } => None,
             Error::NoValidatorNotFound { .. } => None,
             Error::MoreThanOneValidatorFound { .. } => None,
+            Error::InsufficientDocCoverage { .. } => None,
         }
     }
 
@@ -443,15 +485,17 @@ impl Diagnostic for Error {
             Error::Format { .. } => None,
             Error::ValidatorMustReturnBool { named, .. } => Some(named),
             Error::WrongValidatorArity { named, .. } => Some(named.deref()),
-            Error::TestFailure { .. } => None,
+            Error::TestFailure { named, .. } => Some(named),
             Error::Http(_) => None,
             Error::ZipExtract(_) => None,
             Error::JoinError(_) => None,
             Error::UnknownPackageVersion { .. } => None,
+            Error::PackageIntegrityMismatch { .. } => None,
             Error::Json { .. } => None,
             Error::MalformedStakeAddress { .. } => None,
             Error::NoValidatorNotFound { .. } => None,
             Error::MoreThanOneValidatorFound { .. } => None,
+            Error::InsufficientDocCoverage { .. } => None,
         }
     }
 
@@ -475,10 +519,12 @@ impl Diagnostic for Error {
             Error::ZipExtract { .. } => None,
             Error::JoinError { .. } => None,
             Error::UnknownPackageVersion { .. } => None,
+            Error::PackageIntegrityMismatch { .. } => None,
             Error::Json { .. } => None,
             Error::MalformedStakeAddress { .. } => None,
             Error::NoValidatorNotFound { .. } => None,
             Error::MoreThanOneValidatorFound { .. } => None,
+            Error::InsufficientDocCoverage { .. } => None,
         }
     }
 
@@ -502,10 +548,12 @@ impl Diagnostic for Error {
             Error::ZipExtract { .. } => None,
             Error::JoinError { .. } => None,
             Error::UnknownPackageVersion { .. } => None,
+            Error::PackageIntegrityMismatch { .. } => None,
             Error::Json { .. } => None,
             Error::MalformedStakeAddress { .. } => None,
             Error::NoValidatorNotFound { .. } => None,
             Error::MoreThanOneValidatorFound { .. } => None,
+            Error::InsufficientDocCoverage { .. } => None,
         }
     }
 }
@@ -524,6 +572,13 @@ pub enum Warning {
     },
     #[error("{name} is already a dependency.")]
     DependencyAlreadyExists { name: PackageName },
+    #[error("{} isn't a valid module name and was ignored.", path.display())]
+    InvalidModuleName { path: PathBuf },
+    #[error("The {purpose} validator of {title} never looks at its script context.")]
+    UnusedScriptContext {
+        title: String,
+        purpose: validator::Purpose,
+    },
 }
 
 impl Diagnostic for Warning {
@@ -536,6 +591,8 @@ impl Diagnostic for Warning {
             Warning::Type { named, .. } => Some(named),
             Warning::NoValidators => None,
             Warning::DependencyAlreadyExists { .. } => None,
+            Warning::InvalidModuleName { .. } => None,
+            Warning::UnusedScriptContext { .. } => None,
         }
     }
 
@@ -544,6 +601,8 @@ impl Diagnostic for Warning {
             Warning::Type { warning, .. } => warning.labels(),
             Warning::NoValidators => None,
             Warning::DependencyAlreadyExists { .. } => None,
+            Warning::InvalidModuleName { .. } => None,
+            Warning::UnusedScriptContext { .. } => None,
         }
     }
 
@@ -557,6 +616,10 @@ impl Diagnostic for Warning {
             Warning::DependencyAlreadyExists { .. } => {
                 Some(Box::new("aiken::packages::already_exists"))
             }
+            Warning::InvalidModuleName { .. } => Some(Box::new("aiken::module::name")),
+            Warning::UnusedScriptContext { .. } => {
+                Some(Box::new("aiken::validator::unused_context"))
+            }
         }
     }
 
@@ -567,6 +630,12 @@ impl Diagnostic for Warning {
             Warning::DependencyAlreadyExists { .. } => Some(Box::new(
                 "If you need to change the version, try 'aiken packages upgrade' instead.",
             )),
+            Warning::InvalidModuleName { .. } => Some(Box::new(
+                "Every folder and file in a module's path must start with a lowercase letter, followed by lowercase letters, digits or underscores; for example: 'my_module/v2.ak'. Try renaming it.",
+            )),
+            Warning::UnusedScriptContext { .. } => Some(Box::new(
+                "Without its script context, a validator can't check anything about the transaction it's part of, so it lets through any transaction with the right datum and redeemer. If that's really what you want, name the argument '_ctx'.",
+            )),
         }
     }
 }
@@ -612,3 +681,39 @@ pub struct Unformatted {
     pub input: String,
     pub output: String,
 }
+
+/// Explain why a test ending with a binary operation failed, from the value of each operand.
+/// For properties, operands are functions of the value the property failed on.
+fn explain_hint(hint: &EvalHint, counterexample: Option<&PlutusData>) -> Option<String> {
+    let budget = ExBudget {
+        mem: i64::MAX,
+        cpu: i64::MAX,
+    };
+    let eval = |operand: &Program<NamedDeBruijn>| {
+        let result = match counterexample {
+            Some(value) => operand.apply_data(value.clone()).eval(budget),
+            None => operand.eval(budget),
+        };
+        match result {
+            (Ok(term), _, _) => format!("{term}"),
+            (Err(err), _, _) => format!("{err}"),
+        }
+    };
+    let left = pretty::boxed("left", &eval(&hint.left));
+    let right = pretty::boxed("right", &eval(&hint.right));
+    match hint.bin_op {
+        BinOp::And => Some(format!("{left}\n\nand\n\n{right}\n\nshould both be true.")),
+        BinOp::Or => Some(format!("{left}\n\nor\n\n{right}\n\nshould be true.")),
+        BinOp::Eq => Some(format!("{left}\n\nshould be equal to\n\n{right}")),
+        BinOp::NotEq => Some(format!("{left}\n\nshould not be equal to\n\n{right}")),
+        BinOp::LtInt => Some(format!("{left}\n\nshould be lower than\n\n{right}")),
+        BinOp::LtEqInt => Some(format!(
+            "{left}\n\nshould be lower than or equal to\n\n{right}"
+        )),
+        BinOp::GtEqInt => Some(format!("{left}\n\nshould be greater than\n\n{right}")),
+        BinOp::GtInt => Some(format!(
+            "{left}\n\nshould be greater than or equal to\n\n{right}"
+        )),
+        _ => None,
+    }
+}
@@ -125,6 +125,16 @@ fn format_file(problem_files: &mut Vec<Unformatted>, path: PathBuf) -> Result<()
     Ok(())
 }
 
+/// A unified diff turning `before` into `after`, with `path` in its headers.
+pub fn diff(path: &Path, before: &str, after: &str) -> String {
+    let path = path.to_string_lossy();
+
+    similar::TextDiff::from_lines(before, after)
+        .unified_diff()
+        .header(&path, &path)
+        .to_string()
+}
+
 pub fn read_stdin() -> Result<String, Error> {
     let mut src = String::new();
 
@@ -1,3 +1,4 @@
+pub mod address;
 pub mod blueprint;
 pub mod config;
 pub mod deps;
@@ -9,36 +10,59 @@ pub mod options;
 pub mod package_name;
 pub mod paths;
 pub mod pretty;
+pub mod property;
 pub mod script;
 pub mod telemetry;
 
-use crate::blueprint::{schema::Schema, validator, Blueprint};
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    address::Delegation,
+    blueprint::{
+        schema::Schema,
+        validator::{self, Validator},
+        Blueprint,
+    },
+};
 use aiken_lang::{
-    ast::{Definition, Function, ModuleKind, TypedDataType, TypedFunction},
+    ast::{
+        Arg, ArgName, Definition, Edition, Function, ModuleKind, Span, TypedDataType, TypedFunction,
+    },
     builder::{DataTypeKey, FunctionAccessKey},
     builtins,
-    tipo::TypeInfo,
+    expr::TypedExpr,
+    tipo::{self, TypeInfo},
     IdGenerator,
 };
 use deps::UseManifest;
 use indexmap::IndexMap;
+use itertools::Itertools;
 use miette::NamedSource;
-use options::{CodeGenMode, Options};
+use options::{CheckOptions, CodeGenMode, Options};
 use package_name::PackageName;
-use pallas::ledger::addresses::{
-    Address, Network, ShelleyAddress, ShelleyDelegationPart, StakePayload,
+use pallas::ledger::{
+    addresses::{Network, ShelleyAddress},
+    primitives::babbage::Language,
 };
-use script::{EvalHint, EvalInfo, Script};
+use property::{Prng, PROPERTY_RUNS};
+use script::{Counterexample, EvalHint, EvalInfo, Script, TestFilter, TestInfo};
 use std::{
-    collections::HashMap,
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
     fs::{self, File},
+    hash::{Hash, Hasher},
     io::BufReader,
     path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
-use telemetry::EventListener;
+use strum::IntoEnumIterator;
+use telemetry::{EventListener, Timeline};
 use uplc::{
-    ast::{Constant, DeBruijn, Term},
+    ast::{Constant, DeBruijn, NamedDeBruijn, Program, Term},
+    builtins::DefaultFunction,
     machine::cost_model::ExBudget,
+    tx::to_plutus_data,
 };
 
 use crate::{
@@ -71,6 +95,154 @@ where
     event_listener: T,
     functions: IndexMap<FunctionAccessKey, TypedFunction>,
     data_types: IndexMap<DataTypeKey, TypedDataType>,
+    timeline: Option<Timeline>,
+    /// Outcome of the latest type-check of each module, reused by subsequent compilations
+    /// as long as neither the module nor any of its dependencies change.
+    cache: HashMap<String, CachedModule>,
+}
+
+#[derive(Debug, Clone)]
+struct CachedModule {
+    /// A hash of the module's source and location.
+    hash: u64,
+    /// Identifies this particular type-check of the module; a new one is drawn each time it
+    /// is inferred again.
+    generation: u64,
+    /// The generation of each dependency the module was checked against, if cached.
+    deps: Vec<(String, Option<u64>)>,
+    module: CheckedModule,
+    warnings: Vec<tipo::error::Warning>,
+}
+
+/// What inferring a module needs from the project, so that modules can be type-checked from
+/// several threads at once.
+struct TypeChecker<'a> {
+    id_gen: &'a IdGenerator,
+    package: String,
+    module_types: &'a HashMap<String, TypeInfo>,
+    cache: &'a HashMap<String, CachedModule>,
+    timeline: Option<&'a Timeline>,
+}
+
+/// A module that failed to type-check. Unlike [`Error`], it can be sent back from the thread
+/// the module was inferred on.
+struct TypeError {
+    path: PathBuf,
+    code: String,
+    error: tipo::error::Error,
+}
+
+impl From<TypeError> for Error {
+    fn from(TypeError { path, code, error }: TypeError) -> Self {
+        Error::Type {
+            named: NamedSource::new(path.display().to_string(), code.clone()),
+            path,
+            src: code,
+            error,
+        }
+    }
+}
+
+impl<'a> TypeChecker<'a> {
+    /// Reuse the latest type-check of a module when neither its source nor any of its
+    /// dependencies changed since, and infer it again otherwise. Dependencies are checked
+    /// first, so a change cascades to every module depending on it, even indirectly.
+    fn check_module(&self, module: ParsedModule) -> Result<CachedModule, TypeError> {
+        let mut hasher = DefaultHasher::new();
+        module.code.hash(&mut hasher);
+        module.path.hash(&mut hasher);
+        module.package.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let deps = module
+            .deps_for_graph()
+            .1
+            .into_iter()
+            .map(|dep| {
+                let generation = self.cache.get(&dep).map(|cached| cached.generation);
+                (dep, generation)
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(cached) = self.cache.get(&module.name) {
+            if cached.hash == hash
+                && cached.deps == deps
+                && deps
+                    .iter()
+                    .all(|(dep, _)| self.module_types.contains_key(dep))
+            {
+                return Ok(cached.clone());
+            }
+        }
+
+        let (module, warnings) = self.infer_module(module)?;
+
+        Ok(CachedModule {
+            hash,
+            generation: self.id_gen.next(),
+            deps,
+            module,
+            warnings: warnings
+                .into_iter()
+                .filter_map(|warning| match warning {
+                    Warning::Type { warning, .. } => Some(warning),
+                    _ => None,
+                })
+                .collect(),
+        })
+    }
+
+    fn infer_module(
+        &self,
+        ParsedModule {
+            name,
+            path,
+            code,
+            kind,
+            extra,
+            package,
+            ast,
+        }: ParsedModule,
+    ) -> Result<(CheckedModule, Vec<Warning>), TypeError> {
+        let mut type_warnings = Vec::new();
+
+        let start = Instant::now();
+
+        let ast = ast.infer(
+            self.id_gen,
+            kind,
+            &self.package,
+            self.module_types,
+            &mut type_warnings,
+        );
+
+        if let Some(timeline) = self.timeline {
+            timeline.record(name.clone(), "type-check", start);
+        }
+
+        let ast = ast.map_err(|error| TypeError {
+            path: path.clone(),
+            code: code.clone(),
+            error,
+        })?;
+
+        let type_warnings = type_warnings
+            .into_iter()
+            .map(|w| Warning::from_type_warning(w, path.clone(), code.clone()))
+            .collect();
+
+        let module = CheckedModule {
+            kind,
+            extra,
+            name,
+            code,
+            ast,
+            package,
+            input_path: path,
+        };
+
+        Ok((module, type_warnings))
+    }
 }
 
 impl<T> Project<T>
@@ -78,6 +250,14 @@ where
     T: EventListener,
 {
     pub fn new(root: PathBuf, event_listener: T) -> Result<Project<T>, Error> {
+        let config = Config::load(&root)?;
+
+        Ok(Project::new_with_config(root, event_listener, config))
+    }
+
+    /// Like [`Project::new`], but with a given configuration rather than one loaded from the
+    /// project's root. Sources are still looked up under `root`.
+    pub fn new_with_config(root: PathBuf, event_listener: T, config: Config) -> Project<T> {
         let id_gen = IdGenerator::new();
 
         let mut module_types = HashMap::new();
@@ -89,9 +269,7 @@ where
 
         let data_types = builtins::prelude_data_types(&id_gen);
 
-        let config = Config::load(&root)?;
-
-        Ok(Project {
+        Project {
             config,
             checked_modules: CheckedModules::default(),
             defined_modules: HashMap::new(),
@@ -103,18 +281,61 @@ where
             event_listener,
             functions,
             data_types,
-        })
+            timeline: None,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Start recording how long each step of the subsequent compilations takes. See
+    /// [`Project::write_timeline`].
+    pub fn record_timeline(&mut self) {
+        self.timeline = Some(Timeline::new());
+    }
+
+    /// Write the recorded timeline, if any, as a Chrome trace file.
+    pub fn write_timeline(&self, path: &Path) -> Result<(), Error> {
+        if let Some(timeline) = &self.timeline {
+            let json = serde_json::to_string(&timeline.to_json()).unwrap();
+
+            fs::write(path, json).map_err(|error| Error::FileIo {
+                error,
+                path: path.to_path_buf(),
+            })?;
+        }
+
+        Ok(())
     }
 
-    pub fn build(&mut self, uplc: bool) -> Result<(), Error> {
+    pub fn build(&mut self, uplc: bool, filter_traces: bool) -> Result<(), Error> {
         let options = Options {
-            code_gen_mode: CodeGenMode::Build(uplc),
+            code_gen_mode: CodeGenMode::Build {
+                uplc,
+                filter_traces,
+            },
         };
 
         self.compile(options)
     }
 
-    pub fn docs(&mut self, destination: Option<PathBuf>) -> Result<(), Error> {
+    /// Parse and type-check the project, without generating any code, and give back what's
+    /// been inferred about each module (dependencies included), by module name.
+    pub fn check_types(&mut self) -> Result<&HashMap<String, TypeInfo>, Error> {
+        self.compile(Options {
+            code_gen_mode: CodeGenMode::NoOp,
+        })?;
+
+        Ok(&self.module_types)
+    }
+
+    /// Generate the documentation, and report how much of the project's public definitions
+    /// are documented. Fails when that's under `min_coverage` percent, if given.
+    pub fn docs(
+        &mut self,
+        destination: Option<PathBuf>,
+        min_coverage: Option<f64>,
+    ) -> Result<(), Error> {
+        self.reset();
+
         self.compile_deps()?;
 
         self.event_listener
@@ -128,7 +349,7 @@ where
 
         let destination = destination.unwrap_or_else(|| self.root.join("docs"));
 
-        let parsed_modules = self.parse_sources(self.config.name.clone())?;
+        let parsed_modules = self.parse_sources(self.config.name.clone(), self.config.edition)?;
 
         self.type_check(parsed_modules)?;
 
@@ -142,22 +363,58 @@ where
             self.checked_modules.values().collect(),
         );
 
-        for file in doc_files {
-            let path = destination.join(file.path);
-            fs::create_dir_all(path.parent().unwrap())?;
-            fs::write(&path, file.content)?;
-        }
+        let total = doc_files.len();
 
-        Ok(())
+        let updated = docs::write_all(&destination, doc_files)?;
+
+        self.event_listener.handle_event(Event::WroteDocFiles {
+            updated: updated.len(),
+            unchanged: total - updated.len(),
+        });
+
+        let package = self.config.name.to_string();
+
+        let coverage = docs::coverage(
+            &self
+                .checked_modules
+                .values()
+                .filter(|module| module.package == package)
+                .collect::<Vec<_>>(),
+        );
+
+        let percentage = coverage.percentage();
+
+        let undocumented = coverage.undocumented.clone();
+
+        self.event_listener
+            .handle_event(Event::DocCoverage { coverage });
+
+        match min_coverage {
+            Some(threshold) if percentage < threshold => Err(Error::InsufficientDocCoverage {
+                coverage: percentage,
+                threshold,
+                undocumented,
+            }),
+            _ => Ok(()),
+        }
     }
 
-    pub fn check(
-        &mut self,
-        skip_tests: bool,
-        match_tests: Option<Vec<String>>,
-        verbose: bool,
-        exact_match: bool,
-    ) -> Result<(), Error> {
+    pub fn check(&mut self, options: CheckOptions) -> Result<(), Error> {
+        let CheckOptions {
+            skip_tests,
+            match_tests,
+            verbose,
+            exact_match,
+            csv,
+            budget,
+            max_tests,
+            seed,
+            sorted,
+            timeout,
+            coverage,
+            benchmark,
+        } = options;
+
         let options = Options {
             code_gen_mode: if skip_tests {
                 CodeGenMode::NoOp
@@ -166,6 +423,15 @@ where
                     match_tests,
                     verbose,
                     exact_match,
+                    csv,
+                    budget,
+                    max_tests,
+                    // A fixed seed, so that properties are measured on the same values every time.
+                    seed: seed.unwrap_or_else(|| if benchmark { 0 } else { random_seed() }),
+                    sorted,
+                    timeout,
+                    coverage,
+                    benchmark,
                 }
             },
         };
@@ -192,8 +458,19 @@ where
         self.root.join("plutus.json")
     }
 
+    fn read_blueprint(&self) -> Result<Blueprint<serde_json::Value>, Error> {
+        let blueprint = File::open(self.blueprint_path())
+            .map_err(|_| blueprint::error::Error::InvalidOrMissingFile)?;
+
+        let blueprint = serde_json::from_reader(BufReader::new(blueprint))?;
+
+        Ok(Blueprint::from_json(blueprint)?)
+    }
+
     pub fn compile(&mut self, options: Options) -> Result<(), Error> {
-        self.compile_deps()?;
+        self.reset();
+
+        self.traced("dependencies", |p| p.compile_deps())?;
 
         self.event_listener
             .handle_event(Event::StartingCompilation {
@@ -202,32 +479,66 @@ where
                 version: self.config.version.clone(),
             });
 
-        self.read_source_files()?;
+        self.traced("read", |p| p.read_source_files())?;
 
-        let parsed_modules = self.parse_sources(self.config.name.clone())?;
+        let parsed_modules = self.traced("parse", |p| {
+            p.parse_sources(p.config.name.clone(), p.config.edition)
+        })?;
 
-        self.type_check(parsed_modules)?;
+        self.traced("type-check", |p| p.type_check(parsed_modules))?;
 
         match options.code_gen_mode {
-            CodeGenMode::Build(uplc_dump) => {
+            CodeGenMode::Build {
+                uplc: uplc_dump,
+                filter_traces,
+            } => {
                 self.event_listener
                     .handle_event(Event::GeneratingBlueprint {
                         path: self.blueprint_path(),
                     });
 
-                let mut generator = self.checked_modules.new_generator(
-                    &self.functions,
-                    &self.data_types,
-                    &self.module_types,
-                );
-
-                let blueprint = Blueprint::new(&self.config, &self.checked_modules, &mut generator)
-                    .map_err(Error::Blueprint)?;
+                let mut blueprint = self.traced("codegen", |p| {
+                    let mut generator = p
+                        .checked_modules
+                        .new_generator(&p.functions, &p.data_types, &p.module_types)
+                        .prune_unused_lets(p.config.prune_unused_lets)
+                        .inline_single_use(p.config.inline_single_use)
+                        .filter_traces(filter_traces);
+
+                    Blueprint::new(&p.config, &p.checked_modules, &mut generator)
+                        .map_err(Error::Blueprint)
+                })?;
+
+                // Modules are kept in a HashMap, so validators come out in no particular order.
+                blueprint.validators.sort_by(|a, b| {
+                    a.title
+                        .cmp(&b.title)
+                        .then_with(|| a.purpose.cmp(&b.purpose))
+                });
 
                 if blueprint.validators.is_empty() {
                     self.warnings.push(Warning::NoValidators);
                 }
 
+                self.warnings.extend(self.unused_script_contexts());
+
+                if self.config.verify_validators {
+                    self.event_listener
+                        .handle_event(Event::VerifyingValidators {
+                            count: blueprint.validators.len(),
+                        });
+
+                    blueprint.verify().map_err(Error::Blueprint)?;
+                }
+
+                for validator in &blueprint.validators {
+                    self.event_listener.handle_event(Event::ValidatorSize {
+                        title: validator.title.clone(),
+                        purpose: validator.purpose.clone(),
+                        bytes: validator.program.to_cbor().unwrap().len(),
+                    });
+                }
+
                 if uplc_dump {
                     self.dump_uplc(&blueprint)?;
                 }
@@ -242,34 +553,87 @@ where
                 match_tests,
                 verbose,
                 exact_match,
+                csv,
+                budget,
+                max_tests,
+                seed,
+                sorted,
+                timeout,
+                coverage,
+                benchmark,
             } => {
-                let tests = self.collect_tests(verbose)?;
+                let mut tests = self.traced("codegen", |p| p.collect_tests(verbose))?;
+
+                // Sorted before `max_tests` applies, so that the same tests are left out on
+                // every run. Evaluation keeps that order.
+                if sorted {
+                    tests.sort_by(|a, b| (&a.module, &a.name).cmp(&(&b.module, &b.name)));
+                }
 
                 if !tests.is_empty() {
                     self.event_listener.handle_event(Event::RunningTests);
                 }
 
-                let results = self.eval_scripts(tests, match_tests, exact_match);
+                let builtins_used = coverage.then(|| Mutex::new(HashSet::new()));
+
+                let results = self.traced("eval", |p| {
+                    p.eval_scripts(
+                        tests,
+                        match_tests,
+                        exact_match,
+                        budget,
+                        max_tests,
+                        seed,
+                        timeout,
+                        builtins_used.as_ref(),
+                    )
+                });
 
-                let errors: Vec<Error> = results
-                    .iter()
-                    .filter_map(|e| {
-                        if e.success {
-                            None
-                        } else {
-                            Some(Error::TestFailure {
-                                name: e.script.name.clone(),
-                                path: e.script.input_path.clone(),
-                                evaluation_hint: e.script.evaluation_hint.clone(),
-                                src: e.script.program.to_pretty(),
-                                verbose,
-                            })
-                        }
-                    })
-                    .collect();
+                let errors = test_failures(&results, verbose, &self.checked_modules);
 
-                self.event_listener
-                    .handle_event(Event::FinishedTests { tests: results });
+                if benchmark {
+                    let name =
+                        |info: &EvalInfo| format!("{}.{}", info.script.module, info.script.name);
+
+                    let mut passed: Vec<EvalInfo> =
+                        results.into_iter().filter(|info| info.success).collect();
+
+                    passed.sort_by(|a, b| {
+                        b.spent_budget
+                            .cpu
+                            .cmp(&a.spent_budget.cpu)
+                            .then_with(|| name(a).cmp(&name(b)))
+                    });
+
+                    if let Some(path) = csv {
+                        write_csv(&path, &passed)?;
+                    }
+
+                    self.event_listener.handle_event(Event::Benchmark {
+                        results: passed
+                            .iter()
+                            .map(|info| (name(info), info.spent_budget))
+                            .collect(),
+                    });
+                } else {
+                    if let Some(path) = csv {
+                        write_csv(&path, &results)?;
+                    }
+
+                    self.event_listener
+                        .handle_event(Event::FinishedTests { tests: results });
+                }
+
+                if let Some(builtins_used) = builtins_used {
+                    let builtins_used = builtins_used.into_inner().unwrap();
+
+                    let (exercised, unused) = DefaultFunction::iter()
+                        .filter(|fun| fun.is_supported(&Language::PlutusV2))
+                        .partition(|fun| builtins_used.contains(fun));
+
+                    self.event_listener
+                        .handle_event(Event::BuiltinCoverage { exercised, unused });
+                }
 
                 if !errors.is_empty() {
                     Err(Error::List(errors))
@@ -281,89 +645,270 @@ where
         }
     }
 
+    /// Validators (as module name and purpose) whose compiled code may change when the given
+    /// module changes, following what each validator reaches through the functions, constants
+    /// and types it uses. Only meaningful once the project has been checked or built.
+    pub fn validators_affected_by(&self, module: &str) -> Vec<(String, validator::Purpose)> {
+        let mut affected: Vec<(String, validator::Purpose)> = self
+            .checked_modules
+            .validators()
+            .filter(|(validator, def)| {
+                validator.name == module
+                    || self
+                        .checked_modules
+                        .reachable_modules(&validator.name, &def.name)
+                        .contains(module)
+            })
+            .filter_map(|(validator, def)| {
+                let purpose = def.name.clone().try_into().ok()?;
+                Some((validator.name.clone(), purpose))
+            })
+            .collect();
+
+        affected.sort();
+
+        affected
+    }
+
     pub fn address(
         &self,
         title: Option<&String>,
         purpose: Option<&validator::Purpose>,
-        stake_address: Option<&String>,
+        delegation: &Delegation,
+        network: Network,
     ) -> Result<ShelleyAddress, Error> {
-        // Parse stake address
-        let stake_address = stake_address
-            .map(|s| {
-                Address::from_hex(s)
-                    .or_else(|_| Address::from_bech32(s))
-                    .map_err(|error| Error::MalformedStakeAddress { error: Some(error) })
-                    .and_then(|addr| match addr {
-                        Address::Stake(addr) => Ok(addr),
-                        _ => Err(Error::MalformedStakeAddress { error: None }),
-                    })
-            })
-            .transpose()?;
-        let delegation_part = match stake_address.map(|addr| addr.payload().to_owned()) {
-            None => ShelleyDelegationPart::Null,
-            Some(StakePayload::Stake(key)) => ShelleyDelegationPart::Key(key),
-            Some(StakePayload::Script(script)) => ShelleyDelegationPart::Script(script),
-        };
+        let delegation_part = delegation.to_delegation_part()?;
 
         // Read blueprint
-        let blueprint = File::open(self.blueprint_path())
-            .map_err(|_| blueprint::error::Error::InvalidOrMissingFile)?;
-        let blueprint: Blueprint<serde_json::Value> =
-            serde_json::from_reader(BufReader::new(blueprint))?;
+        let blueprint = self.read_blueprint()?;
 
         // Calculate the address
         let when_too_many =
             |known_validators| Error::MoreThanOneValidatorFound { known_validators };
         let when_missing = |known_validators| Error::NoValidatorNotFound { known_validators };
-        blueprint.with_validator(title, purpose, when_too_many, when_missing, |validator| {
+        blueprint.with_validator(title, purpose, when_missing, when_too_many, |validator| {
             let n = validator.parameters.len();
             if n > 0 {
                 Err(blueprint::error::Error::ParameterizedValidator { n }.into())
             } else {
                 Ok(validator
                     .program
-                    .address(Network::Testnet, delegation_part.to_owned()))
+                    .address(network, delegation_part.to_owned()))
             }
         })
     }
 
-    pub fn apply_parameter(
+    /// The hash of a validator's script, which is its policy id when used as a minting policy.
+    pub fn policy(
         &self,
         title: Option<&String>,
         purpose: Option<&validator::Purpose>,
-        param: &Term<DeBruijn>,
-    ) -> Result<Blueprint<serde_json::Value>, Error> {
-        // Read blueprint
-        let blueprint = File::open(self.blueprint_path())
-            .map_err(|_| blueprint::error::Error::InvalidOrMissingFile)?;
-        let mut blueprint: Blueprint<serde_json::Value> =
-            serde_json::from_reader(BufReader::new(blueprint))?;
+    ) -> Result<uplc::Hash<28>, Error> {
+        let blueprint = self.read_blueprint()?;
 
-        // Apply parameters
         let when_too_many =
             |known_validators| Error::MoreThanOneValidatorFound { known_validators };
         let when_missing = |known_validators| Error::NoValidatorNotFound { known_validators };
-        let applied_validator =
-            blueprint.with_validator(title, purpose, when_too_many, when_missing, |validator| {
-                validator.apply(param).map_err(|e| e.into())
-            })?;
+        blueprint.with_validator(title, purpose, when_missing, when_too_many, |validator| {
+            let n = validator.parameters.len();
+            if n > 0 {
+                Err(blueprint::error::Error::ParameterizedValidator { n }.into())
+            } else {
+                Ok(validator.program.hash())
+            }
+        })
+    }
+
+    /// Each module's direct imports, once the project has been checked or built. Only the
+    /// project's own modules are included, unless `include_dependencies` is set.
+    pub fn module_dependencies(&self, include_dependencies: bool) -> HashMap<String, Vec<String>> {
+        let package = self.config.name.to_string();
+
+        let modules: HashMap<&String, &CheckedModule> = self
+            .checked_modules
+            .values()
+            .filter(|module| include_dependencies || module.package == package)
+            .map(|module| (&module.name, module))
+            .collect();
 
-        // Overwrite validator
-        blueprint.validators = blueprint
-            .validators
+        modules
+            .iter()
+            .map(|(name, module)| {
+                let imports = module
+                    .ast
+                    .dependencies()
+                    .into_iter()
+                    .map(|(import, _)| import)
+                    .filter(|import| modules.contains_key(import))
+                    .sorted()
+                    .dedup()
+                    .collect();
+
+                (name.to_string(), imports)
+            })
+            .collect()
+    }
+
+    /// The graph of [`Project::module_dependencies`], in Graphviz's DOT language, with an
+    /// edge from each module to the modules it imports.
+    pub fn modules_graph(&self, include_dependencies: bool) -> String {
+        let dependencies = self.module_dependencies(include_dependencies);
+
+        let mut dot = String::from("digraph modules {\n");
+
+        for (module, imports) in dependencies.iter().sorted() {
+            dot.push_str(&format!("  \"{module}\";\n"));
+
+            for import in imports {
+                dot.push_str(&format!("  \"{module}\" -> \"{import}\";\n"));
+            }
+        }
+
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    /// Check the project and write TypeScript declarations of its validators' datums,
+    /// redeemers and parameters to `destination`.
+    pub fn export_types(&mut self, destination: &Path) -> Result<(), Error> {
+        self.compile(Options {
+            code_gen_mode: CodeGenMode::NoOp,
+        })?;
+
+        let mut generator = self.checked_modules.new_generator(
+            &self.functions,
+            &self.data_types,
+            &self.module_types,
+        );
+
+        let mut blueprint = Blueprint::new(&self.config, &self.checked_modules, &mut generator)
+            .map_err(Error::Blueprint)?;
+
+        blueprint.validators.sort_by(|a, b| {
+            a.title
+                .cmp(&b.title)
+                .then_with(|| a.purpose.cmp(&b.purpose))
+        });
+
+        fs::write(destination, blueprint.to_typescript()).map_err(|error| Error::FileIo {
+            error,
+            path: destination.to_path_buf(),
+        })
+    }
+
+    /// Hex-encoded double-CBOR form of a validator, i.e. its flat-encoded program wrapped
+    /// in a CBOR byte string, itself wrapped in a CBOR byte string, as expected by off-chain
+    /// transaction builders.
+    pub fn export_validator(
+        &self,
+        title: Option<&String>,
+        purpose: Option<&validator::Purpose>,
+    ) -> Result<String, Error> {
+        let blueprint = self.read_blueprint()?;
+
+        let when_too_many =
+            |known_validators| Error::MoreThanOneValidatorFound { known_validators };
+        let when_missing = |known_validators| Error::NoValidatorNotFound { known_validators };
+        blueprint.with_validator(title, purpose, when_missing, when_too_many, |validator| {
+            Ok(hex::encode(validator.program.to_cbor_double().unwrap()))
+        })
+    }
+
+    pub fn apply_parameter(
+        &self,
+        title: Option<&String>,
+        purpose: Option<&validator::Purpose>,
+        param: &Term<DeBruijn>,
+    ) -> Result<Blueprint<serde_json::Value>, Error> {
+        self.apply_parameters(title, purpose, std::slice::from_ref(param))
+    }
+
+    pub fn apply_parameters(
+        &self,
+        title: Option<&String>,
+        purpose: Option<&validator::Purpose>,
+        params: &[Term<DeBruijn>],
+    ) -> Result<Blueprint<serde_json::Value>, Error> {
+        let blueprint = self.read_blueprint()?;
+
+        apply_parameters(blueprint, title, purpose, |validator| {
+            validator.apply_all(params).map_err(|e| e.into())
+        })
+    }
+
+    /// Apply parameters to several validators at once, as listed in a JSON spec file (see
+    /// [`blueprint::ParametersSpec`]). Each validator in the spec must be given all the
+    /// parameters it has left.
+    pub fn apply_parameters_from_spec(
+        &self,
+        path: &Path,
+    ) -> Result<Blueprint<serde_json::Value>, Error> {
+        let invalid = |reason: String| blueprint::error::Error::InvalidParametersSpec { reason };
+
+        let file = File::open(path).map_err(|error| Error::FileIo {
+            error,
+            path: path.to_path_buf(),
+        })?;
+
+        let specs: Vec<blueprint::ParametersSpec> =
+            serde_json::from_reader(BufReader::new(file)).map_err(|e| invalid(e.to_string()))?;
+
+        specs
             .into_iter()
-            .map(|validator| {
-                let same_title = validator.title == applied_validator.title;
-                let same_purpose = validator.purpose == applied_validator.purpose;
-                if same_title && same_purpose {
-                    applied_validator.to_owned()
-                } else {
-                    validator
-                }
+            .try_fold(self.read_blueprint()?, |blueprint, spec| {
+                let params = spec
+                    .parameters
+                    .iter()
+                    .map(|param| {
+                        let data = to_plutus_data::from_json(param)
+                            .map_err(|e| invalid(format!("{}: {e}", spec.title)))?;
+
+                        Ok(Term::Constant(Constant::Data(data).into()))
+                    })
+                    .collect::<Result<Vec<Term<DeBruijn>>, Error>>()?;
+
+                apply_parameters(
+                    blueprint,
+                    Some(&spec.title),
+                    spec.purpose.as_ref(),
+                    |validator| {
+                        if validator.parameters.len() != params.len() {
+                            return Err(blueprint::error::Error::WrongNumberOfParameters {
+                                title: validator.title,
+                                purpose: validator.purpose,
+                                expected: validator.parameters.len(),
+                                given: params.len(),
+                            }
+                            .into());
+                        }
+
+                        validator.apply_all(&params).map_err(|e| e.into())
+                    },
+                )
             })
-            .collect();
+    }
+
+    /// Run a step of the compilation, recording it on the timeline when there's one.
+    fn traced<A>(&mut self, name: &str, step: impl FnOnce(&mut Self) -> A) -> A {
+        let start = Instant::now();
+
+        let result = step(self);
+
+        if let Some(timeline) = &self.timeline {
+            timeline.record(name, "phase", start);
+        }
+
+        result
+    }
 
-        Ok(blueprint)
+    /// Forget the modules of a previous compilation, but for what's cached.
+    fn reset(&mut self) {
+        self.defined_modules.clear();
+        self.checked_modules = CheckedModules::default();
+        self.module_types
+            .retain(|name, _| name == "aiken" || name == "aiken/builtin");
+        self.warnings.clear();
     }
 
     fn compile_deps(&mut self) -> Result<(), Error> {
@@ -375,7 +920,7 @@ where
         )?;
 
         for package in manifest.packages {
-            let lib = self.root.join(paths::build_deps_package(&package.name));
+            let lib = deps::package_path(&self.root, &self.config, &package.name);
 
             self.event_listener
                 .handle_event(Event::StartingCompilation {
@@ -384,11 +929,18 @@ where
                     version: package.version.clone(),
                 });
 
-            self.read_package_source_files(&lib.join("lib"))?;
+            let edition = match Config::load(&lib) {
+                Ok(config) => config.edition,
+                Err(Error::MissingManifest { .. }) => Edition::default(),
+                Err(e) => return Err(e),
+            };
+
+            self.traced("read", |p| p.read_package_source_files(&lib.join("lib")))?;
 
-            let parsed_modules = self.parse_sources(package.name)?;
+            let parsed_modules =
+                self.traced("parse", |p| p.parse_sources(package.name, edition))?;
 
-            self.type_check(parsed_modules)?;
+            self.traced("type-check", |p| p.type_check(parsed_modules))?;
         }
 
         Ok(())
@@ -404,15 +956,60 @@ where
         Ok(())
     }
 
+    /// Check that the project's sources are formatted, without rewriting any of them. Returns
+    /// the path of every source that isn't, along with a unified diff of what formatting would
+    /// change; nothing when all are.
+    pub fn check_format(&self) -> Result<Vec<(PathBuf, String)>, Error> {
+        let mut unformatted = Vec::new();
+        let mut errors = Vec::new();
+
+        for (dir, kind) in [
+            (self.root.join("validators"), ModuleKind::Validator),
+            (self.root.join("lib"), ModuleKind::Lib),
+        ] {
+            for path in aiken_paths(&dir) {
+                let src = fs::read_to_string(&path).map_err(|error| Error::FileIo {
+                    path: path.clone(),
+                    error,
+                })?;
+
+                match aiken_lang::parser::module_with_edition(&src, kind, self.config.edition) {
+                    Ok((module, extra)) => {
+                        let mut output = String::new();
+
+                        aiken_lang::format::pretty(&mut output, module, extra, &src);
+
+                        if src != output {
+                            let diff = format::diff(&path, &src, &output);
+                            unformatted.push((path, diff));
+                        }
+                    }
+                    Err(errs) => errors.push(Error::from_parse_errors(errs, &path, &src)),
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(unformatted)
+        } else {
+            Err(Error::List(errors))
+        }
+    }
+
     fn read_package_source_files(&mut self, lib: &Path) -> Result<(), Error> {
         self.aiken_files(lib, ModuleKind::Lib)?;
 
         Ok(())
     }
 
-    fn parse_sources(&mut self, package_name: PackageName) -> Result<ParsedModules, Error> {
+    fn parse_sources(
+        &mut self,
+        package_name: PackageName,
+        edition: Edition,
+    ) -> Result<ParsedModules, Error> {
         let mut errors = Vec::new();
         let mut parsed_modules = HashMap::with_capacity(self.sources.len());
+        let timeline = self.timeline.as_ref();
 
         for Source {
             path,
@@ -421,7 +1018,15 @@ where
             kind,
         } in self.sources.drain(0..)
         {
-            match aiken_lang::parser::module(&code, kind) {
+            let start = Instant::now();
+
+            let parsed = aiken_lang::parser::module_with_edition(&code, kind, edition);
+
+            if let Some(timeline) = timeline {
+                timeline.record(name.clone(), "parse", start);
+            }
+
+            match parsed {
                 Ok((mut ast, extra)) => {
                     // Store the name
                     ast.name = name.clone();
@@ -472,85 +1077,145 @@ where
     }
 
     fn type_check(&mut self, mut parsed_modules: ParsedModules) -> Result<(), Error> {
-        let processing_sequence = parsed_modules.sequence()?;
+        use rayon::prelude::*;
 
-        for name in processing_sequence {
-            if let Some(ParsedModule {
-                name,
-                path,
-                code,
-                kind,
-                extra,
-                package,
-                ast,
-            }) = parsed_modules.remove(&name)
-            {
-                let mut type_warnings = Vec::new();
+        for layer in parsed_modules.layers()? {
+            let modules = layer
+                .iter()
+                .filter_map(|name| parsed_modules.remove(name))
+                .collect::<Vec<_>>();
+
+            // Modules of a layer only depend on earlier layers, so they're all inferred at once
+            // against the types known so far. Results keep the layer's order, i.e. by name, so
+            // warnings come out the same on every run.
+            let checker = TypeChecker {
+                id_gen: &self.id_gen,
+                package: self.config.name.to_string(),
+                module_types: &self.module_types,
+                cache: &self.cache,
+                timeline: self.timeline.as_ref(),
+            };
 
-                let ast = ast
-                    .infer(
-                        &self.id_gen,
-                        kind,
-                        &self.config.name.to_string(),
-                        &self.module_types,
-                        &mut type_warnings,
-                    )
-                    .map_err(|error| Error::Type {
-                        path: path.clone(),
-                        src: code.clone(),
-                        named: NamedSource::new(path.display().to_string(), code.clone()),
-                        error,
-                    })?;
+            let checked = modules
+                .into_par_iter()
+                .map(|module| checker.check_module(module))
+                .collect::<Vec<_>>();
 
-                // Register any warnings emitted as type warnings
-                let type_warnings = type_warnings
-                    .into_iter()
-                    .map(|w| Warning::from_type_warning(w, path.clone(), code.clone()));
+            for result in checked {
+                let cached = result?;
+                let module = cached.module.clone();
 
-                self.warnings.extend(type_warnings);
+                // Register any warnings emitted as type warnings
+                self.warnings.extend(cached.warnings.iter().map(|warning| {
+                    Warning::from_type_warning(
+                        warning.clone(),
+                        module.input_path.clone(),
+                        module.code.clone(),
+                    )
+                }));
 
                 // Register the types from this module so they can be imported into
                 // other modules.
                 self.module_types
-                    .insert(name.clone(), ast.type_info.clone());
+                    .insert(module.name.clone(), module.ast.type_info.clone());
 
-                self.checked_modules.insert(
-                    name.clone(),
-                    CheckedModule {
-                        kind,
-                        extra,
-                        name,
-                        code,
-                        ast,
-                        package,
-                        input_path: path,
-                    },
-                );
+                self.cache.insert(module.name.clone(), cached);
+
+                self.checked_modules.insert(module.name.clone(), module);
             }
         }
 
         Ok(())
     }
 
-    fn collect_tests(&mut self, verbose: bool) -> Result<Vec<Script>, Error> {
-        let mut scripts = Vec::new();
+    /// Validators whose script context, their last argument, is never referred to. A validator
+    /// explicitly discarding it (e.g. `_ctx`) is assumed to know what it's doing.
+    fn unused_script_contexts(&self) -> Vec<Warning> {
+        let mut warnings = Vec::new();
+        for (module, def) in self.checked_modules.validators() {
+            if let Some(Arg {
+                arg_name: ArgName::Named { name, .. },
+                location,
+                ..
+            }) = def.arguments.last()
+            {
+                if !def.body.references(name, *location) {
+                    warnings.push((
+                        module.name.clone(),
+                        validator::Purpose::try_from(def.name.clone())
+                            .expect("unexpected validator name"),
+                    ));
+                }
+            }
+        }
+
+        warnings.sort();
+
+        warnings
+            .into_iter()
+            .map(|(title, purpose)| Warning::UnusedScriptContext { title, purpose })
+            .collect()
+    }
+
+    /// Every test of the project's own modules, without generating any code for them. The
+    /// project must have been checked beforehand, or there's nothing to list.
+    pub fn list_tests(&self) -> Vec<TestInfo> {
+        self.discover_tests()
+            .into_iter()
+            .map(|(module, func)| TestInfo {
+                input_path: module.input_path.clone(),
+                module: module.name.clone(),
+                name: func.name.clone(),
+                group: func.group.clone(),
+                can_error: func.can_error,
+                is_property: !func.arguments.is_empty(),
+                has_hint: func.test_hint().is_some(),
+            })
+            .collect()
+    }
+
+    fn discover_tests(&self) -> Vec<(&CheckedModule, &TypedFunction)> {
+        let mut tests = Vec::new();
         for module in self.checked_modules.values() {
             if module.package != self.config.name.to_string() {
                 continue;
             }
             for def in module.ast.definitions() {
                 if let Definition::Test(func) = def {
-                    scripts.push((module.input_path.clone(), module.name.clone(), func))
+                    tests.push((module, func))
                 }
             }
         }
 
+        tests
+    }
+
+    fn collect_tests(&mut self, verbose: bool) -> Result<Vec<Script>, Error> {
+        let mut warnings = Vec::new();
+        let mut scripts = Vec::new();
+        for (module, func) in self.discover_tests() {
+            if !func.has_assertion() {
+                warnings.push(Warning::from_type_warning(
+                    tipo::error::Warning::TestWithoutAssertion {
+                        name: func.name.clone(),
+                        location: func.location,
+                    },
+                    module.input_path.clone(),
+                    module.code.clone(),
+                ));
+            }
+
+            scripts.push((module.input_path.clone(), module.name.clone(), func))
+        }
+
         let mut programs = Vec::new();
         for (input_path, module_name, func_def) in scripts {
             let Function {
                 arguments,
                 name,
                 body,
+                can_error,
+                group,
                 ..
             } = func_def;
 
@@ -561,23 +1226,23 @@ where
                 })
             }
 
-            let mut generator = self.checked_modules.new_generator(
-                &self.functions,
-                &self.data_types,
-                &self.module_types,
-            );
+            let mut generator = self
+                .checked_modules
+                .new_generator(&self.functions, &self.data_types, &self.module_types)
+                .prune_unused_lets(self.config.prune_unused_lets)
+                .inline_single_use(self.config.inline_single_use);
 
             let evaluation_hint = if let Some((bin_op, left_src, right_src)) = func_def.test_hint()
             {
                 let left = generator
                     .clone()
-                    .generate(&left_src, &[], false)
+                    .generate(&left_src, arguments, false)
                     .try_into()
                     .unwrap();
 
                 let right = generator
                     .clone()
-                    .generate(&right_src, &[], false)
+                    .generate(&right_src, arguments, false)
                     .try_into()
                     .unwrap();
 
@@ -585,6 +1250,8 @@ where
                     bin_op,
                     left,
                     right,
+                    left_location: operand_location(&left_src),
+                    right_location: operand_location(&right_src),
                 })
             } else {
                 None
@@ -598,71 +1265,56 @@ where
                 name.to_string(),
                 program.try_into().unwrap(),
                 evaluation_hint,
-            );
+                *can_error,
+                !arguments.is_empty(),
+            )
+            .in_group(group.clone());
 
             programs.push(script);
         }
 
+        self.warnings.extend(warnings);
+
         Ok(programs)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn eval_scripts(
         &self,
         scripts: Vec<Script>,
         match_tests: Option<Vec<String>>,
         exact_match: bool,
+        budget: Option<ExBudget>,
+        max_tests: Option<usize>,
+        seed: u64,
+        timeout: Option<Duration>,
+        builtins_used: Option<&Mutex<HashSet<DefaultFunction>>>,
     ) -> Vec<EvalInfo> {
         use rayon::prelude::*;
 
         // TODO: in the future we probably just want to be able to
         // tell the machine to not explode on budget consumption.
-        let initial_budget = ExBudget {
+        let initial_budget = budget.unwrap_or(ExBudget {
             mem: i64::MAX,
             cpu: i64::MAX,
-        };
+        });
 
         let scripts = if let Some(match_tests) = match_tests {
-            let match_tests: Vec<(&str, Option<Vec<String>>)> = match_tests
+            let filters: Vec<TestFilter> = match_tests
                 .iter()
-                .map(|match_test| {
-                    let mut match_split_dot = match_test.split('.');
-
-                    let match_module = if match_test.contains('.') || match_test.contains('/') {
-                        match_split_dot.next().unwrap_or("")
-                    } else {
-                        ""
-                    };
-
-                    let match_names = match_split_dot.next().map(|names| {
-                        let names = names.replace(&['{', '}'][..], "");
-
-                        let names_split_comma = names.split(',');
-
-                        names_split_comma.map(str::to_string).collect()
-                    });
-
-                    (match_module, match_names)
-                })
+                .map(|filter| TestFilter::parse(filter))
                 .collect();
 
             scripts
                 .into_iter()
-                .filter(|script| -> bool {
-                    match_tests.iter().any(|(module, names)| {
-                        let matched_module = module == &"" || script.module.contains(module);
-
-                        let matched_name = match names {
-                            None => true,
-                            Some(names) => names.iter().any(|name| {
-                                if exact_match {
-                                    name == &script.name
-                                } else {
-                                    script.name.contains(name)
-                                }
-                            }),
-                        };
-
-                        matched_module && matched_name
+                .filter(|script| {
+                    filters.iter().any(|filter| {
+                        filter.matches(
+                            &script.module,
+                            &script.name,
+                            script.group.as_deref(),
+                            exact_match,
+                        )
                     })
                 })
                 .collect::<Vec<Script>>()
@@ -670,39 +1322,53 @@ where
             scripts
         };
 
+        let scripts = match max_tests {
+            Some(max_tests) if scripts.len() > max_tests => {
+                self.event_listener.handle_event(Event::SkippedTests {
+                    count: scripts.len() - max_tests,
+                });
+
+                scripts.into_iter().take(max_tests).collect()
+            }
+            _ => scripts,
+        };
+
+        let timeline = self.timeline.as_ref();
+
         scripts
             .into_par_iter()
-            .map(|script| match script.program.eval(initial_budget) {
-                (Ok(result), remaining_budget, logs) => EvalInfo {
-                    success: result != Term::Error
-                        && result != Term::Constant(Constant::Bool(false).into()),
-                    script,
-                    spent_budget: initial_budget - remaining_budget,
-                    output: Some(result),
-                    logs,
-                },
-                (Err(..), remaining_budget, logs) => EvalInfo {
-                    success: false,
-                    script,
-                    spent_budget: initial_budget - remaining_budget,
-                    output: None,
-                    logs,
-                },
+            .map(|script| {
+                let start = Instant::now();
+
+                let deadline = timeout.map(|timeout| start + timeout);
+
+                let name = format!("{}.{}", script.module, script.name);
+
+                let info = if script.is_property {
+                    eval_property(script, initial_budget, seed, deadline, builtins_used)
+                } else {
+                    let evaluation =
+                        eval_program(&script.program, initial_budget, deadline, builtins_used);
+
+                    eval_info(script, initial_budget, evaluation)
+                };
+
+                if let Some(timeline) = timeline {
+                    timeline.record(name, "test", start);
+                }
+
+                info
             })
             .collect()
     }
 
     fn aiken_files(&mut self, dir: &Path, kind: ModuleKind) -> Result<(), Error> {
-        let paths = walkdir::WalkDir::new(dir)
-            .follow_links(true)
-            .into_iter()
-            .filter_map(Result::ok)
-            .filter(|e| e.file_type().is_file())
-            .map(|d| d.into_path())
-            .filter(move |d| is_aiken_path(d, dir));
-
-        for path in paths {
-            self.add_module(path, dir, kind)?;
+        for path in ak_paths(dir) {
+            if is_aiken_path(&path, dir) {
+                self.add_module(path, dir, kind)?;
+            } else {
+                self.warnings.push(Warning::InvalidModuleName { path });
+            }
         }
 
         Ok(())
@@ -748,6 +1414,230 @@ where
     }
 }
 
+fn aiken_paths(dir: &Path) -> impl Iterator<Item = PathBuf> + '_ {
+    ak_paths(dir).filter(move |d| is_aiken_path(d, dir))
+}
+
+/// Every `.ak` file under a directory, whether or not its path makes a valid module name.
+fn ak_paths(dir: &Path) -> impl Iterator<Item = PathBuf> {
+    walkdir::WalkDir::new(dir)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .map(|d| d.into_path())
+        .filter(|d| d.extension().map_or(false, |ext| ext == "ak"))
+}
+
+/// Append evaluation results to a CSV file, writing the header first when the file is new.
+fn write_csv(path: &Path, results: &[EvalInfo]) -> Result<(), Error> {
+    use std::io::Write;
+
+    let is_new = fs::metadata(path).map_or(true, |metadata| metadata.len() == 0);
+
+    let mut rows = Vec::with_capacity(results.len() + 1);
+
+    if is_new {
+        rows.push(EvalInfo::CSV_HEADER.to_string());
+    }
+
+    rows.extend(results.iter().map(EvalInfo::to_csv_row));
+
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{}", rows.join("\n")))
+        .map_err(|error| Error::FileIo {
+            error,
+            path: path.to_path_buf(),
+        })
+}
+
+fn eval_info(
+    script: Script,
+    initial_budget: ExBudget,
+    evaluation: (
+        Result<Term<NamedDeBruijn>, uplc::machine::Error>,
+        ExBudget,
+        Vec<String>,
+    ),
+) -> EvalInfo {
+    match evaluation {
+        (Ok(result), remaining_budget, logs) => EvalInfo {
+            success: script.can_error
+                != (result != Term::Error
+                    && result != Term::Constant(Constant::Bool(false).into())),
+            script,
+            spent_budget: initial_budget - remaining_budget,
+            output: Some(result),
+            logs,
+            counterexample: None,
+            error: None,
+        },
+        (Err(error), remaining_budget, logs) => EvalInfo {
+            success: script.can_error,
+            script,
+            spent_budget: initial_budget - remaining_budget,
+            output: None,
+            logs,
+            counterexample: None,
+            error: Some(error),
+        },
+    }
+}
+
+/// Run a property against `PROPERTY_RUNS` values generated from `seed`, stopping at the first
+/// one it fails on. A property which holds reports the budget spent over all runs, but no
+/// logs, since they would be repeated for every run. All runs share the same `deadline`.
+fn eval_property(
+    script: Script,
+    initial_budget: ExBudget,
+    seed: u64,
+    deadline: Option<Instant>,
+    builtins_used: Option<&Mutex<HashSet<DefaultFunction>>>,
+) -> EvalInfo {
+    let mut prng = Prng::new(seed);
+
+    let mut spent_budget = ExBudget { mem: 0, cpu: 0 };
+
+    for _ in 0..PROPERTY_RUNS {
+        let value = prng.data();
+
+        let evaluation = eval_program(
+            &script.program.apply_data(value.clone()),
+            initial_budget,
+            deadline,
+            builtins_used,
+        );
+
+        let info = eval_info(script.clone(), initial_budget, evaluation);
+
+        if !info.success {
+            return EvalInfo {
+                counterexample: Some(Counterexample { seed, value }),
+                ..info
+            };
+        }
+
+        spent_budget = spent_budget + info.spent_budget;
+    }
+
+    EvalInfo {
+        success: true,
+        script,
+        spent_budget,
+        output: None,
+        logs: vec![],
+        counterexample: None,
+        error: None,
+    }
+}
+
+/// Apply parameters to the validator matching `title` and `purpose`, replacing it in the
+/// blueprint.
+fn apply_parameters(
+    mut blueprint: Blueprint<serde_json::Value>,
+    title: Option<&String>,
+    purpose: Option<&validator::Purpose>,
+    apply: impl Fn(Validator<serde_json::Value>) -> Result<Validator<serde_json::Value>, Error>,
+) -> Result<Blueprint<serde_json::Value>, Error> {
+    let when_too_many = |known_validators| Error::MoreThanOneValidatorFound { known_validators };
+    let when_missing = |known_validators| Error::NoValidatorNotFound { known_validators };
+    let applied_validator =
+        blueprint.with_validator(title, purpose, when_missing, when_too_many, apply)?;
+
+    // Overwrite validator
+    blueprint.validators = blueprint
+        .validators
+        .into_iter()
+        .map(|validator| {
+            let same_title = validator.title == applied_validator.title;
+            let same_purpose = validator.purpose == applied_validator.purpose;
+            if same_title && same_purpose {
+                applied_validator.to_owned()
+            } else {
+                validator
+            }
+        })
+        .collect();
+
+    Ok(blueprint)
+}
+
+/// Evaluate a program, giving up once `deadline` has passed, if any. Builtins the program
+/// applies are added to `builtins_used`, if given.
+fn eval_program(
+    program: &Program<NamedDeBruijn>,
+    initial_budget: ExBudget,
+    deadline: Option<Instant>,
+    builtins_used: Option<&Mutex<HashSet<DefaultFunction>>>,
+) -> (
+    Result<Term<NamedDeBruijn>, uplc::machine::Error>,
+    ExBudget,
+    Vec<String>,
+) {
+    match (builtins_used, deadline) {
+        (Some(builtins_used), _) => {
+            let (term, budget, logs, used) = program.eval_with_coverage(initial_budget, deadline);
+
+            builtins_used.lock().unwrap().extend(used);
+
+            (term, budget, logs)
+        }
+        (None, Some(deadline)) => program.eval_with_deadline(initial_budget, deadline),
+        (None, None) => program.eval(initial_budget),
+    }
+}
+
+fn test_failures(results: &[EvalInfo], verbose: bool, modules: &CheckedModules) -> Vec<Error> {
+    results
+        .iter()
+        .filter_map(|e| {
+            let reason = e.failure_reason()?;
+
+            let code = modules
+                .get(&e.script.module)
+                .map(|module| module.code.clone())
+                .unwrap_or_default();
+
+            Some(Error::TestFailure {
+                name: e.script.name.clone(),
+                path: e.script.input_path.clone(),
+                evaluation_hint: e.script.evaluation_hint.clone(),
+                src: e.script.program.to_pretty(),
+                named: NamedSource::new(e.script.input_path.display().to_string(), code),
+                verbose,
+                can_error: e.script.can_error,
+                reason,
+                counterexample: e.counterexample.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Where an operand of a test's final comparison is in the source. Operands come wrapped in
+/// the expressions preceding the comparison, if any, which have no location of their own.
+fn operand_location(operand: &TypedExpr) -> Span {
+    match operand {
+        TypedExpr::Sequence { expressions, .. } | TypedExpr::Pipeline { expressions, .. } => {
+            expressions
+                .last()
+                .map(operand_location)
+                .unwrap_or_else(|| operand.location())
+        }
+        _ => operand.location(),
+    }
+}
+
+/// A seed for properties, when none is given. Failures report the seed they were found with,
+/// to be passed back in order to reproduce them.
+fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+}
+
 fn is_aiken_path(path: &Path, dir: impl AsRef<Path>) -> bool {
     use regex::Regex;
 
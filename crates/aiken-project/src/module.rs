@@ -1,11 +1,13 @@
 use crate::error::Error;
 use aiken_lang::{
     ast::{
-        DataType, Definition, ModuleKind, TypedDataType, TypedFunction, TypedModule, UntypedModule,
+        ClauseGuard, Constant, DataType, Definition, ModuleKind, TypedDataType, TypedFunction,
+        TypedModule, UntypedModule,
     },
     builder::{DataTypeKey, FunctionAccessKey},
+    expr::TypedExpr,
     parser::extra::{comments_before, Comment, ModuleExtra},
-    tipo::TypeInfo,
+    tipo::{ModuleValueConstructor, Type, TypeInfo, TypeVar, ValueConstructorVariant},
     uplc::CodeGenerator,
     VALIDATOR_NAMES,
 };
@@ -15,6 +17,7 @@ use std::{
     collections::{HashMap, HashSet},
     ops::{Deref, DerefMut},
     path::PathBuf,
+    sync::Arc,
 };
 
 #[derive(Debug)]
@@ -95,13 +98,17 @@ impl ParsedModule {
 pub struct ParsedModules(HashMap<String, ParsedModule>);
 
 impl ParsedModules {
+    /// Module names in an order where each module comes after its dependencies. Modules are
+    /// added to the graph by name, so that the order doesn't vary between runs.
     pub fn sequence(&self) -> Result<Vec<String>, Error> {
-        let inputs = self
+        let mut inputs = self
             .0
             .values()
             .map(|m| m.deps_for_graph())
             .collect::<Vec<(String, Vec<String>)>>();
 
+        inputs.sort();
+
         let capacity = inputs.len();
 
         let mut graph = Graph::<(), ()>::with_capacity(capacity, capacity * 5);
@@ -141,19 +148,60 @@ impl ParsedModules {
             Err(cycle) => {
                 let origin = cycle.node_id();
 
-                let mut path = vec![];
+                let mut path = vec![origin];
 
                 find_cycle(origin, origin, &graph, &mut path, &mut HashSet::new());
 
-                let modules = path
+                let mut modules: Vec<String> =
+                    path.iter().map(|index| values[index].clone()).collect();
+
+                // Start from the first module by name, so that a same cycle is always reported
+                // the same way.
+                modules.pop();
+                let first = modules
                     .iter()
-                    .filter_map(|index| values.remove(index))
-                    .collect();
+                    .enumerate()
+                    .min_by_key(|(_, name)| *name)
+                    .map(|(i, _)| i)
+                    .unwrap_or_default();
+                modules.rotate_left(first);
+                modules.push(modules[0].clone());
 
                 Err(Error::ImportCycle { modules })
             }
         }
     }
+
+    /// Modules grouped in layers, such that each module only depends on modules of earlier
+    /// layers. Modules of a same layer are independent from one another, and sorted by name.
+    pub fn layers(&self) -> Result<Vec<Vec<String>>, Error> {
+        let mut depths: HashMap<String, usize> = HashMap::with_capacity(self.0.len());
+        let mut layers: Vec<Vec<String>> = Vec::new();
+
+        for name in self.sequence()? {
+            let depth = self.0[&name]
+                .deps_for_graph()
+                .1
+                .iter()
+                .filter_map(|dep| depths.get(dep))
+                .map(|depth| depth + 1)
+                .max()
+                .unwrap_or(0);
+
+            if layers.len() <= depth {
+                layers.resize_with(depth + 1, Vec::new);
+            }
+
+            layers[depth].push(name.clone());
+            depths.insert(name, depth);
+        }
+
+        for layer in layers.iter_mut() {
+            layer.sort();
+        }
+
+        Ok(layers)
+    }
 }
 
 impl From<HashMap<String, ParsedModule>> for ParsedModules {
@@ -182,6 +230,8 @@ impl DerefMut for ParsedModules {
     }
 }
 
+/// Find a path from `parent` back to `origin`, appending the nodes it goes through to
+/// `path`, which ends with `origin` when one is found.
 fn find_cycle(
     origin: NodeIndex,
     parent: NodeIndex,
@@ -202,11 +252,13 @@ fn find_cycle(
             continue;
         }
 
-        if find_cycle(origin, node, graph, path, seen) {
-            path.push(node);
+        path.push(node);
 
+        if find_cycle(origin, node, graph, path, seen) {
             return true;
         }
+
+        path.pop();
     }
 
     false
@@ -223,6 +275,41 @@ pub struct CheckedModule {
     pub extra: ModuleExtra,
 }
 
+impl CheckedModule {
+    /// Signatures of the module's public functions, in the order they're defined, with their
+    /// inferred types.
+    pub fn public_functions(&self) -> Vec<FunctionSignature> {
+        self.ast
+            .definitions()
+            .filter_map(|def| match def {
+                Definition::Fn(func) if func.public => {
+                    let value = self.ast.type_info.values.get(&func.name)?;
+
+                    match value.tipo.as_ref() {
+                        Type::Fn { args, ret } => Some(FunctionSignature {
+                            name: func.name.clone(),
+                            doc: func.doc.clone(),
+                            arguments: args.clone(),
+                            return_type: ret.clone(),
+                        }),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// The name, documentation and type of a public function.
+#[derive(Debug, Clone)]
+pub struct FunctionSignature {
+    pub name: String,
+    pub doc: Option<String>,
+    pub arguments: Vec<Arc<Type>>,
+    pub return_type: Arc<Type>,
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct CheckedModules(HashMap<String, CheckedModule>);
 
@@ -271,6 +358,22 @@ impl CheckedModules {
             .filter(|module| module.kind.is_validator())
     }
 
+    /// Modules the code generated for the given function may depend on: the modules defining
+    /// the functions it calls (directly or not), the constants it refers to and the types of
+    /// the values it manipulates.
+    pub fn reachable_modules(&self, module: &str, function: &str) -> HashSet<String> {
+        let mut reachability = Reachability {
+            modules: self,
+            reached: HashSet::new(),
+            functions: HashSet::new(),
+            data_types: HashSet::new(),
+        };
+
+        reachability.function(module, function);
+
+        reachability.reached
+    }
+
     pub fn new_generator<'a>(
         &'a self,
         builtin_functions: &'a IndexMap<FunctionAccessKey, TypedFunction>,
@@ -338,3 +441,255 @@ impl DerefMut for CheckedModules {
         &mut self.0
     }
 }
+
+/// Walks the call graph of a function, collecting the modules it reaches along the way.
+struct Reachability<'a> {
+    modules: &'a CheckedModules,
+    reached: HashSet<String>,
+    functions: HashSet<(String, String)>,
+    data_types: HashSet<(String, String)>,
+}
+
+impl<'a> Reachability<'a> {
+    fn function(&mut self, module: &str, name: &str) {
+        if !self
+            .functions
+            .insert((module.to_string(), name.to_string()))
+        {
+            return;
+        }
+
+        self.reached.insert(module.to_string());
+
+        let modules = self.modules;
+
+        let func = modules.get(module).and_then(|checked| {
+            checked.ast.definitions().find_map(|def| match def {
+                Definition::Fn(func) if func.name == name => Some(func),
+                _ => None,
+            })
+        });
+
+        if let Some(func) = func {
+            for arg in &func.arguments {
+                self.tipo(&arg.tipo);
+            }
+            self.tipo(&func.return_type);
+            self.expr(&func.body);
+        }
+    }
+
+    fn data_type(&mut self, module: &str, name: &str) {
+        if module.is_empty()
+            || !self
+                .data_types
+                .insert((module.to_string(), name.to_string()))
+        {
+            return;
+        }
+
+        self.reached.insert(module.to_string());
+
+        let modules = self.modules;
+
+        let data_type = modules.get(module).and_then(|checked| {
+            checked.ast.definitions().find_map(|def| match def {
+                Definition::DataType(data_type) if data_type.name == name => Some(data_type),
+                _ => None,
+            })
+        });
+
+        if let Some(data_type) = data_type {
+            for constructor in &data_type.constructors {
+                for argument in &constructor.arguments {
+                    self.tipo(&argument.tipo);
+                }
+            }
+        }
+    }
+
+    fn tipo(&mut self, tipo: &Type) {
+        match tipo {
+            Type::App {
+                module, name, args, ..
+            } => {
+                self.data_type(module, name);
+                for arg in args {
+                    self.tipo(arg);
+                }
+            }
+            Type::Fn { args, ret } => {
+                for arg in args {
+                    self.tipo(arg);
+                }
+                self.tipo(ret);
+            }
+            Type::Var { tipo } => {
+                if let TypeVar::Link { tipo } = &*tipo.read().unwrap() {
+                    self.tipo(tipo);
+                }
+            }
+            Type::Tuple { elems } => {
+                for elem in elems {
+                    self.tipo(elem);
+                }
+            }
+        }
+    }
+
+    fn constant(&mut self, constant: &Constant<Arc<Type>, String>) {
+        self.tipo(&constant.tipo());
+
+        match constant {
+            Constant::Tuple { elements, .. } | Constant::List { elements, .. } => {
+                for element in elements {
+                    self.constant(element);
+                }
+            }
+            Constant::Record { args, .. } => {
+                for arg in args {
+                    self.constant(&arg.value);
+                }
+            }
+            Constant::Var {
+                constructor: Some(constructor),
+                ..
+            } => self.variant(&constructor.variant),
+            Constant::Int { .. }
+            | Constant::String { .. }
+            | Constant::ByteArray { .. }
+            | Constant::Var { .. } => {}
+        }
+    }
+
+    fn variant(&mut self, variant: &ValueConstructorVariant) {
+        match variant {
+            ValueConstructorVariant::LocalVariable { .. } => {}
+            ValueConstructorVariant::ModuleConstant {
+                module, literal, ..
+            } => {
+                self.reached.insert(module.clone());
+                self.constant(literal);
+            }
+            ValueConstructorVariant::ModuleFn { module, name, .. } => self.function(module, name),
+            ValueConstructorVariant::Record { module, .. } => {
+                self.reached.insert(module.clone());
+            }
+        }
+    }
+
+    fn guard(&mut self, guard: &ClauseGuard<Arc<Type>, String>) {
+        match guard {
+            ClauseGuard::Not { value, .. } => self.guard(value),
+            ClauseGuard::Equals { left, right, .. }
+            | ClauseGuard::NotEquals { left, right, .. }
+            | ClauseGuard::GtInt { left, right, .. }
+            | ClauseGuard::GtEqInt { left, right, .. }
+            | ClauseGuard::LtInt { left, right, .. }
+            | ClauseGuard::LtEqInt { left, right, .. }
+            | ClauseGuard::Or { left, right, .. }
+            | ClauseGuard::And { left, right, .. } => {
+                self.guard(left);
+                self.guard(right);
+            }
+            ClauseGuard::Var { tipo, .. } => self.tipo(tipo),
+            ClauseGuard::Constant(constant) => self.constant(constant),
+        }
+    }
+
+    fn expr(&mut self, expr: &TypedExpr) {
+        self.tipo(&expr.tipo());
+
+        match expr {
+            TypedExpr::Int { .. }
+            | TypedExpr::String { .. }
+            | TypedExpr::ByteArray { .. }
+            | TypedExpr::Todo { .. }
+            | TypedExpr::ErrorTerm { .. } => {}
+            TypedExpr::Sequence { expressions, .. } | TypedExpr::Pipeline { expressions, .. } => {
+                for expression in expressions {
+                    self.expr(expression);
+                }
+            }
+            TypedExpr::Var { constructor, .. } => self.variant(&constructor.variant),
+            TypedExpr::Fn { args, body, .. } => {
+                for arg in args {
+                    self.tipo(&arg.tipo);
+                }
+                self.expr(body);
+            }
+            TypedExpr::List { elements, tail, .. } => {
+                for element in elements {
+                    self.expr(element);
+                }
+                if let Some(tail) = tail {
+                    self.expr(tail);
+                }
+            }
+            TypedExpr::Call { fun, args, .. } => {
+                self.expr(fun);
+                for arg in args {
+                    self.expr(&arg.value);
+                }
+            }
+            TypedExpr::BinOp { left, right, .. } => {
+                self.expr(left);
+                self.expr(right);
+            }
+            TypedExpr::Assignment { value, .. } => self.expr(value),
+            TypedExpr::Trace { then, .. } => self.expr(then),
+            TypedExpr::When {
+                subjects, clauses, ..
+            } => {
+                for subject in subjects {
+                    self.expr(subject);
+                }
+                for clause in clauses {
+                    if let Some(guard) = &clause.guard {
+                        self.guard(guard);
+                    }
+                    self.expr(&clause.then);
+                }
+            }
+            TypedExpr::If {
+                branches,
+                final_else,
+                ..
+            } => {
+                for branch in branches {
+                    self.expr(&branch.condition);
+                    self.expr(&branch.body);
+                }
+                self.expr(final_else);
+            }
+            TypedExpr::RecordAccess { record, .. } => self.expr(record),
+            TypedExpr::ModuleSelect {
+                module_name,
+                constructor,
+                ..
+            } => match constructor {
+                ModuleValueConstructor::Fn { module, name, .. } => self.function(module, name),
+                ModuleValueConstructor::Constant { literal, .. } => {
+                    self.reached.insert(module_name.clone());
+                    self.constant(literal);
+                }
+                ModuleValueConstructor::Record { .. } => {
+                    self.reached.insert(module_name.clone());
+                }
+            },
+            TypedExpr::Tuple { elems, .. } => {
+                for elem in elems {
+                    self.expr(elem);
+                }
+            }
+            TypedExpr::TupleIndex { tuple, .. } => self.expr(tuple),
+            TypedExpr::RecordUpdate { spread, args, .. } => {
+                self.expr(spread);
+                for arg in args {
+                    self.expr(&arg.value);
+                }
+            }
+            TypedExpr::UnOp { value, .. } => self.expr(value),
+        }
+    }
+}
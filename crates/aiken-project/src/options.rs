@@ -1,13 +1,65 @@
+use std::{path::PathBuf, time::Duration};
+use uplc::machine::cost_model::ExBudget;
+
 pub struct Options {
     pub code_gen_mode: CodeGenMode,
 }
 
+/// What [`crate::Project::check`] does once the project type-checks. The default runs every
+/// test once, with a random seed and neither budget, time nor count limit.
+#[derive(Debug, Clone, Default)]
+pub struct CheckOptions {
+    /// Whether to only type-check, without running any test.
+    pub skip_tests: bool,
+    /// Only run the tests matching any of these, see [`crate::script::TestFilter`].
+    pub match_tests: Option<Vec<String>>,
+    /// Whether to also pretty-print the UPLC of failing tests.
+    pub verbose: bool,
+    /// Whether `match_tests` must match modules and test names exactly.
+    pub exact_match: bool,
+    /// Where to append test results as CSV, if anywhere.
+    pub csv: Option<PathBuf>,
+    /// The budget each test may spend, if limited.
+    pub budget: Option<ExBudget>,
+    /// How many of the matching tests to run at most.
+    pub max_tests: Option<usize>,
+    /// The seed property tests draw their values from; random when none.
+    pub seed: Option<u64>,
+    /// Whether to run and report tests sorted by module and name, rather than in no
+    /// particular order.
+    pub sorted: bool,
+    /// How long each test may run before it fails, if at all.
+    pub timeout: Option<Duration>,
+    /// Whether to report which builtins the tests exercised, and which they didn't.
+    pub coverage: bool,
+    /// Whether to report the budget each passing test consumed, most expensive first, instead
+    /// of the usual test report. Property tests then use a fixed seed unless one is given.
+    pub benchmark: bool,
+}
+
 pub enum CodeGenMode {
     Test {
         match_tests: Option<Vec<String>>,
         verbose: bool,
         exact_match: bool,
+        csv: Option<PathBuf>,
+        budget: Option<ExBudget>,
+        max_tests: Option<usize>,
+        seed: u64,
+        /// Whether to report tests sorted by module and name, rather than in no particular order.
+        sorted: bool,
+        /// How long each test may run before it fails, if at all.
+        timeout: Option<Duration>,
+        /// Whether to report which builtins the tests exercised, and which they didn't.
+        coverage: bool,
+        /// Whether to report the budget each passing test consumed, most expensive first.
+        benchmark: bool,
+    },
+    Build {
+        /// Whether to also write the textual UPLC of each validator.
+        uplc: bool,
+        /// Whether to leave `trace` expressions out of the generated validators.
+        filter_traces: bool,
     },
-    Build(bool),
     NoOp,
 }
@@ -20,7 +20,11 @@ pub fn packages_toml() -> PathBuf {
 }
 
 pub fn build_deps_package(package_name: &PackageName) -> PathBuf {
-    packages().join(format!("{}-{}", package_name.owner, package_name.repo))
+    packages().join(package_dir(package_name))
+}
+
+pub fn package_dir(package_name: &PackageName) -> PathBuf {
+    PathBuf::from(format!("{}-{}", package_name.owner, package_name.repo))
 }
 
 pub fn package_cache_zipball(cache_key: &CacheKey) -> PathBuf {
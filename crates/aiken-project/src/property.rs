@@ -0,0 +1,4 @@
+pub use uplc::prng::Prng;
+
+/// Number of random values a property test is run against.
+pub const PROPERTY_RUNS: usize = 100;
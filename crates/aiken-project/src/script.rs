@@ -1,7 +1,10 @@
 use crate::{ExBudget, Term};
-use aiken_lang::ast::BinOp;
-use std::path::PathBuf;
-use uplc::ast::{NamedDeBruijn, Program};
+use aiken_lang::ast::{BinOp, Span};
+use std::{collections::BTreeMap, fmt, path::PathBuf};
+use uplc::{
+    ast::{to_diagnostic_cbor, Constant, NamedDeBruijn, Program},
+    PlutusData,
+};
 
 #[derive(Debug, Clone)]
 pub struct Script {
@@ -10,6 +13,12 @@ pub struct Script {
     pub name: String,
     pub program: Program<NamedDeBruijn>,
     pub evaluation_hint: Option<EvalHint>,
+    /// Whether the script is expected to fail rather than to succeed.
+    pub can_error: bool,
+    /// Whether the script is a property, taking a `Data` argument.
+    pub is_property: bool,
+    /// The `describe` block the test is declared in, if any.
+    pub group: Option<String>,
 }
 
 unsafe impl Send for Script {}
@@ -21,6 +30,8 @@ impl Script {
         name: String,
         program: Program<NamedDeBruijn>,
         evaluation_hint: Option<EvalHint>,
+        can_error: bool,
+        is_property: bool,
     ) -> Script {
         Script {
             input_path,
@@ -28,8 +39,31 @@ impl Script {
             name,
             program,
             evaluation_hint,
+            can_error,
+            is_property,
+            group: None,
         }
     }
+
+    pub fn in_group(self, group: Option<String>) -> Script {
+        Script { group, ..self }
+    }
+}
+
+/// A test found in the project, before any code is generated for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestInfo {
+    pub input_path: PathBuf,
+    pub module: String,
+    pub name: String,
+    /// The `describe` block the test is declared in, if any.
+    pub group: Option<String>,
+    /// Whether the test is expected to fail rather than to succeed.
+    pub can_error: bool,
+    /// Whether the test is a property, taking a `Data` argument.
+    pub is_property: bool,
+    /// Whether the test ends with a comparison, whose operands are shown when it fails.
+    pub has_hint: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +71,9 @@ pub struct EvalHint {
     pub bin_op: BinOp,
     pub left: Program<NamedDeBruijn>,
     pub right: Program<NamedDeBruijn>,
+    /// Where the operands of the comparison are in the test's source.
+    pub left_location: Span,
+    pub right_location: Span,
 }
 
 #[derive(Debug)]
@@ -46,6 +83,306 @@ pub struct EvalInfo {
     pub spent_budget: ExBudget,
     pub output: Option<Term<NamedDeBruijn>>,
     pub logs: Vec<String>,
+    /// For a property, the first generated value it failed on.
+    pub counterexample: Option<Counterexample>,
+    /// The error the evaluation stopped with, if any.
+    pub error: Option<uplc::machine::Error>,
+}
+
+/// Why a test failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailureReason {
+    /// The test evaluated to `False`.
+    ReturnedFalse,
+    /// The test ran out of budget before completing.
+    ExceededBudget,
+    /// The evaluation of the test failed with the given error.
+    Errored(String),
+    /// The test is expected to fail, yet it succeeded.
+    UnexpectedSuccess,
+    /// The test didn't finish in the time it was given.
+    TimedOut,
+}
+
+impl fmt::Display for FailureReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailureReason::ReturnedFalse => write!(f, "assertion failed"),
+            FailureReason::ExceededBudget => write!(f, "exceeded budget"),
+            FailureReason::Errored(error) => write!(f, "raised an error: {error}"),
+            FailureReason::UnexpectedSuccess => write!(f, "succeeded, but was expected to fail"),
+            FailureReason::TimedOut => write!(f, "timed out"),
+        }
+    }
+}
+
+/// A value a property failed on, along with the seed it was generated from.
+#[derive(Debug, Clone)]
+pub struct Counterexample {
+    pub seed: u64,
+    pub value: PlutusData,
 }
 
 unsafe impl Send for EvalInfo {}
+
+impl EvalInfo {
+    /// Columns of the CSV report, in the order produced by [`EvalInfo::to_csv_row`].
+    pub const CSV_HEADER: &'static str = "name,module,success,cpu,mem,log-count";
+
+    pub fn to_csv_row(&self) -> String {
+        [
+            csv_field(&self.script.name),
+            csv_field(&self.script.module),
+            self.success.to_string(),
+            self.spent_budget.cpu.to_string(),
+            self.spent_budget.mem.to_string(),
+            self.logs.len().to_string(),
+        ]
+        .join(",")
+    }
+
+    /// Why the test failed, if it did.
+    pub fn failure_reason(&self) -> Option<FailureReason> {
+        if self.success {
+            return None;
+        }
+
+        Some(match &self.error {
+            _ if self.script.can_error => FailureReason::UnexpectedSuccess,
+            Some(uplc::machine::Error::OutOfExError(_)) => FailureReason::ExceededBudget,
+            Some(uplc::machine::Error::DeadlineReached) => FailureReason::TimedOut,
+            Some(error) => FailureReason::Errored(error.to_string()),
+            None => FailureReason::ReturnedFalse,
+        })
+    }
+
+    /// The output of the evaluation in CBOR diagnostic notation, when it is some `Data`.
+    pub fn output_diagnostic(&self) -> Option<String> {
+        match &self.output {
+            Some(Term::Constant(constant)) => match constant.as_ref() {
+                Constant::Data(data) => Some(to_diagnostic_cbor(data)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Tests grouped after the `describe` block they're declared in, starting with those
+/// declared outside of any.
+pub fn group_by_describe<'a>(
+    infos: &[&'a EvalInfo],
+) -> BTreeMap<Option<&'a str>, Vec<&'a EvalInfo>> {
+    let mut groups: BTreeMap<Option<&str>, Vec<&EvalInfo>> = BTreeMap::new();
+
+    for info in infos {
+        groups
+            .entry(info.script.group.as_deref())
+            .or_default()
+            .push(info);
+    }
+
+    groups
+}
+
+/// A filter on the tests to run, as given with `--match-tests`: either `module`, `module.name`,
+/// `module.{name_1,name_2}`, or a lone test name. A module is told apart from a test name by
+/// containing a `/` or being followed by a `.`, since neither can appear in test names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestFilter {
+    Module {
+        module: String,
+        names: Option<Vec<String>>,
+    },
+    Name(String),
+}
+
+impl TestFilter {
+    pub fn parse(filter: &str) -> Self {
+        match filter.split_once('.') {
+            Some((module, names)) => {
+                let names = names.replace(&['{', '}'][..], "");
+
+                TestFilter::Module {
+                    module: module.to_string(),
+                    names: (!names.is_empty())
+                        .then(|| names.split(',').map(str::to_string).collect()),
+                }
+            }
+            None if filter.contains('/') => TestFilter::Module {
+                module: filter.to_string(),
+                names: None,
+            },
+            None => TestFilter::Name(filter.to_string()),
+        }
+    }
+
+    /// Whether a test matches the filter. With `exact_match`, modules and names must be
+    /// equal to the ones given; otherwise, they only need to contain them. A lone name also
+    /// matches the name of the `describe` block the test is in, if any.
+    pub fn matches(
+        &self,
+        module: &str,
+        name: &str,
+        group: Option<&str>,
+        exact_match: bool,
+    ) -> bool {
+        let matches = |given: &str, actual: &str| {
+            if exact_match {
+                given == actual
+            } else {
+                actual.contains(given)
+            }
+        };
+
+        match self {
+            TestFilter::Module {
+                module: given_module,
+                names: given_names,
+            } => {
+                matches(given_module, module)
+                    && given_names.as_ref().map_or(true, |given_names| {
+                        given_names
+                            .iter()
+                            .any(|given_name| matches(given_name, name))
+                    })
+            }
+            TestFilter::Name(given_name) => {
+                matches(given_name, name) || group.map_or(false, |group| matches(given_name, group))
+            }
+        }
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use uplc::{BigInt, Constr};
+
+    fn eval_info(name: &str, logs: Vec<String>) -> EvalInfo {
+        EvalInfo {
+            success: true,
+            script: Script::new(
+                PathBuf::new(),
+                "aiken/list".to_string(),
+                name.to_string(),
+                Program {
+                    version: (1, 0, 0),
+                    term: Term::Constant(Constant::Bool(true).into()),
+                },
+                None,
+                false,
+                false,
+            ),
+            spent_budget: ExBudget { mem: 42, cpu: 1337 },
+            output: None,
+            logs,
+            counterexample: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn csv_row() {
+        let info = eval_info("map_1", vec!["foo".to_string(), "bar".to_string()]);
+
+        assert_eq!(info.to_csv_row(), "map_1,aiken/list,true,1337,42,2");
+    }
+
+    #[test]
+    fn csv_row_escaped() {
+        let info = eval_info("some \"odd\", name", vec![]);
+
+        assert_eq!(
+            info.to_csv_row(),
+            "\"some \"\"odd\"\", name\",aiken/list,true,1337,42,0"
+        );
+    }
+
+    #[test]
+    fn output_diagnostic() {
+        let mut info = eval_info("output", vec![]);
+
+        assert_eq!(info.output_diagnostic(), None);
+
+        info.output = Some(Term::Constant(
+            Constant::Data(PlutusData::Constr(Constr {
+                tag: 121,
+                any_constructor: None,
+                fields: vec![PlutusData::BigInt(BigInt::Int(42.into()))],
+            }))
+            .into(),
+        ));
+
+        assert_eq!(info.output_diagnostic(), Some("121([_ 42])".to_string()));
+    }
+
+    #[test]
+    fn test_filter_parse() {
+        assert_eq!(
+            TestFilter::parse("aiken/list"),
+            TestFilter::Module {
+                module: "aiken/list".to_string(),
+                names: None
+            }
+        );
+        assert_eq!(
+            TestFilter::parse("nested/foo.{a,b}"),
+            TestFilter::Module {
+                module: "nested/foo".to_string(),
+                names: Some(vec!["a".to_string(), "b".to_string()])
+            }
+        );
+        assert_eq!(
+            TestFilter::parse("foo.bar"),
+            TestFilter::Module {
+                module: "foo".to_string(),
+                names: Some(vec!["bar".to_string()])
+            }
+        );
+        assert_eq!(
+            TestFilter::parse("foo."),
+            TestFilter::Module {
+                module: "foo".to_string(),
+                names: None
+            }
+        );
+        assert_eq!(
+            TestFilter::parse("foo"),
+            TestFilter::Name("foo".to_string())
+        );
+    }
+
+    #[test]
+    fn test_filter_module_prefix() {
+        let filter = TestFilter::parse("foo.bar");
+
+        assert!(filter.matches("foo", "bar", None, true));
+        assert!(!filter.matches("foobar", "bar", None, true));
+        assert!(filter.matches("foobar", "bar", None, false));
+
+        let filter = TestFilter::parse("nested/foo.{a,b}");
+
+        assert!(filter.matches("nested/foo", "b", None, true));
+        assert!(!filter.matches("nested/foobar", "a", None, true));
+        assert!(!filter.matches("nested/foo", "ab", None, true));
+        assert!(filter.matches("nested/foobar", "ab", None, false));
+
+        let filter = TestFilter::parse("foo");
+
+        assert!(filter.matches("other", "foo", None, true));
+        assert!(!filter.matches("other", "foobar", None, true));
+        assert!(filter.matches("other", "foobar", None, false));
+        assert!(!filter.matches("foo", "anything", None, true));
+        assert!(!filter.matches("foobar", "anything", None, false));
+        assert!(filter.matches("other", "anything", Some("foo"), true));
+    }
+}
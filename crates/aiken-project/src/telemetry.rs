@@ -1,5 +1,7 @@
-use crate::script::EvalInfo;
-use std::path::PathBuf;
+use crate::{blueprint::validator::Purpose, docs::DocCoverage, script::EvalInfo};
+use serde::Serialize;
+use std::{path::PathBuf, sync::Mutex, time::Instant};
+use uplc::{builtins::DefaultFunction, machine::cost_model::ExBudget};
 
 pub trait EventListener: std::fmt::Debug {
     fn handle_event(&self, event: Event);
@@ -19,9 +21,24 @@ pub enum Event {
     GeneratingDocFiles {
         output_path: PathBuf,
     },
+    WroteDocFiles {
+        updated: usize,
+        unchanged: usize,
+    },
+    DocCoverage {
+        coverage: DocCoverage,
+    },
     GeneratingBlueprint {
         path: PathBuf,
     },
+    ValidatorSize {
+        title: String,
+        purpose: Purpose,
+        bytes: usize,
+    },
+    VerifyingValidators {
+        count: usize,
+    },
     DumpingUPLC {
         path: PathBuf,
     },
@@ -33,9 +50,20 @@ pub enum Event {
         results: Vec<EvalInfo>,
     },
     RunningTests,
+    SkippedTests {
+        count: usize,
+    },
     FinishedTests {
         tests: Vec<EvalInfo>,
     },
+    /// Supported builtins the tests applied, and those they didn't, each in order of their tag.
+    BuiltinCoverage {
+        exercised: Vec<DefaultFunction>,
+        unused: Vec<DefaultFunction>,
+    },
+    Benchmark {
+        results: Vec<(String, ExBudget)>,
+    },
     WaitingForBuildDirLock,
     DownloadingPackage {
         name: String,
@@ -46,3 +74,69 @@ pub enum Event {
     },
     ResolvingVersions,
 }
+
+/// Records how long each step of a compilation takes, as complete events of the Chrome
+/// trace event format. The resulting file can be opened with chrome://tracing or Perfetto,
+/// where spans recorded on the same thread nest within one another.
+#[derive(Debug)]
+pub struct Timeline {
+    origin: Instant,
+    events: Mutex<Vec<TraceEvent>>,
+}
+
+#[derive(Debug, Serialize)]
+struct TraceEvent {
+    name: String,
+    cat: &'static str,
+    ph: &'static str,
+    ts: f64,
+    dur: f64,
+    pid: u32,
+    tid: usize,
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Timeline {
+            origin: Instant::now(),
+            events: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Record a span named `name`, going from `start` until now. Spans recorded from
+    /// rayon's worker threads are kept on a separate track for each thread.
+    pub fn record(&self, name: impl Into<String>, category: &'static str, start: Instant) {
+        let end = Instant::now();
+
+        let event = TraceEvent {
+            name: name.into(),
+            cat: category,
+            ph: "X",
+            ts: micros(start.saturating_duration_since(self.origin)),
+            dur: micros(end.saturating_duration_since(start)),
+            pid: 1,
+            tid: rayon::current_thread_index().map_or(0, |index| index + 1),
+        };
+
+        self.events.lock().unwrap().push(event);
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let events = self.events.lock().unwrap();
+
+        serde_json::json!({
+            "traceEvents": *events,
+            "displayTimeUnit": "ms",
+        })
+    }
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn micros(duration: std::time::Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000.0
+}
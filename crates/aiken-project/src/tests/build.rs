@@ -0,0 +1,618 @@
+use super::{Recorded, Silent, TestProject};
+use crate::{
+    address::Delegation,
+    blueprint::{self, validator::Purpose, Blueprint},
+    config::Config,
+    error::{Error, Warning},
+    options::CheckOptions,
+    package_name::PackageName,
+    telemetry::Event,
+    Project,
+};
+use pallas::{codec::minicbor::Decoder, ledger::addresses::Network};
+use std::fs;
+use uplc::{
+    ast::{DeBruijn, Program},
+    machine::cost_model::ExBudget,
+};
+
+#[test]
+fn blueprint_is_deterministic() {
+    let test_project = TestProject::new("deterministic_blueprint")
+        .with_module(
+            "validators/foo.ak",
+            r#"
+            fn spend(datum: Data, redeemer: Data, ctx: Data) {
+                True
+            }
+
+            fn mint(redeemer: Data, ctx: Data) {
+                True
+            }
+            "#,
+        )
+        .with_module(
+            "validators/bar.ak",
+            r#"
+            fn withdraw(redeemer: Data, ctx: Data) {
+                True
+            }
+            "#,
+        )
+        .with_module(
+            "validators/baz.ak",
+            r#"
+            fn publish(redeemer: Data, ctx: Data) {
+                True
+            }
+            "#,
+        );
+
+    let mut builds = Vec::new();
+
+    for _ in 0..2 {
+        test_project.project().build(false, false).unwrap();
+
+        builds.push(fs::read(test_project.root.join("plutus.json")).unwrap());
+    }
+
+    assert_eq!(builds[0], builds[1]);
+
+    let blueprint: Blueprint<serde_json::Value> = serde_json::from_slice(&builds[0]).unwrap();
+
+    assert_eq!(
+        blueprint
+            .validators
+            .into_iter()
+            .map(|v| (v.title, v.purpose))
+            .collect::<Vec<_>>(),
+        vec![
+            ("bar".to_string(), Purpose::Withdraw),
+            ("baz".to_string(), Purpose::Publish),
+            ("foo".to_string(), Purpose::Spend),
+            ("foo".to_string(), Purpose::Mint),
+        ]
+    );
+}
+
+#[test]
+fn export_validator() {
+    let test_project = TestProject::new("export_validator").with_module(
+        "validators/foo.ak",
+        r#"
+        fn spend(datum: Data, redeemer: Data, ctx: Data) {
+            True
+        }
+
+        fn mint(redeemer: Data, ctx: Data) {
+            False
+        }
+        "#,
+    );
+
+    let mut project = test_project.project();
+
+    project.build(false, false).unwrap();
+
+    let blueprint: Blueprint<serde_json::Value> =
+        serde_json::from_slice(&fs::read(test_project.root.join("plutus.json")).unwrap()).unwrap();
+
+    let title = "foo".to_string();
+
+    let hex = project
+        .export_validator(Some(&title), Some(&Purpose::Mint))
+        .unwrap();
+
+    let bytes = hex::decode(&hex).unwrap();
+
+    let single = Decoder::new(&bytes).bytes().unwrap();
+    let flat = Decoder::new(single).bytes().unwrap();
+
+    let program = Program::<DeBruijn>::from_flat(flat).unwrap();
+
+    assert_eq!(Program::from_cbor_double(&bytes).unwrap(), program);
+
+    let mint = blueprint
+        .validators
+        .iter()
+        .find(|v| v.purpose == Purpose::Mint)
+        .unwrap();
+
+    assert_eq!(program, mint.program);
+
+    assert!(matches!(
+        project.export_validator(Some(&title), None),
+        Err(Error::MoreThanOneValidatorFound { .. })
+    ));
+
+    assert!(matches!(
+        project.export_validator(Some(&"bar".to_string()), None),
+        Err(Error::NoValidatorNotFound { .. })
+    ));
+}
+
+#[test]
+fn apply_parameters_from_spec() {
+    let test_project = TestProject::new("apply_parameters_from_spec")
+        .with_module(
+            "validators/foo.ak",
+            r#"
+            fn spend(owner: ByteArray, datum: Data, redeemer: Data, ctx: Data) {
+                True
+            }
+            "#,
+        )
+        .with_module(
+            "validators/bar.ak",
+            r#"
+            fn mint(utxo_ref: Int, tag: Int, redeemer: Data, ctx: Data) {
+                True
+            }
+            "#,
+        );
+
+    let mut project = test_project.project();
+
+    project.build(false, false).unwrap();
+
+    let spec = |json: &str| {
+        let path = test_project.root.join("params.json");
+        fs::write(&path, json).unwrap();
+        project.apply_parameters_from_spec(&path)
+    };
+
+    let blueprint = spec(
+        r#"[
+            { "title": "foo", "purpose": "spend", "parameters": [{ "bytes": "deadbeef" }] },
+            { "title": "bar", "parameters": [{ "int": 42 }, { "int": 14 }] }
+        ]"#,
+    )
+    .unwrap();
+
+    assert!(blueprint
+        .validators
+        .iter()
+        .all(|validator| validator.parameters.is_empty()));
+
+    assert!(matches!(
+        spec(r#"[{ "title": "baz", "parameters": [] }]"#),
+        Err(Error::NoValidatorNotFound { .. })
+    ));
+
+    assert!(matches!(
+        spec(r#"[{ "title": "bar", "parameters": [{ "int": 42 }] }]"#),
+        Err(Error::Blueprint(
+            blueprint::error::Error::WrongNumberOfParameters {
+                expected: 2,
+                given: 1,
+                ..
+            }
+        ))
+    ));
+
+    assert!(matches!(
+        spec(r#"[{ "title": "foo", "parameters": [{ "int": "42" }] }]"#),
+        Err(Error::Blueprint(
+            blueprint::error::Error::InvalidParametersSpec { .. }
+        ))
+    ));
+}
+
+#[test]
+fn validators_affected_by_module() {
+    let test_project = TestProject::new("validators_affected_by_module")
+        .with_module("lib/b.ak", "pub fn inc(n: Int) -> Int {\n  n + 1\n}")
+        .with_module(
+            "lib/a.ak",
+            "use b\n\npub fn double(n: Int) -> Int {\n  b.inc(n) * 2\n}",
+        )
+        .with_module("lib/c.ak", "pub const answer = 42")
+        .with_module("lib/t.ak", "pub type Action {\n  Go\n  Stop\n}")
+        .with_module("lib/unused.ak", "pub fn noop(n: Int) -> Int {\n  n\n}")
+        .with_module(
+            "validators/foo.ak",
+            r#"
+            use a
+            use t.{Action, Go, Stop}
+            use unused
+
+            fn spend(datum: Data, redeemer: Data, ctx: Data) {
+                a.double(1) == 4
+            }
+
+            fn mint(redeemer: Data, ctx: Data) {
+                let action: Action = Go
+                when action is {
+                    Go -> True
+                    Stop -> False
+                }
+            }
+            "#,
+        )
+        .with_module(
+            "validators/bar.ak",
+            r#"
+            use c
+
+            fn withdraw(redeemer: Data, ctx: Data) {
+                c.answer == 42
+            }
+            "#,
+        );
+
+    let mut project = test_project.project();
+
+    project
+        .check(CheckOptions {
+            skip_tests: true,
+            ..CheckOptions::default()
+        })
+        .unwrap();
+
+    let affected = |module| project.validators_affected_by(module);
+
+    assert_eq!(affected("b"), vec![("foo".to_string(), Purpose::Spend)]);
+    assert_eq!(affected("t"), vec![("foo".to_string(), Purpose::Mint)]);
+    assert_eq!(affected("c"), vec![("bar".to_string(), Purpose::Withdraw)]);
+    assert_eq!(
+        affected("foo"),
+        vec![
+            ("foo".to_string(), Purpose::Spend),
+            ("foo".to_string(), Purpose::Mint)
+        ]
+    );
+    assert_eq!(affected("unused"), vec![]);
+}
+
+#[test]
+fn build_with_config() {
+    let test_project = TestProject::new("build_with_config").with_module(
+        "validators/foo.ak",
+        r#"
+        fn mint(redeemer: Data, ctx: Data) {
+            True
+        }
+        "#,
+    );
+
+    fs::remove_file(test_project.root.join("aiken.toml")).unwrap();
+
+    let name = PackageName {
+        owner: "in".to_string(),
+        repo: "memory".to_string(),
+    };
+
+    let config = Config {
+        version: "1.2.3".to_string(),
+        dependencies: vec![],
+        ..Config::default(&name)
+    };
+
+    Project::new_with_config(test_project.root.clone(), Silent, config)
+        .build(false, false)
+        .unwrap();
+
+    let blueprint: Blueprint<serde_json::Value> =
+        serde_json::from_slice(&fs::read(test_project.root.join("plutus.json")).unwrap()).unwrap();
+
+    assert_eq!(blueprint.preamble.title, "in/memory");
+    assert_eq!(blueprint.preamble.version, "1.2.3");
+    assert_eq!(blueprint.validators.len(), 1);
+}
+
+#[test]
+fn export_types() {
+    let test_project = TestProject::new("export_types").with_module(
+        "validators/foo.ak",
+        r#"
+        type Datum {
+          owner: ByteArray,
+          amount: Int,
+        }
+
+        type Action {
+          Claim
+          Cancel(Int)
+        }
+
+        fn spend(datum: Datum, redeemer: Action, ctx: Data) {
+            True
+        }
+        "#,
+    );
+
+    let destination = test_project.root.join("plutus.d.ts");
+
+    test_project.project().export_types(&destination).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(destination).unwrap(),
+        "export interface FooSpendDatum {\n  constructor: 0;\n  fields: [owner: string, amount: bigint];\n}\n\n\
+         export type FooSpendRedeemer = { constructor: 0; fields: [] } | { constructor: 1; fields: [bigint] };\n"
+    );
+}
+
+#[test]
+fn address_delegation() {
+    let test_project = TestProject::new("address_delegation").with_module(
+        "validators/foo.ak",
+        r#"
+        fn spend(datum: Data, redeemer: Data, ctx: Data) {
+            True
+        }
+        "#,
+    );
+
+    let mut project = test_project.project();
+
+    project.build(false, false).unwrap();
+
+    let address = |delegation| project.address(None, None, &delegation, Network::Testnet);
+
+    let enterprise = address(Delegation::Enterprise).unwrap();
+    let base = address(Delegation::StakeAddress(format!("e0{}", "00".repeat(28)))).unwrap();
+    let pointer = address(Delegation::Pointer {
+        slot: 42,
+        tx_index: 1,
+        cert_index: 0,
+    })
+    .unwrap();
+
+    assert!(enterprise.to_bech32().unwrap().starts_with("addr_test1w"));
+    assert!(base.to_bech32().unwrap().starts_with("addr_test1z"));
+    assert!(pointer.to_bech32().unwrap().starts_with("addr_test12"));
+
+    // Same payment part, followed by the delegation part.
+    let payment = &enterprise.to_vec()[1..];
+    assert_eq!(&base.to_vec()[1..29], payment);
+    assert_eq!(&pointer.to_vec()[1..29], payment);
+    assert_eq!(&pointer.to_vec()[29..], &[42, 1, 0]);
+
+    assert!(matches!(
+        address(Delegation::Pointer {
+            slot: 0,
+            tx_index: 1 << 16,
+            cert_index: 0,
+        }),
+        Err(Error::Blueprint(blueprint::error::Error::InvalidPointer { max, .. })) if max == u16::MAX as u64
+    ));
+}
+
+#[test]
+fn address_network() {
+    let test_project = TestProject::new("address_network").with_module(
+        "validators/foo.ak",
+        r#"
+        fn spend(datum: Data, redeemer: Data, ctx: Data) {
+            True
+        }
+        "#,
+    );
+
+    let mut project = test_project.project();
+
+    project.build(false, false).unwrap();
+
+    let address = |network| {
+        project
+            .address(None, None, &Delegation::Enterprise, network)
+            .unwrap()
+    };
+
+    let (testnet, mainnet) = (address(Network::Testnet), address(Network::Mainnet));
+
+    assert!(testnet.to_bech32().unwrap().starts_with("addr_test1"));
+    assert!(mainnet.to_bech32().unwrap().starts_with("addr1"));
+
+    // Only the network differs, in the header's low bits.
+    assert_eq!(testnet.to_vec()[1..], mainnet.to_vec()[1..]);
+}
+
+#[test]
+fn prune_unused_lets() {
+    let test_project = TestProject::new("prune_unused_lets").with_module(
+        "validators/foo.ak",
+        r#"
+        fn spend(datum: Data, redeemer: Data, ctx: Data) {
+          let unused = fn(n) { n + 1 }
+          True
+        }
+        "#,
+    );
+
+    let name = PackageName {
+        owner: "test".to_string(),
+        repo: "project".to_string(),
+    };
+
+    let compiled_size = |prune_unused_lets| {
+        let config = Config {
+            dependencies: vec![],
+            prune_unused_lets,
+            ..Config::default(&name)
+        };
+
+        Project::new_with_config(test_project.root.clone(), Silent, config)
+            .build(false, false)
+            .unwrap();
+
+        let blueprint: Blueprint<serde_json::Value> =
+            serde_json::from_slice(&fs::read(test_project.root.join("plutus.json")).unwrap())
+                .unwrap();
+
+        blueprint.validators[0].program.to_cbor().unwrap().len()
+    };
+
+    let (with, without) = (compiled_size(true), compiled_size(false));
+    assert!(with < without);
+}
+
+#[test]
+fn inline_single_use() {
+    let test_project = TestProject::new("inline_single_use").with_module(
+        "validators/foo.ak",
+        r#"
+        fn spend(datum: Data, redeemer: Data, ctx: Data) {
+          let double = fn(n) { n * 2 }
+          double(1) == 2
+        }
+        "#,
+    );
+
+    let name = PackageName {
+        owner: "test".to_string(),
+        repo: "project".to_string(),
+    };
+
+    let compiled_size = |inline_single_use| {
+        let config = Config {
+            dependencies: vec![],
+            inline_single_use,
+            ..Config::default(&name)
+        };
+
+        Project::new_with_config(test_project.root.clone(), Silent, config)
+            .build(false, false)
+            .unwrap();
+
+        let blueprint: Blueprint<serde_json::Value> =
+            serde_json::from_slice(&fs::read(test_project.root.join("plutus.json")).unwrap())
+                .unwrap();
+
+        blueprint.validators[0].program.to_cbor().unwrap().len()
+    };
+
+    let (with, without) = (compiled_size(true), compiled_size(false));
+    assert!(with < without);
+}
+
+#[test]
+fn filter_traces() {
+    let test_project = TestProject::new("filter_traces").with_module(
+        "validators/foo.ak",
+        r#"
+        fn spend(datum: Data, redeemer: Data, ctx: Data) {
+          trace("checking the redeemer")
+          redeemer == datum
+        }
+        "#,
+    );
+
+    let build = |filter_traces| {
+        test_project.project().build(false, filter_traces).unwrap();
+
+        let blueprint: Blueprint<serde_json::Value> =
+            serde_json::from_slice(&fs::read(test_project.root.join("plutus.json")).unwrap())
+                .unwrap();
+
+        blueprint.validators[0].program.clone()
+    };
+
+    let (kept, filtered) = (build(false), build(true));
+
+    assert!(filtered.to_cbor().unwrap().len() < kept.to_cbor().unwrap().len());
+
+    let run = |program: &Program<DeBruijn>, redeemer: i64| {
+        let args = [42, redeemer, 0].map(|n| uplc::PlutusData::BigInt(uplc::BigInt::Int(n.into())));
+
+        let (result, _, logs) = program.apply_data_all(&args).eval(ExBudget::default());
+
+        (result.is_ok(), logs)
+    };
+
+    for redeemer in [42, 14] {
+        let (kept_ok, kept_logs) = run(&kept, redeemer);
+        let (filtered_ok, filtered_logs) = run(&filtered, redeemer);
+
+        assert_eq!(kept_ok, filtered_ok);
+        assert_eq!(kept_logs, vec!["checking the redeemer"]);
+        assert!(filtered_logs.is_empty());
+    }
+
+    assert!(run(&filtered, 42).0);
+    assert!(!run(&filtered, 14).0);
+}
+
+#[test]
+fn unused_script_context() {
+    let test_project = TestProject::new("unused_script_context")
+        .with_module(
+            "validators/careful.ak",
+            r#"
+            fn spend(datum: Data, redeemer: Data, ctx: Data) {
+                ctx == redeemer
+            }
+            "#,
+        )
+        .with_module(
+            "validators/careless.ak",
+            r#"
+            fn spend(datum: Data, redeemer: Data, ctx: Data) {
+                datum == redeemer
+            }
+
+            fn mint(redeemer: Data, _ctx: Data) {
+                True
+            }
+            "#,
+        );
+
+    let mut project = test_project.project();
+
+    project.build(false, false).unwrap();
+
+    let unused = project
+        .warnings
+        .iter()
+        .filter_map(|warning| match warning {
+            Warning::UnusedScriptContext { title, purpose } => Some((title.as_str(), purpose)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(unused, vec![("careless", &Purpose::Spend)]);
+}
+
+#[test]
+fn verify_validators() {
+    let test_project = TestProject::new("verify_validators").with_module(
+        "validators/foo.ak",
+        r#"
+        fn spend(datum: Data, redeemer: Data, ctx: Data) {
+          True
+        }
+        "#,
+    );
+
+    let name = PackageName {
+        owner: "test".to_string(),
+        repo: "project".to_string(),
+    };
+
+    let verified = |verify_validators| {
+        let config = Config {
+            dependencies: vec![],
+            verify_validators,
+            ..Config::default(&name)
+        };
+
+        let mut project =
+            Project::new_with_config(test_project.root.clone(), Recorded::default(), config);
+
+        project.build(false, false).unwrap();
+
+        project
+            .event_listener
+            .take()
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::VerifyingValidators { count } => Some(count),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert!(!Config::default(&name).verify_validators);
+    assert_eq!(verified(false), Vec::<usize>::new());
+    assert_eq!(verified(true), vec![1]);
+}
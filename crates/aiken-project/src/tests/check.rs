@@ -0,0 +1,1205 @@
+use super::TestProject;
+use crate::{
+    error::{Error, Warning},
+    options::CheckOptions,
+    script::{EvalInfo, FailureReason},
+    telemetry::Event,
+    ExBudget,
+};
+use aiken_lang::tipo;
+use itertools::Itertools;
+use miette::Diagnostic;
+use std::time::Duration;
+use uplc::builtins::DefaultFunction;
+
+const EXPENSIVE: &str = r#"
+fn sum(n: Int) -> Int {
+  if n <= 0 {
+    0
+  } else {
+    n + sum(n - 1)
+  }
+}
+
+test expensive() {
+  sum(100) == 5050
+}
+"#;
+
+#[test]
+fn tests_fail_when_exceeding_budget() {
+    let test_project =
+        TestProject::new("tests_fail_when_exceeding_budget").with_module("lib/foo.ak", EXPENSIVE);
+
+    test_project
+        .project()
+        .check(CheckOptions::default())
+        .unwrap();
+
+    let tight = ExBudget {
+        mem: 10_000,
+        cpu: 10_000_000,
+    };
+
+    match test_project.project().check(CheckOptions {
+        budget: Some(tight),
+        ..CheckOptions::default()
+    }) {
+        Err(Error::List(errors)) => {
+            assert!(matches!(
+                &errors[..],
+                [Error::TestFailure { name, reason: FailureReason::ExceededBudget, .. }] if name == "expensive"
+            ))
+        }
+        result => panic!("expected a test failure, got {result:?}"),
+    }
+}
+
+#[test]
+fn test_failures_tell_why_they_failed() {
+    let test_project = TestProject::new("test_failures_tell_why_they_failed").with_module(
+        "lib/foo.ak",
+        r#"
+        use aiken/builtin
+
+        test returns_false() { 1 == 2 }
+        test errors() { error("nope") }
+        test succeeds() fail { 1 == 1 }
+        test not_a_list() { builtin.un_list_data(builtin.i_data(42)) == [] }
+        "#,
+    );
+
+    match test_project.project().check(CheckOptions::default()) {
+        Err(Error::List(errors)) => {
+            let mut failures: Vec<(String, FailureReason, String)> = errors
+                .iter()
+                .map(|error| match error {
+                    Error::TestFailure { name, reason, .. } => {
+                        (name.clone(), reason.clone(), error.to_string())
+                    }
+                    _ => panic!("expected a test failure, got {error:?}"),
+                })
+                .collect();
+
+            failures.sort_by(|a, b| a.0.cmp(&b.0));
+
+            assert_eq!(
+                failures,
+                vec![
+                    (
+                        "errors".to_string(),
+                        FailureReason::Errored(
+                            "The provided Plutus code called 'error'.".to_string()
+                        ),
+                        "errors failed: raised an error: The provided Plutus code called 'error'."
+                            .to_string()
+                    ),
+                    (
+                        "not_a_list".to_string(),
+                        FailureReason::Errored(
+                            "unListData expected a list, but got: 42".to_string()
+                        ),
+                        "not_a_list failed: raised an error: unListData expected a list, but got: 42"
+                            .to_string()
+                    ),
+                    (
+                        "returns_false".to_string(),
+                        FailureReason::ReturnedFalse,
+                        "returns_false failed: assertion failed".to_string()
+                    ),
+                    (
+                        "succeeds".to_string(),
+                        FailureReason::UnexpectedSuccess,
+                        "succeeds failed: succeeded, but was expected to fail".to_string()
+                    ),
+                ]
+            );
+        }
+        result => panic!("expected test failures, got {result:?}"),
+    }
+
+    let tight = ExBudget {
+        mem: 10_000,
+        cpu: 10_000_000,
+    };
+
+    match TestProject::new("test_failures_tell_why_they_failed_budget")
+        .with_module("lib/foo.ak", EXPENSIVE)
+        .project()
+        .check(CheckOptions {
+            budget: Some(tight),
+            ..CheckOptions::default()
+        }) {
+        Err(Error::List(errors)) => {
+            assert_eq!(
+                errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+                vec!["expensive failed: exceeded budget".to_string()]
+            );
+        }
+        result => panic!("expected a test failure, got {result:?}"),
+    }
+}
+
+#[test]
+fn tests_running_past_the_timeout_fail() {
+    let test_project = TestProject::new("tests_running_past_the_timeout_fail").with_module(
+        "lib/foo.ak",
+        r#"
+        fn spin(n: Int) -> Int {
+          spin(n + 1)
+        }
+
+        test hangs() { spin(0) == 0 }
+        test quick() { 1 + 1 == 2 }
+        "#,
+    );
+
+    match test_project.project().check(CheckOptions {
+        timeout: Some(Duration::from_millis(200)),
+        ..CheckOptions::default()
+    }) {
+        Err(Error::List(errors)) => {
+            assert_eq!(
+                errors.iter().map(|e| e.to_string()).collect::<Vec<_>>(),
+                vec!["hangs failed: timed out".to_string()]
+            );
+        }
+        result => panic!("expected a test failure, got {result:?}"),
+    }
+}
+
+#[test]
+fn max_tests_caps_number_of_tests() {
+    let test_project = TestProject::new("max_tests_caps_number_of_tests").with_module(
+        "lib/foo.ak",
+        r#"
+        test a() { True }
+        test b() { True }
+        test c() { True }
+        "#,
+    );
+
+    let csv = test_project.root.join("results.csv");
+
+    test_project
+        .project()
+        .check(CheckOptions {
+            csv: Some(csv.clone()),
+            max_tests: Some(2),
+            ..CheckOptions::default()
+        })
+        .unwrap();
+
+    // A header, followed by one row per test that ran.
+    assert_eq!(std::fs::read_to_string(csv).unwrap().lines().count(), 3);
+}
+
+#[test]
+fn timeline_records_phases_and_modules() {
+    let test_project = TestProject::new("timeline_records_phases_and_modules")
+        .with_module("lib/foo.ak", "pub fn foo() { 1 }")
+        .with_module("lib/bar.ak", "use foo\n\ntest bar() { foo.foo() == 1 }");
+
+    let trace = test_project.root.join("trace.json");
+
+    let mut project = test_project.project();
+
+    project.record_timeline();
+
+    project.check(CheckOptions::default()).unwrap();
+
+    project.write_timeline(&trace).unwrap();
+
+    let trace: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(trace).unwrap()).unwrap();
+
+    let events: Vec<(&str, &str)> = trace["traceEvents"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|event| {
+            (
+                event["cat"].as_str().unwrap(),
+                event["name"].as_str().unwrap(),
+            )
+        })
+        .collect();
+
+    for phase in ["read", "parse", "type-check", "codegen", "eval"] {
+        assert!(events.contains(&("phase", phase)), "missing {phase}");
+    }
+
+    assert!(events.contains(&("parse", "foo")));
+    assert!(events.contains(&("type-check", "bar")));
+    assert!(events.contains(&("test", "bar.bar")));
+}
+
+#[test]
+fn benchmark_writes_budget_per_test() {
+    let test_project = TestProject::new("benchmark_writes_budget_per_test")
+        .with_module("lib/foo.ak", EXPENSIVE)
+        .with_module(
+            "lib/bar.ak",
+            "test cheap() { True }\n\ntest fails() { False }",
+        );
+
+    let benchmark = |csv: &std::path::Path, budget| {
+        let result = test_project.project().check(CheckOptions {
+            csv: Some(csv.to_path_buf()),
+            budget,
+            benchmark: true,
+            ..CheckOptions::default()
+        });
+
+        let failures = match result {
+            Err(Error::List(errors)) => errors
+                .iter()
+                .map(|error| match error {
+                    Error::TestFailure { name, .. } => name.clone(),
+                    error => panic!("expected a test failure, got {error:?}"),
+                })
+                .sorted()
+                .collect::<Vec<_>>(),
+            result => panic!("expected a test failure, got {result:?}"),
+        };
+
+        let report = std::fs::read_to_string(csv).unwrap();
+        let mut rows = report.lines();
+
+        assert_eq!(rows.next(), Some(EvalInfo::CSV_HEADER));
+
+        let rows: Vec<(String, i64, i64)> = rows
+            .map(|row| match row.split(',').collect::<Vec<_>>()[..] {
+                [name, module, "true", cpu, mem, _] => (
+                    format!("{module}.{name}"),
+                    cpu.parse().unwrap(),
+                    mem.parse().unwrap(),
+                ),
+                _ => panic!("unexpected row: {row}"),
+            })
+            .collect();
+
+        (failures, rows)
+    };
+
+    let (failures, rows) = benchmark(&test_project.root.join("benchmark.csv"), None);
+
+    assert_eq!(failures, vec!["fails"]);
+    assert_eq!(
+        rows.iter()
+            .map(|(name, ..)| name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["foo.expensive", "bar.cheap"]
+    );
+    assert!(rows[0].1 > rows[1].1);
+
+    // Benchmarks run under the same budget as other tests.
+    let tight = ExBudget {
+        mem: rows[0].2 / 2,
+        cpu: rows[0].1 / 2,
+    };
+
+    let (failures, rows) = benchmark(&test_project.root.join("tight.csv"), Some(tight));
+
+    assert_eq!(failures, vec!["expensive", "fails"]);
+    assert_eq!(
+        rows.iter()
+            .map(|(name, ..)| name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["bar.cheap"]
+    );
+}
+
+#[test]
+fn tests_expected_to_fail() {
+    let test_project = TestProject::new("tests_expected_to_fail").with_module(
+        "lib/foo.ak",
+        r#"
+        test errors() fail { error("nope") }
+        test returns_false() fail { 1 == 2 }
+        "#,
+    );
+
+    test_project
+        .project()
+        .check(CheckOptions::default())
+        .unwrap();
+}
+
+#[test]
+fn tests_expected_to_fail_but_succeeding() {
+    let test_project = TestProject::new("tests_expected_to_fail_but_succeeding").with_module(
+        "lib/foo.ak",
+        r#"
+        test errors() fail { error("nope") }
+        test succeeds() fail { 1 == 1 }
+        "#,
+    );
+
+    match test_project.project().check(CheckOptions::default()) {
+        Err(Error::List(errors)) => {
+            assert!(matches!(
+                &errors[..],
+                [Error::TestFailure { name, can_error: true, .. }] if name == "succeeds"
+            ))
+        }
+        result => panic!("expected a test failure, got {result:?}"),
+    }
+}
+
+#[test]
+fn property_tests_report_counterexample() {
+    let test_project = TestProject::new("property_tests_report_counterexample").with_module(
+        "lib/foo.ak",
+        r#"
+        use aiken/builtin
+
+        test reflexive(d: Data) { d == d }
+
+        test only_integers(d: Data) { builtin.un_i_data(d) == builtin.un_i_data(d) }
+        "#,
+    );
+
+    let counterexample = |seed| match test_project.project().check(CheckOptions {
+        seed: Some(seed),
+        ..CheckOptions::default()
+    }) {
+        Err(Error::List(errors)) => match &errors[..] {
+            [Error::TestFailure {
+                name,
+                counterexample: Some(counterexample),
+                ..
+            }] if name == "only_integers" => {
+                assert_eq!(counterexample.seed, seed);
+                assert!(!matches!(counterexample.value, uplc::PlutusData::BigInt(_)));
+                counterexample.value.clone()
+            }
+            _ => panic!("expected a counterexample, got {errors:?}"),
+        },
+        result => panic!("expected a test failure, got {result:?}"),
+    };
+
+    assert_eq!(counterexample(42), counterexample(42));
+}
+
+#[test]
+fn property_tests_take_at_most_one_argument() {
+    let test_project = TestProject::new("property_tests_take_at_most_one_argument")
+        .with_module("lib/foo.ak", "test prop(a: Data, b: Data) { a == b }");
+
+    assert!(matches!(
+        test_project.project().check(CheckOptions::default()),
+        Err(Error::Type { .. })
+    ));
+}
+
+#[test]
+fn edition_is_read_from_config() {
+    let test_project = TestProject::new("edition_is_read_from_config")
+        .with_module(
+            "aiken.toml",
+            "name = \"test/project\"\nversion = \"0.0.0\"\nedition = \"2023\"\n",
+        )
+        .with_module(
+            "lib/foo.ak",
+            "pub fn foo(d: Data) {\n  assert x: Int = d\n  x\n}",
+        );
+
+    let skip_tests = CheckOptions {
+        skip_tests: true,
+        ..CheckOptions::default()
+    };
+
+    assert!(matches!(
+        test_project.project().check(skip_tests),
+        Err(Error::List(errors)) if matches!(&errors[..], [Error::Parse { .. }])
+    ));
+}
+
+#[test]
+fn unsupported_edition() {
+    let test_project = TestProject::new("unsupported_edition").with_module(
+        "aiken.toml",
+        "name = \"test/project\"\nversion = \"0.0.0\"\nedition = \"1999\"\n",
+    );
+
+    match crate::Project::new(test_project.root.clone(), super::Silent) {
+        Err(Error::TomlLoading { help, .. }) => {
+            assert!(
+                help.contains("supported editions are: 2022, 2023"),
+                "{help}"
+            )
+        }
+        Err(e) => panic!("expected an unsupported edition, got {e:?}"),
+        Ok(_) => panic!("expected an unsupported edition"),
+    }
+}
+
+#[test]
+fn unused_imports_are_reported() {
+    let test_project = TestProject::new("unused_imports_are_reported")
+        .with_module("lib/a.ak", "pub fn a() {\n  1\n}")
+        .with_module("lib/b.ak", "pub fn b() {\n  1\n}")
+        .with_module("lib/c.ak", "pub fn c() {\n  1\n}\n\npub fn d() {\n  1\n}")
+        .with_module(
+            "lib/foo.ak",
+            "use a\nuse b\nuse c.{c, d}\n\npub fn foo() {\n  a.a() + c()\n}",
+        );
+
+    let mut project = test_project.project();
+
+    project
+        .check(CheckOptions {
+            skip_tests: true,
+            ..CheckOptions::default()
+        })
+        .unwrap();
+
+    let mut unused = project
+        .warnings
+        .iter()
+        .map(|warning| match warning {
+            Warning::Type {
+                warning: tipo::error::Warning::UnusedImportedModule { name, .. },
+                ..
+            }
+            | Warning::Type {
+                warning: tipo::error::Warning::UnusedImportedValue { name, .. },
+                ..
+            } => name.clone(),
+            other => panic!("unexpected warning: {other:?}"),
+        })
+        .collect::<Vec<_>>();
+
+    unused.sort();
+
+    assert_eq!(unused, vec!["b", "d"]);
+}
+
+#[test]
+fn check_format_reports_diffs() {
+    let test_project = TestProject::new("check_format_reports_diffs")
+        .with_module("lib/formatted.ak", "pub fn foo() {\n  1\n}\n")
+        .with_module("lib/unformatted.ak", "pub fn bar(  ) { 2 }\n");
+
+    let unformatted = test_project.project().check_format().unwrap();
+
+    let path = test_project.root.join("lib").join("unformatted.ak");
+
+    assert_eq!(unformatted.len(), 1, "{unformatted:?}");
+    assert_eq!(unformatted[0].0, path);
+    assert!(
+        unformatted[0]
+            .1
+            .contains("-pub fn bar(  ) { 2 }\n+pub fn bar() {\n+  2\n+}\n"),
+        "{}",
+        unformatted[0].1
+    );
+
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "pub fn bar(  ) { 2 }\n"
+    );
+}
+
+#[test]
+fn test_failure_labels_compared_operands() {
+    let code = "test wrong() {\n  let x = 1\n  x + 1 == 3\n}\n";
+
+    let test_project =
+        TestProject::new("test_failure_labels_compared_operands").with_module("lib/foo.ak", code);
+
+    match test_project.project().check(CheckOptions::default()) {
+        Err(Error::List(errors)) => {
+            let labels = errors[0]
+                .labels()
+                .expect("a failed comparison is labelled")
+                .map(|label| {
+                    let start = label.offset();
+                    (
+                        label.label().unwrap().to_string(),
+                        &code[start..start + label.len()],
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                labels,
+                vec![("left".to_string(), "x + 1"), ("right".to_string(), "3")]
+            );
+        }
+        result => panic!("expected a test failure, got {result:?}"),
+    }
+}
+
+#[test]
+fn type_check_by_layers() {
+    let test_project = TestProject::new("type_check_by_layers")
+        .with_module("lib/a.ak", "pub fn a() -> Int {\n  1\n}")
+        .with_module(
+            "lib/b.ak",
+            "use a\nuse aiken/builtin\n\npub fn b() -> Int {\n  a.a() + 1\n}",
+        )
+        .with_module(
+            "lib/c.ak",
+            "use a\nuse aiken/builtin\n\npub fn c() -> Int {\n  a.a() + 2\n}",
+        )
+        .with_module(
+            "lib/d.ak",
+            "use b\nuse c\n\npub fn d() -> (Int, Int) {\n  (b.b(), c.c())\n}",
+        );
+
+    let parse = |project: &mut crate::Project<super::Silent>| {
+        project.read_source_files().unwrap();
+        project
+            .parse_sources(project.config.name.clone(), project.config.edition)
+            .unwrap()
+    };
+
+    let signatures = |project: &crate::Project<super::Silent>| {
+        project
+            .checked_modules
+            .values()
+            .flat_map(|module| {
+                module.ast.type_info.values.iter().map(|(name, value)| {
+                    (format!("{}.{name}", module.name), value.tipo.to_pretty(0))
+                })
+            })
+            .sorted()
+            .collect::<Vec<_>>()
+    };
+
+    let warnings = |project: &crate::Project<super::Silent>| {
+        project
+            .warnings
+            .iter()
+            .map(|warning| match warning {
+                Warning::Type { path, .. } => path.clone(),
+                warning => panic!("unexpected warning: {warning:?}"),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let layered = || {
+        let mut project = test_project.project();
+        let parsed = parse(&mut project);
+
+        assert_eq!(
+            parsed.layers().unwrap(),
+            vec![vec!["a"], vec!["b", "c"], vec!["d"]]
+        );
+
+        project.type_check(parsed).unwrap();
+
+        (signatures(&project), warnings(&project))
+    };
+
+    // One module after the other, each against the types of those before it.
+    let mut sequential = test_project.project();
+    let mut parsed = parse(&mut sequential);
+
+    for name in parsed.sequence().unwrap() {
+        let module = parsed.remove(&name).unwrap();
+
+        let cached = crate::TypeChecker {
+            id_gen: &sequential.id_gen,
+            package: sequential.config.name.to_string(),
+            module_types: &sequential.module_types,
+            cache: &sequential.cache,
+            timeline: None,
+        }
+        .check_module(module)
+        .unwrap_or_else(|_| panic!("module {name} doesn't type-check"));
+
+        sequential
+            .warnings
+            .extend(cached.warnings.iter().map(|warning| {
+                crate::Warning::from_type_warning(
+                    warning.clone(),
+                    cached.module.input_path.clone(),
+                    cached.module.code.clone(),
+                )
+            }));
+        sequential
+            .module_types
+            .insert(name.clone(), cached.module.ast.type_info.clone());
+        sequential.checked_modules.insert(name, cached.module);
+    }
+
+    let (layered_signatures, layered_warnings) = layered();
+
+    assert_eq!(layered_signatures, signatures(&sequential));
+    assert_eq!(layered_warnings.len(), 2);
+    assert_eq!(
+        layered_warnings.iter().sorted().collect::<Vec<_>>(),
+        warnings(&sequential).iter().sorted().collect::<Vec<_>>()
+    );
+
+    for _ in 0..5 {
+        assert_eq!(
+            layered(),
+            (layered_signatures.clone(), layered_warnings.clone())
+        );
+    }
+}
+
+#[test]
+fn unchanged_modules_are_not_inferred_again() {
+    let test_project = TestProject::new("incremental")
+        .with_module("lib/a.ak", "pub fn a() -> Int {\n  1\n}")
+        .with_module("lib/b.ak", "use a\n\npub fn b() -> Int {\n  a.a() + 1\n}")
+        .with_module(
+            "lib/c.ak",
+            "pub fn c() -> Int {\n  3\n}\n\nfn unused() {\n  Void\n}",
+        );
+
+    let mut project = test_project.project();
+
+    let check = |project: &mut crate::Project<super::Silent>| {
+        project.record_timeline();
+        project
+            .check(CheckOptions {
+                skip_tests: true,
+                ..CheckOptions::default()
+            })
+            .unwrap();
+
+        let mut inferred = project.timeline.as_ref().unwrap().to_json()["traceEvents"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|event| event["cat"] == "type-check")
+            .map(|event| event["name"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+        inferred.sort();
+        inferred
+    };
+
+    assert_eq!(check(&mut project), vec!["a", "b", "c"]);
+    assert_eq!(check(&mut project), Vec::<String>::new());
+
+    // Warnings of reused modules are still reported.
+    assert_eq!(project.warnings.len(), 1);
+
+    // A change cascades to dependents.
+    std::fs::write(
+        test_project.root.join("lib/a.ak"),
+        "pub fn a() -> Int {\n  2\n}",
+    )
+    .unwrap();
+
+    assert_eq!(check(&mut project), vec!["a", "b"]);
+}
+
+/// Results of the tests run, in the order they were reported.
+fn finished_tests(events: Vec<Event>) -> Vec<EvalInfo> {
+    events
+        .into_iter()
+        .flat_map(|event| match event {
+            Event::FinishedTests { tests } => tests,
+            _ => Vec::new(),
+        })
+        .collect()
+}
+
+#[test]
+fn tests_are_grouped_by_describe() {
+    let test_project = TestProject::new("describe").with_module(
+        "lib/foo.ak",
+        r#"
+        describe "minting" {
+          test mint_one() {
+            True
+          }
+
+          test mint_many() {
+            True
+          }
+        }
+
+        test standalone() {
+          True
+        }
+        "#,
+    );
+
+    let run = |match_tests: Option<Vec<String>>| {
+        let mut project = test_project.recorded_project();
+
+        project
+            .check(CheckOptions {
+                match_tests,
+                ..CheckOptions::default()
+            })
+            .unwrap();
+
+        finished_tests(project.event_listener.take())
+    };
+
+    let results = run(None);
+    let infos = results.iter().collect::<Vec<_>>();
+    let groups = crate::script::group_by_describe(&infos)
+        .into_iter()
+        .map(|(group, infos)| {
+            let mut names = infos
+                .iter()
+                .map(|info| info.script.name.as_str())
+                .collect::<Vec<_>>();
+            names.sort();
+            (group.map(str::to_string), names.join(","))
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        groups,
+        vec![
+            (None, "standalone".to_string()),
+            (
+                Some("minting".to_string()),
+                "mint_many,mint_one".to_string()
+            ),
+        ]
+    );
+
+    let mut names = run(Some(vec!["minting".to_string()]))
+        .into_iter()
+        .map(|info| info.script.name)
+        .collect::<Vec<_>>();
+    names.sort();
+
+    assert_eq!(names, vec!["mint_many", "mint_one"]);
+}
+
+#[test]
+fn sorted_tests_are_reported_in_a_stable_order() {
+    let test_project = TestProject::new("sorted_tests")
+        .with_module("lib/foo.ak", "test b() { True }\n\ntest a() { True }")
+        .with_module("lib/bar.ak", "test z() { True }\n\ntest y() { 1 == 2 }")
+        .with_module("lib/bar/baz.ak", "test c() { True }");
+
+    let run = |max_tests| {
+        let mut project = test_project.recorded_project();
+
+        let errors = match project.check(CheckOptions {
+            sorted: true,
+            max_tests,
+            ..CheckOptions::default()
+        }) {
+            Err(Error::List(errors)) => errors
+                .iter()
+                .map(|error| error.to_string())
+                .collect::<Vec<_>>(),
+            result => panic!("expected a test failure, got {result:?}"),
+        };
+
+        let tests = finished_tests(project.event_listener.take())
+            .into_iter()
+            .map(|info| format!("{}.{}", info.script.module, info.script.name))
+            .collect::<Vec<_>>();
+
+        (tests, errors)
+    };
+
+    let (tests, errors) = run(None);
+
+    assert_eq!(tests, vec!["bar.y", "bar.z", "bar/baz.c", "foo.a", "foo.b"]);
+    assert_eq!(errors, vec!["y failed: assertion failed"]);
+
+    for _ in 0..5 {
+        assert_eq!(run(None).0, tests);
+        // The tests left out by `max_tests` are the last ones in that order.
+        assert_eq!(run(Some(3)).0, vec!["bar.y", "bar.z", "bar/baz.c"]);
+    }
+}
+
+#[test]
+fn misnamed_modules_are_reported() {
+    let test_project = TestProject::new("misnamed_modules")
+        .with_module("lib/foo.ak", "pub fn foo() {\n  1\n}")
+        .with_module("lib/myModule.ak", "pub fn bar() {\n  1\n}")
+        .with_module("lib/BLS/curve.ak", "pub fn baz() {\n  1\n}")
+        .with_module("lib/notes.txt", "not a module");
+
+    let mut project = test_project.project();
+
+    project
+        .check(CheckOptions {
+            skip_tests: true,
+            ..CheckOptions::default()
+        })
+        .unwrap();
+
+    let mut ignored = project
+        .warnings
+        .iter()
+        .map(|warning| match warning {
+            Warning::InvalidModuleName { path } => path
+                .strip_prefix(&test_project.root)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/"),
+            other => panic!("unexpected warning: {other:?}"),
+        })
+        .collect::<Vec<_>>();
+    ignored.sort();
+
+    assert_eq!(ignored, vec!["lib/BLS/curve.ak", "lib/myModule.ak"]);
+
+    assert_eq!(
+        project.warnings[0].code().unwrap().to_string(),
+        "aiken::module::name"
+    );
+}
+
+#[test]
+fn public_function_signatures() {
+    let test_project = TestProject::new("public_function_signatures").with_module(
+        "lib/foo.ak",
+        r#"
+        /// Add one.
+        pub fn succ(n: Int, _label: ByteArray) -> Int {
+          double(n) / 2 + 1
+        }
+
+        fn double(n: Int) -> Int {
+          n * 2
+        }
+        "#,
+    );
+
+    let mut project = test_project.project();
+
+    project
+        .check(CheckOptions {
+            skip_tests: true,
+            ..CheckOptions::default()
+        })
+        .unwrap();
+
+    let signatures = project.checked_modules["foo"].public_functions();
+
+    assert_eq!(signatures.len(), 1);
+    assert_eq!(signatures[0].name, "succ");
+    assert_eq!(signatures[0].doc.as_deref(), Some(" Add one."));
+    assert_eq!(
+        signatures[0]
+            .arguments
+            .iter()
+            .map(|tipo| tipo.to_pretty(0))
+            .collect::<Vec<_>>(),
+        vec!["Int", "ByteArray"]
+    );
+    assert_eq!(signatures[0].return_type.to_pretty(0), "Int");
+}
+
+#[test]
+fn import_cycles_are_reported_in_order() {
+    let cycle = |name, modules: &[(&str, &str)]| {
+        let test_project = modules
+            .iter()
+            .fold(TestProject::new(name), |project, (path, code)| {
+                project.with_module(path, code)
+            });
+
+        match test_project.project().check(CheckOptions {
+            skip_tests: true,
+            ..CheckOptions::default()
+        }) {
+            Err(Error::ImportCycle { modules }) => modules,
+            result => panic!("expected an import cycle, got {result:?}"),
+        }
+    };
+
+    assert_eq!(
+        cycle(
+            "two_modules_cycle",
+            &[
+                ("lib/b.ak", "use a\n\npub fn b() {\n  a.a()\n}"),
+                ("lib/a.ak", "use b\n\npub fn a() {\n  b.b()\n}"),
+            ]
+        ),
+        vec!["a", "b", "a"]
+    );
+
+    assert_eq!(
+        cycle(
+            "three_modules_cycle",
+            &[
+                ("lib/a.ak", "use c\n\npub fn a() {\n  c.c()\n}"),
+                ("lib/b.ak", "use a\n\npub fn b() {\n  a.a()\n}"),
+                ("lib/c.ak", "use b\n\npub fn c() {\n  b.b()\n}"),
+                ("lib/d.ak", "use a\n\npub fn d() {\n  a.a()\n}"),
+            ]
+        ),
+        vec!["a", "c", "b", "a"]
+    );
+}
+
+#[test]
+fn shadowing_is_reported() {
+    let warnings = |name, code| {
+        let test_project = TestProject::new(name).with_module("lib/foo.ak", code);
+
+        let mut project = test_project.project();
+
+        project
+            .check(CheckOptions {
+                skip_tests: true,
+                ..CheckOptions::default()
+            })
+            .unwrap();
+
+        project.warnings
+    };
+
+    let shadowing = warnings(
+        "shadowing_is_reported",
+        "pub fn foo(n: Int) {\n  let n = n + 1\n  n * 2\n}",
+    );
+
+    assert_eq!(shadowing.len(), 1);
+    assert!(matches!(
+        &shadowing[0],
+        Warning::Type {
+            warning: tipo::error::Warning::ImplicitShadowing { name, location, shadowed_at },
+            ..
+        } if name == "n"
+            && (location.start, location.end) == (27, 28)
+            && (shadowed_at.start, shadowed_at.end) == (11, 17)
+    ));
+
+    assert!(warnings(
+        "distinct_names_are_not_reported",
+        "pub fn foo(n: Int) {\n  let m = n + 1\n  m * 2\n}",
+    )
+    .is_empty());
+
+    let captures = [
+        "fn add(a: Int, b: Int) -> Int {\n  a + b\n}",
+        "fn apply(f: fn(Int) -> Int, n: Int) -> Int {\n  f(n)\n}",
+        "pub fn foo(n: Int) {\n  apply(add(_, apply(add(_, 1), n)), n)\n}",
+    ]
+    .join("\n\n");
+
+    assert!(warnings("nested_captures_are_not_reported", &captures).is_empty());
+
+    assert!(warnings(
+        "discarded_names_are_not_reported",
+        "pub fn foo(_n: Int) {\n  let _n = 1\n  2\n}",
+    )
+    .is_empty());
+}
+
+#[test]
+fn tests_without_assertion_are_reported() {
+    let test_project = TestProject::new("tests_without_assertion").with_module(
+        "lib/foo.ak",
+        &[
+            "fn is_even(n: Int) -> Bool {\n  n % 2 == 0\n}",
+            "test trivial() {\n  let _ = 1 + 1\n  True\n}",
+            "test compares() {\n  1 + 1 == 2\n}",
+            "test calls() {\n  is_even(2)\n}",
+            "test compares_along_the_way() {\n  let even = is_even(2) == True\n  even\n}",
+            "test with_expect() {\n  expect [_] = [1]\n  True\n}",
+        ]
+        .join("\n\n"),
+    );
+
+    let mut project = test_project.project();
+
+    project.check(CheckOptions::default()).unwrap();
+
+    let mut names = project
+        .warnings
+        .iter()
+        .map(|warning| match warning {
+            Warning::Type {
+                warning: tipo::error::Warning::TestWithoutAssertion { name, .. },
+                ..
+            } => name.as_str(),
+            warning => panic!("unexpected warning: {warning:?}"),
+        })
+        .collect::<Vec<_>>();
+
+    names.sort();
+
+    assert_eq!(names, vec!["calls", "trivial"]);
+}
+
+#[test]
+fn module_dependencies() {
+    let test_project = TestProject::new("module_dependencies")
+        .with_module("lib/a.ak", "pub fn a() -> Int {\n  1\n}")
+        .with_module("lib/b.ak", "use a\n\npub fn b() -> Int {\n  a.a() + 1\n}")
+        .with_module(
+            "lib/c/d.ak",
+            "use a\nuse aiken/builtin\nuse b\n\npub fn d() -> Int {\n  a.a() + b.b()\n}",
+        );
+
+    let mut project = test_project.project();
+
+    project
+        .check(CheckOptions {
+            skip_tests: true,
+            ..CheckOptions::default()
+        })
+        .unwrap();
+
+    let dependencies = project.module_dependencies(false);
+
+    assert_eq!(dependencies.len(), 3);
+    assert_eq!(dependencies["a"], Vec::<String>::new());
+    assert_eq!(dependencies["b"], vec!["a"]);
+    assert_eq!(dependencies["c/d"], vec!["a", "b"]);
+
+    assert_eq!(
+        project.modules_graph(false),
+        [
+            "digraph modules {",
+            "  \"a\";",
+            "  \"b\";",
+            "  \"b\" -> \"a\";",
+            "  \"c/d\";",
+            "  \"c/d\" -> \"a\";",
+            "  \"c/d\" -> \"b\";",
+            "}\n",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn coverage_reports_exercised_builtins() {
+    let test_project = TestProject::new("coverage").with_module(
+        "lib/foo.ak",
+        r#"
+        test add() {
+          1 + 1 == 2
+        }
+        "#,
+    );
+
+    let run = |coverage: bool| {
+        let mut project = test_project.recorded_project();
+
+        project
+            .check(CheckOptions {
+                coverage,
+                ..CheckOptions::default()
+            })
+            .unwrap();
+
+        project
+            .event_listener
+            .take()
+            .into_iter()
+            .find_map(|event| match event {
+                Event::BuiltinCoverage { exercised, unused } => Some((exercised, unused)),
+                _ => None,
+            })
+    };
+
+    assert_eq!(run(false), None);
+
+    let (exercised, unused) = run(true).unwrap();
+
+    assert!(exercised.contains(&DefaultFunction::AddInteger));
+    assert!(exercised.contains(&DefaultFunction::EqualsInteger));
+    assert!(unused.contains(&DefaultFunction::Sha2_256));
+    assert!(!unused.contains(&DefaultFunction::AddInteger));
+
+    // Builtins tests can't use aren't reported at all.
+    for builtin in [DefaultFunction::Keccak_256, DefaultFunction::Blake2b_224] {
+        assert!(!exercised.contains(&builtin));
+        assert!(!unused.contains(&builtin));
+    }
+}
+
+#[test]
+fn list_tests_without_generating_uplc() {
+    let test_project = TestProject::new("list_tests").with_module(
+        "lib/foo.ak",
+        r#"
+        describe "arithmetic" {
+          test add() {
+            1 + 1 == 2
+          }
+        }
+
+        test fails() fail {
+          False
+        }
+
+        test property(_n: Data) {
+          True
+        }
+        "#,
+    );
+
+    let mut project = test_project.recorded_project();
+
+    project
+        .check(CheckOptions {
+            skip_tests: true,
+            verbose: true,
+            ..CheckOptions::default()
+        })
+        .unwrap();
+
+    let mut tests = project.list_tests();
+    tests.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let summary = tests
+        .iter()
+        .map(|test| {
+            (
+                test.module.as_str(),
+                test.name.as_str(),
+                test.group.as_deref(),
+                test.can_error,
+                test.is_property,
+                test.has_hint,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        summary,
+        vec![
+            ("foo", "add", Some("arithmetic"), false, false, true),
+            ("foo", "fails", None, true, false, false),
+            ("foo", "property", None, false, true, false),
+        ]
+    );
+    assert!(!project
+        .event_listener
+        .take()
+        .iter()
+        .any(|event| matches!(event, Event::GeneratingUPLCFor { .. })));
+}
+
+#[test]
+fn check_types_exposes_inferred_types() {
+    let test_project = TestProject::new("check_types").with_module(
+        "lib/foo.ak",
+        r#"
+        pub fn double(n: Int) {
+          n * 2
+        }
+
+        pub fn pair(a) {
+          (a, a)
+        }
+        "#,
+    );
+
+    let mut project = test_project.project();
+
+    let module_types = project.check_types().unwrap();
+
+    let foo = &module_types["foo"];
+
+    assert_eq!(foo.values["double"].tipo.to_pretty(0), "fn(Int) -> Int");
+    assert_eq!(foo.values["pair"].tipo.to_pretty(0), "fn(a) -> (a, a)");
+    assert!(module_types.contains_key("aiken"));
+}
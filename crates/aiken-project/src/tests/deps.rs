@@ -0,0 +1,154 @@
+use super::TestProject;
+use crate::{deps::package_integrity, error::Error, options::CheckOptions};
+use aiken_lang::tipo;
+use std::fs;
+
+const CONFIG: &str = r#"
+name = "test/project"
+version = "0.0.0"
+
+[[dependencies]]
+name = "aiken-lang/stdlib"
+version = "main"
+source = "github"
+"#;
+
+const PACKAGES: &str = r#"
+[[packages]]
+name = "aiken-lang/stdlib"
+version = "main"
+source = "github"
+"#;
+
+#[test]
+fn dependencies_are_locked_by_hash() {
+    let test_project = TestProject::new("dependencies_are_locked_by_hash")
+        .with_module("aiken.toml", CONFIG)
+        .with_module("build/packages/packages.toml", PACKAGES)
+        .with_module(
+            "build/packages/aiken-lang-stdlib/lib/aiken/answer.ak",
+            "pub fn answer() -> Int {\n  42\n}",
+        )
+        .with_module(
+            "lib/foo.ak",
+            "use aiken/answer\n\ntest answer() {\n  answer.answer() == 42\n}",
+        );
+
+    let package = test_project.root.join("build/packages/aiken-lang-stdlib");
+    let lock = test_project.root.join("aiken.lock");
+
+    let check = || test_project.project().check(CheckOptions::default());
+
+    // The hash of the package is recorded the first time it's used.
+    check().unwrap();
+
+    let integrity = package_integrity(&package).unwrap();
+
+    let locked = fs::read_to_string(&lock).unwrap();
+
+    assert!(locked.contains(&format!("integrity = \"{integrity}\"")));
+
+    check().unwrap();
+
+    // A tampered hash no longer matches the package.
+    fs::write(
+        &lock,
+        locked.replace(&integrity, &"0".repeat(integrity.len())),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        check(),
+        Err(Error::PackageIntegrityMismatch { expected, found, .. })
+            if expected == "0".repeat(integrity.len()) && found == integrity
+    ));
+
+    // Nor does a tampered package.
+    fs::write(&lock, &locked).unwrap();
+
+    fs::write(
+        package.join("lib/aiken/answer.ak"),
+        "pub fn answer() -> Int {\n  43\n}",
+    )
+    .unwrap();
+
+    assert!(matches!(
+        check(),
+        Err(Error::PackageIntegrityMismatch { expected, .. }) if expected == integrity
+    ));
+}
+
+#[test]
+fn dependencies_are_looked_up_in_search_paths_first() {
+    // Keys of a TOML document must come before its tables.
+    let config = CONFIG.replacen(
+        "version = \"0.0.0\"\n",
+        "version = \"0.0.0\"\nsearch_paths = [\"vendor\", \"extra\"]\n",
+        1,
+    );
+
+    let answer = |n| format!("pub fn answer() -> Int {{\n  {n}\n}}");
+
+    let test_project = TestProject::new("dependencies_search_paths")
+        .with_module("aiken.toml", &config)
+        .with_module("build/packages/packages.toml", PACKAGES)
+        .with_module(
+            "build/packages/aiken-lang-stdlib/lib/aiken/answer.ak",
+            &answer(3),
+        )
+        .with_module("vendor/aiken-lang-stdlib/lib/aiken/answer.ak", &answer(1))
+        .with_module("extra/aiken-lang-stdlib/lib/aiken/answer.ak", &answer(2));
+
+    let check = |expected: u8| {
+        let _ = fs::remove_file(test_project.root.join("aiken.lock"));
+
+        fs::write(
+            test_project.root.join("lib/foo.ak"),
+            format!("use aiken/answer\n\ntest answer() {{\n  answer.answer() == {expected}\n}}"),
+        )
+        .unwrap();
+
+        test_project.project().check(CheckOptions::default())
+    };
+
+    fs::create_dir_all(test_project.root.join("lib")).unwrap();
+
+    // Search paths come first, in order.
+    check(1).unwrap();
+
+    fs::remove_dir_all(test_project.root.join("vendor")).unwrap();
+    check(2).unwrap();
+
+    // Then, the packages downloaded in build/packages. Vendored packages aren't recorded as
+    // downloaded, so this one would otherwise be fetched again.
+    fs::remove_dir_all(test_project.root.join("extra")).unwrap();
+    fs::write(
+        test_project.root.join("build/packages/packages.toml"),
+        PACKAGES,
+    )
+    .unwrap();
+    check(3).unwrap();
+
+    // A module that none of them has isn't found.
+    fs::write(
+        test_project.root.join("lib/foo.ak"),
+        "use aiken/missing\n\ntest answer() {\n  missing.answer() == 3\n}",
+    )
+    .unwrap();
+
+    let unknown_module = |error: &Error| {
+        matches!(
+            error,
+            Error::Type {
+                error: tipo::error::Error::UnknownModule { .. },
+                ..
+            }
+        )
+    };
+
+    match test_project.project().check(CheckOptions::default()) {
+        Err(Error::List(errors)) => assert!(errors.iter().any(unknown_module)),
+        Err(error) => assert!(unknown_module(&error), "{error:?}"),
+        Ok(()) => panic!("expected an unknown module"),
+    }
+}
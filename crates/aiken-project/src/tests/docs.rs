@@ -0,0 +1,224 @@
+use super::TestProject;
+use crate::{error::Error, telemetry::Event};
+use std::fs;
+
+#[test]
+fn docs_search_index() {
+    let test_project = TestProject::new("docs_search_index").with_module(
+        "lib/foo/bar.ak",
+        r#"
+        /// Some documentation that shouldn't end up in the index.
+        pub fn double(n: Int) -> Int {
+          n * 2
+        }
+
+        fn private() -> Int {
+          42
+        }
+
+        pub type Color {
+          Red
+          Green
+        }
+
+        pub const answer: Int = 42
+        "#,
+    );
+
+    let destination = test_project.root.join("docs");
+
+    test_project
+        .project()
+        .docs(Some(destination.clone()), None)
+        .unwrap();
+
+    let index: serde_json::Value =
+        serde_json::from_slice(&fs::read(destination.join("search-index.json")).unwrap()).unwrap();
+
+    assert_eq!(
+        index,
+        serde_json::json!([
+            { "module": "foo/bar", "name": "Color", "kind": "type", "url": "foo/bar.html#Color" },
+            { "module": "foo/bar", "name": "answer", "kind": "constant", "url": "foo/bar.html#answer" },
+            { "module": "foo/bar", "name": "double", "kind": "function", "url": "foo/bar.html#double" },
+        ])
+    );
+}
+
+#[test]
+fn docs_coverage() {
+    let test_project = TestProject::new("docs_coverage").with_module(
+        "lib/foo.ak",
+        r#"
+        /// Twice the given number.
+        pub fn double(n: Int) -> Int {
+          n * 2
+        }
+
+        pub fn triple(n: Int) -> Int {
+          n * 3
+        }
+
+        fn private() -> Int {
+          42
+        }
+
+        /// Primary colors.
+        pub type Color {
+          Red
+          Green
+          Blue
+        }
+
+        pub const answer: Int = 42
+        "#,
+    );
+
+    let destination = test_project.root.join("docs");
+
+    test_project
+        .project()
+        .docs(Some(destination.clone()), Some(50.0))
+        .unwrap();
+
+    match test_project.project().docs(Some(destination), Some(75.0)) {
+        Err(Error::InsufficientDocCoverage {
+            coverage,
+            undocumented,
+            ..
+        }) => {
+            assert_eq!(coverage, 50.0);
+            assert_eq!(undocumented, vec!["foo.answer", "foo.triple"]);
+        }
+        result => panic!("expected insufficient coverage, got {result:?}"),
+    }
+}
+
+#[test]
+fn docs_are_deterministic() {
+    let test_project = TestProject::new("docs_are_deterministic")
+        .with_module(
+            "lib/foo.ak",
+            r#"
+            pub fn zeta() -> Int {
+              1
+            }
+
+            pub type Color {
+              Red
+              Green
+            }
+
+            pub fn alpha() -> Int {
+              2
+            }
+            "#,
+        )
+        .with_module("lib/bar.ak", "pub fn bar() {\n  1\n}")
+        .with_module("lib/baz/qux.ak", "pub const qux: Int = 42");
+
+    let generate = |name: &str| {
+        let destination = test_project.root.join(name);
+
+        test_project
+            .project()
+            .docs(Some(destination.clone()), None)
+            .unwrap();
+
+        walkdir::WalkDir::new(&destination)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| {
+                (
+                    entry
+                        .path()
+                        .strip_prefix(&destination)
+                        .unwrap()
+                        .to_path_buf(),
+                    fs::read_to_string(entry.path()).unwrap(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let first = generate("docs_1");
+
+    assert_eq!(first, generate("docs_2"));
+
+    let (_, foo) = first
+        .iter()
+        .find(|(path, _)| path.ends_with("foo.html"))
+        .unwrap();
+
+    assert!(foo.contains(r#"<h2 id="alpha">"#));
+    assert!(foo.contains(r#"id="Color.Green""#));
+
+    // Definitions come in the order they're written in.
+    assert!(foo.find(r#"id="zeta""#).unwrap() < foo.find(r#"id="alpha""#).unwrap());
+}
+
+#[test]
+fn docs_only_rewrite_changed_files() {
+    let test_project = TestProject::new("docs_only_rewrite_changed_files")
+        .with_module("lib/foo.ak", "pub fn foo() -> Int {\n  1\n}");
+
+    let destination = test_project.root.join("docs");
+
+    let mut project = test_project.recorded_project();
+
+    project.docs(Some(destination.clone()), None).unwrap();
+    project.docs(Some(destination.clone()), None).unwrap();
+
+    let written = project
+        .event_listener
+        .take()
+        .into_iter()
+        .filter_map(|event| match event {
+            Event::WroteDocFiles { updated, unchanged } => Some((updated, unchanged)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(written.len(), 2);
+
+    let (updated, unchanged) = written[0];
+    assert!(updated > 0);
+    assert_eq!(unchanged, 0);
+
+    assert_eq!(written[1], (0, updated));
+}
+
+#[test]
+fn docs_prune_removed_modules() {
+    let test_project = TestProject::new("docs_prune_removed_modules")
+        .with_module("lib/foo.ak", "pub fn foo() -> Int {\n  1\n}")
+        .with_module("lib/bar/baz.ak", "pub fn baz() -> Int {\n  2\n}");
+
+    let destination = test_project.root.join("docs");
+
+    std::fs::create_dir_all(destination.join("notes")).unwrap();
+    std::fs::write(destination.join("NOTES.md"), "mine").unwrap();
+    std::fs::write(destination.join("notes/todo.md"), "mine too").unwrap();
+
+    test_project
+        .project()
+        .docs(Some(destination.clone()), None)
+        .unwrap();
+
+    assert!(destination.join("bar/baz.html").exists());
+
+    std::fs::remove_file(test_project.root.join("lib/bar/baz.ak")).unwrap();
+
+    test_project
+        .project()
+        .docs(Some(destination.clone()), None)
+        .unwrap();
+
+    assert!(destination.join("foo.html").exists());
+    assert!(!destination.join("bar/baz.html").exists());
+    assert!(!destination.join("bar").exists());
+    assert!(destination.join("NOTES.md").exists());
+    assert!(destination.join("notes/todo.md").exists());
+}
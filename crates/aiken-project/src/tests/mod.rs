@@ -0,0 +1,94 @@
+use crate::{
+    telemetry::{Event, EventListener},
+    Project,
+};
+use std::{
+    cell::RefCell,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+mod build;
+mod check;
+mod deps;
+mod docs;
+
+#[derive(Debug)]
+struct Silent;
+
+impl EventListener for Silent {
+    fn handle_event(&self, _event: Event) {}
+}
+
+/// Keeps every event it's given, for tests to pick those they're interested in.
+#[derive(Default)]
+struct Recorded(RefCell<Vec<Event>>);
+
+impl Recorded {
+    /// The events received so far, leaving none behind.
+    fn take(&self) -> Vec<Event> {
+        self.0.take()
+    }
+}
+
+impl fmt::Debug for Recorded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Recorded({} events)", self.0.borrow().len())
+    }
+}
+
+impl EventListener for Recorded {
+    fn handle_event(&self, event: Event) {
+        self.0.borrow_mut().push(event);
+    }
+}
+
+/// A throwaway project on disk, removed when dropped.
+struct TestProject {
+    root: PathBuf,
+}
+
+impl TestProject {
+    fn new(name: &str) -> Self {
+        let root =
+            std::env::temp_dir().join(format!("aiken-project-{name}-{}", std::process::id()));
+
+        if root.exists() {
+            fs::remove_dir_all(&root).unwrap();
+        }
+
+        fs::create_dir_all(&root).unwrap();
+
+        fs::write(
+            root.join("aiken.toml"),
+            "name = \"test/project\"\nversion = \"0.0.0\"\n",
+        )
+        .unwrap();
+
+        TestProject { root }
+    }
+
+    fn with_module(self, path: impl AsRef<Path>, code: &str) -> Self {
+        let path = self.root.join(path);
+
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+        fs::write(path, code).unwrap();
+
+        self
+    }
+
+    fn project(&self) -> Project<Silent> {
+        Project::new(self.root.clone(), Silent).unwrap()
+    }
+
+    fn recorded_project(&self) -> Project<Recorded> {
+        Project::new(self.root.clone(), Recorded::default()).unwrap()
+    }
+}
+
+impl Drop for TestProject {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
@@ -1,5 +1,7 @@
 use crate::with_project;
 use aiken_lang::VALIDATOR_NAMES;
+use aiken_project::address::Delegation;
+use pallas_addresses::Network;
 use std::path::PathBuf;
 
 #[derive(clap::Args)]
@@ -21,6 +23,20 @@ pub struct Args {
     #[clap(long)]
     delegated_to: Option<String>,
 
+    /// Stake registration certificate to point to instead of a stake address, located by
+    /// slot, transaction index and certificate index.
+    #[clap(
+        long,
+        number_of_values = 3,
+        value_names = &["SLOT", "TX_INDEX", "CERT_INDEX"],
+        conflicts_with = "delegated-to"
+    )]
+    pointer: Option<Vec<u64>>,
+
+    /// Produce a mainnet address, rather than a testnet one.
+    #[clap(long)]
+    mainnet: bool,
+
     /// Force the project to be rebuilt, otherwise relies on existing artifacts (i.e. plutus.json).
     #[clap(long)]
     rebuild: bool,
@@ -32,12 +48,24 @@ pub fn exec(
         validator,
         purpose,
         delegated_to,
+        pointer,
+        mainnet,
         rebuild,
     }: Args,
 ) -> miette::Result<()> {
+    let delegation = match (delegated_to, pointer.as_deref()) {
+        (Some(stake_address), _) => Delegation::StakeAddress(stake_address),
+        (None, Some(&[slot, tx_index, cert_index])) => Delegation::Pointer {
+            slot,
+            tx_index,
+            cert_index,
+        },
+        _ => Delegation::Enterprise,
+    };
+
     with_project(directory, |p| {
         if rebuild {
-            p.build(false)?;
+            p.build(false, false)?;
         }
         let address = p.address(
             validator.as_ref(),
@@ -45,7 +73,12 @@ pub fn exec(
                 .as_ref()
                 .map(|p| p.clone().try_into().unwrap())
                 .as_ref(),
-            delegated_to.as_ref(),
+            &delegation,
+            if mainnet {
+                Network::Mainnet
+            } else {
+                Network::Testnet
+            },
         )?;
         println!("{}", address.to_bech32().unwrap());
         Ok(())
@@ -10,7 +10,7 @@ use uplc::{
 
 #[derive(clap::Args)]
 #[clap(setting(clap::AppSettings::DeriveDisplayOrder))]
-/// Apply a parameter to a parameterized validator.
+/// Apply one or more parameters to a parameterized validator.
 pub struct Args {
     /// Path to project
     directory: Option<PathBuf>,
@@ -23,8 +23,15 @@ pub struct Args {
     #[clap(short, long, possible_values=&VALIDATOR_NAMES)]
     purpose: Option<String>,
 
-    /// The parameter, using high-level UPLC-syntax
-    parameter: String,
+    /// The parameters, using high-level UPLC-syntax. They're applied in order.
+    #[clap(required_unless_present = "spec")]
+    parameters: Vec<String>,
+
+    /// Apply parameters to several validators at once, as listed in a JSON file: a list of
+    /// `{ "title", "purpose", "parameters" }` objects, with parameters as Plutus data in the
+    /// detailed JSON schema
+    #[clap(long, conflicts_with_all = &["validator", "purpose", "parameters"])]
+    spec: Option<PathBuf>,
 }
 
 pub fn exec(
@@ -32,23 +39,32 @@ pub fn exec(
         directory,
         validator,
         purpose,
-        parameter,
+        parameters,
+        spec,
     }: Args,
 ) -> miette::Result<()> {
-    let term: Term<DeBruijn> = parser::term(&parameter)
-        .into_diagnostic()?
-        .try_into()
-        .into_diagnostic()?;
+    let terms = parameters
+        .iter()
+        .map(|parameter| {
+            parser::term(parameter)
+                .into_diagnostic()?
+                .try_into()
+                .into_diagnostic()
+        })
+        .collect::<miette::Result<Vec<Term<DeBruijn>>>>()?;
 
     with_project(directory, |p| {
-        let blueprint = p.apply_parameter(
-            validator.as_ref(),
-            purpose
-                .as_ref()
-                .map(|p| p.clone().try_into().unwrap())
-                .as_ref(),
-            &term,
-        )?;
+        let blueprint = match &spec {
+            Some(spec) => p.apply_parameters_from_spec(spec)?,
+            None => p.apply_parameters(
+                validator.as_ref(),
+                purpose
+                    .as_ref()
+                    .map(|p| p.clone().try_into().unwrap())
+                    .as_ref(),
+                &terms,
+            )?,
+        };
 
         let json = serde_json::to_string_pretty(&blueprint).unwrap();
         fs::write(p.blueprint_path(), json).map_err(|error| Error::FileIo {
@@ -1,5 +1,7 @@
 pub mod address;
 pub mod apply;
+pub mod policy;
+pub mod types;
 
 use clap::Subcommand;
 
@@ -9,11 +11,15 @@ use clap::Subcommand;
 pub enum Cmd {
     Address(address::Args),
     Apply(apply::Args),
+    Policy(policy::Args),
+    Types(types::Args),
 }
 
 pub fn exec(cmd: Cmd) -> miette::Result<()> {
     match cmd {
         Cmd::Address(args) => address::exec(args),
         Cmd::Apply(args) => apply::exec(args),
+        Cmd::Policy(args) => policy::exec(args),
+        Cmd::Types(args) => types::exec(args),
     }
 }
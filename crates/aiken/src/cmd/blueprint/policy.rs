@@ -0,0 +1,56 @@
+use crate::with_project;
+use aiken_lang::VALIDATOR_NAMES;
+use std::path::PathBuf;
+
+#[derive(clap::Args)]
+#[clap(setting(clap::AppSettings::DeriveDisplayOrder))]
+/// Compute a minting policy's id, that is, the hash of its validator.
+pub struct Args {
+    /// Path to project
+    directory: Option<PathBuf>,
+
+    /// Name of the validator's module within the project. Optional if there's only one validator.
+    #[clap(short, long)]
+    validator: Option<String>,
+
+    /// Purpose of the validator within the module. Optional if there's only one validator.
+    #[clap(short, long, possible_values=&VALIDATOR_NAMES)]
+    purpose: Option<String>,
+
+    /// Print the hash as bech32 (CIP-0005 'script' prefix) instead of hex.
+    #[clap(long)]
+    bech32: bool,
+
+    /// Force the project to be rebuilt, otherwise relies on existing artifacts (i.e. plutus.json).
+    #[clap(long)]
+    rebuild: bool,
+}
+
+pub fn exec(
+    Args {
+        directory,
+        validator,
+        purpose,
+        bech32,
+        rebuild,
+    }: Args,
+) -> miette::Result<()> {
+    with_project(directory, |p| {
+        if rebuild {
+            p.build(false, false)?;
+        }
+        let policy = p.policy(
+            validator.as_ref(),
+            purpose
+                .as_ref()
+                .map(|p| p.clone().try_into().unwrap())
+                .as_ref(),
+        )?;
+        if bech32 {
+            println!("{}", uplc::ast::script_hash_to_bech32(&policy));
+        } else {
+            println!("{policy}");
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,20 @@
+use crate::with_project;
+use std::path::PathBuf;
+
+#[derive(clap::Args)]
+#[clap(setting(clap::AppSettings::DeriveDisplayOrder))]
+/// Generate TypeScript declarations of the validators' datums, redeemers and parameters.
+pub struct Args {
+    /// Path to project
+    directory: Option<PathBuf>,
+
+    /// Where to write the declarations. Defaults to 'plutus.d.ts' in the project.
+    #[clap(short, long)]
+    out: Option<PathBuf>,
+}
+
+pub fn exec(Args { directory, out }: Args) -> miette::Result<()> {
+    let out = out.unwrap_or_else(|| directory.clone().unwrap_or_default().join("plutus.d.ts"));
+
+    with_project(directory, |p| p.export_types(&out))
+}
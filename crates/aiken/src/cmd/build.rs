@@ -9,8 +9,25 @@ pub struct Args {
     /// Also dump textual uplc
     #[clap(short, long)]
     uplc: bool,
+
+    /// Leave traces out of the built validators, making them smaller and cheaper to run
+    #[clap(long)]
+    filter_traces: bool,
+
+    /// Write a timeline of the compilation to this file, in the Chrome trace format
+    #[clap(long)]
+    timeline: Option<PathBuf>,
 }
 
-pub fn exec(Args { directory, uplc }: Args) -> miette::Result<()> {
-    crate::with_project(directory, |p| p.build(uplc))
+pub fn exec(
+    Args {
+        directory,
+        uplc,
+        filter_traces,
+        timeline,
+    }: Args,
+) -> miette::Result<()> {
+    crate::with_project(directory, |p| {
+        crate::with_timeline(p, timeline.as_deref(), |p| p.build(uplc, filter_traces))
+    })
 }
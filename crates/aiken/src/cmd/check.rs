@@ -1,4 +1,6 @@
-use std::path::PathBuf;
+use aiken_project::options::CheckOptions;
+use std::{path::PathBuf, time::Duration};
+use uplc::machine::cost_model::ExBudget;
 
 #[derive(clap::Args)]
 /// Type-check an Aiken project
@@ -15,15 +17,58 @@ pub struct Args {
     debug: bool,
 
     /// Only run tests if they match any of these strings.
-    /// You can match a module with `-m aiken/list` or `-m list`.
+    /// You can match a module with `-m aiken/list` or `-m list.` (a lone word such
+    /// as `-m list` matches a test name or a `describe` block).
     /// You can match a test with `-m "aiken/list.{map}"` or `-m "aiken/option.{flatten_1}"`
     #[clap(short, long)]
     match_tests: Option<Vec<String>>,
 
     /// This is meant to be used with `--match-tests`.
-    /// It forces test names to match exactly
+    /// It forces modules and test names to match exactly
     #[clap(short, long)]
     exact_match: bool,
+
+    /// Append test results (name, module, success, cpu, mem, log-count) to a CSV file
+    #[clap(long)]
+    csv: Option<PathBuf>,
+
+    /// Run each test with at most this many CPU units (unlimited by default)
+    #[clap(long)]
+    max_cpu: Option<i64>,
+
+    /// Run each test with at most this many memory units (unlimited by default)
+    #[clap(long)]
+    max_mem: Option<i64>,
+
+    /// Run at most this many tests, after filtering with `--match-tests`
+    #[clap(long)]
+    max_tests: Option<usize>,
+
+    /// Seed for the values property tests run against (random by default)
+    #[clap(long)]
+    seed: Option<u64>,
+
+    /// Run tests sorted by module and name, so that neither the output nor the tests left out
+    /// by `--max-tests` vary between runs
+    #[clap(long)]
+    sorted: bool,
+
+    /// Fail any test still running after this many milliseconds (no limit by default)
+    #[clap(long)]
+    timeout: Option<u64>,
+
+    /// After the tests, list the builtins they exercised and those they never applied
+    #[clap(long)]
+    coverage: bool,
+
+    /// Report the budget consumed by each passing test, most expensive first. With `--csv`,
+    /// only passing tests are written, in that order
+    #[clap(long)]
+    benchmark: bool,
+
+    /// Write a timeline of the compilation to this file, in the Chrome trace format
+    #[clap(long)]
+    timeline: Option<PathBuf>,
 }
 
 pub fn exec(
@@ -33,9 +78,39 @@ pub fn exec(
         debug,
         match_tests,
         exact_match,
+        csv,
+        max_cpu,
+        max_mem,
+        max_tests,
+        seed,
+        sorted,
+        timeout,
+        coverage,
+        benchmark,
+        timeline,
     }: Args,
 ) -> miette::Result<()> {
+    let budget = (max_cpu.is_some() || max_mem.is_some()).then(|| ExBudget {
+        cpu: max_cpu.unwrap_or(i64::MAX),
+        mem: max_mem.unwrap_or(i64::MAX),
+    });
+
     crate::with_project(directory, |p| {
-        p.check(skip_tests, match_tests.clone(), debug, exact_match)
+        crate::with_timeline(p, timeline.as_deref(), |p| {
+            p.check(CheckOptions {
+                skip_tests,
+                match_tests: match_tests.clone(),
+                verbose: debug,
+                exact_match,
+                csv: csv.clone(),
+                budget,
+                max_tests,
+                seed,
+                sorted,
+                timeout: timeout.map(Duration::from_millis),
+                coverage,
+                benchmark,
+            })
+        })
     })
 }
@@ -9,13 +9,18 @@ pub struct Args {
     /// Output directory for the documentation
     #[clap(short = 'o', long)]
     destination: Option<PathBuf>,
+
+    /// Fail when less than this percentage of the public definitions are documented
+    #[clap(long)]
+    min_coverage: Option<f64>,
 }
 
 pub fn exec(
     Args {
         directory,
         destination,
+        min_coverage,
     }: Args,
 ) -> miette::Result<()> {
-    crate::with_project(directory, |p| p.docs(destination.clone()))
+    crate::with_project(directory, |p| p.docs(destination.clone(), min_coverage))
 }
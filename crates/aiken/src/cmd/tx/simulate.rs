@@ -104,13 +104,13 @@ pub fn exec(
             Ok(redeemers) => {
                 println!("\nTotal Budget Used\n-----------------\n");
 
-                let total_budget_used =
-                    redeemers
-                        .iter()
-                        .fold(ExBudget { mem: 0, cpu: 0 }, |accum, curr| ExBudget {
-                            mem: accum.mem + curr.ex_units.mem as i64,
-                            cpu: accum.cpu + curr.ex_units.steps as i64,
-                        });
+                let total_budget_used = redeemers
+                    .iter()
+                    .map(|redeemer| ExBudget {
+                        mem: redeemer.ex_units.mem as i64,
+                        cpu: redeemer.ex_units.steps as i64,
+                    })
+                    .fold(ExBudget { mem: 0, cpu: 0 }, |total, budget| total + budget);
 
                 println!("mem: {}", total_budget_used.mem);
                 println!("cpu: {}", total_budget_used.cpu);
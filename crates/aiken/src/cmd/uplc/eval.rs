@@ -2,7 +2,7 @@ use miette::IntoDiagnostic;
 use std::path::PathBuf;
 use uplc::{
     ast::{FakeNamedDeBruijn, Name, NamedDeBruijn, Program, Term},
-    machine::cost_model::ExBudget,
+    machine::{cost_model::ExBudget, TraceFormat},
     parser,
 };
 
@@ -17,6 +17,10 @@ pub struct Args {
     #[clap(short, long)]
     cbor: bool,
 
+    /// Prefix traces with the machine step at which they were emitted
+    #[clap(long)]
+    trace_steps: bool,
+
     /// Arguments to pass to the uplc program
     args: Vec<String>,
 }
@@ -27,6 +31,7 @@ pub fn exec(
         flat,
         args,
         cbor,
+        trace_steps,
     }: Args,
 ) -> miette::Result<()> {
     let mut program = if cbor {
@@ -63,7 +68,13 @@ pub fn exec(
 
     let budget = ExBudget::default();
 
-    let (term, cost, logs) = program.eval(budget);
+    let trace_format = if trace_steps {
+        TraceFormat::StepIndex
+    } else {
+        TraceFormat::Raw
+    };
+
+    let (term, cost, logs) = program.eval_with_trace_format(budget, trace_format);
 
     match term {
         Ok(term) => {
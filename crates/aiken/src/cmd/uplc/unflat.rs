@@ -1,6 +1,6 @@
 use miette::IntoDiagnostic;
 use std::{fs, path::PathBuf};
-use uplc::ast::{DeBruijn, Name, Program};
+use uplc::ast::{DeBruijn, Program};
 
 #[derive(clap::Args)]
 /// Decode flat bytes to textual Untyped Plutus Core
@@ -42,9 +42,7 @@ pub fn exec(
         Program::<DeBruijn>::from_flat(&bytes).into_diagnostic()?
     };
 
-    let program: Program<Name> = program.try_into().into_diagnostic()?;
-
-    let pretty = program.to_pretty();
+    let pretty = program.disassemble().into_diagnostic()?;
 
     if print {
         println!("{pretty}");
@@ -1,8 +1,17 @@
-use aiken_project::{pretty, script::EvalInfo, telemetry, Project};
+use aiken_project::{
+    pretty,
+    script::{group_by_describe, EvalInfo},
+    telemetry, Project,
+};
 use miette::IntoDiagnostic;
 use owo_colors::OwoColorize;
-use std::{collections::BTreeMap, env, path::PathBuf, process};
-use uplc::machine::cost_model::ExBudget;
+use std::{
+    collections::BTreeMap,
+    env,
+    path::{Path, PathBuf},
+    process,
+};
+use uplc::machine::cost_model::{thousands, ExBudget};
 
 pub mod cmd;
 
@@ -59,6 +68,43 @@ where
     Ok(())
 }
 
+/// Run `action`, recording a timeline of it when a destination is given. The timeline is
+/// written even when the action fails, as long as it got somewhere. Failing to write it
+/// is only a warning, so it never hides the outcome of the action itself.
+pub fn with_timeline<A>(
+    project: &mut Project<Terminal>,
+    destination: Option<&Path>,
+    action: A,
+) -> Result<(), aiken_project::error::Error>
+where
+    A: FnOnce(&mut Project<Terminal>) -> Result<(), aiken_project::error::Error>,
+{
+    match destination {
+        None => action(project),
+        Some(destination) => {
+            project.record_timeline();
+
+            let result = action(project);
+
+            if let Err(err) = project.write_timeline(destination) {
+                let reason = match err {
+                    aiken_project::error::Error::FileIo { error, .. } => error.to_string(),
+                    err => err.to_string(),
+                };
+
+                eprintln!(
+                    "{} couldn't write timeline to {}: {}",
+                    "      Warning".bold().yellow(),
+                    destination.display().bright_blue(),
+                    reason
+                );
+            }
+
+            result
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Terminal;
 
@@ -94,6 +140,28 @@ impl telemetry::EventListener for Terminal {
             telemetry::Event::WaitingForBuildDirLock => {
                 println!("{}", "Waiting for build directory lock ...".bold().purple());
             }
+            telemetry::Event::ValidatorSize {
+                title,
+                purpose,
+                bytes,
+            } => {
+                println!(
+                    "{} {}.{} ({} bytes)",
+                    "         Size".bold().purple(),
+                    title.bold(),
+                    purpose,
+                    bytes.to_string().bright_blue()
+                );
+            }
+            telemetry::Event::VerifyingValidators { count } => {
+                let plural = if count == 1 { "" } else { "s" };
+
+                println!(
+                    "{} {} validator{plural}",
+                    "    Verifying".bold().purple(),
+                    count.to_string().bold(),
+                );
+            }
             telemetry::Event::DumpingUPLC { path } => {
                 println!(
                     "{} {} ({})",
@@ -117,6 +185,32 @@ impl telemetry::EventListener for Terminal {
                     output_path.to_str().unwrap_or("").bright_blue()
                 );
             }
+            telemetry::Event::WroteDocFiles { updated, unchanged } => {
+                let plural = if updated == 1 { "" } else { "s" };
+
+                println!(
+                    "{} {} file{plural} ({unchanged} unchanged)",
+                    "      Writing".bold().purple(),
+                    updated.to_string().bold(),
+                );
+            }
+            telemetry::Event::DocCoverage { coverage } => {
+                println!(
+                    "{} {} ({}/{} public definitions documented)",
+                    "     Coverage".bold().purple(),
+                    format!("{:.1}%", coverage.percentage()).bold(),
+                    coverage.documented(),
+                    coverage.total,
+                );
+
+                for name in &coverage.undocumented {
+                    println!(
+                        "{} {}",
+                        "              ↳".bright_yellow(),
+                        name.bright_black()
+                    );
+                }
+            }
             telemetry::Event::GeneratingUPLCFor { name, path } => {
                 println!(
                     "{} {}.{{{}}}",
@@ -137,15 +231,35 @@ impl telemetry::EventListener for Terminal {
             telemetry::Event::RunningTests => {
                 println!("{} {}\n", "      Testing".bold().purple(), "...".bold());
             }
+            telemetry::Event::SkippedTests { count } => {
+                let plural = if count == 1 { "" } else { "s" };
+
+                println!(
+                    "{} {} test{plural} over the limit\n",
+                    "     Skipping".bold().purple(),
+                    count.to_string().bold(),
+                );
+            }
             telemetry::Event::FinishedTests { tests } => {
                 let (max_mem, max_cpu) = find_max_execution_units(&tests);
 
                 for (module, infos) in &group_by_module(&tests) {
                     let title = module.bold().blue().to_string();
 
-                    let tests = infos
-                        .iter()
-                        .map(|eval_info| fmt_test(eval_info, max_mem, max_cpu, true))
+                    let tests = group_by_describe(infos)
+                        .into_iter()
+                        .flat_map(|(group, infos)| {
+                            let tests = infos
+                                .iter()
+                                .map(|eval_info| fmt_test(eval_info, max_mem, max_cpu, true));
+
+                            match group {
+                                None => tests.collect::<Vec<String>>(),
+                                Some(group) => std::iter::once(group.bold().to_string())
+                                    .chain(tests.map(|test| pretty::indent(&test, 2)))
+                                    .collect(),
+                            }
+                        })
                         .collect::<Vec<String>>()
                         .join("\n");
 
@@ -162,6 +276,50 @@ impl telemetry::EventListener for Terminal {
                     );
                 }
             }
+            telemetry::Event::BuiltinCoverage { exercised, unused } => {
+                println!(
+                    "{} {}/{} builtins exercised",
+                    "     Coverage".bold().purple(),
+                    exercised.len().to_string().bold(),
+                    exercised.len() + unused.len(),
+                );
+
+                for fun in &unused {
+                    println!(
+                        "{} {}",
+                        "              ↳".bright_yellow(),
+                        fun.aiken_name().bright_black()
+                    );
+                }
+
+                println!();
+            }
+            telemetry::Event::Benchmark { results } => {
+                println!("{}\n", "   Benchmarks".bold().purple());
+
+                let max_mem = results
+                    .iter()
+                    .map(|(_, budget)| thousands(budget.mem).len())
+                    .max()
+                    .unwrap_or_default();
+
+                let max_cpu = results
+                    .iter()
+                    .map(|(_, budget)| thousands(budget.cpu).len())
+                    .max()
+                    .unwrap_or_default();
+
+                for (name, ExBudget { mem, cpu }) in &results {
+                    println!(
+                        "    [mem: {}, cpu: {}] {}",
+                        pretty::pad_left(thousands(*mem), max_mem, " ").bright_white(),
+                        pretty::pad_left(thousands(*cpu), max_cpu, " ").bright_white(),
+                        name.bright_blue(),
+                    );
+                }
+
+                println!();
+            }
             telemetry::Event::DownloadingPackage { name } => {
                 println!("{} {}", "  Downloading".bold().purple(), name.bold())
             }
@@ -192,8 +350,8 @@ fn fmt_test(eval_info: &EvalInfo, max_mem: usize, max_cpu: usize, styled: bool)
     } = eval_info;
 
     let ExBudget { mem, cpu } = spent_budget;
-    let mem_pad = pretty::pad_left(mem.to_string(), max_mem, " ");
-    let cpu_pad = pretty::pad_left(cpu.to_string(), max_cpu, " ");
+    let mem_pad = pretty::pad_left(thousands(*mem), max_mem, " ");
+    let cpu_pad = pretty::pad_left(thousands(*cpu), max_cpu, " ");
 
     let test = format!(
         "{status} [mem: {mem_unit}, cpu: {cpu_unit}] {module}",
@@ -269,8 +427,8 @@ fn fmt_eval(eval_info: &EvalInfo, max_mem: usize, max_cpu: usize) -> String {
         "    {}::{} [mem: {}, cpu: {}]\n    │\n    ╰─▶ {}",
         script.module.blue(),
         script.name.bright_blue(),
-        pretty::pad_left(mem.to_string(), max_mem, " "),
-        pretty::pad_left(cpu.to_string(), max_cpu, " "),
+        pretty::pad_left(thousands(*mem), max_mem, " "),
+        pretty::pad_left(thousands(*cpu), max_cpu, " "),
         output
             .as_ref()
             .map(|x| format!("{x}"))
@@ -303,5 +461,5 @@ fn find_max_execution_units(xs: &[EvalInfo]) -> (usize, usize) {
         },
     );
 
-    (max_mem.to_string().len(), max_cpu.to_string().len())
+    (thousands(max_mem).len(), thousands(max_cpu).len())
 }
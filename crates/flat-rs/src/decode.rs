@@ -1,10 +1,12 @@
 mod decoder;
 mod error;
+mod stream;
 
 use crate::filler::Filler;
 
 pub use decoder::Decoder;
 pub use error::Error;
+pub use stream::StreamDecoder;
 
 pub trait Decode<'b>: Sized {
     fn decode(d: &mut Decoder) -> Result<Self, Error>;
@@ -50,12 +50,9 @@ impl<'b> Decoder<'b> {
     /// Decode a single bit of the buffer to get a bool.
     /// We mask out a single bit of the buffer based on used bits.
     /// and check if it is 0 for false or 1 for true.
-    // TODO: use bit() instead of this custom implementation.
+    /// Throws EndOfBuffer error if used at the end of the array.
     pub fn bool(&mut self) -> Result<bool, Error> {
-        let current_byte = self.buffer[self.pos];
-        let b = 0 != (current_byte & (128 >> self.used_bits));
-        self.increment_buffer_by_bit();
-        Ok(b)
+        self.bit()
     }
 
     /// Decode a byte from the buffer.
@@ -142,6 +139,32 @@ impl<'b> Decoder<'b> {
         Ok(final_word)
     }
 
+    /// Decode a signed integer of any size, without zigzag.
+    /// This is byte alignment agnostic.
+    /// We decode 7 bits at a time, least significant first, for as long as the most significant
+    /// bit of each 8 bits is 1, as with [`Decoder::word`]. The 7th bit of the last group is the
+    /// sign bit: if it is 1 we extend it over the remaining bits to get back a negative number.
+    /// This only reads what `Encoder::raw_word` writes. Reading it with [`Decoder::integer`]
+    /// instead (or the other way around) silently yields a different number.
+    pub fn raw_word(&mut self) -> Result<isize, Error> {
+        let mut final_word: isize = 0;
+        let mut shl: u32 = 0;
+        loop {
+            let word8 = self.bits8(8)?;
+            let word7 = word8 & 127;
+            if shl < isize::BITS {
+                final_word |= (word7 as isize) << shl;
+            }
+            shl += 7;
+            if word8 & 128 == 0 {
+                if shl < isize::BITS && word7 & 64 != 0 {
+                    final_word |= -1 << shl;
+                }
+                return Ok(final_word);
+            }
+        }
+    }
+
     /// Decode a word of 128 bits size.
     /// This is byte alignment agnostic.
     /// First we decode the next 8 bits of the buffer.
@@ -258,6 +281,10 @@ impl<'b> Decoder<'b> {
             return Err(Error::IncorrectNumBits);
         }
 
+        if num_bits == 0 {
+            return Ok(0);
+        }
+
         self.ensure_bits(num_bits)?;
 
         let unused_bits = 8 - self.used_bits as usize;
@@ -275,6 +302,16 @@ impl<'b> Decoder<'b> {
         Ok(x)
     }
 
+    /// Decode any number of bits, most significant first.
+    /// This is byte alignment agnostic.
+    /// Throws a NotEnoughBits error, without consuming anything,
+    /// if there are less bits remaining in the buffer than num_bits.
+    pub fn bits(&mut self, num_bits: usize) -> Result<Vec<bool>, Error> {
+        self.ensure_bits(num_bits)?;
+
+        (0..num_bits).map(|_| self.bit()).collect()
+    }
+
     /// Ensures the buffer has the required bytes passed in by required_bytes.
     /// Throws a NotEnoughBytes error if there are less bytes remaining in the buffer than required_bytes.
     fn ensure_bytes(&mut self, required_bytes: usize) -> Result<(), Error> {
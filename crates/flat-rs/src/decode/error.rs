@@ -23,5 +23,7 @@ pub enum Error {
     #[error("Unknown term constructor tag: {0}.\n\nHere are the buffer bytes ({1} preceding) {2}\n\nBuffer position is {3} and buffer length is {4}")]
     UnknownTermConstructor(u8, usize, String, usize, usize),
     #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
     Custom(#[from] anyhow::Error),
 }
@@ -0,0 +1,111 @@
+use std::io::Read;
+
+use super::{Decode, Decoder, Error};
+
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Decode values incrementally from a reader, holding in memory only the bytes of the
+/// value being decoded. This is meant for large inputs made of many concatenated values
+/// (e.g. a dump of flat-encoded programs), where reading everything upfront isn't an option.
+///
+/// Values are decoded with the same [`Decode`] instances as [`Decoder`]; when a value runs
+/// past the end of the buffered bytes, more bytes are read and decoding is retried from where
+/// the value started. The bit position within the current byte is carried over between values,
+/// so fillers keep aligning exactly as they would over an in-memory buffer.
+#[derive(Debug)]
+pub struct StreamDecoder<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    pos: usize,
+    used_bits: i64,
+    chunk_size: usize,
+    exhausted: bool,
+}
+
+impl<R: Read> StreamDecoder<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_chunk_size(reader, DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Like [`StreamDecoder::new`], but reading at least `chunk_size` bytes at a time.
+    pub fn with_chunk_size(reader: R, chunk_size: usize) -> Self {
+        StreamDecoder {
+            reader,
+            buffer: Vec::new(),
+            pos: 0,
+            used_bits: 0,
+            chunk_size: chunk_size.max(1),
+            exhausted: false,
+        }
+    }
+
+    /// Decode any type that implements [`Decode`], reading more bytes as needed.
+    pub fn decode<T>(&mut self) -> Result<T, Error>
+    where
+        T: for<'b> Decode<'b>,
+    {
+        loop {
+            let mut d = Decoder {
+                buffer: &self.buffer,
+                pos: self.pos,
+                used_bits: self.used_bits,
+            };
+
+            match T::decode(&mut d) {
+                Ok(value) => {
+                    self.pos = d.pos;
+                    self.used_bits = d.used_bits;
+                    self.compact();
+                    return Ok(value);
+                }
+                Err(e) if !self.exhausted && is_end_of_input(&e) => self.refill()?,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Whether all bytes from the reader have been consumed.
+    pub fn is_empty(&mut self) -> Result<bool, Error> {
+        if self.pos >= self.buffer.len() && !self.exhausted {
+            self.refill()?;
+        }
+
+        Ok(self.pos >= self.buffer.len())
+    }
+
+    /// Read another chunk, growing with the buffer so that decoding a large value
+    /// only needs a logarithmic number of retries.
+    fn refill(&mut self) -> Result<(), Error> {
+        let size = self.chunk_size.max(self.buffer.len());
+
+        let read = self
+            .reader
+            .by_ref()
+            .take(size as u64)
+            .read_to_end(&mut self.buffer)?;
+
+        if read < size {
+            self.exhausted = true;
+        }
+
+        Ok(())
+    }
+
+    /// Drop fully consumed bytes. A partially consumed byte is kept, along with the
+    /// number of bits already used in it.
+    fn compact(&mut self) {
+        self.buffer.drain(..self.pos);
+        self.pos = 0;
+    }
+}
+
+fn is_end_of_input(error: &Error) -> bool {
+    match error {
+        Error::EndOfBuffer | Error::NotEnoughBytes(_) | Error::NotEnoughBits(_) => true,
+        Error::ParseError(_, source) | Error::Custom(source) => source
+            .downcast_ref::<Error>()
+            .map(is_end_of_input)
+            .unwrap_or(false),
+        _ => false,
+    }
+}
@@ -111,6 +111,34 @@ impl Encoder {
         self
     }
 
+    /// Encode a signed integer of any size, without zigzag.
+    /// This is byte alignment agnostic.
+    /// Unlike [`Encoder::integer`], the number is written as-is in two's complement: we encode
+    /// the 7 least significant bits, with a leading 1 if more groups follow. We stop once the
+    /// remaining bits are all copies of the sign bit, which is the 7th bit of the last group.
+    /// So 127 takes two bytes here, when it fits in one with [`Encoder::word`].
+    /// This is not interchangeable with `integer`: decode it with `Decoder::raw_word` only.
+    pub fn raw_word(&mut self, i: isize) -> &mut Self {
+        let mut d = i;
+        loop {
+            let mut w = (d & 127) as u8;
+            d >>= 7;
+
+            let done = (d == 0 && w & 64 == 0) || (d == -1 && w & 64 != 0);
+
+            if !done {
+                w |= 128;
+            }
+            self.bits(8, w);
+
+            if done {
+                break;
+            }
+        }
+
+        self
+    }
+
     /// Encode a char of 32 bits.
     /// This is byte alignment agnostic.
     /// We encode the 7 least significant bits of the unsigned byte. If the char value is greater than
@@ -217,6 +245,7 @@ impl Encoder {
     /// The param num_bits is i64 to match unused_bits type.
     pub fn bits(&mut self, num_bits: i64, val: u8) -> &mut Self {
         match (num_bits, val) {
+            (0, _) => {}
             (1, 0) => self.zero(),
             (1, 1) => self.one(),
             (2, 0) => {
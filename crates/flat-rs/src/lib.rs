@@ -19,6 +19,10 @@ pub trait Flat<'b>: en::Encode + de::Decode<'b> {
     fn unflat(bytes: &'b [u8]) -> Result<Self, de::Error> {
         decode(bytes)
     }
+
+    fn unflat_prefix(bytes: &'b [u8]) -> Result<(Self, usize), de::Error> {
+        decode_prefix(bytes)
+    }
 }
 
 pub fn encode<T>(value: &T) -> Result<Vec<u8>, en::Error>
@@ -45,3 +49,19 @@ where
 
     Ok(value)
 }
+
+/// Like [`decode`], but only decoding a value at the start of `bytes`, whatever follows it.
+/// Returns the value along with the number of bytes it spans, filler included, which is
+/// where the next value starts.
+pub fn decode_prefix<'b, T>(bytes: &'b [u8]) -> Result<(T, usize), de::Error>
+where
+    T: de::Decode<'b>,
+{
+    let mut d = de::Decoder::new(bytes);
+
+    let value = d.decode()?;
+
+    d.decode::<filler::Filler>()?;
+
+    Ok((value, d.pos))
+}
@@ -1,7 +1,12 @@
 #[cfg(test)]
 mod test {
     use flat_rs::filler::Filler;
-    use flat_rs::{decode, encode};
+    use flat_rs::{
+        de::{Decoder, Error, StreamDecoder},
+        decode,
+        en::Encoder,
+        encode,
+    };
     use proptest::prelude::*;
 
     prop_compose! {
@@ -106,6 +111,56 @@ mod test {
         }
     }
 
+    #[test]
+    fn raw_word_round_trips() {
+        for (x, expected) in [
+            (0, vec![0b00000000]),
+            (63, vec![0b00111111]),
+            (64, vec![0b11000000, 0b00000000]),
+            (127, vec![0b11111111, 0b00000000]),
+            (128, vec![0b10000000, 0b00000001]),
+            (16384, vec![0b10000000, 0b10000000, 0b00000001]),
+            (-1, vec![0b01111111]),
+            (-128, vec![0b10000000, 0b01111111]),
+        ] {
+            let mut e = Encoder::new();
+            e.raw_word(x);
+            assert_eq!(e.buffer, expected, "encoding {x}");
+
+            let mut d = Decoder::new(&expected);
+            assert_eq!(d.raw_word().unwrap(), x, "decoding {x}");
+        }
+    }
+
+    #[test]
+    fn raw_word_is_not_zigzag() {
+        let mut e = Encoder::new();
+        e.raw_word(128);
+
+        let mut d = Decoder::new(&e.buffer);
+        assert_ne!(d.integer().unwrap(), 128);
+    }
+
+    proptest! {
+        #[test]
+        fn encode_raw_word(x: isize, offset in 0..8usize) {
+            let mut e = Encoder::new();
+            for _ in 0..offset {
+                e.bool(true);
+            }
+            e.raw_word(x);
+            e.bool(false);
+            e.encode(Filler::FillerEnd).unwrap();
+
+            let mut d = Decoder::new(&e.buffer);
+            for _ in 0..offset {
+                prop_assert!(d.bool().unwrap());
+            }
+            prop_assert_eq!(d.raw_word().unwrap(), x);
+            prop_assert!(!d.bool().unwrap());
+        }
+    }
+
     #[test]
     fn encode_filler() {
         let bytes = encode(&Filler::FillerEnd).unwrap();
@@ -123,4 +178,84 @@ mod test {
 
         assert_eq!(bytes, vec![0b0000001, 0b00000001]);
     }
+
+    #[test]
+    fn stream_decode_unaligned_values() {
+        let mut e = Encoder::new();
+
+        e.encode(true).unwrap();
+        e.encode(7u8).unwrap();
+        e.encode(false).unwrap();
+        e.encode(vec![1u8, 2, 3]).unwrap();
+        e.encode(true).unwrap();
+        e.encode(300usize).unwrap();
+        e.encode(Filler::FillerEnd).unwrap();
+
+        let mut d = StreamDecoder::with_chunk_size(e.buffer.as_slice(), 1);
+
+        assert!(d.decode::<bool>().unwrap());
+        assert_eq!(d.decode::<u8>().unwrap(), 7);
+        assert!(!d.decode::<bool>().unwrap());
+        assert_eq!(d.decode::<Vec<u8>>().unwrap(), vec![1, 2, 3]);
+        assert!(d.decode::<bool>().unwrap());
+        assert_eq!(d.decode::<usize>().unwrap(), 300);
+        d.decode::<Filler>().unwrap();
+
+        assert!(d.is_empty().unwrap());
+        assert!(d.decode::<bool>().is_err());
+    }
+
+    #[test]
+    fn decode_bits_across_bytes() {
+        let bytes = [0b1011_0011, 0b1000_1111];
+
+        let mut d = Decoder::new(&bytes);
+
+        assert_eq!(d.bits8(3).unwrap(), 0b101);
+        assert_eq!(d.bits8(0).unwrap(), 0);
+        // The next 7 bits span both bytes.
+        assert_eq!(d.bits8(7).unwrap(), 0b100_1110);
+        assert_eq!(d.bits(5).unwrap(), vec![false, false, true, true, true]);
+
+        // Asking for more than what's left consumes nothing.
+        assert!(matches!(d.bits(2), Err(Error::NotEnoughBits(2))));
+        assert!(matches!(d.bits8(2), Err(Error::NotEnoughBits(2))));
+        assert_eq!(d.bits(1).unwrap(), vec![true]);
+        assert_eq!(d.bits(0).unwrap(), Vec::<bool>::new());
+        assert!(matches!(d.bits(1), Err(Error::NotEnoughBits(1))));
+
+        let mut d = Decoder::new(&bytes);
+
+        assert!(matches!(d.bits8(9), Err(Error::IncorrectNumBits)));
+        assert_eq!(d.bits(16).unwrap().iter().filter(|b| **b).count(), 10);
+    }
+
+    #[test]
+    fn decode_zero_bits() {
+        let mut e = Encoder::new();
+        e.bits(1, 1);
+        e.bits(8, 0);
+
+        let mut d = Decoder::new(&e.buffer);
+        d.bits(0).unwrap();
+
+        assert_eq!(d.bits8(1).unwrap(), 1);
+    }
+
+    proptest! {
+        #[test]
+        fn decode_bits_after_encoded_bits(offset in 0..8i64, x: u8, n in 1..=8i64) {
+            let x = x >> (8 - n);
+
+            let mut e = Encoder::new();
+            e.bits(offset, 0);
+            e.bits(n, x);
+            e.bits(8, 0);
+
+            let mut d = Decoder::new(&e.buffer);
+            d.bits(offset as usize).unwrap();
+
+            prop_assert_eq!(d.bits8(n as usize).unwrap(), x);
+        }
+    }
 }
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod test {
-    use flat_rs::zigzag::{to_isize, to_usize};
+    use flat_rs::zigzag::{to_i128, to_isize, to_u128, to_usize};
     use proptest::prelude::*;
 
     proptest! {
@@ -17,5 +17,25 @@ mod test {
             let converted_u = to_usize(i);
             assert_eq!(converted_u, u);
         }
+
+        #[test]
+        fn zigzag_i128(i: i128) {
+            assert_eq!(to_i128(to_u128(i)), i);
+        }
+    }
+
+    #[test]
+    fn zigzag_boundaries() {
+        for i in [isize::MIN, isize::MIN / 2, isize::MAX / 2 + 1, isize::MAX] {
+            assert_eq!(to_isize(to_usize(i)), i);
+        }
+
+        for i in [i128::MIN, i128::MIN / 2, i128::MAX / 2 + 1, i128::MAX] {
+            assert_eq!(to_i128(to_u128(i)), i);
+        }
+
+        assert_eq!(to_usize(-1), 1);
+        assert_eq!(to_usize(1), 2);
+        assert_eq!(to_u128(-2), 3);
     }
 }
@@ -0,0 +1,185 @@
+//! [`Arbitrary`] implementations to generate random, well-formed programs, for
+//! property-based testing and fuzzing. Only available with the `arbitrary` feature.
+//!
+//! Generated terms are closed: variables only ever refer to a lambda in scope. Generated
+//! constants are well-typed: lists and pairs hold values of the types they carry. Integers
+//! are limited to what the flat encoding supports, that is, 128-bit integers.
+
+use std::rc::Rc;
+
+use pallas_codec::utils::KeyValuePairs;
+use pallas_primitives::alonzo::{BigInt, Constr, PlutusData};
+use proptest::{collection::vec, prelude::*, sample::select};
+use strum::IntoEnumIterator;
+
+use crate::{
+    ast::{Constant, DeBruijn, Program, Term, Type},
+    builtins::DefaultFunction,
+    machine::runtime::{convert_constr_to_tag, ANY_TAG},
+};
+
+/// Maximum nesting of generated terms, types and data.
+const MAX_DEPTH: u32 = 4;
+
+/// Maximum number of elements in generated lists, maps and constructor fields.
+const MAX_LENGTH: usize = 4;
+
+impl Arbitrary for Type {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let leaf = prop_oneof![
+            Just(Type::Bool),
+            Just(Type::Integer),
+            Just(Type::String),
+            Just(Type::ByteString),
+            Just(Type::Unit),
+            Just(Type::Data),
+        ];
+
+        leaf.prop_recursive(MAX_DEPTH, 16, 2, |inner| {
+            prop_oneof![
+                inner.clone().prop_map(|t| Type::List(t.into())),
+                (inner.clone(), inner).prop_map(|(t1, t2)| Type::Pair(t1.into(), t2.into())),
+            ]
+        })
+        .boxed()
+    }
+}
+
+impl Arbitrary for Constant {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<Type>().prop_flat_map(|t| constant(&t)).boxed()
+    }
+}
+
+/// A random constant of the given type.
+pub fn constant(typ: &Type) -> BoxedStrategy<Constant> {
+    match typ {
+        Type::Bool => any::<bool>().prop_map(Constant::Bool).boxed(),
+        Type::Integer => any::<i128>()
+            .prop_map(|i| Constant::Integer(i.into()))
+            .boxed(),
+        Type::String => any::<String>().prop_map(Constant::String).boxed(),
+        Type::ByteString => vec(any::<u8>(), 0..64)
+            .prop_map(Constant::ByteString)
+            .boxed(),
+        Type::Unit => Just(Constant::Unit).boxed(),
+        Type::Data => data().prop_map(Constant::Data).boxed(),
+        Type::List(t) => {
+            let t = t.as_ref().clone();
+
+            vec(constant(&t), 0..=MAX_LENGTH)
+                .prop_map(move |elems| Constant::ProtoList(t.clone(), elems))
+                .boxed()
+        }
+        Type::Pair(t1, t2) => {
+            let (t1, t2) = (t1.as_ref().clone(), t2.as_ref().clone());
+
+            (constant(&t1), constant(&t2))
+                .prop_map(move |(a, b)| {
+                    Constant::ProtoPair(t1.clone(), t2.clone(), a.into(), b.into())
+                })
+                .boxed()
+        }
+    }
+}
+
+/// A random `Data` value: integers, bytestrings, lists, maps and constructors, with both
+/// compact and general constructor tags.
+pub fn data() -> BoxedStrategy<PlutusData> {
+    let leaf = prop_oneof![
+        any::<i64>().prop_map(|i| PlutusData::BigInt(BigInt::Int(i.into()))),
+        vec(any::<u8>(), 0..128).prop_map(|bytes| PlutusData::BoundedBytes(bytes.into())),
+    ];
+
+    leaf.prop_recursive(MAX_DEPTH, 64, MAX_LENGTH as u32, |inner| {
+        prop_oneof![
+            vec(inner.clone(), 0..=MAX_LENGTH).prop_map(PlutusData::Array),
+            vec((inner.clone(), inner.clone()), 0..=MAX_LENGTH)
+                .prop_map(|entries| PlutusData::Map(KeyValuePairs::Def(entries))),
+            (0..200_u64, vec(inner, 0..=MAX_LENGTH)).prop_map(|(index, fields)| {
+                PlutusData::Constr(Constr {
+                    tag: convert_constr_to_tag(index).unwrap_or(ANY_TAG),
+                    any_constructor: convert_constr_to_tag(index).map_or(Some(index), |_| None),
+                    fields,
+                })
+            }),
+        ]
+    })
+    .boxed()
+}
+
+impl Arbitrary for Term<DeBruijn> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        term(0, MAX_DEPTH)
+    }
+}
+
+/// A random term whose free variables, if any, all refer to one of the `binders` lambdas
+/// the term is nested in.
+///
+/// Lambdas hold a `DeBruijn::new(0)` parameter, which is what decoding gives back since
+/// the flat encoding doesn't store parameters.
+pub fn term(binders: usize, depth: u32) -> BoxedStrategy<Term<DeBruijn>> {
+    let builtins: Vec<DefaultFunction> = DefaultFunction::iter().collect();
+
+    let mut leaves = vec![
+        Just(Term::Error).boxed(),
+        select(builtins).prop_map(Term::Builtin).boxed(),
+        any::<Constant>()
+            .prop_map(|c| Term::Constant(c.into()))
+            .boxed(),
+    ];
+
+    if binders > 0 {
+        leaves.push(
+            (1..=binders)
+                .prop_map(|i| Term::Var(DeBruijn::new(i).into()))
+                .boxed(),
+        );
+    }
+
+    let leaf = proptest::strategy::Union::new(leaves).boxed();
+
+    if depth == 0 {
+        return leaf;
+    }
+
+    let lambda = term(binders + 1, depth - 1).prop_map(|body| Term::Lambda {
+        parameter_name: DeBruijn::new(0).into(),
+        body: body.into(),
+    });
+
+    prop_oneof![
+        2 => leaf,
+        1 => term(binders, depth - 1).prop_map(|t| Term::Delay(t.into())),
+        1 => term(binders, depth - 1).prop_map(|t| Term::Force(t.into())),
+        2 => lambda,
+        2 => (term(binders, depth - 1), term(binders, depth - 1)).prop_map(
+            |(function, argument)| Term::Apply {
+                function: Rc::new(function),
+                argument: Rc::new(argument),
+            }
+        ),
+    ]
+    .boxed()
+}
+
+impl Arbitrary for Program<DeBruijn> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<(usize, usize, usize)>(), any::<Term<DeBruijn>>())
+            .prop_map(|(version, term)| Program { version, term })
+            .boxed()
+    }
+}
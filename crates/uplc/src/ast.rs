@@ -1,9 +1,11 @@
 use std::{
+    collections::HashSet,
     fmt::{self, Display},
     hash::{self, Hash},
     rc::Rc,
 };
 
+use bech32::{FromBase32, ToBase32};
 use num_bigint::BigInt;
 use serde::{
     self,
@@ -24,8 +26,10 @@ use crate::{
     flat::Binder,
     machine::{
         cost_model::{initialize_cost_model, CostModel, ExBudget},
-        Machine,
+        runtime::ANY_TAG,
+        Binding, Checkpoint, Machine, MachineStats, Profile, TraceFormat, DEFAULT_SLIPPAGE,
     },
+    prng::Prng,
 };
 
 pub mod builder;
@@ -43,6 +47,15 @@ impl<T> Program<T>
 where
     T: Clone,
 {
+    /// Check, without evaluating it, that the program only uses builtins which the machine
+    /// supports under the given language version.
+    pub fn evaluability_report(&self, language: &Language) -> EvaluabilityReport {
+        EvaluabilityReport {
+            language: language.clone(),
+            unsupported: self.term.uses_builtins_not_in(language),
+        }
+    }
+
     /// We use this to apply the validator to Datum,
     /// then redeemer, then ScriptContext. If datum is
     /// even necessary (i.e. minting policy).
@@ -84,6 +97,22 @@ where
             term: applied_term,
         }
     }
+
+    /// Apply each of the given arguments in turn, as would as many calls to `apply_data`;
+    /// for example, a spending validator to its datum, redeemer and script context.
+    pub fn apply_data_all(&self, args: &[PlutusData]) -> Self {
+        let term = args
+            .iter()
+            .fold(self.term.clone(), |function, arg| Term::Apply {
+                function: Rc::new(function),
+                argument: Rc::new(Term::Constant(Constant::Data(arg.clone()).into())),
+            });
+
+        Program {
+            version: self.version,
+            term,
+        }
+    }
 }
 
 impl<'a, T> Display for Program<T>
@@ -98,8 +127,9 @@ where
 impl Serialize for Program<DeBruijn> {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         let cbor = self.to_cbor().unwrap();
-        let mut s = serializer.serialize_struct("Program<DeBruijn>", 2)?;
+        let mut s = serializer.serialize_struct("Program<DeBruijn>", 3)?;
         s.serialize_field("compiledCode", &hex::encode(&cbor))?;
+        s.serialize_field("size", &cbor.len())?;
         s.serialize_field("hash", &cardano::PlutusV2Script(cbor.into()).compute_hash())?;
         s.end()
     }
@@ -159,15 +189,63 @@ impl<'a> Deserialize<'a> for Program<DeBruijn> {
 }
 
 impl Program<DeBruijn> {
+    /// Render a (typically decoded from on-chain CBOR) program as readable UPLC.
+    /// Binders are given distinct names (`i_0`, `i_1`, ...) in order of appearance.
+    pub fn disassemble(&self) -> Result<String, debruijn::Error> {
+        let program: Program<Name> = self.clone().try_into()?;
+
+        Ok(program.to_pretty())
+    }
+
     pub fn address(&self, network: Network, delegation: ShelleyDelegationPart) -> ShelleyAddress {
+        ShelleyAddress::new(network, ShelleyPaymentPart::Script(self.hash()), delegation)
+    }
+
+    /// The hash of the program as a Plutus V2 script; also its policy id, when used to mint.
+    pub fn hash(&self) -> pallas_crypto::hash::Hash<28> {
         let cbor = self.to_cbor().unwrap();
-        let validator_hash = cardano::PlutusV2Script(cbor.into()).compute_hash();
-        ShelleyAddress::new(
-            network,
-            ShelleyPaymentPart::Script(validator_hash),
-            delegation,
-        )
+        cardano::PlutusV2Script(cbor.into()).compute_hash()
     }
+
+    /// The program's hash, encoded in bech32 with the `script` prefix from CIP-0005.
+    pub fn hash_bech32(&self) -> String {
+        script_hash_to_bech32(&self.hash())
+    }
+
+    /// Check that both programs behave the same when applied to `samples` random `Data`
+    /// arguments generated from `seed`: either both fail, or both evaluate to
+    /// alpha-equivalent terms. Stops at the first argument they disagree on.
+    pub fn behaviorally_eq(&self, other: &Self, samples: usize, seed: u64) -> bool {
+        let mut prng = Prng::new(seed);
+
+        (0..samples).all(|_| {
+            let data = prng.data();
+
+            let left: Program<NamedDeBruijn> = self.apply_data(data.clone()).into();
+            let right: Program<NamedDeBruijn> = other.apply_data(data).into();
+
+            left.eval_alpha_eq(&right, ExBudget::default()).is_ok()
+        })
+    }
+}
+
+/// Encode a script hash in bech32, with the `script` prefix from CIP-0005.
+pub fn script_hash_to_bech32(hash: &pallas_crypto::hash::Hash<28>) -> String {
+    bech32::encode("script", hash.to_vec().to_base32(), bech32::Variant::Bech32)
+        .expect("'script' is a valid human-readable part")
+}
+
+/// Decode a script hash from its `script`-prefixed bech32 encoding, if valid.
+pub fn script_hash_from_bech32(s: &str) -> Option<pallas_crypto::hash::Hash<28>> {
+    let (hrp, data, variant) = bech32::decode(s).ok()?;
+
+    if hrp != "script" || variant != bech32::Variant::Bech32 {
+        return None;
+    }
+
+    let bytes: [u8; 28] = Vec::<u8>::from_base32(&data).ok()?.try_into().ok()?;
+
+    Some(bytes.into())
 }
 
 /// This represents a term in Untyped Plutus Core.
@@ -213,6 +291,154 @@ impl<T> Term<T> {
     pub fn delay_wrap(self) -> Self {
         Term::Delay(self.into())
     }
+
+    /// Total number of nodes in the term, counting every node as one.
+    pub fn size(&self) -> usize {
+        match self {
+            Term::Var(_) | Term::Constant(_) | Term::Error | Term::Builtin(_) => 1,
+            Term::Delay(term) | Term::Force(term) => 1 + term.size(),
+            Term::Lambda { body, .. } => 1 + body.size(),
+            Term::Apply { function, argument } => 1 + function.size() + argument.size(),
+        }
+    }
+
+    /// Builtins used by the term that aren't available in the given language version, each
+    /// once, in order of their tag.
+    pub fn uses_builtins_not_in(&self, language: &Language) -> Vec<DefaultFunction> {
+        fn go<T>(term: &Term<T>, found: &mut Vec<DefaultFunction>) {
+            match term {
+                Term::Builtin(fun) => found.push(*fun),
+                Term::Delay(term) | Term::Force(term) => go(term, found),
+                Term::Lambda { body, .. } => go(body, found),
+                Term::Apply { function, argument } => {
+                    go(function, found);
+                    go(argument, found);
+                }
+                Term::Var(_) | Term::Constant(_) | Term::Error => {}
+            }
+        }
+
+        let mut builtins = Vec::new();
+
+        go(self, &mut builtins);
+
+        let mut unsupported: Vec<DefaultFunction> = builtins
+            .into_iter()
+            .filter(|fun| !fun.is_supported(language))
+            .collect();
+
+        unsupported.sort_by_key(|fun| *fun as u8);
+        unsupported.dedup();
+
+        unsupported
+    }
+
+    /// Length of the longest path from the root to a leaf, leaves having depth 1.
+    pub fn depth(&self) -> usize {
+        match self {
+            Term::Var(_) | Term::Constant(_) | Term::Error | Term::Builtin(_) => 1,
+            Term::Delay(term) | Term::Force(term) => 1 + term.depth(),
+            Term::Lambda { body, .. } => 1 + body.depth(),
+            Term::Apply { function, argument } => 1 + function.depth().max(argument.depth()),
+        }
+    }
+}
+
+impl Term<Name> {
+    /// Compare two terms up to a consistent renaming of their lambda-bound
+    /// variables. Free variables are compared by name.
+    pub fn alpha_eq(&self, other: &Term<Name>) -> bool {
+        fn go<'a>(
+            left: &'a Term<Name>,
+            right: &'a Term<Name>,
+            binders: &mut Vec<(&'a Name, &'a Name)>,
+        ) -> bool {
+            match (left, right) {
+                (Term::Var(x), Term::Var(y)) => {
+                    let x_binder = binders.iter().rposition(|(l, _)| *l == x.as_ref());
+                    let y_binder = binders.iter().rposition(|(_, r)| *r == y.as_ref());
+
+                    match (x_binder, y_binder) {
+                        (None, None) => x.text == y.text,
+                        (x_binder, y_binder) => x_binder == y_binder,
+                    }
+                }
+                (
+                    Term::Lambda {
+                        parameter_name: x,
+                        body: x_body,
+                    },
+                    Term::Lambda {
+                        parameter_name: y,
+                        body: y_body,
+                    },
+                ) => {
+                    binders.push((x, y));
+
+                    let result = go(x_body, y_body, binders);
+
+                    binders.pop();
+
+                    result
+                }
+                (
+                    Term::Apply {
+                        function: x_function,
+                        argument: x_argument,
+                    },
+                    Term::Apply {
+                        function: y_function,
+                        argument: y_argument,
+                    },
+                ) => go(x_function, y_function, binders) && go(x_argument, y_argument, binders),
+                (Term::Delay(x), Term::Delay(y)) | (Term::Force(x), Term::Force(y)) => {
+                    go(x, y, binders)
+                }
+                (Term::Constant(x), Term::Constant(y)) => x == y,
+                (Term::Builtin(x), Term::Builtin(y)) => x == y,
+                (Term::Error, Term::Error) => true,
+                _ => false,
+            }
+        }
+
+        go(self, other, &mut Vec::new())
+    }
+
+    /// Variables not bound by any enclosing lambda. A term with free variables can't be
+    /// converted to DeBruijn indices, which makes this a cheap check to run beforehand.
+    pub fn free_vars(&self) -> HashSet<Name> {
+        fn go<'a>(term: &'a Term<Name>, bound: &mut Vec<&'a Name>, free: &mut HashSet<Name>) {
+            match term {
+                Term::Var(name) => {
+                    if !bound.contains(&name.as_ref()) {
+                        free.insert(name.as_ref().clone());
+                    }
+                }
+                Term::Lambda {
+                    parameter_name,
+                    body,
+                } => {
+                    bound.push(parameter_name);
+
+                    go(body, bound, free);
+
+                    bound.pop();
+                }
+                Term::Apply { function, argument } => {
+                    go(function, bound, free);
+                    go(argument, bound, free);
+                }
+                Term::Delay(term) | Term::Force(term) => go(term, bound, free),
+                Term::Constant(_) | Term::Error | Term::Builtin(_) => {}
+            }
+        }
+
+        let mut free = HashSet::new();
+
+        go(self, &mut Vec::new(), &mut free);
+
+        free
+    }
 }
 
 impl<'a, T> Display for Term<T>
@@ -248,6 +474,145 @@ pub enum Constant {
     Data(PlutusData),
 }
 
+impl Constant {
+    /// Compare two constants by value. Unlike `==`, the types carried by lists and pairs are
+    /// only compared when the values don't determine them, that is, for empty lists.
+    pub fn value_eq(&self, other: &Constant) -> bool {
+        match (self, other) {
+            (Constant::ProtoList(left_type, left), Constant::ProtoList(right_type, right)) => {
+                if left.is_empty() && right.is_empty() {
+                    left_type == right_type
+                } else {
+                    left.len() == right.len() && left.iter().zip(right).all(|(l, r)| l.value_eq(r))
+                }
+            }
+            (
+                Constant::ProtoPair(_, _, left_fst, left_snd),
+                Constant::ProtoPair(_, _, right_fst, right_snd),
+            ) => left_fst.value_eq(right_fst) && left_snd.value_eq(right_snd),
+            _ => self == other,
+        }
+    }
+
+    /// The type of a constant. The types lists and pairs carry are checked against their
+    /// values, so that a wrongly annotated constant is caught before it gets encoded.
+    pub fn constant_type(&self) -> Result<Type, ConstantTypeError> {
+        match self {
+            Constant::Integer(_) => Ok(Type::Integer),
+            Constant::ByteString(_) => Ok(Type::ByteString),
+            Constant::String(_) => Ok(Type::String),
+            Constant::Unit => Ok(Type::Unit),
+            Constant::Bool(_) => Ok(Type::Bool),
+            Constant::ProtoList(typ, elems) => {
+                for elem in elems {
+                    expect_type(typ, elem)?;
+                }
+
+                Ok(Type::List(typ.clone().into()))
+            }
+            Constant::ProtoPair(fst_type, snd_type, fst, snd) => {
+                expect_type(fst_type, fst)?;
+                expect_type(snd_type, snd)?;
+
+                Ok(Type::Pair(fst_type.clone().into(), snd_type.clone().into()))
+            }
+            Constant::Data(_) => Ok(Type::Data),
+        }
+    }
+
+    /// Build a list, inferring the type of its elements. Fails when the list is empty, as
+    /// there is then nothing to infer from, or when elements have different types.
+    pub fn list(elems: Vec<Constant>) -> Result<Constant, ConstantTypeError> {
+        let typ = elems
+            .first()
+            .ok_or(ConstantTypeError::EmptyList)?
+            .constant_type()?;
+
+        for elem in elems.iter().skip(1) {
+            expect_type(&typ, elem)?;
+        }
+
+        Ok(Constant::ProtoList(typ, elems))
+    }
+
+    /// Build a pair, inferring the types of its components.
+    pub fn pair(fst: Constant, snd: Constant) -> Result<Constant, ConstantTypeError> {
+        Ok(Constant::ProtoPair(
+            fst.constant_type()?,
+            snd.constant_type()?,
+            fst.into(),
+            snd.into(),
+        ))
+    }
+}
+
+fn expect_type(expected: &Type, constant: &Constant) -> Result<(), ConstantTypeError> {
+    let found = constant.constant_type()?;
+
+    if &found == expected {
+        Ok(())
+    } else {
+        Err(ConstantTypeError::Mismatch {
+            expected: expected.clone(),
+            found,
+        })
+    }
+}
+
+/// Render some `Data` in CBOR diagnostic notation (RFC 8949, §8), following the encoding used
+/// on-chain: e.g. `121([_ 42, h'deadbeef'])` for the first constructor of a type holding an
+/// integer and a bytestring.
+pub fn to_diagnostic_cbor(data: &PlutusData) -> String {
+    match data {
+        PlutusData::Constr(cardano::Constr {
+            tag,
+            any_constructor,
+            fields,
+        }) => match any_constructor {
+            Some(index) if *tag == ANY_TAG => {
+                format!("{tag}([{index}, {}])", diagnostic_list(fields))
+            }
+            _ => format!("{tag}({})", diagnostic_list(fields)),
+        },
+        PlutusData::Map(entries) => {
+            let entries = entries
+                .iter()
+                .map(|(k, v)| format!("{}: {}", to_diagnostic_cbor(k), to_diagnostic_cbor(v)))
+                .collect::<Vec<_>>();
+
+            format!("{{{}}}", entries.join(", "))
+        }
+        PlutusData::BigInt(cardano::BigInt::Int(i)) => i128::from(*i).to_string(),
+        PlutusData::BigInt(cardano::BigInt::BigUInt(bytes)) => {
+            format!("2(h'{}')", hex::encode(&**bytes))
+        }
+        PlutusData::BigInt(cardano::BigInt::BigNInt(bytes)) => {
+            format!("3(h'{}')", hex::encode(&**bytes))
+        }
+        PlutusData::BoundedBytes(bytes) => format!("h'{}'", hex::encode(&**bytes)),
+        PlutusData::Array(items) => diagnostic_list(items),
+    }
+}
+
+/// Non-empty lists are encoded with an indefinite length, which the notation marks with `_`.
+fn diagnostic_list(items: &[PlutusData]) -> String {
+    if items.is_empty() {
+        "[]".to_string()
+    } else {
+        let items = items.iter().map(to_diagnostic_cbor).collect::<Vec<_>>();
+
+        format!("[_ {}]", items.join(", "))
+    }
+}
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ConstantTypeError {
+    #[error("Cannot infer the type of the elements of an empty list")]
+    EmptyList,
+    #[error("Expected a constant of type {expected} but found one of type {found}")]
+    Mismatch { expected: Type, found: Type },
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Type {
     Bool,
@@ -602,20 +967,237 @@ impl Program<NamedDeBruijn> {
         Result<Term<NamedDeBruijn>, crate::machine::Error>,
         ExBudget,
         Vec<String>,
+    ) {
+        self.eval_with_limits(initial_budget, None)
+    }
+
+    /// Like `eval`, but also stops with [`crate::machine::Error::StepLimitReached`] after
+    /// `max_steps` machine steps, if given, however much budget is left.
+    pub fn eval_with_limits(
+        &self,
+        initial_budget: ExBudget,
+        max_steps: Option<u64>,
+    ) -> (
+        Result<Term<NamedDeBruijn>, crate::machine::Error>,
+        ExBudget,
+        Vec<String>,
+    ) {
+        let mut machine = Machine::new(
+            Language::PlutusV2,
+            CostModel::default(),
+            initial_budget,
+            DEFAULT_SLIPPAGE,
+        );
+
+        machine.max_steps = max_steps;
+
+        let term = machine.run(&self.term);
+
+        (term, machine.ex_budget, machine.logs)
+    }
+
+    /// Like `eval`, but also stops with [`crate::machine::Error::DeadlineReached`] once
+    /// `deadline` has passed, however much budget is left.
+    pub fn eval_with_deadline(
+        &self,
+        initial_budget: ExBudget,
+        deadline: std::time::Instant,
+    ) -> (
+        Result<Term<NamedDeBruijn>, crate::machine::Error>,
+        ExBudget,
+        Vec<String>,
+    ) {
+        let mut machine = Machine::new(
+            Language::PlutusV2,
+            CostModel::default(),
+            initial_budget,
+            DEFAULT_SLIPPAGE,
+        );
+
+        machine.deadline = Some(deadline);
+
+        let term = machine.run(&self.term);
+
+        (term, machine.ex_budget, machine.logs)
+    }
+
+    /// Like `eval`, but charging machine steps to the budget every `slippage` steps instead
+    /// of every [`DEFAULT_SLIPPAGE`] steps. A smaller slippage stops closer to the step that
+    /// exhausted the budget, but evaluates slower.
+    pub fn eval_with_slippage(
+        &self,
+        initial_budget: ExBudget,
+        slippage: u32,
+    ) -> (
+        Result<Term<NamedDeBruijn>, crate::machine::Error>,
+        ExBudget,
+        Vec<String>,
+    ) {
+        let mut machine = Machine::new(
+            Language::PlutusV2,
+            CostModel::default(),
+            initial_budget,
+            slippage,
+        );
+
+        let term = machine.run(&self.term);
+
+        (term, machine.ex_budget, machine.logs)
+    }
+
+    /// Like `eval`, but also reports how many times each
+    /// CEK rule fired during evaluation.
+    pub fn eval_with_stats(
+        &self,
+        initial_budget: ExBudget,
+    ) -> (
+        Result<Term<NamedDeBruijn>, crate::machine::Error>,
+        ExBudget,
+        Vec<String>,
+        MachineStats,
+    ) {
+        let mut machine = Machine::new(
+            Language::PlutusV2,
+            CostModel::default(),
+            initial_budget,
+            DEFAULT_SLIPPAGE,
+        );
+
+        let term = machine.run(&self.term);
+
+        (term, machine.ex_budget, machine.logs, machine.stats)
+    }
+
+    /// Like `eval`, but also reports how the budget was spent: on machine steps, and on
+    /// each builtin.
+    pub fn eval_profiled(
+        &self,
+        initial_budget: ExBudget,
+    ) -> (
+        Result<Term<NamedDeBruijn>, crate::machine::Error>,
+        ExBudget,
+        Vec<String>,
+        Profile,
     ) {
         let mut machine = Machine::new(
             Language::PlutusV2,
             CostModel::default(),
             initial_budget,
-            200,
+            DEFAULT_SLIPPAGE,
         );
 
+        machine.profile = Some(Profile::default());
+
+        let term = machine.run(&self.term);
+
+        (
+            term,
+            machine.ex_budget,
+            machine.logs,
+            machine.profile.unwrap_or_default(),
+        )
+    }
+
+    /// Like `eval`, but also reports which builtins were applied to all their arguments. Like
+    /// `eval_with_deadline`, it stops once `deadline` has passed, if given.
+    pub fn eval_with_coverage(
+        &self,
+        initial_budget: ExBudget,
+        deadline: Option<std::time::Instant>,
+    ) -> (
+        Result<Term<NamedDeBruijn>, crate::machine::Error>,
+        ExBudget,
+        Vec<String>,
+        HashSet<DefaultFunction>,
+    ) {
+        let mut machine = Machine::new(
+            Language::PlutusV2,
+            CostModel::default(),
+            initial_budget,
+            DEFAULT_SLIPPAGE,
+        );
+
+        machine.deadline = deadline;
+        machine.builtins_used = Some(HashSet::new());
+
+        let term = machine.run(&self.term);
+
+        (
+            term,
+            machine.ex_budget,
+            machine.logs,
+            machine.builtins_used.unwrap_or_default(),
+        )
+    }
+
+    /// Like `eval`, but records trace messages in the given format.
+    pub fn eval_with_trace_format(
+        &self,
+        initial_budget: ExBudget,
+        trace_format: TraceFormat,
+    ) -> (
+        Result<Term<NamedDeBruijn>, crate::machine::Error>,
+        ExBudget,
+        Vec<String>,
+    ) {
+        let mut machine = Machine::new(
+            Language::PlutusV2,
+            CostModel::default(),
+            initial_budget,
+            DEFAULT_SLIPPAGE,
+        );
+
+        machine.trace_format = trace_format;
+
         let term = machine.run(&self.term);
 
         (term, machine.ex_budget, machine.logs)
     }
 
-    /// Evaluate a Program as PlutusV1
+    /// Like `eval`, but also returns the variable bindings of the environment the machine
+    /// stopped in, to help figure out why a program evaluated to an unexpected value.
+    pub fn eval_with_environment(
+        &self,
+        initial_budget: ExBudget,
+    ) -> (
+        Result<Term<NamedDeBruijn>, crate::machine::Error>,
+        ExBudget,
+        Vec<String>,
+        Vec<Binding>,
+    ) {
+        let mut machine = Machine::new(
+            Language::PlutusV2,
+            CostModel::default(),
+            initial_budget,
+            DEFAULT_SLIPPAGE,
+        );
+
+        let (term, bindings) = machine.run_with_environment(&self.term);
+
+        (term, machine.ex_budget, machine.logs, bindings)
+    }
+
+    /// Like `eval`, but keeps the result as a [`Checkpoint`] and hands the machine back. Once
+    /// more arguments are known, evaluation can carry on with [`Machine::resume`], rather
+    /// than evaluating the program again with [`Program::apply_term`].
+    pub fn eval_checkpoint(
+        &self,
+        initial_budget: ExBudget,
+    ) -> (Result<Checkpoint, crate::machine::Error>, Machine) {
+        let mut machine = Machine::new(
+            Language::PlutusV2,
+            CostModel::default(),
+            initial_budget,
+            DEFAULT_SLIPPAGE,
+        );
+
+        let checkpoint = machine.checkpoint(&self.term);
+
+        (checkpoint, machine)
+    }
+
+    /// Evaluate a Program as PlutusV1. Builtins that don't exist in PlutusV1 aren't rejected
+    /// up front; use [`Term::uses_builtins_not_in`] to do so.
     pub fn eval_v1(
         &self,
     ) -> (
@@ -623,7 +1205,24 @@ impl Program<NamedDeBruijn> {
         ExBudget,
         Vec<String>,
     ) {
-        let mut machine = Machine::new(Language::PlutusV1, CostModel::v1(), ExBudget::v1(), 200);
+        self.eval_v1_with_budget(ExBudget::v1())
+    }
+
+    /// Like `eval_v1`, but with the given budget.
+    pub fn eval_v1_with_budget(
+        &self,
+        initial_budget: ExBudget,
+    ) -> (
+        Result<Term<NamedDeBruijn>, crate::machine::Error>,
+        ExBudget,
+        Vec<String>,
+    ) {
+        let mut machine = Machine::new(
+            Language::PlutusV1,
+            CostModel::v1(),
+            initial_budget,
+            DEFAULT_SLIPPAGE,
+        );
 
         let term = machine.run(&self.term);
 
@@ -649,13 +1248,227 @@ impl Program<NamedDeBruijn> {
             version.clone(),
             initialize_cost_model(version, costs),
             budget,
-            200, //slippage
+            DEFAULT_SLIPPAGE,
         );
 
         let term = machine.run(&self.term);
 
         (term, machine.ex_budget, machine.logs)
     }
+
+    /// Evaluate both programs and check that their results are alpha-equivalent.
+    /// Two failing evaluations are considered equivalent, regardless of the error.
+    pub fn eval_alpha_eq(&self, other: &Self, initial_budget: ExBudget) -> Result<(), EvalDiff> {
+        fn result(
+            program: &Program<NamedDeBruijn>,
+            budget: ExBudget,
+        ) -> Result<Term<Name>, String> {
+            let (term, _, _) = program.eval(budget);
+
+            term.map_err(|e| e.to_string())
+                .and_then(|term| Term::<Name>::try_from(term).map_err(|e| e.to_string()))
+        }
+
+        match (result(self, initial_budget), result(other, initial_budget)) {
+            (Ok(left), Ok(right)) if left.alpha_eq(&right) => Ok(()),
+            (Err(_), Err(_)) => Ok(()),
+            (left, right) => {
+                let render = |result: Result<Term<Name>, String>| match result {
+                    Ok(term) => term.to_pretty(),
+                    Err(e) => format!("error: {e}"),
+                };
+
+                Err(EvalDiff {
+                    left: render(left),
+                    right: render(right),
+                })
+            }
+        }
+    }
+}
+
+/// Builtins used by a program that the machine can't evaluate under a given language
+/// version, as returned by [`Program::evaluability_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvaluabilityReport {
+    pub language: Language,
+    /// Each unsupported builtin, once, in order of their tag.
+    pub unsupported: Vec<DefaultFunction>,
+}
+
+impl EvaluabilityReport {
+    pub fn is_evaluable(&self) -> bool {
+        self.unsupported.is_empty()
+    }
+}
+
+impl Display for EvaluabilityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_evaluable() {
+            write!(f, "can be evaluated as {:?}", self.language)
+        } else {
+            write!(
+                f,
+                "cannot be evaluated as {:?}, unsupported builtins: {}",
+                self.language,
+                self.unsupported
+                    .iter()
+                    .map(|fun| fun.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+    }
+}
+
+impl Program<Name> {
+    /// Walk both programs' terms in lockstep and report where they structurally differ. Each
+    /// divergence is reported once, at the outermost node where it shows, without descending
+    /// further; distinct subterms may each diverge. Empty when both programs are equal.
+    pub fn diff(&self, other: &Self) -> Vec<TermDiff> {
+        // Names are otherwise equal as soon as their uniques are, whatever their text.
+        fn same_name(x: &Name, y: &Name) -> bool {
+            x == y && x.text == y.text
+        }
+
+        fn go(left: &Term<Name>, right: &Term<Name>, path: &str, diffs: &mut Vec<TermDiff>) {
+            let at = |segments: &str| {
+                if path.is_empty() {
+                    segments.to_string()
+                } else {
+                    format!("{path}.{segments}")
+                }
+            };
+
+            match (left, right) {
+                (
+                    Term::Apply {
+                        function: x_function,
+                        argument: x_argument,
+                    },
+                    Term::Apply {
+                        function: y_function,
+                        argument: y_argument,
+                    },
+                ) => {
+                    go(x_function, y_function, &at("apply.function"), diffs);
+                    go(x_argument, y_argument, &at("apply.argument"), diffs);
+                }
+                (
+                    Term::Lambda {
+                        parameter_name: x,
+                        body: x_body,
+                    },
+                    Term::Lambda {
+                        parameter_name: y,
+                        body: y_body,
+                    },
+                ) => {
+                    if !same_name(x, y) {
+                        diffs.push(TermDiff {
+                            path: at("lambda.parameter_name"),
+                            left: x.text.clone(),
+                            right: y.text.clone(),
+                        });
+                    }
+
+                    go(x_body, y_body, &at("lambda.body"), diffs);
+                }
+                (Term::Delay(x), Term::Delay(y)) => go(x, y, &at("delay"), diffs),
+                (Term::Force(x), Term::Force(y)) => go(x, y, &at("force"), diffs),
+                (Term::Var(x), Term::Var(y)) if same_name(x, y) => {}
+                (left, right) if left != right => diffs.push(TermDiff {
+                    path: path.to_string(),
+                    left: left.to_pretty(),
+                    right: right.to_pretty(),
+                }),
+                _ => {}
+            }
+        }
+
+        let mut diffs = Vec::new();
+
+        if self.version != other.version {
+            let version = |(major, minor, patch)| format!("{major}.{minor}.{patch}");
+
+            diffs.push(TermDiff {
+                path: "version".to_string(),
+                left: version(self.version),
+                right: version(other.version),
+            });
+        }
+
+        go(&self.term, &other.term, "", &mut diffs);
+
+        diffs
+    }
+}
+
+/// A place where two programs differ, as returned by [`Program::diff`]: the path to it from
+/// the root term, such as `apply.argument.lambda.body`, and what each program has there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TermDiff {
+    pub path: String,
+    pub left: String,
+    pub right: String,
+}
+
+impl Display for TermDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "the root"
+        } else {
+            &self.path
+        };
+
+        write!(f, "at {path}:\n- {}\n+ {}", self.left, self.right)
+    }
+}
+
+/// Pretty-printed results of two evaluations that didn't match, as returned by
+/// [`Program::eval_alpha_eq`]. Displays as a line diff of both results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalDiff {
+    pub left: String,
+    pub right: String,
+}
+
+impl Display for EvalDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let left: Vec<&str> = self.left.lines().collect();
+        let right: Vec<&str> = self.right.lines().collect();
+
+        let prefix = left
+            .iter()
+            .zip(right.iter())
+            .take_while(|(l, r)| l == r)
+            .count();
+
+        let suffix = left[prefix..]
+            .iter()
+            .rev()
+            .zip(right[prefix..].iter().rev())
+            .take_while(|(l, r)| l == r)
+            .count();
+
+        for line in &left[..prefix] {
+            writeln!(f, "  {line}")?;
+        }
+
+        for line in &left[prefix..left.len() - suffix] {
+            writeln!(f, "- {line}")?;
+        }
+
+        for line in &right[prefix..right.len() - suffix] {
+            writeln!(f, "+ {line}")?;
+        }
+
+        for line in &left[left.len() - suffix..] {
+            writeln!(f, "  {line}")?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Program<DeBruijn> {
@@ -671,6 +1484,32 @@ impl Program<DeBruijn> {
 
         program.eval(initial_budget)
     }
+
+    /// Like [`Program::eval_with_environment`] on named programs, except that bindings are
+    /// only known by their index since there are no names to recover.
+    pub fn eval_with_environment(
+        &self,
+        initial_budget: ExBudget,
+    ) -> (
+        Result<Term<NamedDeBruijn>, crate::machine::Error>,
+        ExBudget,
+        Vec<String>,
+        Vec<Binding>,
+    ) {
+        let program: Program<NamedDeBruijn> = self.clone().into();
+
+        let (term, budget, logs, bindings) = program.eval_with_environment(initial_budget);
+
+        let bindings = bindings
+            .into_iter()
+            .map(|binding| Binding {
+                name: None,
+                ..binding
+            })
+            .collect();
+
+        (term, budget, logs, bindings)
+    }
 }
 
 impl Term<NamedDeBruijn> {
@@ -678,3 +1517,521 @@ impl Term<NamedDeBruijn> {
         !matches!(self, Term::Error)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Constant, ConstantTypeError, DeBruijn, DefaultFunction, Language, Name, NamedDeBruijn,
+        Program, Term, Type, Unique,
+    };
+    use crate::machine::{
+        cost_model::{CostModel, ExBudget, StepKind},
+        DEFAULT_SLIPPAGE,
+    };
+    use crate::{parser, BigInt, PlutusData};
+    use std::rc::Rc;
+
+    fn name(text: &str, unique: isize) -> Name {
+        Name {
+            text: text.to_string(),
+            unique: Unique::new(unique),
+        }
+    }
+
+    #[test]
+    fn disassemble_distinct_names() {
+        let program: Program<DeBruijn> = parser::program("(program 1.0.0 (lam x (lam y [x y])))")
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let hex = program.to_hex().unwrap();
+
+        let decoded =
+            Program::<DeBruijn>::from_hex(&hex, &mut Vec::new(), &mut Vec::new()).unwrap();
+
+        assert_eq!(
+            decoded.disassemble().unwrap(),
+            "(program\n  1.0.0\n  (lam i_0 (lam i_1 [ i_0 i_1 ]))\n)"
+        );
+    }
+
+    #[test]
+    fn free_vars_closed_term() {
+        let term = parser::term("(lam x (lam y [x y]))").unwrap();
+
+        assert!(term.free_vars().is_empty());
+    }
+
+    #[test]
+    fn free_vars_shadowing() {
+        // The outer `x` is free, even though an inner lambda binds the same name.
+        let term = parser::term("[(lam x x) x]").unwrap();
+
+        let free: Vec<String> = term.free_vars().into_iter().map(|n| n.text).collect();
+
+        assert_eq!(free, vec!["x"]);
+
+        let term = parser::term("(lam y [(lam x [x y]) (force (delay x))])").unwrap();
+
+        let free: Vec<String> = term.free_vars().into_iter().map(|n| n.text).collect();
+
+        assert_eq!(free, vec!["x"]);
+    }
+
+    #[test]
+    fn free_vars_nested_applies() {
+        let term = parser::term("[[f (lam a [a b])] [g [(lam b b) c]]]").unwrap();
+
+        let mut free: Vec<String> = term.free_vars().into_iter().map(|n| n.text).collect();
+
+        free.sort();
+
+        assert_eq!(free, vec!["b", "c", "f", "g"]);
+
+        let program: Result<Program<DeBruijn>, _> = Program {
+            version: (1, 0, 0),
+            term,
+        }
+        .try_into();
+
+        assert!(program.is_err());
+    }
+
+    #[test]
+    fn alpha_eq_renamed_binders() {
+        let left = parser::term("(lam x x)").unwrap();
+        let right = parser::term("(lam y y)").unwrap();
+
+        assert!(left.alpha_eq(&right));
+    }
+
+    #[test]
+    fn alpha_eq_different_uniques() {
+        let x = name("x", 0);
+        let y = name("y", 42);
+
+        let left = Term::Lambda {
+            parameter_name: x.clone().into(),
+            body: Term::Var(x.into()).into(),
+        };
+
+        let right = Term::Lambda {
+            parameter_name: y.clone().into(),
+            body: Term::Var(y.into()).into(),
+        };
+
+        assert_ne!(left, right);
+        assert!(left.alpha_eq(&right));
+    }
+
+    #[test]
+    fn alpha_eq_free_variable() {
+        let left = parser::term("(lam x x)").unwrap();
+        let right = parser::term("(lam x z)").unwrap();
+
+        assert!(!left.alpha_eq(&right));
+    }
+
+    #[test]
+    fn alpha_eq_free_variables_by_name() {
+        let left = parser::term("[(lam x x) z]").unwrap();
+        let right = parser::term("[(lam y y) z]").unwrap();
+        let other = parser::term("[(lam y y) w]").unwrap();
+
+        assert!(left.alpha_eq(&right));
+        assert!(!left.alpha_eq(&other));
+    }
+
+    #[test]
+    fn alpha_eq_shadowing() {
+        let left = parser::term("(lam x (lam y x))").unwrap();
+        let right = parser::term("(lam a (lam b a))").unwrap();
+        let other = parser::term("(lam a (lam b b))").unwrap();
+
+        assert!(left.alpha_eq(&right));
+        assert!(!left.alpha_eq(&other));
+    }
+
+    #[test]
+    fn size_and_depth_of_leaves() {
+        for source in ["x", "(con integer 42)", "(error)", "(builtin addInteger)"] {
+            let term = parser::term(source).unwrap();
+
+            assert_eq!((term.size(), term.depth()), (1, 1), "{source}");
+        }
+    }
+
+    #[test]
+    fn size_and_depth_of_nested_terms() {
+        let term = parser::term("(lam x (force (delay x)))").unwrap();
+        assert_eq!((term.size(), term.depth()), (4, 4));
+
+        let term =
+            parser::term("[[(builtin addInteger) (con integer 1)] (con integer 2)]").unwrap();
+        assert_eq!((term.size(), term.depth()), (5, 3));
+
+        let term = parser::term("[(lam x [x x]) (lam y y)]").unwrap();
+        assert_eq!((term.size(), term.depth()), (7, 4));
+    }
+
+    fn named_program(source: &str) -> Program<NamedDeBruijn> {
+        parser::program(source).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn eval_alpha_eq_same_result() {
+        let reference = named_program(
+            "(program 1.0.0 [[(builtin addInteger) (con integer 1)] (con integer 2)])",
+        );
+        let optimized = named_program("(program 1.0.0 (con integer 3))");
+
+        assert_eq!(
+            reference.eval_alpha_eq(&optimized, ExBudget::default()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn eval_alpha_eq_renamed_lambdas() {
+        let left = named_program("(program 1.0.0 [(lam f (lam x [f x])) (lam y y)])");
+        let right = named_program("(program 1.0.0 (lam z [(lam w w) z]))");
+
+        assert_eq!(left.eval_alpha_eq(&right, ExBudget::default()), Ok(()));
+    }
+
+    #[test]
+    fn eval_alpha_eq_mismatch() {
+        let left = named_program("(program 1.0.0 (con integer 3))");
+        let right = named_program("(program 1.0.0 (con integer 4))");
+
+        let diff = left.eval_alpha_eq(&right, ExBudget::default()).unwrap_err();
+
+        assert_eq!(diff.to_string(), "- (con integer 3)\n+ (con integer 4)\n");
+
+        let failing = named_program("(program 1.0.0 (error))");
+
+        assert!(left.eval_alpha_eq(&failing, ExBudget::default()).is_err());
+        assert_eq!(failing.eval_alpha_eq(&failing, ExBudget::default()), Ok(()));
+    }
+
+    #[test]
+    fn eval_checkpoint_resumes_like_full_evaluation() {
+        let validator = "(lam a [(force (builtin trace)) (con string \"checked a\") (lam b (lam c [[(builtin addInteger) a] [[(builtin addInteger) b] c]]))])";
+
+        let full = named_program(&format!(
+            "(program 1.0.0 [[[{validator} (con integer 1)] (con integer 2)] (con integer 3)])"
+        ));
+
+        let partial = named_program(&format!("(program 1.0.0 [{validator} (con integer 1)])"));
+
+        let (expected, expected_budget, expected_logs) = full.eval(ExBudget::default());
+
+        let (checkpoint, mut machine) = partial.eval_checkpoint(ExBudget::default());
+
+        let checkpoint = machine
+            .resume(
+                &checkpoint.unwrap(),
+                &Term::Constant(Constant::Integer(2.into()).into()),
+            )
+            .unwrap();
+
+        let result = machine
+            .resume(
+                &checkpoint,
+                &Term::Constant(Constant::Integer(3.into()).into()),
+            )
+            .unwrap();
+
+        assert_eq!(result.term(), expected.unwrap());
+        assert_eq!(machine.ex_budget, expected_budget);
+        assert_eq!(machine.logs, expected_logs);
+
+        // The same checkpoint can be resumed with a different argument.
+        let other = machine
+            .resume(
+                &checkpoint,
+                &Term::Constant(Constant::Integer(10.into()).into()),
+            )
+            .unwrap();
+
+        assert_eq!(
+            other.term(),
+            Term::Constant(Constant::Integer(13.into()).into())
+        );
+    }
+
+    #[test]
+    fn evaluability_report_lists_unsupported_builtins() {
+        let program = named_program(
+            "(program 1.0.0 [(lam x [[[(builtin verifySchnorrSecp256k1Signature) x] x] x]) [(builtin verifyEcdsaSecp256k1Signature) [(builtin sha2_256) (con bytestring #)]]])",
+        );
+
+        let report = program.evaluability_report(&Language::PlutusV1);
+
+        assert_eq!(
+            report.unsupported,
+            vec![
+                DefaultFunction::VerifyEcdsaSecp256k1Signature,
+                DefaultFunction::VerifySchnorrSecp256k1Signature
+            ]
+        );
+        assert_eq!(
+            report.to_string(),
+            "cannot be evaluated as PlutusV1, unsupported builtins: verifyEcdsaSecp256k1Signature, verifySchnorrSecp256k1Signature"
+        );
+
+        assert!(program
+            .evaluability_report(&Language::PlutusV2)
+            .is_evaluable());
+    }
+
+    #[test]
+    fn uses_builtins_not_in() {
+        let term = named_program(
+            "(program 1.0.0 (lam d [(builtin sha2_256) [(builtin serialiseData) d]]))",
+        )
+        .term;
+
+        assert_eq!(
+            term.uses_builtins_not_in(&Language::PlutusV1),
+            vec![DefaultFunction::SerialiseData]
+        );
+        assert!(term.uses_builtins_not_in(&Language::PlutusV2).is_empty());
+    }
+
+    #[test]
+    fn constant_value_eq_empty_lists() {
+        let integers = Constant::ProtoList(Type::Integer, vec![]);
+        let data = Constant::ProtoList(Type::Data, vec![]);
+
+        assert!(integers.value_eq(&integers.clone()));
+        assert!(!integers.value_eq(&data));
+    }
+
+    #[test]
+    fn constant_value_eq_ignores_annotations() {
+        let list = |tipo| Constant::ProtoList(tipo, vec![Constant::Integer(1.into())]);
+
+        assert!(list(Type::Integer).value_eq(&list(Type::Data)));
+        assert_ne!(list(Type::Integer), list(Type::Data));
+        assert!(!list(Type::Integer).value_eq(&Constant::ProtoList(
+            Type::Integer,
+            vec![Constant::Integer(2.into())]
+        )));
+
+        let pair = |fst, snd, inner| {
+            Constant::ProtoPair(
+                fst,
+                snd,
+                Rc::new(Constant::Bool(true)),
+                Rc::new(Constant::ProtoList(inner, vec![])),
+            )
+        };
+
+        assert!(pair(Type::Bool, Type::Data, Type::Unit).value_eq(&pair(
+            Type::Integer,
+            Type::Unit,
+            Type::Unit
+        )));
+        // The nested empty list still needs its type to match.
+        assert!(!pair(Type::Bool, Type::Data, Type::Unit).value_eq(&pair(
+            Type::Bool,
+            Type::Data,
+            Type::Integer
+        )));
+    }
+
+    #[test]
+    fn constant_type_of_list_of_pairs() {
+        let pair = |i: i32, b| {
+            Constant::pair(Constant::Integer(i.into()), Constant::ByteString(b)).unwrap()
+        };
+
+        let list = Constant::list(vec![pair(1, vec![0xff]), pair(2, vec![])]).unwrap();
+
+        let pair_type = Type::Pair(Type::Integer.into(), Type::ByteString.into());
+
+        assert_eq!(
+            list,
+            Constant::ProtoList(
+                pair_type.clone(),
+                vec![pair(1, vec![0xff]), pair(2, vec![])]
+            )
+        );
+        assert_eq!(list.constant_type(), Ok(Type::List(pair_type.into())));
+
+        let nested = Constant::list(vec![list.clone(), list]).unwrap();
+
+        assert!(matches!(
+            nested.constant_type(),
+            Ok(Type::List(inner)) if matches!(inner.as_ref(), Type::List(_))
+        ));
+    }
+
+    #[test]
+    fn constant_type_errors() {
+        assert_eq!(Constant::list(vec![]), Err(ConstantTypeError::EmptyList));
+
+        // An annotated empty list is fine.
+        assert_eq!(
+            Constant::ProtoList(Type::Data, vec![]).constant_type(),
+            Ok(Type::List(Type::Data.into()))
+        );
+
+        assert_eq!(
+            Constant::list(vec![Constant::Integer(1.into()), Constant::Bool(true)]),
+            Err(ConstantTypeError::Mismatch {
+                expected: Type::Integer,
+                found: Type::Bool
+            })
+        );
+
+        let mislabelled = Constant::ProtoPair(
+            Type::Integer,
+            Type::Unit,
+            Constant::Integer(1.into()).into(),
+            Constant::ProtoList(Type::String, vec![Constant::Unit]).into(),
+        );
+
+        assert_eq!(
+            mislabelled.constant_type(),
+            Err(ConstantTypeError::Mismatch {
+                expected: Type::String,
+                found: Type::Unit
+            })
+        );
+    }
+
+    #[test]
+    fn apply_data_all() {
+        let program: Program<DeBruijn> =
+            parser::program("(program 1.2.3 (lam a (lam b (lam c a))))")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        let data = |i: i64| PlutusData::BigInt(BigInt::Int(i.into()));
+
+        let applied = program.apply_data_all(&[data(1), data(2), data(3)]);
+
+        assert_eq!(
+            applied,
+            program
+                .apply_data(data(1))
+                .apply_data(data(2))
+                .apply_data(data(3))
+        );
+        assert_eq!(applied.version, (1, 2, 3));
+        assert_eq!(program.apply_data_all(&[]), program);
+    }
+
+    #[test]
+    fn behaviorally_eq() {
+        let program = |source: &str| -> Program<DeBruijn> {
+            parser::program(source).unwrap().try_into().unwrap()
+        };
+
+        let original = program(
+            "(program 1.0.0 (lam d [(builtin constrData) [(builtin addInteger) (con integer 1) (con integer 2)] [(builtin mkCons) d [(builtin mkNilData) (con unit ())]]]))",
+        );
+
+        let folded = program(
+            "(program 1.0.0 (lam d [(builtin constrData) (con integer 3) [(builtin mkCons) d [(builtin mkNilData) (con unit ())]]]))",
+        );
+
+        let broken = program(
+            "(program 1.0.0 (lam d [(builtin constrData) (con integer 3) [(builtin mkNilData) (con unit ())]]))",
+        );
+
+        let failing = program("(program 1.0.0 (lam d [(builtin unIData) d]))");
+
+        assert!(original.behaviorally_eq(&folded, 50, 42));
+        assert!(!original.behaviorally_eq(&broken, 50, 42));
+        assert!(!original.behaviorally_eq(&failing, 50, 42));
+        assert!(failing.behaviorally_eq(&failing, 50, 42));
+    }
+
+    #[test]
+    fn eval_with_slippage() {
+        // 30 nested applications of the identity, that is, about 100 machine steps.
+        let source = (0..30).fold("(con integer 42)".to_string(), |term, _| {
+            format!("[(lam x x) {term}]")
+        });
+
+        let program = named_program(&format!("(program 1.0.0 {source})"));
+
+        let (result, remaining, _) = program.eval(ExBudget::default());
+        assert!(result.is_ok());
+
+        let spent = ExBudget::default() - remaining;
+
+        // Only enough budget for about half of the steps.
+        let budget = ExBudget {
+            mem: spent.mem / 2,
+            cpu: spent.cpu / 2,
+        };
+
+        let step = CostModel::default().machine_costs.get(StepKind::Apply);
+
+        // With the default slippage, steps are charged in batches, way past the budget.
+        let (result, remaining, _) = program.eval_with_slippage(budget, DEFAULT_SLIPPAGE);
+        assert!(matches!(
+            result,
+            Err(crate::machine::Error::OutOfExError(..))
+        ));
+        assert!(remaining.cpu < -10 * step.cpu);
+
+        // With a slippage of 1, the evaluation stops at the step exceeding the budget.
+        let (result, remaining, _) = program.eval_with_slippage(budget, 1);
+        assert!(matches!(
+            result,
+            Err(crate::machine::Error::OutOfExError(..))
+        ));
+        assert!(remaining.cpu < 0 && remaining.cpu >= -step.cpu);
+
+        // Either way, the same budget is spent when there's enough.
+        let (result, remaining, _) = program.eval_with_slippage(ExBudget::default(), 1);
+        assert_eq!(
+            result.unwrap(),
+            Term::Constant(Constant::Integer(42.into()).into())
+        );
+        assert_eq!(ExBudget::default() - remaining, spent);
+    }
+
+    #[test]
+    fn diff_reports_divergence_paths() {
+        let left = parser::program(
+            "(program 1.0.0 [(lam f [f (con integer 1)]) (lam x (delay [x (con integer 2)]))])",
+        )
+        .unwrap();
+        let right = parser::program(
+            "(program 1.0.0 [(lam f [f (con integer 1)]) (lam x (delay [x (con integer 3)]))])",
+        )
+        .unwrap();
+
+        let diffs = left.diff(&right);
+
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(
+            diffs[0].path,
+            "apply.argument.lambda.body.delay.apply.argument"
+        );
+        assert_eq!(diffs[0].left, "(con integer 2)");
+        assert_eq!(diffs[0].right, "(con integer 3)");
+
+        assert!(left.diff(&left).is_empty());
+
+        let renamed = parser::program("(program 1.0.1 (lam y (error)))").unwrap();
+
+        assert_eq!(
+            parser::program("(program 1.0.0 (lam x (error)))")
+                .unwrap()
+                .diff(&renamed)
+                .into_iter()
+                .map(|diff| diff.path)
+                .collect::<Vec<_>>(),
+            vec!["version", "lambda.parameter_name"]
+        );
+    }
+}
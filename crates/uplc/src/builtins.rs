@@ -3,13 +3,14 @@ use std::{fmt::Display, rc::Rc, str::FromStr};
 use strum_macros::EnumIter;
 
 use flat_rs::de;
+use pallas_primitives::babbage::Language;
 
 use crate::ast::Term;
 
 /// All the possible builtin functions in Untyped Plutus Core.
 #[repr(u8)]
 #[allow(non_camel_case_types)]
-#[derive(Debug, Clone, PartialEq, Eq, Copy, EnumIter)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy, EnumIter)]
 pub enum DefaultFunction {
     // Integer functions
     AddInteger = 0,
@@ -35,6 +36,8 @@ pub enum DefaultFunction {
     Sha2_256 = 18,
     Sha3_256 = 19,
     Blake2b_256 = 20,
+    Blake2b_224 = 72,
+    Keccak_256 = 71,
     VerifyEd25519Signature = 21,
     VerifyEcdsaSecp256k1Signature = 52,
     VerifySchnorrSecp256k1Signature = 53,
@@ -138,6 +141,8 @@ impl TryFrom<u8> for DefaultFunction {
             v if v == DefaultFunction::Sha2_256 as u8 => Ok(DefaultFunction::Sha2_256),
             v if v == DefaultFunction::Sha3_256 as u8 => Ok(DefaultFunction::Sha3_256),
             v if v == DefaultFunction::Blake2b_256 as u8 => Ok(DefaultFunction::Blake2b_256),
+            v if v == DefaultFunction::Blake2b_224 as u8 => Ok(DefaultFunction::Blake2b_224),
+            v if v == DefaultFunction::Keccak_256 as u8 => Ok(DefaultFunction::Keccak_256),
             v if v == DefaultFunction::VerifyEd25519Signature as u8 => {
                 Ok(DefaultFunction::VerifyEd25519Signature)
             }
@@ -226,6 +231,8 @@ impl FromStr for DefaultFunction {
             "sha2_256" => Ok(Sha2_256),
             "sha3_256" => Ok(Sha3_256),
             "blake2b_256" => Ok(Blake2b_256),
+            "blake2b_224" => Ok(Blake2b_224),
+            "keccak_256" => Ok(Keccak_256),
             "verifyEd25519Signature" => Ok(VerifyEd25519Signature),
             "verifyEcdsaSecp256k1Signature" => Ok(VerifyEcdsaSecp256k1Signature),
             "verifySchnorrSecp256k1Signature" => Ok(VerifySchnorrSecp256k1Signature),
@@ -290,6 +297,8 @@ impl Display for DefaultFunction {
             Sha2_256 => write!(f, "sha2_256"),
             Sha3_256 => write!(f, "sha3_256"),
             Blake2b_256 => write!(f, "blake2b_256"),
+            Blake2b_224 => write!(f, "blake2b_224"),
+            Keccak_256 => write!(f, "keccak_256"),
             VerifyEd25519Signature => write!(f, "verifySignature"),
             VerifyEcdsaSecp256k1Signature => write!(f, "verifyEcdsaSecp256k1Signature"),
             VerifySchnorrSecp256k1Signature => write!(f, "verifySchnorrSecp256k1Signature"),
@@ -328,6 +337,26 @@ impl Display for DefaultFunction {
 }
 
 impl DefaultFunction {
+    /// Whether the machine can evaluate this builtin under the given language version. The
+    /// secp256k1 signature verifications were only introduced with PlutusV2, and have no
+    /// cost under PlutusV1. `keccak_256` and `blake2b_224` only come with PlutusV3, so
+    /// neither language supports them.
+    pub fn is_supported(&self, language: &Language) -> bool {
+        use DefaultFunction::*;
+
+        match language {
+            Language::PlutusV1 => !matches!(
+                self,
+                SerialiseData
+                    | VerifyEcdsaSecp256k1Signature
+                    | VerifySchnorrSecp256k1Signature
+                    | Keccak_256
+                    | Blake2b_224
+            ),
+            Language::PlutusV2 => !matches!(self, Keccak_256 | Blake2b_224),
+        }
+    }
+
     pub fn aiken_name(&self) -> String {
         use DefaultFunction::*;
 
@@ -353,6 +382,8 @@ impl DefaultFunction {
             Sha2_256 => "sha2_256",
             Sha3_256 => "sha3_256",
             Blake2b_256 => "blake2b_256",
+            Blake2b_224 => "blake2b_224",
+            Keccak_256 => "keccak_256",
             VerifyEd25519Signature => "verify_ed25519_signature",
             VerifyEcdsaSecp256k1Signature => "verify_ecdsa_secp256k1_signature",
             VerifySchnorrSecp256k1Signature => "verify_schnorr_secp256k1_signature",
@@ -402,3 +433,30 @@ impl<T> From<DefaultFunction> for Rc<Term<T>> {
         Term::Builtin(builtin).into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pallas_primitives::babbage::Language;
+
+    use super::DefaultFunction;
+
+    #[test]
+    fn is_supported() {
+        use DefaultFunction::*;
+
+        for fun in [SerialiseData, VerifyEcdsaSecp256k1Signature] {
+            assert!(!fun.is_supported(&Language::PlutusV1));
+            assert!(fun.is_supported(&Language::PlutusV2));
+        }
+
+        for fun in [Keccak_256, Blake2b_224] {
+            assert!(!fun.is_supported(&Language::PlutusV1));
+            assert!(!fun.is_supported(&Language::PlutusV2));
+        }
+
+        for fun in [AddInteger, Sha2_256, Blake2b_256] {
+            assert!(fun.is_supported(&Language::PlutusV1));
+            assert!(fun.is_supported(&Language::PlutusV2));
+        }
+    }
+}
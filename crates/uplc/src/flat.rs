@@ -47,6 +47,12 @@ where
         Self::unflat(bytes)
     }
 
+    /// Decode a program at the start of some flat bytes, ignoring whatever follows it. Returns
+    /// the program along with the number of bytes it was decoded from.
+    pub fn from_flat_prefix(bytes: &'b [u8]) -> Result<(Self, usize), de::Error> {
+        Self::unflat_prefix(bytes)
+    }
+
     pub fn from_hex(
         hex_str: &str,
         cbor_buffer: &'b mut Vec<u8>,
@@ -88,6 +94,48 @@ where
     }
 }
 
+/// Scripts come in two CBOR envelopes, easily mixed up as both are CBOR bytestrings:
+///
+/// - single: the flat-encoded program wrapped in one CBOR bytestring. That's what
+///   [`Program::to_cbor`] produces, what blueprints hold as `compiledCode`, and what
+///   transactions carry in their witness set.
+/// - double: the single envelope, wrapped again in a CBOR bytestring. That's the `cborHex`
+///   of cardano-cli's script files, and what some off-chain libraries expect.
+impl Program<DeBruijn> {
+    /// The flat-encoded program in a single CBOR bytestring; same as [`Program::to_cbor`].
+    pub fn to_cbor_single(&self) -> Result<Vec<u8>, en::Error> {
+        self.to_cbor()
+    }
+
+    /// Decode a program from the flat bytes of a single CBOR bytestring.
+    pub fn from_cbor_single(bytes: &[u8]) -> Result<Self, de::Error> {
+        Self::from_cbor(bytes, &mut Vec::new())
+    }
+
+    /// The flat-encoded program in a CBOR bytestring, itself in a CBOR bytestring.
+    pub fn to_cbor_double(&self) -> Result<Vec<u8>, en::Error> {
+        let single = self.to_cbor()?;
+
+        let mut bytes = Vec::new();
+
+        pallas_codec::minicbor::Encoder::new(&mut bytes)
+            .bytes(&single)
+            .map_err(|err| en::Error::Message(err.to_string()))?;
+
+        Ok(bytes)
+    }
+
+    /// Decode a program from the flat bytes of a CBOR bytestring, itself in a CBOR
+    /// bytestring.
+    pub fn from_cbor_double(bytes: &[u8]) -> Result<Self, de::Error> {
+        let single = pallas_codec::minicbor::Decoder::new(bytes)
+            .bytes()
+            .map_err(|err| de::Error::Message(err.to_string()))?;
+
+        Self::from_cbor_single(single)
+    }
+}
+
 impl<'b, T> Encode for Program<T>
 where
     T: Binder<'b> + Debug,
@@ -810,9 +858,18 @@ pub fn decode_constant_tag(d: &mut Decoder) -> Result<u8, de::Error> {
 
 #[cfg(test)]
 mod test {
-    use flat_rs::Flat;
+    use std::io::Read;
+
+    use flat_rs::{
+        de::{Decoder, StreamDecoder},
+        filler::Filler,
+        Flat,
+    };
 
-    use crate::ast::{Name, Type};
+    use crate::{
+        ast::{DeBruijn, Name, Type},
+        parser,
+    };
 
     use super::{Constant, Program, Term};
 
@@ -961,4 +1018,115 @@ mod test {
 
         assert_eq!(actual_program, expected_program)
     }
+
+    /// A reader handing out at most 3 bytes at a time.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = buf.len().min(3).min(self.0.len());
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn flat_decode_stream() {
+        let sources = [
+            "(program 1.0.0 [(lam x x) (con bytestring #deadbeef)])",
+            "(program 1.0.0 (con string \"hello\"))",
+            "(program 2.0.0 [(builtin sha2_256) (con data #d87980)])",
+        ];
+
+        let programs: Vec<Program<DeBruijn>> = sources
+            .iter()
+            .map(|source| parser::program(source).unwrap().try_into().unwrap())
+            .collect();
+
+        let bytes: Vec<u8> = programs
+            .iter()
+            .flat_map(|program| program.to_flat().unwrap())
+            .collect();
+
+        let mut all_at_once = Decoder::new(&bytes);
+
+        let mut stream = StreamDecoder::with_chunk_size(Trickle(&bytes), 1);
+
+        for program in programs {
+            let expected: Program<DeBruijn> = all_at_once.decode().unwrap();
+            all_at_once.decode::<Filler>().unwrap();
+
+            let actual: Program<DeBruijn> = stream.decode().unwrap();
+            stream.decode::<Filler>().unwrap();
+
+            assert_eq!(expected, program);
+            assert_eq!(actual, program);
+        }
+
+        assert!(stream.is_empty().unwrap());
+    }
+
+    #[test]
+    fn flat_decode_prefix() {
+        let first: Program<DeBruijn> = parser::program("(program 1.0.0 (lam x x))")
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let second: Program<DeBruijn> =
+            parser::program("(program 1.0.0 [(builtin addInteger) (con integer 42)])")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        let first_bytes = first.to_flat().unwrap();
+
+        let bytes = [first_bytes.clone(), second.to_flat().unwrap()].concat();
+
+        let (decoded, consumed) = Program::<DeBruijn>::from_flat_prefix(&bytes).unwrap();
+
+        assert_eq!(decoded, first);
+        assert_eq!(consumed, first_bytes.len());
+
+        let (decoded, consumed) =
+            Program::<DeBruijn>::from_flat_prefix(&bytes[consumed..]).unwrap();
+
+        assert_eq!(decoded, second);
+        assert_eq!(consumed, bytes.len() - first_bytes.len());
+    }
+
+    #[test]
+    fn cbor_envelopes() {
+        for source in [
+            "(program 1.0.0 (lam x x))",
+            "(program 1.0.0 (con bytestring #000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f))",
+        ] {
+            let program: Program<DeBruijn> = parser::program(source).unwrap().try_into().unwrap();
+
+            let single = program.to_cbor_single().unwrap();
+            let double = program.to_cbor_double().unwrap();
+
+            assert_eq!(single, program.to_cbor().unwrap());
+            assert_eq!(Program::from_cbor_single(&single).unwrap(), program);
+            assert_eq!(Program::from_cbor_double(&double).unwrap(), program);
+
+            // The outer envelope only adds a CBOR bytestring header: one byte for up to 23
+            // bytes, two bytes for up to 255 bytes.
+            let header = if single.len() < 24 { 1 } else { 2 };
+            assert_eq!(double.len(), single.len() + header);
+            assert_eq!(&double[header..], &single[..]);
+        }
+    }
+
+    proptest::proptest! {
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(2000))]
+
+        #[test]
+        fn flat_roundtrip(program: Program<DeBruijn>) {
+            let bytes = program.to_flat().unwrap();
+
+            proptest::prop_assert_eq!(Program::<DeBruijn>::from_flat(&bytes).unwrap(), program);
+        }
+    }
 }
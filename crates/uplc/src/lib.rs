@@ -1,3 +1,5 @@
+#[cfg(any(test, feature = "arbitrary"))]
+pub mod arbitrary;
 pub mod ast;
 pub mod builtins;
 mod debruijn;
@@ -6,6 +8,7 @@ pub mod machine;
 pub mod optimize;
 pub mod parser;
 mod pretty;
+pub mod prng;
 pub mod program_builder;
 pub mod tx;
 
@@ -15,6 +18,7 @@ pub use pallas_primitives::{
     alonzo::{BigInt, Constr, PlutusData},
     babbage::{PostAlonzoTransactionOutput, TransactionInput, TransactionOutput, Value},
 };
+pub use pretty::PrettyOptions;
 
 use pallas_primitives::{Error, Fragment};
 
@@ -1,5 +1,10 @@
 use num_traits::sign::Signed;
-use std::{collections::VecDeque, ops::Deref, rc::Rc};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    ops::Deref,
+    rc::Rc,
+    time::Instant,
+};
 
 use crate::{
     ast::{Constant, NamedDeBruijn, Term, Type},
@@ -17,23 +22,40 @@ use pallas_primitives::babbage::{self as pallas, Language, PlutusData};
 
 use self::{cost_model::CostModel, runtime::BuiltinRuntime};
 
+/// How many machine steps are accumulated before being charged to the budget, by default.
+/// A smaller slippage catches budget exhaustion closer to the step that caused it, at the cost
+/// of checking the budget more often; a slippage of 1 checks it after every step.
+pub const DEFAULT_SLIPPAGE: u32 = 200;
+
 enum MachineStep {
     Return(Rc<Context>, Rc<Value>),
     Compute(Rc<Context>, Rc<Vec<Rc<Value>>>, Rc<Term<NamedDeBruijn>>),
-    Done(Rc<Term<NamedDeBruijn>>),
+    Done(Rc<Value>),
 }
 
-impl TryFrom<Option<MachineStep>> for Term<NamedDeBruijn> {
-    type Error = Error;
+/// The value a term evaluated to, kept in the machine's own representation rather than
+/// discharged back to a term. When that value is a function, evaluation can carry on from
+/// there with [`Machine::resume`] once its argument is known, without evaluating the term
+/// again. A checkpoint can be resumed any number of times.
+#[derive(Clone, Debug)]
+pub struct Checkpoint(Rc<Value>);
 
-    fn try_from(value: Option<MachineStep>) -> Result<Self, Error> {
-        match value {
-            Some(MachineStep::Done(term)) => Ok(Rc::as_ref(&term).clone()),
-            _ => Err(Error::MachineNeverReachedDone),
-        }
+impl Checkpoint {
+    pub fn term(&self) -> Term<NamedDeBruijn> {
+        discharge_value(self.0.clone()).as_ref().clone()
     }
 }
 
+/// A variable bound in the environment the machine was in when it stopped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    /// DeBruijn index of the variable, starting from 1 for the innermost binding.
+    pub index: usize,
+    /// Name of the variable, when the term being computed refers to it.
+    pub name: Option<String>,
+    pub value: Term<NamedDeBruijn>,
+}
+
 #[derive(Clone)]
 enum PartialTerm {
     // tag: 0
@@ -61,16 +83,107 @@ enum DischargeStep {
     PopArgStack(PartialTerm),
 }
 
+/// Number of times each CEK rule fired during an evaluation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MachineStats {
+    pub constant: u64,
+    pub var: u64,
+    pub lambda: u64,
+    pub apply: u64,
+    pub delay: u64,
+    pub force: u64,
+    pub builtin: u64,
+}
+
+impl MachineStats {
+    fn record(&mut self, step: &StepKind) {
+        match step {
+            StepKind::Constant => self.constant += 1,
+            StepKind::Var => self.var += 1,
+            StepKind::Lambda => self.lambda += 1,
+            StepKind::Apply => self.apply += 1,
+            StepKind::Delay => self.delay += 1,
+            StepKind::Force => self.force += 1,
+            StepKind::Builtin => self.builtin += 1,
+            StepKind::StartUp => {}
+        }
+    }
+
+    /// Total number of steps taken by the machine.
+    pub fn total(&self) -> u64 {
+        self.constant + self.var + self.lambda + self.apply + self.delay + self.force + self.builtin
+    }
+}
+
+/// Budget spent during an evaluation, split between the machine's own steps and each
+/// builtin, so that one can tell what a program's cost goes to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    /// Spent on machine steps, start-up included.
+    pub machine: ExBudget,
+    pub builtins: HashMap<DefaultFunction, ExBudget>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Profile {
+            machine: ExBudget { mem: 0, cpu: 0 },
+            builtins: HashMap::new(),
+        }
+    }
+}
+
+impl Profile {
+    /// Total budget spent, that is, what the machine's budget went down by.
+    pub fn total(&self) -> ExBudget {
+        self.builtins
+            .values()
+            .fold(self.machine, |total, spent| total + *spent)
+    }
+}
+
+/// How trace messages are recorded in the machine's logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// The message, as emitted by the program.
+    Raw,
+    /// The message, prefixed with the index of the step at which it was emitted.
+    StepIndex,
+}
+
+impl Default for TraceFormat {
+    fn default() -> Self {
+        TraceFormat::Raw
+    }
+}
+
 pub struct Machine {
     costs: CostModel,
     pub ex_budget: ExBudget,
     slippage: u32,
     unbudgeted_steps: [u32; 8],
+    pub stats: MachineStats,
     pub logs: Vec<String>,
+    pub trace_format: TraceFormat,
+    /// How many steps the machine may take before giving up with
+    /// [`Error::StepLimitReached`], regardless of the budget left.
+    pub max_steps: Option<u64>,
+    /// When the machine gives up with [`Error::DeadlineReached`], however far along it is.
+    /// Only checked when steps are charged to the budget, that is, every slippage steps.
+    pub deadline: Option<Instant>,
+    /// Where spent budget is broken down, if profiling. Nothing is recorded otherwise.
+    pub profile: Option<Profile>,
+    /// Builtins applied to all their arguments so far, if recording. Nothing is recorded
+    /// otherwise.
+    pub builtins_used: Option<HashSet<DefaultFunction>>,
     stack: Vec<MachineStep>,
     version: Language,
+    environment: Option<ComputeEnv>,
 }
 
+/// The environment of a computed term, along with that term.
+type ComputeEnv = (Rc<Vec<Rc<Value>>>, Rc<Term<NamedDeBruijn>>);
+
 impl Machine {
     pub fn new(
         version: Language,
@@ -83,32 +196,151 @@ impl Machine {
             ex_budget: initial_budget,
             slippage,
             unbudgeted_steps: [0; 8],
+            stats: MachineStats::default(),
             logs: vec![],
+            trace_format: TraceFormat::default(),
+            max_steps: None,
+            deadline: None,
+            profile: None,
+            builtins_used: None,
             stack: vec![],
             version,
+            environment: None,
         }
     }
 
     pub fn run(&mut self, term: &Term<NamedDeBruijn>) -> Result<Term<NamedDeBruijn>, Error> {
-        use MachineStep::*;
+        self.checkpoint(term).map(|checkpoint| checkpoint.term())
+    }
+
+    /// Like `run`, but also returns the environment of the last term computed before the
+    /// machine stopped, whether it reached a value or failed. Bindings are ordered from the
+    /// innermost to the outermost one.
+    pub fn run_with_environment(
+        &mut self,
+        term: &Term<NamedDeBruijn>,
+    ) -> (Result<Term<NamedDeBruijn>, Error>, Vec<Binding>) {
+        self.environment = Some((Rc::new(vec![]), Rc::new(term.clone())));
+
+        let result = self.run(term);
+
+        let bindings = match self.environment.take() {
+            Some((env, term)) => {
+                let mut names = BTreeMap::new();
+
+                collect_names(&term, 0, &mut names);
+
+                env.iter()
+                    .rev()
+                    .enumerate()
+                    .map(|(ix, value)| Binding {
+                        index: ix + 1,
+                        name: names.remove(&(ix + 1)),
+                        value: discharge_value(value.clone()).as_ref().clone(),
+                    })
+                    .collect()
+            }
+            None => vec![],
+        };
+
+        (result, bindings)
+    }
+
+    /// Like `run`, but calls `hook` before each step computing a term, and before each
+    /// application of a builtin to all of its arguments, with the term at hand and the budget
+    /// left. Machine steps are only charged every so often (see the machine's slippage), so
+    /// the budget left may lag a few steps behind; builtin costs are charged right after the
+    /// hook is called.
+    pub fn run_with_hook<F>(
+        &mut self,
+        term: &Term<NamedDeBruijn>,
+        mut hook: F,
+    ) -> Result<Term<NamedDeBruijn>, Error>
+    where
+        F: FnMut(&Term<NamedDeBruijn>, &ExBudget),
+    {
+        self.checkpoint_with_hook(term, &mut hook)
+            .map(|checkpoint| checkpoint.term())
+    }
+
+    /// Like `run`, but keeps the result as a [`Checkpoint`].
+    pub fn checkpoint(&mut self, term: &Term<NamedDeBruijn>) -> Result<Checkpoint, Error> {
+        self.checkpoint_with_hook(term, &mut |_, _| {})
+    }
 
+    fn checkpoint_with_hook<F>(
+        &mut self,
+        term: &Term<NamedDeBruijn>,
+        hook: &mut F,
+    ) -> Result<Checkpoint, Error>
+    where
+        F: FnMut(&Term<NamedDeBruijn>, &ExBudget),
+    {
         let startup_budget = self.costs.machine_costs.get(StepKind::StartUp);
 
+        if let Some(profile) = &mut self.profile {
+            profile.machine = profile.machine + startup_budget;
+        }
+
         self.spend_budget(startup_budget)?;
 
-        self.stack.push(Compute(
-            Rc::new(Context::NoFrame),
-            Rc::new(vec![]),
-            Rc::new(term.clone()),
-        ));
+        self.evaluate(
+            MachineStep::Compute(
+                Rc::new(Context::NoFrame),
+                Rc::new(vec![]),
+                Rc::new(term.clone()),
+            ),
+            hook,
+        )
+    }
+
+    /// Apply the value of a checkpoint to a (closed) argument and carry on evaluating. This
+    /// costs the same as if the application had been part of the term evaluated in the first
+    /// place, so a checkpoint should be resumed by the machine which produced it for the
+    /// budget and logs to add up.
+    pub fn resume(
+        &mut self,
+        checkpoint: &Checkpoint,
+        argument: &Term<NamedDeBruijn>,
+    ) -> Result<Checkpoint, Error> {
+        self.step_and_maybe_spend(StepKind::Apply)?;
+
+        self.evaluate(
+            MachineStep::Compute(
+                Rc::new(Context::FrameApplyFun(
+                    checkpoint.0.clone(),
+                    Rc::new(Context::NoFrame),
+                )),
+                Rc::new(vec![]),
+                Rc::new(argument.clone()),
+            ),
+            &mut |_, _| {},
+        )
+    }
+
+    fn evaluate<F>(&mut self, start: MachineStep, hook: &mut F) -> Result<Checkpoint, Error>
+    where
+        F: FnMut(&Term<NamedDeBruijn>, &ExBudget),
+    {
+        use MachineStep::*;
+
+        self.stack.clear();
+
+        self.stack.push(start);
 
         while let Some(step) = self.stack.pop() {
             match step {
                 Compute(context, env, t) => {
+                    if self.environment.is_some() {
+                        self.environment = Some((env.clone(), t.clone()));
+                    }
+
+                    hook(&t, &self.ex_budget);
+
                     self.compute(context, env, t)?;
                 }
                 Return(context, value) => {
-                    self.return_compute(context, value)?;
+                    self.return_compute(context, value, hook)?;
                 }
                 d @ Done(_) => {
                     self.stack.push(d);
@@ -118,7 +350,10 @@ impl Machine {
             };
         }
 
-        self.stack.pop().try_into()
+        match self.stack.pop() {
+            Some(Done(value)) => Ok(Checkpoint(value)),
+            _ => Err(Error::MachineNeverReachedDone),
+        }
     }
 
     fn compute(
@@ -189,6 +424,10 @@ impl Machine {
             }
             Term::Error => return Err(Error::EvaluationFailure),
             Term::Builtin(fun) => {
+                if !fun.is_supported(&self.version) {
+                    return Err(Error::UnsupportedBuiltin(*fun, self.version.clone()));
+                }
+
                 self.step_and_maybe_spend(StepKind::Builtin)?;
 
                 let runtime: BuiltinRuntime = (*fun).into();
@@ -208,10 +447,18 @@ impl Machine {
         Ok(())
     }
 
-    fn return_compute(&mut self, context: Rc<Context>, value: Rc<Value>) -> Result<(), Error> {
+    fn return_compute<F>(
+        &mut self,
+        context: Rc<Context>,
+        value: Rc<Value>,
+        hook: &mut F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(&Term<NamedDeBruijn>, &ExBudget),
+    {
         match context.as_ref() {
             Context::FrameApplyFun(function, ctx) => {
-                self.apply_evaluate(ctx.to_owned(), function.clone(), value)?
+                self.apply_evaluate(ctx.to_owned(), function.clone(), value, hook)?
             }
             Context::FrameApplyArg(arg_var_env, arg, ctx) => {
                 self.stack.push(MachineStep::Compute(
@@ -220,22 +467,28 @@ impl Machine {
                     Rc::clone(arg),
                 ));
             }
-            Context::FrameForce(ctx) => self.force_evaluate(ctx.to_owned(), value)?,
+            Context::FrameForce(ctx) => self.force_evaluate(ctx.to_owned(), value, hook)?,
             Context::NoFrame => {
                 if self.unbudgeted_steps[7] > 0 {
                     self.spend_unbudgeted_steps()?;
                 }
 
-                let term = discharge_value(value);
-
-                self.stack.push(MachineStep::Done(term));
+                self.stack.push(MachineStep::Done(value));
             }
         };
 
         Ok(())
     }
 
-    fn force_evaluate(&mut self, context: Rc<Context>, mut value: Rc<Value>) -> Result<(), Error> {
+    fn force_evaluate<F>(
+        &mut self,
+        context: Rc<Context>,
+        mut value: Rc<Value>,
+        hook: &mut F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(&Term<NamedDeBruijn>, &ExBudget),
+    {
         let value = Rc::make_mut(&mut value);
 
         match value {
@@ -253,7 +506,7 @@ impl Machine {
                 if mut_runtime.needs_force() {
                     mut_runtime.consume_force();
 
-                    let res = self.eval_builtin_app(*fun, force_term, runtime.clone())?;
+                    let res = self.eval_builtin_app(*fun, force_term, runtime.clone(), hook)?;
 
                     self.stack.push(MachineStep::Return(context, res));
 
@@ -268,12 +521,16 @@ impl Machine {
         }
     }
 
-    fn apply_evaluate(
+    fn apply_evaluate<F>(
         &mut self,
         context: Rc<Context>,
         mut function: Rc<Value>,
         argument: Rc<Value>,
-    ) -> Result<(), Error> {
+        hook: &mut F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(&Term<NamedDeBruijn>, &ExBudget),
+    {
         let function = Rc::make_mut(&mut function);
 
         match function {
@@ -303,7 +560,7 @@ impl Machine {
                 if mut_runtime.is_arrow() && !mut_runtime.needs_force() {
                     mut_runtime.push(argument)?;
 
-                    let res = self.eval_builtin_app(*fun, t, runtime.to_owned())?;
+                    let res = self.eval_builtin_app(*fun, t, runtime.to_owned(), hook)?;
 
                     self.stack.push(MachineStep::Return(context, res));
 
@@ -319,20 +576,51 @@ impl Machine {
         }
     }
 
-    fn eval_builtin_app(
+    fn eval_builtin_app<F>(
         &mut self,
         fun: DefaultFunction,
         term: Rc<Term<NamedDeBruijn>>,
         runtime: Rc<BuiltinRuntime>,
-    ) -> Result<Rc<Value>, Error> {
+        hook: &mut F,
+    ) -> Result<Rc<Value>, Error>
+    where
+        F: FnMut(&Term<NamedDeBruijn>, &ExBudget),
+    {
         if runtime.is_ready() {
+            hook(&term, &self.ex_budget);
+
             let cost = match self.version {
                 Language::PlutusV1 => runtime.to_ex_budget_v1(&self.costs.builtin_costs),
                 Language::PlutusV2 => runtime.to_ex_budget_v2(&self.costs.builtin_costs),
             };
+            if let Some(profile) = &mut self.profile {
+                let spent = profile
+                    .builtins
+                    .entry(fun)
+                    .or_insert(ExBudget { mem: 0, cpu: 0 });
+
+                *spent = *spent + cost;
+            }
+
+            if let Some(builtins_used) = &mut self.builtins_used {
+                builtins_used.insert(fun);
+            }
+
             self.spend_budget(cost)?;
 
-            runtime.call(&mut self.logs)
+            let traced = self.logs.len();
+
+            let result = runtime.call(&mut self.logs);
+
+            if self.trace_format == TraceFormat::StepIndex {
+                let step = self.stats.total();
+
+                for log in self.logs.iter_mut().skip(traced) {
+                    *log = format!("[{step}] {log}");
+                }
+            }
+
+            result
         } else {
             Ok(Value::Builtin { fun, term, runtime }.into())
         }
@@ -345,12 +633,26 @@ impl Machine {
     }
 
     fn step_and_maybe_spend(&mut self, step: StepKind) -> Result<(), Error> {
+        self.stats.record(&step);
+
+        if let Some(max_steps) = self.max_steps {
+            if self.stats.total() > max_steps {
+                return Err(Error::StepLimitReached(max_steps));
+            }
+        }
+
         let index = step as u8;
         self.unbudgeted_steps[index as usize] += 1;
         self.unbudgeted_steps[7] += 1;
 
         if self.unbudgeted_steps[7] >= self.slippage {
             self.spend_unbudgeted_steps()?;
+
+            if let Some(deadline) = self.deadline {
+                if Instant::now() >= deadline {
+                    return Err(Error::DeadlineReached);
+                }
+            }
         }
 
         Ok(())
@@ -363,6 +665,10 @@ impl Machine {
 
             unspent_step_budget.occurences(self.unbudgeted_steps[i] as i64);
 
+            if let Some(profile) = &mut self.profile {
+                profile.machine = profile.machine + unspent_step_budget;
+            }
+
             self.spend_budget(unspent_step_budget)?;
 
             self.unbudgeted_steps[i] = 0;
@@ -385,6 +691,29 @@ impl Machine {
     }
 }
 
+/// Names of the variables a term refers to, keyed by their index in the environment the
+/// term is computed in.
+fn collect_names(term: &Term<NamedDeBruijn>, depth: usize, names: &mut BTreeMap<usize, String>) {
+    match term {
+        Term::Var(name) => {
+            let index = usize::from(name.index);
+
+            if index > depth {
+                names
+                    .entry(index - depth)
+                    .or_insert_with(|| name.text.clone());
+            }
+        }
+        Term::Delay(body) | Term::Force(body) => collect_names(body, depth, names),
+        Term::Lambda { body, .. } => collect_names(body, depth + 1, names),
+        Term::Apply { function, argument } => {
+            collect_names(function, depth, names);
+            collect_names(argument, depth, names);
+        }
+        Term::Constant(_) | Term::Error | Term::Builtin(_) => {}
+    }
+}
+
 fn discharge_value(value: Rc<Value>) -> Rc<Term<NamedDeBruijn>> {
     let mut stack = vec![DischargeStep::DischargeValue(value)];
     let mut arg_stack = vec![];
@@ -764,12 +1093,19 @@ impl From<&Constant> for Type {
 #[cfg(test)]
 mod tests {
     use num_bigint::BigInt;
+    use pallas_primitives::babbage::{self as pallas, Language, PlutusData};
 
-    use super::{cost_model::ExBudget, integer_log2, Value};
+    use super::{
+        cost_model::{CostModel, ExBudget},
+        integer_log2,
+        runtime::BuiltinRuntime,
+        Binding, Error, Machine, MachineStats, Profile, TraceFormat, Value, DEFAULT_SLIPPAGE,
+    };
     use crate::{
-        ast::{Constant, NamedDeBruijn, Program, Term},
+        ast::{Constant, DeBruijn, NamedDeBruijn, Program, Term},
         builtins::DefaultFunction,
     };
+    use std::collections::HashSet;
 
     #[test]
     fn add_big_ints() {
@@ -800,6 +1136,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn builtin_type_mismatch() {
+        let program: Program<NamedDeBruijn> = Program {
+            version: (1, 0, 0),
+            term: Term::Apply {
+                function: Term::Builtin(DefaultFunction::UnListData).into(),
+                argument: Term::Constant(
+                    Constant::Data(PlutusData::BigInt(pallas::BigInt::Int(42.into()))).into(),
+                )
+                .into(),
+            },
+        };
+
+        let (eval_result, _, _) = program.eval(ExBudget::default());
+
+        let error = eval_result.unwrap_err();
+
+        assert!(matches!(
+            &error,
+            Error::BuiltinTypeMismatch {
+                builtin: DefaultFunction::UnListData,
+                expected: "a list",
+                ..
+            }
+        ));
+        assert_eq!(error.to_string(), "unListData expected a list, but got: 42");
+    }
+
     #[test]
     fn divide_integer() {
         let make_program = |fun: DefaultFunction, n: i32, m: i32| Program::<NamedDeBruijn> {
@@ -843,6 +1207,360 @@ mod tests {
         }
     }
 
+    #[test]
+    fn eval_with_stats() {
+        let program: Program<NamedDeBruijn> = Program {
+            version: (0, 0, 0),
+            term: Term::Apply {
+                function: Term::Lambda {
+                    parameter_name: NamedDeBruijn {
+                        text: "x".to_string(),
+                        index: 0.into(),
+                    }
+                    .into(),
+                    body: Term::Force(
+                        Term::Delay(
+                            Term::Var(
+                                NamedDeBruijn {
+                                    text: "x".to_string(),
+                                    index: 1.into(),
+                                }
+                                .into(),
+                            )
+                            .into(),
+                        )
+                        .into(),
+                    )
+                    .into(),
+                }
+                .into(),
+                argument: Term::Constant(Constant::Integer(42.into()).into()).into(),
+            },
+        };
+
+        let (eval_result, _, _, stats) = program.eval_with_stats(ExBudget::default());
+
+        assert_eq!(
+            eval_result.unwrap(),
+            Term::Constant(Constant::Integer(42.into()).into())
+        );
+
+        assert_eq!(
+            stats,
+            MachineStats {
+                constant: 1,
+                var: 1,
+                lambda: 1,
+                apply: 1,
+                delay: 1,
+                force: 1,
+                builtin: 0,
+            }
+        );
+
+        assert_eq!(stats.total(), 6);
+    }
+
+    #[test]
+    fn run_with_hook() {
+        let program: Program<NamedDeBruijn> = crate::parser::program(
+            r#"
+            (program 1.0.0
+              [ (lam x [ (builtin addInteger) x (con integer 2) ]) (con integer 1) ]
+            )
+            "#,
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+        let mut machine = Machine::new(
+            Language::PlutusV2,
+            CostModel::default(),
+            ExBudget::default(),
+            1,
+        );
+
+        let mut steps = vec![];
+
+        let result = machine.run_with_hook(&program.term, |term, budget| {
+            steps.push((term.clone(), *budget));
+        });
+
+        assert_eq!(
+            result.unwrap(),
+            Term::Constant(Constant::Integer(3.into()).into())
+        );
+
+        // Every computed term, plus the one application of `addInteger`.
+        assert_eq!(steps.len() as u64, machine.stats.total() + 1);
+        assert_eq!(
+            steps
+                .iter()
+                .filter(|(term, _)| matches!(term, Term::Apply { .. }))
+                .count(),
+            4
+        );
+
+        // With a slippage of 1, the budget left never increases.
+        assert!(steps
+            .windows(2)
+            .all(|w| w[1].1.cpu <= w[0].1.cpu && w[1].1.mem <= w[0].1.mem));
+        // Only the startup cost is charged before the first step.
+        assert!(steps[0].1.cpu < ExBudget::default().cpu);
+    }
+
+    #[test]
+    fn eval_with_environment() {
+        let program: Program<NamedDeBruijn> = crate::parser::program(
+            r#"
+            (program 1.0.0
+              [ (lam x [ (lam y (lam z x)) (con integer 2) ]) (con integer 1) ]
+            )
+            "#,
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+        let (result, _, _, bindings) = program.eval_with_environment(ExBudget::default());
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            bindings,
+            vec![
+                Binding {
+                    index: 1,
+                    name: None,
+                    value: Term::Constant(Constant::Integer(2.into()).into()),
+                },
+                Binding {
+                    index: 2,
+                    name: Some("x".to_string()),
+                    value: Term::Constant(Constant::Integer(1.into()).into()),
+                },
+            ]
+        );
+
+        let program: Program<DeBruijn> = program.into();
+
+        let (_, _, _, bindings) = program.eval_with_environment(ExBudget::default());
+
+        assert!(bindings.iter().all(|binding| binding.name.is_none()));
+        assert_eq!(bindings.len(), 2);
+    }
+
+    #[test]
+    fn eval_with_step_limit() {
+        // Loops forever, while each step costs little.
+        let program: Program<NamedDeBruijn> =
+            crate::parser::program("(program 1.0.0 [ (lam x [ x x ]) (lam x [ x x ]) ])")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        let (result, remaining, _) = program.eval_with_limits(ExBudget::default(), Some(1000));
+
+        assert!(matches!(result, Err(Error::StepLimitReached(1000))));
+        assert!(remaining.cpu > 0 && remaining.mem > 0);
+
+        let program: Program<NamedDeBruijn> =
+            crate::parser::program("(program 1.0.0 [ (lam x x) (con integer 42) ])")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        let (result, _, _) = program.eval_with_limits(ExBudget::default(), Some(4));
+
+        assert_eq!(
+            result.unwrap(),
+            Term::Constant(Constant::Integer(42.into()).into())
+        );
+
+        let (result, _, _) = program.eval_with_limits(ExBudget::default(), Some(3));
+
+        assert!(matches!(result, Err(Error::StepLimitReached(3))));
+    }
+
+    #[test]
+    fn eval_with_deadline() {
+        // Loops forever, while each step costs little.
+        let program: Program<NamedDeBruijn> =
+            crate::parser::program("(program 1.0.0 [ (lam x [ x x ]) (lam x [ x x ]) ])")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_millis(50);
+
+        let budget = ExBudget {
+            mem: i64::MAX,
+            cpu: i64::MAX,
+        };
+
+        let (result, remaining, _) = program.eval_with_deadline(budget, deadline);
+
+        assert!(matches!(result, Err(Error::DeadlineReached)));
+        assert!(remaining.cpu > 0 && remaining.mem > 0);
+    }
+
+    #[test]
+    fn eval_profiled() {
+        let program: Program<NamedDeBruijn> = crate::parser::program(
+            r#"
+            (program 1.0.0
+              [ (lam x
+                  [ (builtin multiplyInteger)
+                    [ (builtin addInteger) x (con integer 2) ]
+                    [ (builtin addInteger) x (con integer 3) ]
+                  ]
+                )
+                (con integer 1)
+              ]
+            )
+            "#,
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+        let (result, remaining, _, profile) = program.eval_profiled(ExBudget::default());
+
+        assert_eq!(
+            result.unwrap(),
+            Term::Constant(Constant::Integer(12.into()).into())
+        );
+
+        assert_eq!(profile.total(), ExBudget::default() - remaining);
+
+        let mut builtins: Vec<_> = profile.builtins.keys().collect();
+        builtins.sort_by_key(|fun| **fun as u8);
+
+        assert_eq!(
+            builtins,
+            vec![
+                &DefaultFunction::AddInteger,
+                &DefaultFunction::MultiplyInteger
+            ]
+        );
+        assert!(profile.builtins[&DefaultFunction::AddInteger].cpu > 0);
+        assert!(profile.machine.cpu > 0);
+
+        // Nothing is recorded unless asked for.
+        let mut machine = Machine::new(
+            Language::PlutusV2,
+            CostModel::default(),
+            ExBudget::default(),
+            DEFAULT_SLIPPAGE,
+        );
+
+        machine.run(&program.term).unwrap();
+
+        assert_eq!(machine.profile, None);
+
+        // Budget spent before running out is accounted for too.
+        let budget = ExBudget {
+            mem: 1000,
+            cpu: 1_000_000,
+        };
+
+        let (result, remaining, _, profile) = program.eval_profiled(budget);
+
+        assert!(matches!(result, Err(Error::OutOfExError(_))));
+        assert_eq!(profile.total(), budget - remaining);
+        assert_ne!(profile, Profile::default());
+    }
+
+    #[test]
+    fn eval_with_coverage() {
+        let program: Program<NamedDeBruijn> = crate::parser::program(
+            r#"
+            (program 1.0.0
+              [ (lam x [ (builtin addInteger) x [ (builtin addInteger) x (con integer 2) ] ])
+                (con integer 1)
+              ]
+            )
+            "#,
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+        let (result, _, _, builtins_used) = program.eval_with_coverage(ExBudget::default(), None);
+
+        assert_eq!(
+            result.unwrap(),
+            Term::Constant(Constant::Integer(4.into()).into())
+        );
+        assert_eq!(builtins_used, HashSet::from([DefaultFunction::AddInteger]));
+
+        // A builtin only partially applied isn't counted.
+        let partial: Program<NamedDeBruijn> =
+            crate::parser::program("(program 1.0.0 [ (builtin subtractInteger) (con integer 1) ])")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        assert!(partial
+            .eval_with_coverage(ExBudget::default(), None)
+            .3
+            .is_empty());
+    }
+
+    #[test]
+    fn eval_with_environment_on_failure() {
+        let program: Program<NamedDeBruijn> = crate::parser::program(
+            r#"
+            (program 1.0.0
+              [ (lam x [ (lam y [ y x ]) (lam z (error)) ]) (con integer 1) ]
+            )
+            "#,
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+        let (result, _, _, bindings) = program.eval_with_environment(ExBudget::default());
+
+        assert!(matches!(result, Err(Error::EvaluationFailure)));
+
+        assert_eq!(
+            bindings.iter().map(|b| b.index).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+
+        assert_eq!(
+            bindings[0].value,
+            Term::Constant(Constant::Integer(1.into()).into())
+        );
+    }
+
+    #[test]
+    fn trace_format_step_index() {
+        let program: Program<NamedDeBruijn> = crate::parser::program(
+            r#"
+            (program 1.0.0
+              [ (lam x [ (force (builtin trace)) (con string "bar") x ])
+                [ (force (builtin trace)) (con string "foo") (con unit ()) ]
+              ]
+            )
+            "#,
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+        let (_, _, logs) = program.eval(ExBudget::default());
+
+        assert_eq!(logs, vec!["foo", "bar"]);
+
+        let (_, _, logs) =
+            program.eval_with_trace_format(ExBudget::default(), TraceFormat::StepIndex);
+
+        assert_eq!(logs, vec!["[8] foo", "[14] bar"]);
+    }
+
     #[test]
     fn to_ex_mem_bigint() {
         let value = Value::Con(Constant::Integer(1.into()).into());
@@ -990,4 +1708,87 @@ mod tests {
             279
         );
     }
+
+    fn hash(builtin: DefaultFunction, message: &[u8]) -> Vec<u8> {
+        let value = Value::Con(Constant::ByteString(message.to_vec()).into());
+
+        match builtin.call(&[value.into()], &mut vec![]).unwrap().as_ref() {
+            Value::Con(constant) => match constant.as_ref() {
+                Constant::ByteString(digest) => digest.clone(),
+                constant => panic!("{builtin} returned {constant:?}"),
+            },
+            _ => panic!("{builtin} didn't return a constant"),
+        }
+    }
+
+    #[test]
+    fn hash_test_vectors() {
+        let vectors = [
+            (
+                DefaultFunction::Keccak_256,
+                &b""[..],
+                "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            ),
+            (
+                DefaultFunction::Keccak_256,
+                b"abc",
+                "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+            ),
+            (
+                DefaultFunction::Blake2b_224,
+                b"",
+                "836cc68931c2e4e3e838602eca1902591d216837bafddfe6f0c8cb07",
+            ),
+            (
+                DefaultFunction::Blake2b_224,
+                b"abc",
+                "9bd237b02a29e43bdd6738afa5b53ff0eee178d6210b618e4511aec8",
+            ),
+        ];
+
+        for (builtin, message, digest) in vectors {
+            assert_eq!(
+                hash(builtin, message),
+                hex::decode(digest).unwrap(),
+                "{builtin}"
+            );
+        }
+    }
+
+    #[test]
+    fn hash_wrong_argument_type() {
+        for builtin in [DefaultFunction::Keccak_256, DefaultFunction::Blake2b_224] {
+            let mut runtime = BuiltinRuntime::new(builtin);
+
+            assert!(matches!(
+                runtime.push(Value::Con(Constant::Integer(42.into()).into()).into()),
+                Err(super::Error::TypeMismatch(..))
+            ));
+        }
+    }
+
+    #[test]
+    fn plutus_v3_builtins_are_rejected() {
+        for builtin in [DefaultFunction::Keccak_256, DefaultFunction::Blake2b_224] {
+            for version in [Language::PlutusV1, Language::PlutusV2] {
+                let mut machine = Machine::new(
+                    version.clone(),
+                    CostModel::default(),
+                    ExBudget::default(),
+                    200,
+                );
+
+                let term = Term::Apply {
+                    function: Term::Builtin(builtin).into(),
+                    argument: Term::Constant(Constant::ByteString(vec![]).into()).into(),
+                };
+
+                assert!(matches!(
+                    machine.run(&term),
+                    Err(super::Error::UnsupportedBuiltin(fun, language))
+                        if fun == builtin && language == version
+                ));
+            }
+        }
+    }
 }
@@ -1,24 +1,14 @@
 use std::{collections::HashMap, rc::Rc};
 
 use pallas_primitives::babbage::Language;
+use serde::{Deserialize, Serialize};
 
 use crate::builtins::DefaultFunction;
 
 use super::Value;
 
-macro_rules! hashmap {
-    // map-like
-    ($($k:expr => $v:expr),* $(,)?) => {{
-        core::convert::From::from([$(($k, $v),)*])
-    }};
-    // set-like
-    ($($v:expr),* $(,)?) => {{
-        core::convert::From::from([$($v,)*])
-    }};
-}
-
 /// Can be negative
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Serialize, Deserialize)]
 pub struct ExBudget {
     pub mem: i64,
     pub cpu: i64,
@@ -36,6 +26,24 @@ impl ExBudget {
             cpu: 10000000000,
         }
     }
+
+    /// The maximum budget of a single (Plutus V2) transaction on mainnet, as per the current
+    /// protocol parameters.
+    pub fn max_tx_v2() -> Self {
+        ExBudget {
+            mem: 14000000,
+            cpu: 10000000000,
+        }
+    }
+
+    /// Fractions of a limit (typically [`ExBudget::max_tx_v2`]) this budget represents, as
+    /// `(cpu, mem)`.
+    pub fn fraction_of(&self, limit: &ExBudget) -> (f64, f64) {
+        (
+            self.cpu as f64 / limit.cpu as f64,
+            self.mem as f64 / limit.mem as f64,
+        )
+    }
 }
 
 impl Default for ExBudget {
@@ -47,6 +55,72 @@ impl Default for ExBudget {
     }
 }
 
+/// Shows both units with thousands separators, e.g. `mem: 14,000,000, cpu: 10,000,000,000`.
+/// The alternate form (`{:#}`) scales them instead, e.g. `mem: 14M, cpu: 10G`.
+impl std::fmt::Display for ExBudget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let units = if f.alternate() { scaled } else { thousands };
+
+        write!(f, "mem: {}, cpu: {}", units(self.mem), units(self.cpu))
+    }
+}
+
+/// Write a number of units with a comma between each group of three digits.
+pub fn thousands(n: i64) -> String {
+    let digits = n.unsigned_abs().to_string();
+
+    let (head, tail) = digits.split_at(match digits.len() % 3 {
+        0 => 3,
+        r => r,
+    });
+
+    let mut grouped = if n < 0 {
+        format!("-{head}")
+    } else {
+        head.to_string()
+    };
+
+    for group in tail.as_bytes().chunks(3) {
+        grouped.push(',');
+        grouped.push_str(std::str::from_utf8(group).unwrap());
+    }
+
+    grouped
+}
+
+/// Write a number of units with at most two decimals and a `k`, `M` or `G` suffix, if large
+/// enough for one. The suffix is picked once rounded, so 999,999 is `1M` rather than `1000k`.
+pub fn scaled(n: i64) -> String {
+    if n.unsigned_abs() < 1_000 {
+        return n.to_string();
+    }
+
+    let rounded = |divisor: f64| format!("{:.2}", n.unsigned_abs() as f64 / divisor);
+
+    let (value, suffix) = [(1e9, "G"), (1e6, "M"), (1e3, "k")]
+        .iter()
+        .map(|(divisor, suffix)| (rounded(*divisor), *suffix))
+        .find(|(value, _)| value.parse::<f64>().unwrap() >= 1.0)
+        .expect("at least a thousand units");
+
+    format!(
+        "{}{}{suffix}",
+        if n < 0 { "-" } else { "" },
+        value.trim_end_matches('0').trim_end_matches('.')
+    )
+}
+
+impl std::ops::Add for ExBudget {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        ExBudget {
+            mem: self.mem + rhs.mem,
+            cpu: self.cpu + rhs.cpu,
+        }
+    }
+}
+
 impl std::ops::Sub for ExBudget {
     type Output = Self;
 
@@ -198,6 +272,8 @@ pub struct BuiltinCosts {
     pub sha2_256: CostingFun<OneArgument>,
     pub sha3_256: CostingFun<OneArgument>,
     pub blake2b_256: CostingFun<OneArgument>,
+    pub blake2b_224: CostingFun<OneArgument>,
+    pub keccak_256: CostingFun<OneArgument>,
     pub verify_ed25519_signature: CostingFun<ThreeArguments>,
     pub verify_ecdsa_secp256k1_signature: CostingFun<ThreeArguments>,
     pub verify_schnorr_secp256k1_signature: CostingFun<ThreeArguments>,
@@ -433,6 +509,20 @@ impl BuiltinCosts {
                     slope: 10475,
                 }),
             },
+            blake2b_224: CostingFun {
+                mem: OneArgument::ConstantCost(4),
+                cpu: OneArgument::LinearCost(LinearSize {
+                    intercept: 207616,
+                    slope: 8310,
+                }),
+            },
+            keccak_256: CostingFun {
+                mem: OneArgument::ConstantCost(4),
+                cpu: OneArgument::LinearCost(LinearSize {
+                    intercept: 2261318,
+                    slope: 64571,
+                }),
+            },
             verify_ed25519_signature: CostingFun {
                 mem: ThreeArguments::ConstantCost(10),
                 cpu: ThreeArguments::LinearInZ(LinearSize {
@@ -799,6 +889,20 @@ impl Default for BuiltinCosts {
                     slope: 10475,
                 }),
             },
+            blake2b_224: CostingFun {
+                mem: OneArgument::ConstantCost(4),
+                cpu: OneArgument::LinearCost(LinearSize {
+                    intercept: 207616,
+                    slope: 8310,
+                }),
+            },
+            keccak_256: CostingFun {
+                mem: OneArgument::ConstantCost(4),
+                cpu: OneArgument::LinearCost(LinearSize {
+                    intercept: 2261318,
+                    slope: 64571,
+                }),
+            },
             verify_ed25519_signature: CostingFun {
                 mem: ThreeArguments::ConstantCost(10),
                 cpu: ThreeArguments::LinearInZ(LinearSize {
@@ -1163,6 +1267,14 @@ impl BuiltinCosts {
                 mem: self.blake2b_256.mem.cost(args[0].to_ex_mem()),
                 cpu: self.blake2b_256.cpu.cost(args[0].to_ex_mem()),
             },
+            DefaultFunction::Blake2b_224 => ExBudget {
+                mem: self.blake2b_224.mem.cost(args[0].to_ex_mem()),
+                cpu: self.blake2b_224.cpu.cost(args[0].to_ex_mem()),
+            },
+            DefaultFunction::Keccak_256 => ExBudget {
+                mem: self.keccak_256.mem.cost(args[0].to_ex_mem()),
+                cpu: self.keccak_256.cpu.cost(args[0].to_ex_mem()),
+            },
             DefaultFunction::VerifyEd25519Signature => ExBudget {
                 mem: self.verify_ed25519_signature.mem.cost(
                     args[0].to_ex_mem(),
@@ -1590,6 +1702,14 @@ impl BuiltinCosts {
                 mem: self.blake2b_256.mem.cost(args[0].to_ex_mem()),
                 cpu: self.blake2b_256.cpu.cost(args[0].to_ex_mem()),
             },
+            DefaultFunction::Blake2b_224 => ExBudget {
+                mem: self.blake2b_224.mem.cost(args[0].to_ex_mem()),
+                cpu: self.blake2b_224.cpu.cost(args[0].to_ex_mem()),
+            },
+            DefaultFunction::Keccak_256 => ExBudget {
+                mem: self.keccak_256.mem.cost(args[0].to_ex_mem()),
+                cpu: self.keccak_256.cpu.cost(args[0].to_ex_mem()),
+            },
             DefaultFunction::VerifyEd25519Signature => ExBudget {
                 mem: self.verify_ed25519_signature.mem.cost(
                     args[0].to_ex_mem(),
@@ -1803,358 +1923,124 @@ impl BuiltinCosts {
     }
 }
 
-pub fn initialize_cost_model(version: &Language, costs: &[i64]) -> CostModel {
-    let cost_map: HashMap<&str, i64> = match version {
-        Language::PlutusV1 => {
-            hashmap! {
-                "add_integer-cpu-arguments-intercept" => costs[0],
-                "add_integer-cpu-arguments-slope" => costs[1],
-                "add_integer-mem-arguments-intercept" => costs[2],
-                "add_integer-mem-arguments-slope" => costs[3],
-                "append_byte_string-cpu-arguments-intercept" => costs[4],
-                "append_byte_string-cpu-arguments-slope" => costs[5],
-                "append_byte_string-mem-arguments-intercept" => costs[6],
-                "append_byte_string-mem-arguments-slope" => costs[7],
-                "append_string-cpu-arguments-intercept" => costs[8],
-                "append_string-cpu-arguments-slope" => costs[9],
-                "append_string-mem-arguments-intercept" => costs[10],
-                "append_string-mem-arguments-slope" => costs[11],
-                "b_data-cpu-arguments" => costs[12],
-                "b_data-mem-arguments" => costs[13],
-                "blake2b_256-cpu-arguments-intercept" => costs[14],
-                "blake2b_256-cpu-arguments-slope" => costs[15],
-                "blake2b_256-mem-arguments" => costs[16],
-                "cek_apply_cost-exBudgetCPU" => costs[17],
-                "cek_apply_cost-exBudgetmem" => costs[18],
-                "cek_builtin_cost-exBudgetCPU" => costs[19],
-                "cek_builtin_cost-exBudgetmem" => costs[20],
-                "cek_const_cost-exBudgetCPU" => costs[21],
-                "cek_const_cost-exBudgetmem" => costs[22],
-                "cek_delay_cost-exBudgetCPU" => costs[23],
-                "cek_delay_cost-exBudgetmem" => costs[24],
-                "cek_force_cost-exBudgetCPU" => costs[25],
-                "cek_force_cost-exBudgetmem" => costs[26],
-                "cek_lam_cost-exBudgetCPU" => costs[27],
-                "cek_lam_cost-exBudgetmem" => costs[28],
-                "cek_startup_cost-exBudgetCPU" => costs[29],
-                "cek_startup_cost-exBudgetmem" => costs[30],
-                "cek_var_cost-exBudgetCPU" => costs[31],
-                "cek_var_cost-exBudgetmem" => costs[32],
-                "choose_data-cpu-arguments" => costs[33],
-                "choose_data-mem-arguments" => costs[34],
-                "choose_list-cpu-arguments" => costs[35],
-                "choose_list-mem-arguments" => costs[36],
-                "choose_unit-cpu-arguments" => costs[37],
-                "choose_unit-mem-arguments" => costs[38],
-                "cons_byte_string-cpu-arguments-intercept" => costs[39],
-                "cons_byte_string-cpu-arguments-slope" => costs[40],
-                "cons_byte_string-mem-arguments-intercept" => costs[41],
-                "cons_byte_string-mem-arguments-slope" => costs[42],
-                "constr_data-cpu-arguments" => costs[43],
-                "constr_data-mem-arguments" => costs[44],
-                "decode_utf8-cpu-arguments-intercept" => costs[45],
-                "decode_utf8-cpu-arguments-slope" => costs[46],
-                "decode_utf8-mem-arguments-intercept" => costs[47],
-                "decode_utf8-mem-arguments-slope" => costs[48],
-                "divide_integer-cpu-arguments-constant" => costs[49],
-                "divide_integer-cpu-arguments-model-arguments-intercept" => costs[50],
-                "divide_integer-cpu-arguments-model-arguments-slope" => costs[51],
-                "divide_integer-mem-arguments-intercept" => costs[52],
-                "divide_integer-mem-arguments-minimum" => costs[53],
-                "divide_integer-mem-arguments-slope" => costs[54],
-                "encode_utf8-cpu-arguments-intercept" => costs[55],
-                "encode_utf8-cpu-arguments-slope" => costs[56],
-                "encode_utf8-mem-arguments-intercept" => costs[57],
-                "encode_utf8-mem-arguments-slope" => costs[58],
-                "equals_byte_string-cpu-arguments-constant" => costs[59],
-                "equals_byte_string-cpu-arguments-intercept" => costs[60],
-                "equals_byte_string-cpu-arguments-slope" => costs[61],
-                "equals_byte_string-mem-arguments" => costs[62],
-                "equals_data-cpu-arguments-intercept" => costs[63],
-                "equals_data-cpu-arguments-slope" => costs[64],
-                "equals_data-mem-arguments" => costs[65],
-                "equals_integer-cpu-arguments-intercept" => costs[66],
-                "equals_integer-cpu-arguments-slope" => costs[67],
-                "equals_integer-mem-arguments" => costs[68],
-                "equals_string-cpu-arguments-constant" => costs[69],
-                "equals_string-cpu-arguments-intercept" => costs[70],
-                "equals_string-cpu-arguments-slope" => costs[71],
-                "equals_string-mem-arguments" => costs[72],
-                "fst_pair-cpu-arguments" => costs[73],
-                "fst_pair-mem-arguments" => costs[74],
-                "head_list-cpu-arguments" => costs[75],
-                "head_list-mem-arguments" => costs[76],
-                "i_data-cpu-arguments" => costs[77],
-                "i_data-mem-arguments" => costs[78],
-                "if_then_else-cpu-arguments" => costs[79],
-                "if_then_else-mem-arguments" => costs[80],
-                "index_byte_string-cpu-arguments" => costs[81],
-                "index_byte_string-mem-arguments" => costs[82],
-                "length_of_byte_string-cpu-arguments" => costs[83],
-                "length_of_byte_string-mem-arguments" => costs[84],
-                "less_than_byte_string-cpu-arguments-intercept" => costs[85],
-                "less_than_byte_string-cpu-arguments-slope" => costs[86],
-                "less_than_byte_string-mem-arguments" => costs[87],
-                "less_than_equals_byte_string-cpu-arguments-intercept" => costs[88],
-                "less_than_equals_byte_string-cpu-arguments-slope" => costs[89],
-                "less_than_equals_byte_string-mem-arguments" => costs[90],
-                "less_than_equals_integer-cpu-arguments-intercept" => costs[91],
-                "less_than_equals_integer-cpu-arguments-slope" => costs[92],
-                "less_than_equals_integer-mem-arguments" => costs[93],
-                "less_than_integer-cpu-arguments-intercept" => costs[94],
-                "less_than_integer-cpu-arguments-slope" => costs[95],
-                "less_than_integer-mem-arguments" => costs[96],
-                "list_data-cpu-arguments" => costs[97],
-                "list_data-mem-arguments" => costs[98],
-                "map_data-cpu-arguments" => costs[99],
-                "map_data-mem-arguments" => costs[100],
-                "mk_cons-cpu-arguments" => costs[101],
-                "mk_cons-mem-arguments" => costs[102],
-                "mk_nil_data-cpu-arguments" => costs[103],
-                "mk_nil_data-mem-arguments" => costs[104],
-                "mk_nil_pair_data-cpu-arguments" => costs[105],
-                "mk_nil_pair_data-mem-arguments" => costs[106],
-                "mk_pair_data-cpu-arguments" => costs[107],
-                "mk_pair_data-mem-arguments" => costs[108],
-                "mod_integer-cpu-arguments-constant" => costs[109],
-                "mod_integer-cpu-arguments-model-arguments-intercept" => costs[110],
-                "mod_integer-cpu-arguments-model-arguments-slope" => costs[111],
-                "mod_integer-mem-arguments-intercept" => costs[112],
-                "mod_integer-mem-arguments-minimum" => costs[113],
-                "mod_integer-mem-arguments-slope" => costs[114],
-                "multiply_integer-cpu-arguments-intercept" => costs[115],
-                "multiply_integer-cpu-arguments-slope" => costs[116],
-                "multiply_integer-mem-arguments-intercept" => costs[117],
-                "multiply_integer-mem-arguments-slope" => costs[118],
-                "null_list-cpu-arguments" => costs[119],
-                "null_list-mem-arguments" => costs[120],
-                "quotient_integer-cpu-arguments-constant" => costs[121],
-                "quotient_integer-cpu-arguments-model-arguments-intercept" => costs[122],
-                "quotient_integer-cpu-arguments-model-arguments-slope" => costs[123],
-                "quotient_integer-mem-arguments-intercept" => costs[124],
-                "quotient_integer-mem-arguments-minimum" => costs[125],
-                "quotient_integer-mem-arguments-slope" => costs[126],
-                "remainder_integer-cpu-arguments-constant" => costs[127],
-                "remainder_integer-cpu-arguments-model-arguments-intercept" => costs[128],
-                "remainder_integer-cpu-arguments-model-arguments-slope" => costs[129],
-                "remainder_integer-mem-arguments-intercept" => costs[130],
-                "remainder_integer-mem-arguments-minimum" => costs[131],
-                "remainder_integer-mem-arguments-slope" => costs[132],
-                "sha2_256-cpu-arguments-intercept" => costs[133],
-                "sha2_256-cpu-arguments-slope" => costs[134],
-                "sha2_256-mem-arguments" => costs[135],
-                "sha3_256-cpu-arguments-intercept" => costs[136],
-                "sha3_256-cpu-arguments-slope" => costs[137],
-                "sha3_256-mem-arguments" => costs[138],
-                "slice_byte_string-cpu-arguments-intercept" => costs[139],
-                "slice_byte_string-cpu-arguments-slope" => costs[140],
-                "slice_byte_string-mem-arguments-intercept" => costs[141],
-                "slice_byte_string-mem-arguments-slope" => costs[142],
-                "snd_pair-cpu-arguments" => costs[143],
-                "snd_pair-mem-arguments" => costs[144],
-                "subtract_integer-cpu-arguments-intercept" => costs[145],
-                "subtract_integer-cpu-arguments-slope" => costs[146],
-                "subtract_integer-mem-arguments-intercept" => costs[147],
-                "subtract_integer-mem-arguments-slope" => costs[148],
-                "tail_list-cpu-arguments" => costs[149],
-                "tail_list-mem-arguments" => costs[150],
-                "trace-cpu-arguments" => costs[151],
-                "trace-mem-arguments" => costs[152],
-                "un_b_data-cpu-arguments" => costs[153],
-                "un_b_data-mem-arguments" => costs[154],
-                "un_constr_data-cpu-arguments" => costs[155],
-                "un_constr_data-mem-arguments" => costs[156],
-                "un_i_data-cpu-arguments" => costs[157],
-                "un_i_data-mem-arguments" => costs[158],
-                "un_list_data-cpu-arguments" => costs[159],
-                "un_list_data-mem-arguments" => costs[160],
-                "un_map_data-cpu-arguments" => costs[161],
-                "un_map_data-mem-arguments" => costs[162],
-                "verify_ed25519_signature-cpu-arguments-intercept" => costs[163],
-                "verify_ed25519_signature-cpu-arguments-slope" => costs[164],
-                "verify_ed25519_signature-mem-arguments" => costs[165]
-            }
-        }
-        Language::PlutusV2 => {
-            hashmap! {
-                "add_integer-cpu-arguments-intercept"=> costs[0],
-                "add_integer-cpu-arguments-slope"=> costs[1],
-                "add_integer-mem-arguments-intercept"=> costs[2],
-                "add_integer-mem-arguments-slope"=> costs[3],
-                "append_byte_string-cpu-arguments-intercept"=> costs[4],
-                "append_byte_string-cpu-arguments-slope"=> costs[5],
-                "append_byte_string-mem-arguments-intercept"=> costs[6],
-                "append_byte_string-mem-arguments-slope"=> costs[7],
-                "append_string-cpu-arguments-intercept"=> costs[8],
-                "append_string-cpu-arguments-slope"=> costs[9],
-                "append_string-mem-arguments-intercept"=> costs[10],
-                "append_string-mem-arguments-slope"=> costs[11],
-                "b_data-cpu-arguments"=> costs[12],
-                "b_data-mem-arguments"=> costs[13],
-                "blake2b_256-cpu-arguments-intercept"=> costs[14],
-                "blake2b_256-cpu-arguments-slope"=> costs[15],
-                "blake2b_256-mem-arguments"=> costs[16],
-                "cek_apply_cost-exBudgetCPU"=> costs[17],
-                "cek_apply_cost-exBudgetmem"=> costs[18],
-                "cek_builtin_cost-exBudgetCPU"=> costs[19],
-                "cek_builtin_cost-exBudgetmem"=> costs[20],
-                "cek_const_cost-exBudgetCPU"=> costs[21],
-                "cek_const_cost-exBudgetmem"=> costs[22],
-                "cek_delay_cost-exBudgetCPU"=> costs[23],
-                "cek_delay_cost-exBudgetmem"=> costs[24],
-                "cek_force_cost-exBudgetCPU"=> costs[25],
-                "cek_force_cost-exBudgetmem"=> costs[26],
-                "cek_lam_cost-exBudgetCPU"=> costs[27],
-                "cek_lam_cost-exBudgetmem"=> costs[28],
-                "cek_startup_cost-exBudgetCPU"=> costs[29],
-                "cek_startup_cost-exBudgetmem"=> costs[30],
-                "cek_var_cost-exBudgetCPU"=> costs[31],
-                "cek_var_cost-exBudgetmem"=> costs[32],
-                "choose_data-cpu-arguments"=> costs[33],
-                "choose_data-mem-arguments"=> costs[34],
-                "choose_list-cpu-arguments"=> costs[35],
-                "choose_list-mem-arguments"=> costs[36],
-                "choose_unit-cpu-arguments"=> costs[37],
-                "choose_unit-mem-arguments"=> costs[38],
-                "cons_byte_string-cpu-arguments-intercept"=> costs[39],
-                "cons_byte_string-cpu-arguments-slope"=> costs[40],
-                "cons_byte_string-mem-arguments-intercept"=> costs[41],
-                "cons_byte_string-mem-arguments-slope"=> costs[42],
-                "constr_data-cpu-arguments"=> costs[43],
-                "constr_data-mem-arguments"=> costs[44],
-                "decode_utf8-cpu-arguments-intercept"=> costs[45],
-                "decode_utf8-cpu-arguments-slope"=> costs[46],
-                "decode_utf8-mem-arguments-intercept"=> costs[47],
-                "decode_utf8-mem-arguments-slope"=> costs[48],
-                "divide_integer-cpu-arguments-constant"=> costs[49],
-                "divide_integer-cpu-arguments-model-arguments-intercept"=> costs[50],
-                "divide_integer-cpu-arguments-model-arguments-slope"=> costs[51],
-                "divide_integer-mem-arguments-intercept"=> costs[52],
-                "divide_integer-mem-arguments-minimum"=> costs[53],
-                "divide_integer-mem-arguments-slope"=> costs[54],
-                "encode_utf8-cpu-arguments-intercept"=> costs[55],
-                "encode_utf8-cpu-arguments-slope"=> costs[56],
-                "encode_utf8-mem-arguments-intercept"=> costs[57],
-                "encode_utf8-mem-arguments-slope"=> costs[58],
-                "equals_byte_string-cpu-arguments-constant"=> costs[59],
-                "equals_byte_string-cpu-arguments-intercept"=> costs[60],
-                "equals_byte_string-cpu-arguments-slope"=> costs[61],
-                "equals_byte_string-mem-arguments"=> costs[62],
-                "equals_data-cpu-arguments-intercept"=> costs[63],
-                "equals_data-cpu-arguments-slope"=> costs[64],
-                "equals_data-mem-arguments"=> costs[65],
-                "equals_integer-cpu-arguments-intercept"=> costs[66],
-                "equals_integer-cpu-arguments-slope"=> costs[67],
-                "equals_integer-mem-arguments"=> costs[68],
-                "equals_string-cpu-arguments-constant"=> costs[69],
-                "equals_string-cpu-arguments-intercept"=> costs[70],
-                "equals_string-cpu-arguments-slope"=> costs[71],
-                "equals_string-mem-arguments"=> costs[72],
-                "fst_pair-cpu-arguments"=> costs[73],
-                "fst_pair-mem-arguments"=> costs[74],
-                "head_list-cpu-arguments"=> costs[75],
-                "head_list-mem-arguments"=> costs[76],
-                "i_data-cpu-arguments"=> costs[77],
-                "i_data-mem-arguments"=> costs[78],
-                "if_then_else-cpu-arguments"=> costs[79],
-                "if_then_else-mem-arguments"=> costs[80],
-                "index_byte_string-cpu-arguments"=> costs[81],
-                "index_byte_string-mem-arguments"=> costs[82],
-                "length_of_byte_string-cpu-arguments"=> costs[83],
-                "length_of_byte_string-mem-arguments"=> costs[84],
-                "less_than_byte_string-cpu-arguments-intercept"=> costs[85],
-                "less_than_byte_string-cpu-arguments-slope"=> costs[86],
-                "less_than_byte_string-mem-arguments"=> costs[87],
-                "less_than_equals_byte_string-cpu-arguments-intercept"=> costs[88],
-                "less_than_equals_byte_string-cpu-arguments-slope"=> costs[89],
-                "less_than_equals_byte_string-mem-arguments"=> costs[90],
-                "less_than_equals_integer-cpu-arguments-intercept"=> costs[91],
-                "less_than_equals_integer-cpu-arguments-slope"=> costs[92],
-                "less_than_equals_integer-mem-arguments"=> costs[93],
-                "less_than_integer-cpu-arguments-intercept"=> costs[94],
-                "less_than_integer-cpu-arguments-slope"=> costs[95],
-                "less_than_integer-mem-arguments"=> costs[96],
-                "list_data-cpu-arguments"=> costs[97],
-                "list_data-mem-arguments"=> costs[98],
-                "map_data-cpu-arguments"=> costs[99],
-                "map_data-mem-arguments"=> costs[100],
-                "mk_cons-cpu-arguments"=> costs[101],
-                "mk_cons-mem-arguments"=> costs[102],
-                "mk_nil_data-cpu-arguments"=> costs[103],
-                "mk_nil_data-mem-arguments"=> costs[104],
-                "mk_nil_pair_data-cpu-arguments"=> costs[105],
-                "mk_nil_pair_data-mem-arguments"=> costs[106],
-                "mk_pair_data-cpu-arguments"=> costs[107],
-                "mk_pair_data-mem-arguments"=> costs[108],
-                "mod_integer-cpu-arguments-constant"=> costs[109],
-                "mod_integer-cpu-arguments-model-arguments-intercept"=> costs[110],
-                "mod_integer-cpu-arguments-model-arguments-slope"=> costs[111],
-                "mod_integer-mem-arguments-intercept"=> costs[112],
-                "mod_integer-mem-arguments-minimum"=> costs[113],
-                "mod_integer-mem-arguments-slope"=> costs[114],
-                "multiply_integer-cpu-arguments-intercept"=> costs[115],
-                "multiply_integer-cpu-arguments-slope"=> costs[116],
-                "multiply_integer-mem-arguments-intercept"=> costs[117],
-                "multiply_integer-mem-arguments-slope"=> costs[118],
-                "null_list-cpu-arguments"=> costs[119],
-                "null_list-mem-arguments"=> costs[120],
-                "quotient_integer-cpu-arguments-constant"=> costs[121],
-                "quotient_integer-cpu-arguments-model-arguments-intercept"=> costs[122],
-                "quotient_integer-cpu-arguments-model-arguments-slope"=> costs[123],
-                "quotient_integer-mem-arguments-intercept"=> costs[124],
-                "quotient_integer-mem-arguments-minimum"=> costs[125],
-                "quotient_integer-mem-arguments-slope"=> costs[126],
-                "remainder_integer-cpu-arguments-constant"=> costs[127],
-                "remainder_integer-cpu-arguments-model-arguments-intercept"=> costs[128],
-                "remainder_integer-cpu-arguments-model-arguments-slope"=> costs[129],
-                "remainder_integer-mem-arguments-intercept"=> costs[130],
-                "remainder_integer-mem-arguments-minimum"=> costs[131],
-                "remainder_integer-mem-arguments-slope"=> costs[132],
-                "serialise_data-cpu-arguments-intercept"=> costs[133],
-                "serialise_data-cpu-arguments-slope"=> costs[134],
-                "serialise_data-mem-arguments-intercept"=> costs[135],
-                "serialise_data-mem-arguments-slope"=> costs[136],
-                "sha2_256-cpu-arguments-intercept"=> costs[137],
-                "sha2_256-cpu-arguments-slope"=> costs[138],
-                "sha2_256-mem-arguments"=> costs[139],
-                "sha3_256-cpu-arguments-intercept"=> costs[140],
-                "sha3_256-cpu-arguments-slope"=> costs[141],
-                "sha3_256-mem-arguments"=> costs[142],
-                "slice_byte_string-cpu-arguments-intercept"=> costs[143],
-                "slice_byte_string-cpu-arguments-slope"=> costs[144],
-                "slice_byte_string-mem-arguments-intercept"=> costs[145],
-                "slice_byte_string-mem-arguments-slope"=> costs[146],
-                "snd_pair-cpu-arguments"=> costs[147],
-                "snd_pair-mem-arguments"=> costs[148],
-                "subtract_integer-cpu-arguments-intercept"=> costs[149],
-                "subtract_integer-cpu-arguments-slope"=> costs[150],
-                "subtract_integer-mem-arguments-intercept"=> costs[151],
-                "subtract_integer-mem-arguments-slope"=> costs[152],
-                "tail_list-cpu-arguments"=> costs[153],
-                "tail_list-mem-arguments"=> costs[154],
-                "trace-cpu-arguments"=> costs[155],
-                "trace-mem-arguments"=> costs[156],
-                "un_b_data-cpu-arguments"=> costs[157],
-                "un_b_data-mem-arguments"=> costs[158],
-                "un_constr_data-cpu-arguments"=> costs[159],
-                "un_constr_data-mem-arguments"=> costs[160],
-                "un_i_data-cpu-arguments"=> costs[161],
-                "un_i_data-mem-arguments"=> costs[162],
-                "un_list_data-cpu-arguments"=> costs[163],
-                "un_list_data-mem-arguments"=> costs[164],
-                "un_map_data-cpu-arguments"=> costs[165],
-                "un_map_data-mem-arguments"=> costs[166],
-                "verify_ecdsa_secp256k1_signature-cpu-arguments"=> costs[167],
-                "verify_ecdsa_secp256k1_signature-mem-arguments"=> costs[168],
-                "verify_ed25519_signature-cpu-arguments-intercept"=> costs[169],
-                "verify_ed25519_signature-cpu-arguments-slope"=> costs[170],
-                "verify_ed25519_signature-mem-arguments"=> costs[171],
-                "verify_schnorr_secp256k1_signature-cpu-arguments-intercept"=> costs[172],
-                "verify_schnorr_secp256k1_signature-cpu-arguments-slope"=> costs[173],
-                "verify_schnorr_secp256k1_signature-mem-arguments"=> costs[174]
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum CostModelError {
+    #[error("Missing cost model parameter '{0}'")]
+    MissingParameter(String),
+    #[error("Cost model parameter '{0}' must be an integer")]
+    InvalidParameter(String),
+    #[error("Expected {expected} cost model parameters but got {given}")]
+    WrongNumberOfParameters { expected: usize, given: usize },
+}
+
+impl CostModel {
+    /// Build a cost model from named parameters, as found in protocol parameters JSON.
+    /// See [`params_from_json`] for the accepted shapes.
+    pub fn from_params_json(
+        params: &serde_json::Value,
+        language: &Language,
+    ) -> Result<CostModel, CostModelError> {
+        let costs = params_from_json(params, language)?;
+
+        Ok(initialize_cost_model(language, &costs))
+    }
+}
+
+/// Turn named cost model parameters into their positional representation, as
+/// expected by [`initialize_cost_model`]. The JSON is either a map from parameter names
+/// to costs, or the `costModels` object of protocol parameters holding such a map for
+/// the given language. Names are expected in camelCase (e.g. `addInteger-cpu-arguments-intercept`),
+/// although snake_case names are accepted too.
+pub fn params_from_json(
+    params: &serde_json::Value,
+    language: &Language,
+) -> Result<Vec<i64>, CostModelError> {
+    let params = language_keys(language)
+        .iter()
+        .find_map(|key| params.get(key))
+        .unwrap_or(params);
+
+    parameter_names(language)
+        .iter()
+        .map(|name| {
+            let camel_case = to_camel_case(name);
+
+            let value = params
+                .get(&camel_case)
+                .or_else(|| params.get(name))
+                .ok_or_else(|| CostModelError::MissingParameter(camel_case.clone()))?;
+
+            value
+                .as_i64()
+                .ok_or(CostModelError::InvalidParameter(camel_case))
+        })
+        .collect()
+}
+
+/// Inverse of [`params_from_json`], producing a map of camelCase parameter names to costs.
+pub fn params_to_json(
+    costs: &[i64],
+    language: &Language,
+) -> Result<serde_json::Value, CostModelError> {
+    let names = parameter_names(language);
+
+    if names.len() != costs.len() {
+        return Err(CostModelError::WrongNumberOfParameters {
+            expected: names.len(),
+            given: costs.len(),
+        });
+    }
+
+    Ok(names
+        .iter()
+        .zip(costs)
+        .map(|(name, cost)| (to_camel_case(name), serde_json::Value::from(*cost)))
+        .collect::<serde_json::Map<_, _>>()
+        .into())
+}
+
+fn language_keys(language: &Language) -> [&'static str; 2] {
+    match language {
+        Language::PlutusV1 => ["PlutusV1", "PlutusScriptV1"],
+        Language::PlutusV2 => ["PlutusV2", "PlutusScriptV2"],
+    }
+}
+
+/// Names in protocol parameters are camelCase, except when an underscore precedes
+/// a digit (e.g. `sha2_256`).
+fn to_camel_case(name: &str) -> String {
+    let mut camel_case = String::with_capacity(name.len());
+    let mut chars = name.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match chars.peek() {
+            Some(next) if c == '_' && next.is_ascii_alphabetic() => {
+                camel_case.push(next.to_ascii_uppercase());
+                chars.next();
             }
+            _ => camel_case.push(c),
         }
-    };
+    }
+
+    camel_case
+}
+
+/// Names of the cost model parameters, in the order in which they appear in
+/// the ledger's positional representation (e.g. in protocol parameters' CBOR).
+pub fn parameter_names(version: &Language) -> &'static [&'static str] {
+    match version {
+        Language::PlutusV1 => &PLUTUS_V1_PARAMETERS,
+        Language::PlutusV2 => &PLUTUS_V2_PARAMETERS,
+    }
+}
+
+pub fn initialize_cost_model(version: &Language, costs: &[i64]) -> CostModel {
+    let cost_map: HashMap<&str, i64> = parameter_names(version)
+        .iter()
+        .copied()
+        .zip(costs.iter().copied())
+        .collect();
+
     CostModel {
         machine_costs: MachineCosts {
             startup: ExBudget {
@@ -2597,6 +2483,36 @@ pub fn initialize_cost_model(version: &Language, costs: &[i64]) -> CostModel {
                         .unwrap_or(&30000000000),
                 }),
             },
+            blake2b_224: CostingFun {
+                mem: OneArgument::ConstantCost(
+                    *cost_map
+                        .get("blake2b_224-mem-arguments")
+                        .unwrap_or(&30000000000),
+                ),
+                cpu: OneArgument::LinearCost(LinearSize {
+                    intercept: *cost_map
+                        .get("blake2b_224-cpu-arguments-intercept")
+                        .unwrap_or(&30000000000),
+                    slope: *cost_map
+                        .get("blake2b_224-cpu-arguments-slope")
+                        .unwrap_or(&30000000000),
+                }),
+            },
+            keccak_256: CostingFun {
+                mem: OneArgument::ConstantCost(
+                    *cost_map
+                        .get("keccak_256-mem-arguments")
+                        .unwrap_or(&30000000000),
+                ),
+                cpu: OneArgument::LinearCost(LinearSize {
+                    intercept: *cost_map
+                        .get("keccak_256-cpu-arguments-intercept")
+                        .unwrap_or(&30000000000),
+                    slope: *cost_map
+                        .get("keccak_256-cpu-arguments-slope")
+                        .unwrap_or(&30000000000),
+                }),
+            },
             verify_ed25519_signature: CostingFun {
                 mem: ThreeArguments::ConstantCost(
                     *cost_map
@@ -3205,3 +3121,472 @@ impl TryFrom<u8> for StepKind {
         }
     }
 }
+
+const PLUTUS_V1_PARAMETERS: [&str; 166] = [
+    "add_integer-cpu-arguments-intercept",
+    "add_integer-cpu-arguments-slope",
+    "add_integer-mem-arguments-intercept",
+    "add_integer-mem-arguments-slope",
+    "append_byte_string-cpu-arguments-intercept",
+    "append_byte_string-cpu-arguments-slope",
+    "append_byte_string-mem-arguments-intercept",
+    "append_byte_string-mem-arguments-slope",
+    "append_string-cpu-arguments-intercept",
+    "append_string-cpu-arguments-slope",
+    "append_string-mem-arguments-intercept",
+    "append_string-mem-arguments-slope",
+    "b_data-cpu-arguments",
+    "b_data-mem-arguments",
+    "blake2b_256-cpu-arguments-intercept",
+    "blake2b_256-cpu-arguments-slope",
+    "blake2b_256-mem-arguments",
+    "cek_apply_cost-exBudgetCPU",
+    "cek_apply_cost-exBudgetmem",
+    "cek_builtin_cost-exBudgetCPU",
+    "cek_builtin_cost-exBudgetmem",
+    "cek_const_cost-exBudgetCPU",
+    "cek_const_cost-exBudgetmem",
+    "cek_delay_cost-exBudgetCPU",
+    "cek_delay_cost-exBudgetmem",
+    "cek_force_cost-exBudgetCPU",
+    "cek_force_cost-exBudgetmem",
+    "cek_lam_cost-exBudgetCPU",
+    "cek_lam_cost-exBudgetmem",
+    "cek_startup_cost-exBudgetCPU",
+    "cek_startup_cost-exBudgetmem",
+    "cek_var_cost-exBudgetCPU",
+    "cek_var_cost-exBudgetmem",
+    "choose_data-cpu-arguments",
+    "choose_data-mem-arguments",
+    "choose_list-cpu-arguments",
+    "choose_list-mem-arguments",
+    "choose_unit-cpu-arguments",
+    "choose_unit-mem-arguments",
+    "cons_byte_string-cpu-arguments-intercept",
+    "cons_byte_string-cpu-arguments-slope",
+    "cons_byte_string-mem-arguments-intercept",
+    "cons_byte_string-mem-arguments-slope",
+    "constr_data-cpu-arguments",
+    "constr_data-mem-arguments",
+    "decode_utf8-cpu-arguments-intercept",
+    "decode_utf8-cpu-arguments-slope",
+    "decode_utf8-mem-arguments-intercept",
+    "decode_utf8-mem-arguments-slope",
+    "divide_integer-cpu-arguments-constant",
+    "divide_integer-cpu-arguments-model-arguments-intercept",
+    "divide_integer-cpu-arguments-model-arguments-slope",
+    "divide_integer-mem-arguments-intercept",
+    "divide_integer-mem-arguments-minimum",
+    "divide_integer-mem-arguments-slope",
+    "encode_utf8-cpu-arguments-intercept",
+    "encode_utf8-cpu-arguments-slope",
+    "encode_utf8-mem-arguments-intercept",
+    "encode_utf8-mem-arguments-slope",
+    "equals_byte_string-cpu-arguments-constant",
+    "equals_byte_string-cpu-arguments-intercept",
+    "equals_byte_string-cpu-arguments-slope",
+    "equals_byte_string-mem-arguments",
+    "equals_data-cpu-arguments-intercept",
+    "equals_data-cpu-arguments-slope",
+    "equals_data-mem-arguments",
+    "equals_integer-cpu-arguments-intercept",
+    "equals_integer-cpu-arguments-slope",
+    "equals_integer-mem-arguments",
+    "equals_string-cpu-arguments-constant",
+    "equals_string-cpu-arguments-intercept",
+    "equals_string-cpu-arguments-slope",
+    "equals_string-mem-arguments",
+    "fst_pair-cpu-arguments",
+    "fst_pair-mem-arguments",
+    "head_list-cpu-arguments",
+    "head_list-mem-arguments",
+    "i_data-cpu-arguments",
+    "i_data-mem-arguments",
+    "if_then_else-cpu-arguments",
+    "if_then_else-mem-arguments",
+    "index_byte_string-cpu-arguments",
+    "index_byte_string-mem-arguments",
+    "length_of_byte_string-cpu-arguments",
+    "length_of_byte_string-mem-arguments",
+    "less_than_byte_string-cpu-arguments-intercept",
+    "less_than_byte_string-cpu-arguments-slope",
+    "less_than_byte_string-mem-arguments",
+    "less_than_equals_byte_string-cpu-arguments-intercept",
+    "less_than_equals_byte_string-cpu-arguments-slope",
+    "less_than_equals_byte_string-mem-arguments",
+    "less_than_equals_integer-cpu-arguments-intercept",
+    "less_than_equals_integer-cpu-arguments-slope",
+    "less_than_equals_integer-mem-arguments",
+    "less_than_integer-cpu-arguments-intercept",
+    "less_than_integer-cpu-arguments-slope",
+    "less_than_integer-mem-arguments",
+    "list_data-cpu-arguments",
+    "list_data-mem-arguments",
+    "map_data-cpu-arguments",
+    "map_data-mem-arguments",
+    "mk_cons-cpu-arguments",
+    "mk_cons-mem-arguments",
+    "mk_nil_data-cpu-arguments",
+    "mk_nil_data-mem-arguments",
+    "mk_nil_pair_data-cpu-arguments",
+    "mk_nil_pair_data-mem-arguments",
+    "mk_pair_data-cpu-arguments",
+    "mk_pair_data-mem-arguments",
+    "mod_integer-cpu-arguments-constant",
+    "mod_integer-cpu-arguments-model-arguments-intercept",
+    "mod_integer-cpu-arguments-model-arguments-slope",
+    "mod_integer-mem-arguments-intercept",
+    "mod_integer-mem-arguments-minimum",
+    "mod_integer-mem-arguments-slope",
+    "multiply_integer-cpu-arguments-intercept",
+    "multiply_integer-cpu-arguments-slope",
+    "multiply_integer-mem-arguments-intercept",
+    "multiply_integer-mem-arguments-slope",
+    "null_list-cpu-arguments",
+    "null_list-mem-arguments",
+    "quotient_integer-cpu-arguments-constant",
+    "quotient_integer-cpu-arguments-model-arguments-intercept",
+    "quotient_integer-cpu-arguments-model-arguments-slope",
+    "quotient_integer-mem-arguments-intercept",
+    "quotient_integer-mem-arguments-minimum",
+    "quotient_integer-mem-arguments-slope",
+    "remainder_integer-cpu-arguments-constant",
+    "remainder_integer-cpu-arguments-model-arguments-intercept",
+    "remainder_integer-cpu-arguments-model-arguments-slope",
+    "remainder_integer-mem-arguments-intercept",
+    "remainder_integer-mem-arguments-minimum",
+    "remainder_integer-mem-arguments-slope",
+    "sha2_256-cpu-arguments-intercept",
+    "sha2_256-cpu-arguments-slope",
+    "sha2_256-mem-arguments",
+    "sha3_256-cpu-arguments-intercept",
+    "sha3_256-cpu-arguments-slope",
+    "sha3_256-mem-arguments",
+    "slice_byte_string-cpu-arguments-intercept",
+    "slice_byte_string-cpu-arguments-slope",
+    "slice_byte_string-mem-arguments-intercept",
+    "slice_byte_string-mem-arguments-slope",
+    "snd_pair-cpu-arguments",
+    "snd_pair-mem-arguments",
+    "subtract_integer-cpu-arguments-intercept",
+    "subtract_integer-cpu-arguments-slope",
+    "subtract_integer-mem-arguments-intercept",
+    "subtract_integer-mem-arguments-slope",
+    "tail_list-cpu-arguments",
+    "tail_list-mem-arguments",
+    "trace-cpu-arguments",
+    "trace-mem-arguments",
+    "un_b_data-cpu-arguments",
+    "un_b_data-mem-arguments",
+    "un_constr_data-cpu-arguments",
+    "un_constr_data-mem-arguments",
+    "un_i_data-cpu-arguments",
+    "un_i_data-mem-arguments",
+    "un_list_data-cpu-arguments",
+    "un_list_data-mem-arguments",
+    "un_map_data-cpu-arguments",
+    "un_map_data-mem-arguments",
+    "verify_ed25519_signature-cpu-arguments-intercept",
+    "verify_ed25519_signature-cpu-arguments-slope",
+    "verify_ed25519_signature-mem-arguments",
+];
+
+const PLUTUS_V2_PARAMETERS: [&str; 175] = [
+    "add_integer-cpu-arguments-intercept",
+    "add_integer-cpu-arguments-slope",
+    "add_integer-mem-arguments-intercept",
+    "add_integer-mem-arguments-slope",
+    "append_byte_string-cpu-arguments-intercept",
+    "append_byte_string-cpu-arguments-slope",
+    "append_byte_string-mem-arguments-intercept",
+    "append_byte_string-mem-arguments-slope",
+    "append_string-cpu-arguments-intercept",
+    "append_string-cpu-arguments-slope",
+    "append_string-mem-arguments-intercept",
+    "append_string-mem-arguments-slope",
+    "b_data-cpu-arguments",
+    "b_data-mem-arguments",
+    "blake2b_256-cpu-arguments-intercept",
+    "blake2b_256-cpu-arguments-slope",
+    "blake2b_256-mem-arguments",
+    "cek_apply_cost-exBudgetCPU",
+    "cek_apply_cost-exBudgetmem",
+    "cek_builtin_cost-exBudgetCPU",
+    "cek_builtin_cost-exBudgetmem",
+    "cek_const_cost-exBudgetCPU",
+    "cek_const_cost-exBudgetmem",
+    "cek_delay_cost-exBudgetCPU",
+    "cek_delay_cost-exBudgetmem",
+    "cek_force_cost-exBudgetCPU",
+    "cek_force_cost-exBudgetmem",
+    "cek_lam_cost-exBudgetCPU",
+    "cek_lam_cost-exBudgetmem",
+    "cek_startup_cost-exBudgetCPU",
+    "cek_startup_cost-exBudgetmem",
+    "cek_var_cost-exBudgetCPU",
+    "cek_var_cost-exBudgetmem",
+    "choose_data-cpu-arguments",
+    "choose_data-mem-arguments",
+    "choose_list-cpu-arguments",
+    "choose_list-mem-arguments",
+    "choose_unit-cpu-arguments",
+    "choose_unit-mem-arguments",
+    "cons_byte_string-cpu-arguments-intercept",
+    "cons_byte_string-cpu-arguments-slope",
+    "cons_byte_string-mem-arguments-intercept",
+    "cons_byte_string-mem-arguments-slope",
+    "constr_data-cpu-arguments",
+    "constr_data-mem-arguments",
+    "decode_utf8-cpu-arguments-intercept",
+    "decode_utf8-cpu-arguments-slope",
+    "decode_utf8-mem-arguments-intercept",
+    "decode_utf8-mem-arguments-slope",
+    "divide_integer-cpu-arguments-constant",
+    "divide_integer-cpu-arguments-model-arguments-intercept",
+    "divide_integer-cpu-arguments-model-arguments-slope",
+    "divide_integer-mem-arguments-intercept",
+    "divide_integer-mem-arguments-minimum",
+    "divide_integer-mem-arguments-slope",
+    "encode_utf8-cpu-arguments-intercept",
+    "encode_utf8-cpu-arguments-slope",
+    "encode_utf8-mem-arguments-intercept",
+    "encode_utf8-mem-arguments-slope",
+    "equals_byte_string-cpu-arguments-constant",
+    "equals_byte_string-cpu-arguments-intercept",
+    "equals_byte_string-cpu-arguments-slope",
+    "equals_byte_string-mem-arguments",
+    "equals_data-cpu-arguments-intercept",
+    "equals_data-cpu-arguments-slope",
+    "equals_data-mem-arguments",
+    "equals_integer-cpu-arguments-intercept",
+    "equals_integer-cpu-arguments-slope",
+    "equals_integer-mem-arguments",
+    "equals_string-cpu-arguments-constant",
+    "equals_string-cpu-arguments-intercept",
+    "equals_string-cpu-arguments-slope",
+    "equals_string-mem-arguments",
+    "fst_pair-cpu-arguments",
+    "fst_pair-mem-arguments",
+    "head_list-cpu-arguments",
+    "head_list-mem-arguments",
+    "i_data-cpu-arguments",
+    "i_data-mem-arguments",
+    "if_then_else-cpu-arguments",
+    "if_then_else-mem-arguments",
+    "index_byte_string-cpu-arguments",
+    "index_byte_string-mem-arguments",
+    "length_of_byte_string-cpu-arguments",
+    "length_of_byte_string-mem-arguments",
+    "less_than_byte_string-cpu-arguments-intercept",
+    "less_than_byte_string-cpu-arguments-slope",
+    "less_than_byte_string-mem-arguments",
+    "less_than_equals_byte_string-cpu-arguments-intercept",
+    "less_than_equals_byte_string-cpu-arguments-slope",
+    "less_than_equals_byte_string-mem-arguments",
+    "less_than_equals_integer-cpu-arguments-intercept",
+    "less_than_equals_integer-cpu-arguments-slope",
+    "less_than_equals_integer-mem-arguments",
+    "less_than_integer-cpu-arguments-intercept",
+    "less_than_integer-cpu-arguments-slope",
+    "less_than_integer-mem-arguments",
+    "list_data-cpu-arguments",
+    "list_data-mem-arguments",
+    "map_data-cpu-arguments",
+    "map_data-mem-arguments",
+    "mk_cons-cpu-arguments",
+    "mk_cons-mem-arguments",
+    "mk_nil_data-cpu-arguments",
+    "mk_nil_data-mem-arguments",
+    "mk_nil_pair_data-cpu-arguments",
+    "mk_nil_pair_data-mem-arguments",
+    "mk_pair_data-cpu-arguments",
+    "mk_pair_data-mem-arguments",
+    "mod_integer-cpu-arguments-constant",
+    "mod_integer-cpu-arguments-model-arguments-intercept",
+    "mod_integer-cpu-arguments-model-arguments-slope",
+    "mod_integer-mem-arguments-intercept",
+    "mod_integer-mem-arguments-minimum",
+    "mod_integer-mem-arguments-slope",
+    "multiply_integer-cpu-arguments-intercept",
+    "multiply_integer-cpu-arguments-slope",
+    "multiply_integer-mem-arguments-intercept",
+    "multiply_integer-mem-arguments-slope",
+    "null_list-cpu-arguments",
+    "null_list-mem-arguments",
+    "quotient_integer-cpu-arguments-constant",
+    "quotient_integer-cpu-arguments-model-arguments-intercept",
+    "quotient_integer-cpu-arguments-model-arguments-slope",
+    "quotient_integer-mem-arguments-intercept",
+    "quotient_integer-mem-arguments-minimum",
+    "quotient_integer-mem-arguments-slope",
+    "remainder_integer-cpu-arguments-constant",
+    "remainder_integer-cpu-arguments-model-arguments-intercept",
+    "remainder_integer-cpu-arguments-model-arguments-slope",
+    "remainder_integer-mem-arguments-intercept",
+    "remainder_integer-mem-arguments-minimum",
+    "remainder_integer-mem-arguments-slope",
+    "serialise_data-cpu-arguments-intercept",
+    "serialise_data-cpu-arguments-slope",
+    "serialise_data-mem-arguments-intercept",
+    "serialise_data-mem-arguments-slope",
+    "sha2_256-cpu-arguments-intercept",
+    "sha2_256-cpu-arguments-slope",
+    "sha2_256-mem-arguments",
+    "sha3_256-cpu-arguments-intercept",
+    "sha3_256-cpu-arguments-slope",
+    "sha3_256-mem-arguments",
+    "slice_byte_string-cpu-arguments-intercept",
+    "slice_byte_string-cpu-arguments-slope",
+    "slice_byte_string-mem-arguments-intercept",
+    "slice_byte_string-mem-arguments-slope",
+    "snd_pair-cpu-arguments",
+    "snd_pair-mem-arguments",
+    "subtract_integer-cpu-arguments-intercept",
+    "subtract_integer-cpu-arguments-slope",
+    "subtract_integer-mem-arguments-intercept",
+    "subtract_integer-mem-arguments-slope",
+    "tail_list-cpu-arguments",
+    "tail_list-mem-arguments",
+    "trace-cpu-arguments",
+    "trace-mem-arguments",
+    "un_b_data-cpu-arguments",
+    "un_b_data-mem-arguments",
+    "un_constr_data-cpu-arguments",
+    "un_constr_data-mem-arguments",
+    "un_i_data-cpu-arguments",
+    "un_i_data-mem-arguments",
+    "un_list_data-cpu-arguments",
+    "un_list_data-mem-arguments",
+    "un_map_data-cpu-arguments",
+    "un_map_data-mem-arguments",
+    "verify_ecdsa_secp256k1_signature-cpu-arguments",
+    "verify_ecdsa_secp256k1_signature-mem-arguments",
+    "verify_ed25519_signature-cpu-arguments-intercept",
+    "verify_ed25519_signature-cpu-arguments-slope",
+    "verify_ed25519_signature-mem-arguments",
+    "verify_schnorr_secp256k1_signature-cpu-arguments-intercept",
+    "verify_schnorr_secp256k1_signature-cpu-arguments-slope",
+    "verify_schnorr_secp256k1_signature-mem-arguments",
+];
+
+#[cfg(test)]
+mod tests {
+    use pallas_primitives::babbage::Language;
+
+    use super::{
+        initialize_cost_model, params_from_json, params_to_json, CostModel, CostModelError,
+        ExBudget,
+    };
+
+    fn mainnet() -> serde_json::Value {
+        serde_json::from_str(include_str!("../../test_data/cost_models/mainnet.json")).unwrap()
+    }
+
+    #[test]
+    fn params_json_roundtrip() {
+        let cost_models = mainnet();
+
+        for (language, key) in [
+            (Language::PlutusV1, "PlutusV1"),
+            (Language::PlutusV2, "PlutusV2"),
+        ] {
+            let costs = params_from_json(&cost_models, &language).unwrap();
+
+            assert_eq!(costs[0], 205665);
+
+            assert_eq!(params_to_json(&costs, &language).unwrap(), cost_models[key]);
+
+            let from_json = CostModel::from_params_json(&cost_models[key], &language).unwrap();
+            let from_costs = initialize_cost_model(&language, &costs);
+
+            assert_eq!(
+                from_json.machine_costs.startup,
+                from_costs.machine_costs.startup
+            );
+            assert_eq!(
+                from_json.machine_costs.apply,
+                from_costs.machine_costs.apply
+            );
+        }
+    }
+
+    #[test]
+    fn params_json_missing_parameter() {
+        let mut cost_models = mainnet();
+
+        cost_models["PlutusV2"]
+            .as_object_mut()
+            .unwrap()
+            .remove("cekApplyCost-exBudgetCPU");
+
+        assert_eq!(
+            params_from_json(&cost_models, &Language::PlutusV2),
+            Err(CostModelError::MissingParameter(
+                "cekApplyCost-exBudgetCPU".to_string()
+            ))
+        );
+
+        assert!(params_from_json(&cost_models, &Language::PlutusV1).is_ok());
+    }
+
+    #[test]
+    fn budget_add_sub() {
+        let a = ExBudget { mem: 42, cpu: 1337 };
+        let b = ExBudget { mem: 14, cpu: 100 };
+        let zero = ExBudget { mem: 0, cpu: 0 };
+
+        assert_eq!(a + zero, a);
+        assert_eq!(a - zero, a);
+        assert_eq!(a - a, zero);
+        assert_eq!(a + b, b + a);
+        assert_eq!(a + b - b, a);
+        assert_eq!(a + b, ExBudget { mem: 56, cpu: 1437 });
+    }
+
+    #[test]
+    fn budget_fraction_of() {
+        let limit = ExBudget::max_tx_v2();
+
+        let budget = ExBudget {
+            mem: limit.mem / 2,
+            cpu: limit.cpu / 4,
+        };
+
+        assert_eq!(budget.fraction_of(&limit), (0.25, 0.5));
+        assert_eq!(limit.fraction_of(&limit), (1.0, 1.0));
+    }
+
+    #[test]
+    fn budget_display_and_serde() {
+        let budget = ExBudget {
+            mem: 1_234_567,
+            cpu: 10_000_000_000,
+        };
+
+        assert_eq!(budget.to_string(), "mem: 1,234,567, cpu: 10,000,000,000");
+        assert_eq!(format!("{budget:#}"), "mem: 1.23M, cpu: 10G");
+
+        let small = ExBudget {
+            mem: 999,
+            cpu: -1_500,
+        };
+
+        assert_eq!(small.to_string(), "mem: 999, cpu: -1,500");
+        assert_eq!(format!("{small:#}"), "mem: 999, cpu: -1.5k");
+
+        let rounded_up = ExBudget {
+            mem: 999_999,
+            cpu: 999_995_000,
+        };
+
+        assert_eq!(format!("{rounded_up:#}"), "mem: 1M, cpu: 1G");
+
+        let json = serde_json::to_value(budget).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({ "mem": 1_234_567, "cpu": 10_000_000_000_i64 })
+        );
+        assert_eq!(serde_json::from_value::<ExBudget>(json).unwrap(), budget);
+    }
+}
@@ -1,8 +1,12 @@
 use std::string::FromUtf8Error;
 
 use num_bigint::BigInt;
+use pallas_primitives::babbage::Language;
 
-use crate::ast::{NamedDeBruijn, Term, Type};
+use crate::{
+    ast::{to_diagnostic_cbor, Constant, NamedDeBruijn, Term, Type},
+    builtins::DefaultFunction,
+};
 
 use super::{ExBudget, Value};
 
@@ -34,6 +38,10 @@ pub enum Error {
     BuiltinTermArgumentExpected(Term<NamedDeBruijn>),
     #[error("Unable to unlift value because it is not a constant:\n\n{0:#?}")]
     NotAConstant(Value),
+    #[error("The evaluation went over its limit of {0} steps")]
+    StepLimitReached(u64),
+    #[error("The evaluation didn't finish in time")]
+    DeadlineReached,
     #[error("The evaluation never reached a final state")]
     MachineNeverReachedDone,
     #[error("Decoding utf8")]
@@ -46,8 +54,17 @@ pub enum Error {
     UnexpectedEd25519PublicKeyLength(usize),
     #[error("Ed25519S Signature should be 64 bytes but it was {0}")]
     UnexpectedEd25519SignatureLength(usize),
+    #[deprecated(note = "Data builtins now fail with Error::BuiltinTypeMismatch")]
     #[error("Failed to deserialise PlutusData using {0}:\n\n{1:#?}")]
     DeserialisationError(String, Value),
+    #[error("{builtin} expected {expected}, but got: {got}")]
+    BuiltinTypeMismatch {
+        builtin: DefaultFunction,
+        expected: &'static str,
+        got: String,
+    },
+    #[error("The builtin {0} isn't available in {1:?}")]
+    UnsupportedBuiltin(DefaultFunction, Language),
     #[error("Integer overflow")]
     OverflowError,
     #[cfg(not(feature = "native-secp256k1"))]
@@ -57,3 +74,25 @@ pub enum Error {
     #[error(transparent)]
     Secp256k1(#[from] k256::ecdsa::Error),
 }
+
+impl Error {
+    /// A builtin received a value of the right type, but not of the expected shape, e.g.
+    /// `unListData` applied to some `Data` which isn't a list.
+    pub(crate) fn builtin_type_mismatch(
+        builtin: DefaultFunction,
+        expected: &'static str,
+        got: &Value,
+    ) -> Self {
+        Error::BuiltinTypeMismatch {
+            builtin,
+            expected,
+            got: match got {
+                Value::Con(constant) => match constant.as_ref() {
+                    Constant::Data(data) => to_diagnostic_cbor(data),
+                    constant => constant.to_pretty(),
+                },
+                value => format!("{value:#?}"),
+            },
+        }
+    }
+}
@@ -103,6 +103,8 @@ impl DefaultFunction {
             DefaultFunction::Sha2_256 => 1,
             DefaultFunction::Sha3_256 => 1,
             DefaultFunction::Blake2b_256 => 1,
+            DefaultFunction::Blake2b_224 => 1,
+            DefaultFunction::Keccak_256 => 1,
             DefaultFunction::VerifyEd25519Signature => 3,
             DefaultFunction::VerifyEcdsaSecp256k1Signature => 3,
             DefaultFunction::VerifySchnorrSecp256k1Signature => 3,
@@ -162,6 +164,8 @@ impl DefaultFunction {
             DefaultFunction::Sha2_256 => 0,
             DefaultFunction::Sha3_256 => 0,
             DefaultFunction::Blake2b_256 => 0,
+            DefaultFunction::Blake2b_224 => 0,
+            DefaultFunction::Keccak_256 => 0,
             DefaultFunction::VerifyEd25519Signature => 0,
             DefaultFunction::VerifyEcdsaSecp256k1Signature => 0,
             DefaultFunction::VerifySchnorrSecp256k1Signature => 0,
@@ -239,6 +243,8 @@ impl DefaultFunction {
             DefaultFunction::Sha2_256 => arg.expect_type(Type::ByteString),
             DefaultFunction::Sha3_256 => arg.expect_type(Type::ByteString),
             DefaultFunction::Blake2b_256 => arg.expect_type(Type::ByteString),
+            DefaultFunction::Blake2b_224 => arg.expect_type(Type::ByteString),
+            DefaultFunction::Keccak_256 => arg.expect_type(Type::ByteString),
             DefaultFunction::VerifyEd25519Signature => arg.expect_type(Type::ByteString),
             DefaultFunction::VerifyEcdsaSecp256k1Signature => arg.expect_type(Type::ByteString),
             DefaultFunction::VerifySchnorrSecp256k1Signature => arg.expect_type(Type::ByteString),
@@ -649,6 +655,42 @@ impl DefaultFunction {
                 },
                 _ => unreachable!(),
             },
+            DefaultFunction::Blake2b_224 => match args[0].as_ref() {
+                Value::Con(byte_string) => match byte_string.as_ref() {
+                    Constant::ByteString(arg1) => {
+                        use cryptoxide::{blake2b::Blake2b, digest::Digest};
+
+                        let mut digest = [0u8; 28];
+                        let mut context = Blake2b::new(28);
+
+                        context.input(arg1);
+                        context.result(&mut digest);
+
+                        Ok(Value::Con(Constant::ByteString(digest.to_vec()).into()).into())
+                    }
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            },
+            DefaultFunction::Keccak_256 => match args[0].as_ref() {
+                Value::Con(byte_string) => match byte_string.as_ref() {
+                    Constant::ByteString(arg1) => {
+                        use cryptoxide::{digest::Digest, sha3::Keccak256};
+
+                        let mut hasher = Keccak256::new();
+
+                        hasher.input(arg1);
+
+                        let mut bytes = vec![0; hasher.output_bytes()];
+
+                        hasher.result(&mut bytes);
+
+                        Ok(Value::Con(Constant::ByteString(bytes).into()).into())
+                    }
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            },
             DefaultFunction::VerifyEd25519Signature => {
                 match (args[0].as_ref(), args[1].as_ref(), args[2].as_ref()) {
                     (Value::Con(public_key), Value::Con(message), Value::Con(signature)) => {
@@ -988,14 +1030,16 @@ impl DefaultFunction {
                         .into(),
                     )
                     .into()),
-                    v => Err(Error::DeserialisationError(
-                        "UnConstrData".to_string(),
-                        Value::Con(v.clone().into()),
+                    v => Err(Error::builtin_type_mismatch(
+                        DefaultFunction::UnConstrData,
+                        "a constructor",
+                        &Value::Con(v.clone().into()),
                     )),
                 },
-                v => Err(Error::DeserialisationError(
-                    "UnConstrData".to_string(),
-                    v.clone(),
+                v => Err(Error::builtin_type_mismatch(
+                    DefaultFunction::UnConstrData,
+                    "a constructor",
+                    v,
                 )),
             },
             DefaultFunction::UnMapData => match args[0].as_ref() {
@@ -1018,14 +1062,16 @@ impl DefaultFunction {
                         .into(),
                     )
                     .into()),
-                    v => Err(Error::DeserialisationError(
-                        "UnMapData".to_string(),
-                        Value::Con(v.clone().into()),
+                    v => Err(Error::builtin_type_mismatch(
+                        DefaultFunction::UnMapData,
+                        "a map",
+                        &Value::Con(v.clone().into()),
                     )),
                 },
-                v => Err(Error::DeserialisationError(
-                    "UnMapData".to_string(),
-                    v.clone(),
+                v => Err(Error::builtin_type_mismatch(
+                    DefaultFunction::UnMapData,
+                    "a map",
+                    v,
                 )),
             },
             DefaultFunction::UnListData => match args[0].as_ref() {
@@ -1041,14 +1087,16 @@ impl DefaultFunction {
                         .into(),
                     )
                     .into()),
-                    v => Err(Error::DeserialisationError(
-                        "UnListData".to_string(),
-                        Value::Con(v.clone().into()),
+                    v => Err(Error::builtin_type_mismatch(
+                        DefaultFunction::UnListData,
+                        "a list",
+                        &Value::Con(v.clone().into()),
                     )),
                 },
-                v => Err(Error::DeserialisationError(
-                    "UnListData".to_string(),
-                    v.clone(),
+                v => Err(Error::builtin_type_mismatch(
+                    DefaultFunction::UnListData,
+                    "a list",
+                    v,
                 )),
             },
             DefaultFunction::UnIData => match args[0].as_ref() {
@@ -1056,14 +1104,16 @@ impl DefaultFunction {
                     Constant::Data(PlutusData::BigInt(b)) => {
                         Ok(Value::Con(Constant::Integer(from_pallas_bigint(b)).into()).into())
                     }
-                    v => Err(Error::DeserialisationError(
-                        "UnIData".to_string(),
-                        Value::Con(v.clone().into()),
+                    v => Err(Error::builtin_type_mismatch(
+                        DefaultFunction::UnIData,
+                        "an integer",
+                        &Value::Con(v.clone().into()),
                     )),
                 },
-                v => Err(Error::DeserialisationError(
-                    "UnIData".to_string(),
-                    v.clone(),
+                v => Err(Error::builtin_type_mismatch(
+                    DefaultFunction::UnIData,
+                    "an integer",
+                    v,
                 )),
             },
             DefaultFunction::UnBData => match args[0].as_ref() {
@@ -1071,14 +1121,16 @@ impl DefaultFunction {
                     Constant::Data(PlutusData::BoundedBytes(b)) => {
                         Ok(Value::Con(Constant::ByteString(b.to_vec()).into()).into())
                     }
-                    v => Err(Error::DeserialisationError(
-                        "UnBData".to_string(),
-                        Value::Con(v.clone().into()),
+                    v => Err(Error::builtin_type_mismatch(
+                        DefaultFunction::UnBData,
+                        "a bytestring",
+                        &Value::Con(v.clone().into()),
                     )),
                 },
-                v => Err(Error::DeserialisationError(
-                    "UnBData".to_string(),
-                    v.clone(),
+                v => Err(Error::builtin_type_mismatch(
+                    DefaultFunction::UnBData,
+                    "a bytestring",
+                    v,
                 )),
             },
             DefaultFunction::EqualsData => match (args[0].as_ref(), args[1].as_ref()) {
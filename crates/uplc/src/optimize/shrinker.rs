@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::{collections::HashSet, rc::Rc};
 
 use indexmap::IndexMap;
 use itertools::Itertools;
@@ -66,6 +66,28 @@ impl Program<Name> {
     }
 }
 
+impl Term<Name> {
+    /// Remove `let`-style bindings, that is `[(lam x body) value]`, where `x` is never used
+    /// in `body`, until there are none left. Only bindings to values (variables, constants,
+    /// builtins, lambdas and delayed terms) are removed: evaluating anything else may fail or
+    /// emit traces, which must still happen.
+    pub fn prune_unused_lets(&self) -> Term<Name> {
+        let mut term = self.clone();
+        while prune_unused_lets(&mut term) {}
+        term
+    }
+
+    /// Inline `let`-style bindings, that is `[(lam x body) value]`, where `x` is used exactly
+    /// once in `body`, until there are none left. As with [`Term::prune_unused_lets`], only
+    /// bindings to values are inlined. A binding is left alone when inlining its value would
+    /// put one of the value's free variables under a lambda of `body` binding the same name.
+    pub fn inline_single_use(&self) -> Term<Name> {
+        let mut term = self.clone();
+        while inline_single_use(&mut term) {}
+        term
+    }
+}
+
 fn builtin_force_reduce(term: &mut Term<Name>, builtin_map: &mut IndexMap<u8, ()>) {
     match term {
         Term::Force(f) => {
@@ -165,6 +187,128 @@ fn inline_basic_reduce(term: &mut Term<Name>) {
     }
 }
 
+/// Returns whether any binding was removed.
+fn prune_unused_lets(term: &mut Term<Name>) -> bool {
+    match term {
+        Term::Delay(d) => prune_unused_lets(Rc::make_mut(d)),
+        Term::Force(f) => prune_unused_lets(Rc::make_mut(f)),
+        Term::Lambda { body, .. } => prune_unused_lets(Rc::make_mut(body)),
+        Term::Apply { function, argument } => {
+            let pruned_function = prune_unused_lets(Rc::make_mut(function));
+            let pruned_argument = prune_unused_lets(Rc::make_mut(argument));
+
+            if let Term::Lambda {
+                parameter_name,
+                body,
+            } = function.as_ref()
+            {
+                if let Term::Var(_)
+                | Term::Constant(_)
+                | Term::Builtin(_)
+                | Term::Delay(_)
+                | Term::Lambda { .. } = argument.as_ref()
+                {
+                    let mut occurrences = 0;
+                    var_occurrences(body, parameter_name.clone(), &mut occurrences);
+                    if occurrences == 0 {
+                        *term = body.as_ref().clone();
+                        return true;
+                    }
+                }
+            }
+
+            pruned_function || pruned_argument
+        }
+        _ => false,
+    }
+}
+
+/// Returns whether any binding was inlined.
+fn inline_single_use(term: &mut Term<Name>) -> bool {
+    match term {
+        Term::Delay(d) => inline_single_use(Rc::make_mut(d)),
+        Term::Force(f) => inline_single_use(Rc::make_mut(f)),
+        Term::Lambda { body, .. } => inline_single_use(Rc::make_mut(body)),
+        Term::Apply { function, argument } => {
+            let inlined_function = inline_single_use(Rc::make_mut(function));
+            let inlined_argument = inline_single_use(Rc::make_mut(argument));
+
+            if let Term::Lambda {
+                parameter_name,
+                body,
+            } = function.as_ref()
+            {
+                if let Term::Var(_)
+                | Term::Constant(_)
+                | Term::Builtin(_)
+                | Term::Delay(_)
+                | Term::Lambda { .. } = argument.as_ref()
+                {
+                    let mut occurrences = 0;
+                    var_occurrences(body, parameter_name.clone(), &mut occurrences);
+                    if occurrences == 1 {
+                        if let Some(inlined) = substitute_term_without_capture(
+                            body,
+                            parameter_name,
+                            argument,
+                            &argument.free_vars(),
+                        ) {
+                            *term = inlined;
+                            return true;
+                        }
+                    }
+                }
+            }
+
+            inlined_function || inlined_argument
+        }
+        _ => false,
+    }
+}
+
+/// Like `substitute_term`, but gives up when `replace_with` would end up under a lambda
+/// binding one of its `free` variables.
+fn substitute_term_without_capture(
+    term: &Term<Name>,
+    original: &Rc<Name>,
+    replace_with: &Term<Name>,
+    free: &HashSet<Name>,
+) -> Option<Term<Name>> {
+    Some(match term {
+        Term::Var(name) if name.as_ref() == original.as_ref() => replace_with.clone(),
+        Term::Delay(body) => {
+            Term::Delay(substitute_term_without_capture(body, original, replace_with, free)?.into())
+        }
+        Term::Lambda {
+            parameter_name,
+            body,
+        } if parameter_name.as_ref() != original.as_ref() => {
+            if free.contains(parameter_name) {
+                let mut occurrences = 0;
+                var_occurrences(body, original.clone(), &mut occurrences);
+                if occurrences > 0 {
+                    return None;
+                }
+            }
+
+            Term::Lambda {
+                parameter_name: parameter_name.clone(),
+                body: substitute_term_without_capture(body, original, replace_with, free)?.into(),
+            }
+        }
+        Term::Apply { function, argument } => Term::Apply {
+            function: substitute_term_without_capture(function, original, replace_with, free)?
+                .into(),
+            argument: substitute_term_without_capture(argument, original, replace_with, free)?
+                .into(),
+        },
+        Term::Force(x) => {
+            Term::Force(substitute_term_without_capture(x, original, replace_with, free)?.into())
+        }
+        x => x.clone(),
+    })
+}
+
 fn var_occurrences(term: &Term<Name>, search_for: Rc<Name>, occurrences: &mut usize) {
     match term {
         Term::Var(name) => {
@@ -292,3 +436,108 @@ fn substitute_term(term: &Term<Name>, original: Rc<Name>, replace_with: &Term<Na
         x => x.clone(),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        ast::{DeBruijn, Name, NamedDeBruijn, Program, Term},
+        machine::cost_model::ExBudget,
+        parser,
+    };
+
+    fn program(source: &str) -> Program<Name> {
+        parser::program(source).unwrap()
+    }
+
+    fn pruned(program: &Program<Name>) -> Program<Name> {
+        Program {
+            version: program.version,
+            term: program.term.prune_unused_lets(),
+        }
+    }
+
+    fn inlined(program: &Program<Name>) -> Program<Name> {
+        Program {
+            version: program.version,
+            term: program.term.inline_single_use(),
+        }
+    }
+
+    fn eval(program: Program<Name>) -> Term<NamedDeBruijn> {
+        let program: Program<NamedDeBruijn> = program.try_into().unwrap();
+
+        program.eval(ExBudget::default()).0.unwrap()
+    }
+
+    #[test]
+    fn prune_unused_lets() {
+        // Once `unused` is gone, `helper` isn't used either.
+        let original = program(
+            r#"(program 1.0.0
+                [(lam helper [(lam unused [(lam used used) (con integer 42)]) (lam y [helper y])]) (lam x x)]
+            )"#,
+        );
+
+        let optimized = pruned(&original);
+
+        assert!(optimized
+            .term
+            .alpha_eq(&program("(program 1.0.0 [(lam used used) (con integer 42)])").term));
+        assert!(optimized.term.size() < original.term.size());
+        assert_eq!(eval(optimized), eval(original));
+    }
+
+    #[test]
+    fn prune_unused_lets_keeps_effects() {
+        for source in [
+            "(program 1.0.0 [(lam x (con integer 1)) (error)])",
+            r#"(program 1.0.0 [(lam x (con integer 1)) [(force (builtin trace)) (con string "hi") (con unit ())]])"#,
+            "(program 1.0.0 [(lam x [x (con integer 1)]) (lam y y)])",
+        ] {
+            let original = program(source);
+
+            assert_eq!(pruned(&original), original);
+        }
+    }
+
+    #[test]
+    fn inline_single_use() {
+        // Once `f` is inlined, so is `x`.
+        let original = program(
+            r#"(program 1.0.0
+                (lam d [(lam f [(builtin iData) [(builtin addInteger) (con integer 1) [f d]]]) (lam x [(builtin unIData) x])])
+            )"#,
+        );
+
+        let optimized = inlined(&original);
+
+        assert!(optimized.term.alpha_eq(
+            &program(
+                "(program 1.0.0 (lam d [(builtin iData) [(builtin addInteger) (con integer 1) [(builtin unIData) d]]]))"
+            )
+            .term
+        ));
+        assert!(optimized.term.size() < original.term.size());
+
+        let original: Program<DeBruijn> = original.try_into().unwrap();
+        let optimized: Program<DeBruijn> = optimized.try_into().unwrap();
+
+        assert!(original.behaviorally_eq(&optimized, 50, 42));
+    }
+
+    #[test]
+    fn inline_single_use_leaves_other_bindings() {
+        for source in [
+            // `x` is used twice.
+            "(program 1.0.0 [(lam x [x x]) (lam y y)])",
+            // Evaluating the argument may fail.
+            "(program 1.0.0 [(lam x (lam y x)) (error)])",
+            // The inner `y` would capture the outer one.
+            "(program 1.0.0 (lam y [(lam x (lam y x)) y]))",
+        ] {
+            let original = program(source);
+
+            assert_eq!(inlined(&original), original);
+        }
+    }
+}
@@ -6,33 +6,79 @@ use crate::{
     plutus_data_to_bytes,
 };
 
+/// Layout of pretty-printed programs and terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyOptions {
+    /// Number of spaces nested terms are indented by.
+    pub indent: usize,
+    /// Width lines are broken at, if any. Without one, terms are kept on a single line.
+    pub max_width: Option<usize>,
+    /// Whether bytestrings made only of printable ASCII are shown as text, e.g. `"hello"`
+    /// instead of `#68656c6c6f`. Handy to read token names, but the parser only reads hex back.
+    pub ascii_bytestrings: bool,
+    /// Whether to print `(error)` as the Plutus reference printer does, rather than `(error )`.
+    /// Nothing else differs; other terms may still be laid out differently than by the
+    /// Haskell implementation.
+    pub verbose: bool,
+}
+
+impl Default for PrettyOptions {
+    fn default() -> Self {
+        PrettyOptions {
+            indent: 2,
+            max_width: Some(80),
+            ascii_bytestrings: false,
+            verbose: false,
+        }
+    }
+}
+
+fn render(doc: RcDoc<()>, max_width: Option<usize>) -> String {
+    let mut w = Vec::new();
+
+    doc.render(max_width.unwrap_or(isize::MAX as usize), &mut w)
+        .unwrap();
+
+    String::from_utf8(w)
+        .unwrap()
+        .lines()
+        // This is a hack to deal with blank newlines
+        // that end up with a bunch of useless whitespace
+        // because of the nesting
+        .map(|l| {
+            if l.chars().all(|c| c.is_whitespace()) {
+                "".to_string()
+            } else {
+                l.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 impl<'a, T> Program<T>
 where
     T: Binder<'a>,
 {
     pub fn to_pretty(&self) -> String {
-        let mut w = Vec::new();
-
-        self.to_doc().render(80, &mut w).unwrap();
-
-        String::from_utf8(w)
-            .unwrap()
-            .lines()
-            // This is a hack to deal with blank newlines
-            // that end up with a bunch of useless whitespace
-            // because of the nesting
-            .map(|l| {
-                if l.chars().all(|c| c.is_whitespace()) {
-                    "".to_string()
-                } else {
-                    l.to_string()
-                }
-            })
-            .collect::<Vec<_>>()
-            .join("\n")
+        self.to_pretty_with(PrettyOptions::default())
     }
 
-    fn to_doc(&self) -> RcDoc<()> {
+    /// Like [`Program::to_pretty`], but with `(error)` printed as the Plutus reference printer
+    /// does (see [`PrettyOptions::verbose`]).
+    pub fn to_pretty_verbose(&self) -> String {
+        self.to_pretty_with(PrettyOptions {
+            verbose: true,
+            ..PrettyOptions::default()
+        })
+    }
+
+    pub fn to_pretty_with(&self, options: PrettyOptions) -> String {
+        render(self.to_doc(&options), options.max_width)
+    }
+
+    fn to_doc(&self, options: &PrettyOptions) -> RcDoc<()> {
+        let indent = options.indent as isize;
         let version = format!("{}.{}.{}", self.version.0, self.version.1, self.version.2);
 
         RcDoc::text("(")
@@ -40,8 +86,8 @@ where
             .append(RcDoc::line())
             .append(RcDoc::text(version))
             .append(RcDoc::line())
-            .append(self.term.to_doc())
-            .nest(2)
+            .append(self.term.to_doc(options))
+            .nest(indent)
             .append(RcDoc::line_())
             .append(RcDoc::text(")"))
     }
@@ -52,36 +98,33 @@ where
     T: Binder<'a>,
 {
     pub fn to_pretty(&self) -> String {
-        let mut w = Vec::new();
-
-        self.to_doc().render(80, &mut w).unwrap();
-
-        String::from_utf8(w)
-            .unwrap()
-            .lines()
-            // This is a hack to deal with blank newlines
-            // that end up with a bunch of useless whitespace
-            // because of the nesting
-            .map(|l| {
-                if l.chars().all(|c| c.is_whitespace()) {
-                    "".to_string()
-                } else {
-                    l.to_string()
-                }
-            })
-            .collect::<Vec<_>>()
-            .join("\n")
+        self.to_pretty_with(PrettyOptions::default())
     }
 
-    fn to_doc(&self) -> RcDoc<()> {
+    /// Like [`Term::to_pretty`], but with `(error)` printed as the Plutus reference printer
+    /// does (see [`PrettyOptions::verbose`]).
+    pub fn to_pretty_verbose(&self) -> String {
+        self.to_pretty_with(PrettyOptions {
+            verbose: true,
+            ..PrettyOptions::default()
+        })
+    }
+
+    pub fn to_pretty_with(&self, options: PrettyOptions) -> String {
+        render(self.to_doc(&options), options.max_width)
+    }
+
+    fn to_doc(&self, options: &PrettyOptions) -> RcDoc<()> {
+        let indent = options.indent as isize;
+
         match self {
             Term::Var(name) => RcDoc::text(name.text()),
             Term::Delay(term) => RcDoc::text("(")
                 .append(
                     RcDoc::text("delay")
                         .append(RcDoc::line())
-                        .append(term.to_doc())
-                        .nest(2),
+                        .append(term.to_doc(options))
+                        .nest(indent),
                 )
                 .append(RcDoc::line_())
                 .append(RcDoc::text(")")),
@@ -94,8 +137,8 @@ where
                         .append(RcDoc::line())
                         .append(RcDoc::text(parameter_name.text()))
                         .append(RcDoc::line())
-                        .append(body.to_doc())
-                        .nest(2),
+                        .append(body.to_doc(options))
+                        .nest(indent),
                 )
                 .append(RcDoc::line_())
                 .append(RcDoc::text(")")),
@@ -104,12 +147,12 @@ where
                     RcDoc::line()
                         .append(
                             function
-                                .to_doc()
+                                .to_doc(options)
                                 .append(RcDoc::line())
-                                .append(argument.to_doc())
+                                .append(argument.to_doc(options))
                                 .group(),
                         )
-                        .nest(2),
+                        .nest(indent),
                 )
                 .append(RcDoc::line())
                 .append(RcDoc::text("]")),
@@ -117,8 +160,8 @@ where
                 .append(
                     RcDoc::text("con")
                         .append(RcDoc::line())
-                        .append(constant.to_doc())
-                        .nest(2),
+                        .append(constant.to_doc(options))
+                        .nest(indent),
                 )
                 .append(RcDoc::line_())
                 .append(RcDoc::text(")")),
@@ -126,13 +169,14 @@ where
                 .append(
                     RcDoc::text("force")
                         .append(RcDoc::line())
-                        .append(term.to_doc())
-                        .nest(2),
+                        .append(term.to_doc(options))
+                        .nest(indent),
                 )
                 .append(RcDoc::line_())
                 .append(RcDoc::text(")")),
+            Term::Error if options.verbose => RcDoc::text("(error)"),
             Term::Error => RcDoc::text("(")
-                .append(RcDoc::text("error").nest(2))
+                .append(RcDoc::text("error").nest(indent))
                 .append(RcDoc::line())
                 .append(RcDoc::line_())
                 .append(RcDoc::text(")")),
@@ -141,7 +185,7 @@ where
                     RcDoc::text("builtin")
                         .append(RcDoc::line())
                         .append(RcDoc::text(builtin.to_string()))
-                        .nest(2),
+                        .nest(indent),
                 )
                 .append(RcDoc::line_())
                 .append(RcDoc::text(")")),
@@ -152,36 +196,19 @@ where
 
 impl Constant {
     pub fn to_pretty(&self) -> String {
-        let mut w = Vec::new();
-
-        self.to_doc().render(80, &mut w).unwrap();
-
-        String::from_utf8(w)
-            .unwrap()
-            .lines()
-            // This is a hack to deal with blank newlines
-            // that end up with a bunch of useless whitespace
-            // because of the nesting
-            .map(|l| {
-                if l.chars().all(|c| c.is_whitespace()) {
-                    "".to_string()
-                } else {
-                    l.to_string()
-                }
-            })
-            .collect::<Vec<_>>()
-            .join("\n")
+        let options = PrettyOptions::default();
+
+        render(self.to_doc(&options), options.max_width)
     }
 
-    fn to_doc(&self) -> RcDoc<()> {
+    fn to_doc(&self, options: &PrettyOptions) -> RcDoc<()> {
         match self {
             Constant::Integer(i) => RcDoc::text("integer")
                 .append(RcDoc::line())
                 .append(RcDoc::as_string(i)),
             Constant::ByteString(bs) => RcDoc::text("bytestring")
                 .append(RcDoc::line())
-                .append(RcDoc::text("#"))
-                .append(RcDoc::text(hex::encode(bs))),
+                .append(bytestring_to_doc(bs, options)),
             Constant::String(s) => RcDoc::text("string")
                 .append(RcDoc::line())
                 .append(RcDoc::text("\""))
@@ -201,7 +228,7 @@ impl Constant {
                 .append(RcDoc::line())
                 .append(RcDoc::text("["))
                 .append(RcDoc::intersperse(
-                    items.iter().map(|c| c.to_doc_list()),
+                    items.iter().map(|c| c.to_doc_list(options)),
                     RcDoc::text(","),
                 ))
                 .append(RcDoc::text("]")),
@@ -214,18 +241,20 @@ impl Constant {
                 .append(RcDoc::text(">"))
                 .append(RcDoc::line())
                 .append(RcDoc::text("["))
-                .append(left.to_doc_list())
+                .append(left.to_doc_list(options))
                 .append(RcDoc::text(","))
-                .append(right.to_doc_list())
+                .append(right.to_doc_list(options))
                 .append(RcDoc::text("]")),
-            d @ Constant::Data(_) => RcDoc::text("data ").append(d.to_doc_list()),
+            d @ Constant::Data(_) => RcDoc::text("data")
+                .append(RcDoc::line())
+                .append(d.to_doc_list(options)),
         }
     }
 
-    fn to_doc_list(&self) -> RcDoc<()> {
+    fn to_doc_list(&self, options: &PrettyOptions) -> RcDoc<()> {
         match self {
             Constant::Integer(i) => RcDoc::as_string(i),
-            Constant::ByteString(bs) => RcDoc::text("#").append(RcDoc::text(hex::encode(bs))),
+            Constant::ByteString(bs) => bytestring_to_doc(bs, options),
             Constant::String(s) => RcDoc::text("\"")
                 .append(RcDoc::text(s))
                 .append(RcDoc::text("\"")),
@@ -233,14 +262,14 @@ impl Constant {
             Constant::Bool(b) => RcDoc::text(if *b { "True" } else { "False" }),
             Constant::ProtoList(_, items) => RcDoc::text("[")
                 .append(RcDoc::intersperse(
-                    items.iter().map(|c| c.to_doc_list()),
+                    items.iter().map(|c| c.to_doc_list(options)),
                     RcDoc::text(","),
                 ))
                 .append(RcDoc::text("]")),
             Constant::ProtoPair(_, _, left, right) => RcDoc::text("(")
-                .append((*left).to_doc_list())
+                .append((*left).to_doc_list(options))
                 .append(RcDoc::text(", "))
-                .append((*right).to_doc_list())
+                .append((*right).to_doc_list(options))
                 .append(RcDoc::text(")")),
 
             Constant::Data(data) => RcDoc::text("#").append(RcDoc::text(hex::encode(
@@ -250,6 +279,18 @@ impl Constant {
     }
 }
 
+fn bytestring_to_doc<'a>(bs: &[u8], options: &PrettyOptions) -> RcDoc<'a, ()> {
+    let printable = bs
+        .iter()
+        .all(|b| (b' '..=b'~').contains(b) && *b != b'"' && *b != b'\\');
+
+    if options.ascii_bytestrings && !bs.is_empty() && printable {
+        RcDoc::text(format!("\"{}\"", String::from_utf8_lossy(bs)))
+    } else {
+        RcDoc::text("#").append(RcDoc::text(hex::encode(bs)))
+    }
+}
+
 impl Type {
     fn to_doc(&self) -> RcDoc<()> {
         match self {
@@ -272,3 +313,141 @@ impl Type {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::PrettyOptions;
+    use crate::{
+        ast::{Constant, Name, Term},
+        parser,
+    };
+    use pallas_primitives::alonzo::{BigInt, Constr, PlutusData};
+
+    #[test]
+    fn pretty_at_different_widths() {
+        let program =
+            parser::program("(program 1.0.0 [(lam x (lam y x)) (con integer 42)])").unwrap();
+
+        let narrow = PrettyOptions {
+            indent: 4,
+            max_width: Some(20),
+            ..PrettyOptions::default()
+        };
+
+        let wide = PrettyOptions {
+            indent: 4,
+            max_width: None,
+            ..PrettyOptions::default()
+        };
+
+        assert_eq!(
+            program.to_pretty_with(narrow),
+            [
+                "(program",
+                "    1.0.0",
+                "    [",
+                "        (lam",
+                "            x",
+                "            (lam",
+                "                y",
+                "                x",
+                "            )",
+                "        )",
+                "        (con",
+                "            integer",
+                "            42",
+                "        )",
+                "    ]",
+                ")",
+            ]
+            .join("\n")
+        );
+        assert_eq!(
+            program.to_pretty_with(wide),
+            "(program\n    1.0.0\n    [ (lam x (lam y x)) (con integer 42) ]\n)"
+        );
+        assert_eq!(
+            program.to_pretty(),
+            program.to_pretty_with(PrettyOptions::default())
+        );
+    }
+
+    #[test]
+    fn pretty_parenthesizes_every_term() {
+        let term = parser::term(
+            "[[[(force (builtin ifThenElse)) (con bool True)] (delay (con integer 1))] (delay (error))]",
+        )
+        .unwrap();
+
+        let pretty = term.to_pretty_verbose();
+
+        assert_eq!(
+            pretty,
+            [
+                "[",
+                "  [ [ (force (builtin ifThenElse)) (con bool True) ] (delay (con integer 1)) ]",
+                "  (delay (error))",
+                "]",
+            ]
+            .join("\n")
+        );
+        assert_eq!(parser::term(&pretty).unwrap(), term);
+
+        let wide = PrettyOptions {
+            max_width: None,
+            verbose: true,
+            ..PrettyOptions::default()
+        };
+
+        assert_eq!(
+            term.to_pretty_with(wide),
+            "[ [ [ (force (builtin ifThenElse)) (con bool True) ] (delay (con integer 1)) ] (delay (error)) ]"
+        );
+        assert_eq!(
+            term.to_pretty_with(PrettyOptions {
+                verbose: false,
+                ..wide
+            }),
+            "[ [ [ (force (builtin ifThenElse)) (con bool True) ] (delay (con integer 1)) ] (delay (error )) ]"
+        );
+    }
+
+    #[test]
+    fn pretty_data_round_trips() {
+        let datum = PlutusData::Constr(Constr {
+            tag: 122,
+            any_constructor: None,
+            fields: vec![
+                PlutusData::BigInt(BigInt::Int(42.into())),
+                PlutusData::BoundedBytes(vec![0xca, 0xfe].into()),
+            ],
+        });
+
+        let term: Term<Name> = Term::Constant(Constant::Data(datum).into());
+
+        let pretty = term.to_pretty();
+
+        assert_eq!(pretty, "(con data #d87a9f182a42cafeff)");
+        assert_eq!(parser::term(&pretty).unwrap(), term);
+    }
+
+    #[test]
+    fn pretty_ascii_bytestrings() {
+        let printable: Term<Name> = Term::Constant(Constant::ByteString(b"hello".to_vec()).into());
+        let binary: Term<Name> = Term::Constant(Constant::ByteString(vec![0x68, 0x00]).into());
+
+        let ascii = PrettyOptions {
+            ascii_bytestrings: true,
+            ..PrettyOptions::default()
+        };
+
+        assert_eq!(printable.to_pretty(), "(con bytestring #68656c6c6f)");
+        assert_eq!(
+            printable.to_pretty_with(ascii),
+            "(con bytestring \"hello\")"
+        );
+
+        assert_eq!(binary.to_pretty(), "(con bytestring #6800)");
+        assert_eq!(binary.to_pretty_with(ascii), "(con bytestring #6800)");
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    machine::runtime::{convert_constr_to_tag, ANY_TAG},
+    BigInt, Constr, KeyValuePairs, PlutusData,
+};
+
+/// Maximum nesting of lists, maps and constructors in generated values.
+const MAX_DEPTH: u64 = 3;
+
+/// Maximum number of elements in generated lists, maps and constructor fields.
+const MAX_LENGTH: u64 = 4;
+
+/// A small pseudo-random number generator (SplitMix64). What it generates only depends on
+/// the seed, regardless of the platform or of the order tests run in, so that a failing
+/// property can be reproduced from its seed.
+#[derive(Debug, Clone)]
+pub struct Prng {
+    state: u64,
+}
+
+impl Prng {
+    pub fn new(seed: u64) -> Self {
+        Prng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    /// A random `Data` value: integers (biased towards small ones), bytestrings, lists,
+    /// maps and constructors.
+    pub fn data(&mut self) -> PlutusData {
+        self.data_with_depth(0)
+    }
+
+    fn data_with_depth(&mut self, depth: u64) -> PlutusData {
+        // Only leaves once deep enough, so that values stay small.
+        let kinds = if depth >= MAX_DEPTH { 2 } else { 5 };
+
+        match self.below(kinds) {
+            0 => PlutusData::BigInt(BigInt::Int(self.integer().into())),
+            1 => PlutusData::BoundedBytes(self.bytes().into()),
+            2 => PlutusData::Array(self.fields(depth)),
+            3 => {
+                let entries = (0..self.below(MAX_LENGTH + 1))
+                    .map(|_| {
+                        (
+                            self.data_with_depth(depth + 1),
+                            self.data_with_depth(depth + 1),
+                        )
+                    })
+                    .collect();
+
+                PlutusData::Map(KeyValuePairs::Def(entries))
+            }
+            _ => {
+                let index = self.below(10);
+
+                PlutusData::Constr(Constr {
+                    tag: convert_constr_to_tag(index).unwrap_or(ANY_TAG),
+                    any_constructor: convert_constr_to_tag(index).map_or(Some(index), |_| None),
+                    fields: self.fields(depth),
+                })
+            }
+        }
+    }
+
+    fn fields(&mut self, depth: u64) -> Vec<PlutusData> {
+        (0..self.below(MAX_LENGTH + 1))
+            .map(|_| self.data_with_depth(depth + 1))
+            .collect()
+    }
+
+    /// An integer, more often than not a small one, positive or negative.
+    pub fn integer(&mut self) -> i64 {
+        match self.below(4) {
+            0 => self.next_u64() as i64,
+            _ => self.below(201) as i64 - 100,
+        }
+    }
+
+    /// A bytestring of up to 32 bytes, possibly empty.
+    pub fn bytes(&mut self) -> Vec<u8> {
+        (0..self.below(MAX_LENGTH * 8 + 1))
+            .map(|_| self.next_u64() as u8)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_values() {
+        let values = |seed| {
+            let mut prng = Prng::new(seed);
+            (0..100).map(|_| prng.data()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(values(42), values(42));
+        assert_ne!(values(42), values(1337));
+    }
+}
@@ -1,8 +1,12 @@
+use num_bigint::BigUint;
+use num_integer::Integer;
 use pallas_primitives::{
-    babbage::{CostMdls, MintedTx, Redeemer, TransactionInput, TransactionOutput},
+    babbage::{CostMdls, MintedTx, Redeemer, TransactionInput, TransactionOutput, Value},
     Fragment,
 };
 use pallas_traverse::{Era, MultiEraTx};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use error::Error;
 pub use eval::get_script_and_datum_lookup_table;
@@ -18,6 +22,7 @@ use crate::{
 pub mod error;
 mod eval;
 mod phase_one;
+pub mod property;
 pub mod script_context;
 #[cfg(test)]
 mod tests;
@@ -36,6 +41,32 @@ pub fn eval_phase_two(
     slot_config: &SlotConfig,
     run_phase_one: bool,
 ) -> Result<Vec<Redeemer>, Error> {
+    let redeemers = eval_phase_two_with_logs(
+        tx,
+        utxos,
+        cost_mdls,
+        initial_budget,
+        slot_config,
+        run_phase_one,
+    )?;
+
+    Ok(redeemers
+        .into_iter()
+        .map(|(redeemer, _logs)| redeemer)
+        .collect())
+}
+
+/// This function is the same as [`eval_phase_two`]
+/// but also returns, next to each redeemer, the traces
+/// emitted while evaluating its script.
+pub fn eval_phase_two_with_logs(
+    tx: &MintedTx,
+    utxos: &[ResolvedInput],
+    cost_mdls: Option<&CostMdls>,
+    initial_budget: Option<&ExBudget>,
+    slot_config: &SlotConfig,
+    run_phase_one: bool,
+) -> Result<Vec<(Redeemer, Vec<String>)>, Error> {
     let redeemers = tx.transaction_witness_set.redeemer.as_ref();
 
     let lookup_table = get_script_and_datum_lookup_table(tx, utxos);
@@ -47,27 +78,52 @@ pub fn eval_phase_two(
 
     match redeemers {
         Some(rs) => {
-            let mut collected_redeemers = vec![];
-
-            let mut remaining_budget = *initial_budget.unwrap_or(&ExBudget::default());
+            let initial_budget = *initial_budget.unwrap_or(&ExBudget::default());
 
-            for redeemer in rs.iter() {
-                let redeemer = eval::eval_redeemer(
+            let eval_redeemer = |redeemer, budget: &ExBudget| {
+                eval::eval_redeemer(
                     tx,
                     utxos,
                     slot_config,
                     redeemer,
                     &lookup_table,
                     cost_mdls,
-                    &remaining_budget,
-                )?;
+                    budget,
+                )
+            };
+
+            // Redeemers are independent from one another, except for sharing the budget.
+            // So, they are all evaluated in parallel with the whole budget. Then, going
+            // through them in order, those that failed or don't fit in what's left of the
+            // budget are evaluated again with what's left, exactly as if evaluating them
+            // one after the other. This way, results and errors are the same either way.
+            // Errors aren't `Send` though, so only successful outcomes are kept at first.
+            let evaluated: Vec<_> = rs
+                .par_iter()
+                .map(|redeemer| eval_redeemer(redeemer, &initial_budget).ok())
+                .collect();
+
+            let mut collected_redeemers = Vec::with_capacity(rs.len());
+
+            let mut remaining_budget = initial_budget;
+
+            for (redeemer, evaluated) in rs.iter().zip(evaluated) {
+                let (redeemer, logs) = match evaluated {
+                    Some((evaluated, logs))
+                        if evaluated.ex_units.steps as i64 <= remaining_budget.cpu
+                            && evaluated.ex_units.mem as i64 <= remaining_budget.mem =>
+                    {
+                        (evaluated, logs)
+                    }
+                    _ => eval_redeemer(redeemer, &remaining_budget)?,
+                };
 
                 // The substraction is safe here as ex units counting is done during evaluation.
                 // Redeemer would fail already if budget was negative.
                 remaining_budget.cpu -= redeemer.ex_units.steps as i64;
                 remaining_budget.mem -= redeemer.ex_units.mem as i64;
 
-                collected_redeemers.push(redeemer)
+                collected_redeemers.push((redeemer, logs))
             }
 
             Ok(collected_redeemers)
@@ -76,6 +132,35 @@ pub fn eval_phase_two(
     }
 }
 
+/// The outcome of evaluating a redeemer, as returned by [`eval_phase_two_raw_with_logs`].
+/// Everything is plain numbers and strings so that it serializes to a JSON object callers
+/// can use as-is, for example to display fees and traces.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvaluatedRedeemer {
+    /// The redeemer, with its execution units, encoded as CBOR.
+    #[serde(with = "hex_bytes")]
+    pub redeemer: Vec<u8>,
+    pub tag: String,
+    pub index: u32,
+    pub cpu: u64,
+    pub mem: u64,
+    /// Traces emitted by the script, in order.
+    pub logs: Vec<String>,
+}
+
+impl EvaluatedRedeemer {
+    pub fn new(redeemer: &Redeemer, logs: Vec<String>) -> Self {
+        EvaluatedRedeemer {
+            redeemer: redeemer.encode_fragment().unwrap(),
+            tag: eval::redeemer_tag_to_string(&redeemer.tag),
+            index: redeemer.index,
+            cpu: redeemer.ex_units.steps,
+            mem: redeemer.ex_units.mem as u64,
+            logs,
+        }
+    }
+}
+
 /// This function is the same as [`eval_phase_two`]
 /// but the inputs are raw bytes.
 /// initial_budget expects (cpu, mem).
@@ -88,6 +173,29 @@ pub fn eval_phase_two_raw(
     slot_config: (u64, u64, u32),
     run_phase_one: bool,
 ) -> Result<Vec<Vec<u8>>, Error> {
+    let redeemers = eval_phase_two_raw_with_logs(
+        tx_bytes,
+        utxos_bytes,
+        cost_mdls_bytes,
+        initial_budget,
+        slot_config,
+        run_phase_one,
+    )?;
+
+    Ok(redeemers.into_iter().map(|r| r.redeemer).collect())
+}
+
+/// This function is the same as [`eval_phase_two_raw`]
+/// but returns, for each redeemer, its execution units
+/// and the traces its script emitted next to the encoded redeemer.
+pub fn eval_phase_two_raw_with_logs(
+    tx_bytes: &[u8],
+    utxos_bytes: &[(Vec<u8>, Vec<u8>)],
+    cost_mdls_bytes: &[u8],
+    initial_budget: (u64, u64),
+    slot_config: (u64, u64, u32),
+    run_phase_one: bool,
+) -> Result<Vec<EvaluatedRedeemer>, Error> {
     let multi_era_tx = MultiEraTx::decode(Era::Babbage, tx_bytes)
         .or_else(|_| MultiEraTx::decode(Era::Alonzo, tx_bytes))?;
 
@@ -115,7 +223,7 @@ pub fn eval_phase_two_raw(
 
     match multi_era_tx {
         MultiEraTx::Babbage(tx) => {
-            match eval_phase_two(
+            match eval_phase_two_with_logs(
                 &tx,
                 &utxos,
                 Some(&cost_mdls),
@@ -125,7 +233,7 @@ pub fn eval_phase_two_raw(
             ) {
                 Ok(redeemers) => Ok(redeemers
                     .iter()
-                    .map(|r| r.encode_fragment().unwrap())
+                    .map(|(redeemer, logs)| EvaluatedRedeemer::new(redeemer, logs.clone()))
                     .collect()),
                 Err(err) => Err(err),
             }
@@ -142,6 +250,100 @@ pub fn eval_phase_two_raw(
     }
 }
 
+/// The script execution fee, in lovelace, of a transaction's redeemers once their
+/// `ExUnits` are known, e.g. from [`eval_phase_two`]. Prices are the protocol parameters
+/// `priceMem` and `priceSteps`, as `(numerator, denominator)`.
+///
+/// As in the ledger, prices are applied to the total units of all redeemers, and the
+/// result is rounded up only once.
+pub fn script_fee(redeemers: &[Redeemer], price_mem: (u64, u64), price_steps: (u64, u64)) -> u64 {
+    let (mem, steps) = redeemers.iter().fold(
+        (BigUint::default(), BigUint::default()),
+        |(mem, steps), redeemer| (mem + redeemer.ex_units.mem, steps + redeemer.ex_units.steps),
+    );
+
+    let (mem_numerator, mem_denominator) = price_mem;
+    let (steps_numerator, steps_denominator) = price_steps;
+
+    let numerator =
+        mem * mem_numerator * steps_denominator + steps * steps_numerator * mem_denominator;
+
+    let denominator = BigUint::from(mem_denominator) * steps_denominator;
+
+    numerator
+        .div_ceil(&denominator)
+        .try_into()
+        .expect("script fee overflows u64")
+}
+
+/// Check evaluated redeemers, e.g. from [`eval_phase_two`], against the limits of the
+/// transaction, beyond what evaluating each script checks:
+///
+/// - their total `ExUnits` must fit in `max_budget`, the maximum budget of a transaction;
+/// - the transaction's collateral, i.e. its collateral inputs minus its collateral return,
+///   must cover `collateral_percentage` percent of the fee the transaction declares. Like
+///   the ledger, this is the whole fee, size-based part included, and not only the
+///   [`script_fee`] of the redeemers.
+pub fn check_budget_and_collateral(
+    tx: &MintedTx,
+    utxos: &[ResolvedInput],
+    redeemers: &[Redeemer],
+    max_budget: &ExBudget,
+    collateral_percentage: u64,
+) -> Result<(), Error> {
+    let spent = redeemers
+        .iter()
+        .fold(ExBudget { mem: 0, cpu: 0 }, |spent, redeemer| ExBudget {
+            mem: spent.mem + redeemer.ex_units.mem as i64,
+            cpu: spent.cpu + redeemer.ex_units.steps as i64,
+        });
+
+    if spent.cpu > max_budget.cpu || spent.mem > max_budget.mem {
+        return Err(Error::OverBudget {
+            spent,
+            max: *max_budget,
+        });
+    }
+
+    let required: u64 = (BigUint::from(tx.transaction_body.fee) * collateral_percentage)
+        .div_ceil(&BigUint::from(100u64))
+        .try_into()
+        .expect("required collateral overflows u64");
+
+    let mut provided: u64 = 0;
+
+    for input in tx.transaction_body.collateral.iter().flatten() {
+        let utxo = utxos
+            .iter()
+            .find(|utxo| &utxo.input == input)
+            .ok_or(Error::ResolvedInputNotFound)?;
+
+        provided += lovelace(&utxo.output);
+    }
+
+    if let Some(collateral_return) = &tx.transaction_body.collateral_return {
+        provided = provided.saturating_sub(lovelace(collateral_return));
+    }
+
+    if provided < required {
+        return Err(Error::InsufficientCollateral { provided, required });
+    }
+
+    Ok(())
+}
+
+fn lovelace(output: &TransactionOutput) -> u64 {
+    let value = match output {
+        TransactionOutput::Legacy(output) => &output.amount,
+        TransactionOutput::PostAlonzo(output) => &output.value,
+    };
+
+    match value {
+        Value::Coin(coin) => *coin,
+        Value::Multiasset(coin, _) => *coin,
+    }
+}
+
 pub fn apply_params_to_script(
     params_bytes: &[u8], // PlutusData array
     plutus_script_bytes: &[u8],
@@ -152,14 +354,25 @@ pub fn apply_params_to_script(
     };
 
     let mut buffer = Vec::new();
-    let mut program = Program::<DeBruijn>::from_cbor(plutus_script_bytes, &mut buffer)?;
-
-    for param in params {
-        program = program.apply_data(param);
-    }
+    let program =
+        Program::<DeBruijn>::from_cbor(plutus_script_bytes, &mut buffer)?.apply_data_all(&params);
 
     match program.to_cbor() {
         Ok(res) => Ok(res),
         Err(_) => Err(Error::ApplyParamsError),
     }
 }
+
+mod hex_bytes {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'a, D: Deserializer<'a>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+
+        hex::decode(s).map_err(de::Error::custom)
+    }
+}
@@ -57,4 +57,15 @@ pub enum Error {
     },
     #[error("Failed to apply parameters to Plutus script.")]
     ApplyParamsError,
+    #[error("Malformed JSON data: {0}")]
+    MalformedJsonData(String),
+    #[error("Malformed UTxO JSON: {0}")]
+    MalformedUtxoJson(String),
+    #[error(
+        "Redeemers spend {} cpu and {} mem, over the transaction budget of {} cpu and {} mem.",
+        .spent.cpu, .spent.mem, .max.cpu, .max.mem
+    )]
+    OverBudget { spent: ExBudget, max: ExBudget },
+    #[error("Collateral of {provided} lovelace doesn't cover the {required} lovelace required.")]
+    InsufficientCollateral { provided: u64, required: u64 },
 }
@@ -12,7 +12,12 @@ use pallas_primitives::babbage::{
     StakeCredential, TransactionInput, TransactionOutput, Value, Withdrawals,
 };
 use pallas_traverse::{ComputeHash, OriginalHash};
-use std::{collections::HashMap, convert::TryInto, ops::Deref, vec};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryInto,
+    ops::Deref,
+    vec,
+};
 
 use super::{
     script_context::{
@@ -24,23 +29,18 @@ use super::{
 };
 use itertools::Itertools;
 
-fn slot_to_begin_posix_time(slot: u64, sc: &SlotConfig) -> u64 {
-    let ms_after_begin = (slot - sc.zero_slot) * sc.slot_length as u64;
-    sc.zero_time + ms_after_begin
-}
-
 fn slot_range_to_posix_time_range(slot_range: TimeRange, sc: &SlotConfig) -> TimeRange {
     TimeRange {
         lower_bound: slot_range
             .lower_bound
-            .map(|lower_bound| slot_to_begin_posix_time(lower_bound, sc)),
+            .map(|lower_bound| sc.slot_to_posix(lower_bound)),
         upper_bound: slot_range
             .upper_bound
-            .map(|upper_bound| slot_to_begin_posix_time(upper_bound, sc)),
+            .map(|upper_bound| sc.slot_to_posix(upper_bound)),
     }
 }
 
-fn redeemer_tag_to_string(redeemer_tag: &RedeemerTag) -> String {
+pub fn redeemer_tag_to_string(redeemer_tag: &RedeemerTag) -> String {
     match redeemer_tag {
         RedeemerTag::Spend => "Spend".to_string(),
         RedeemerTag::Mint => "Mint".to_string(),
@@ -113,12 +113,19 @@ enum ExecutionPurpose {
 pub struct DataLookupTable {
     datum: HashMap<DatumHash, PlutusData>,
     scripts: HashMap<ScriptHash, ScriptVersion>,
+    reference_scripts: HashSet<ScriptHash>,
 }
 
 impl DataLookupTable {
     pub fn scripts(&self) -> HashMap<ScriptHash, ScriptVersion> {
         self.scripts.clone()
     }
+
+    /// Hashes of the scripts only provided by reference, through the outputs spent or
+    /// referenced by the transaction, rather than in its witness set.
+    pub fn reference_scripts(&self) -> &HashSet<ScriptHash> {
+        &self.reference_scripts
+    }
 }
 
 pub fn get_tx_in_info_v1(
@@ -662,32 +669,56 @@ pub fn get_script_and_datum_lookup_table(
         scripts.insert(script.compute_hash(), ScriptVersion::V2(script.clone()));
     }
 
-    // discovery in utxos (script ref)
+    // discovery in utxos (script ref), only for those spent or referenced by the transaction
+
+    let mut reference_scripts = HashSet::new();
+
+    let reference_inputs = tx
+        .transaction_body
+        .reference_inputs
+        .clone()
+        .unwrap_or_default();
 
-    for utxo in utxos.iter() {
+    let referenced_utxos = utxos.iter().filter(|utxo| {
+        tx.transaction_body.inputs.contains(&utxo.input) || reference_inputs.contains(&utxo.input)
+    });
+
+    for utxo in referenced_utxos {
         match &utxo.output {
             TransactionOutput::Legacy(_) => {}
             TransactionOutput::PostAlonzo(output) => {
                 if let Some(script) = &output.script_ref {
-                    match &script.0 {
+                    let (hash, script) = match &script.0 {
                         Script::NativeScript(ns) => {
-                            scripts.insert(ns.compute_hash(), ScriptVersion::Native(ns.clone()));
+                            (ns.compute_hash(), ScriptVersion::Native(ns.clone()))
                         }
                         Script::PlutusV1Script(v1) => {
-                            scripts.insert(v1.compute_hash(), ScriptVersion::V1(v1.clone()));
+                            (v1.compute_hash(), ScriptVersion::V1(v1.clone()))
                         }
                         Script::PlutusV2Script(v2) => {
-                            scripts.insert(v2.compute_hash(), ScriptVersion::V2(v2.clone()));
+                            (v2.compute_hash(), ScriptVersion::V2(v2.clone()))
                         }
+                    };
+
+                    if !scripts.contains_key(&hash) {
+                        reference_scripts.insert(hash);
                     }
+
+                    scripts.insert(hash, script);
                 }
             }
         }
     }
 
-    DataLookupTable { datum, scripts }
+    DataLookupTable {
+        datum,
+        scripts,
+        reference_scripts,
+    }
 }
 
+/// Evaluate the script a redeemer points to. Returns the redeemer with the execution units
+/// the script needed, along with the traces it emitted.
 pub fn eval_redeemer(
     tx: &MintedTx,
     utxos: &[ResolvedInput],
@@ -696,7 +727,7 @@ pub fn eval_redeemer(
     lookup_table: &DataLookupTable,
     cost_mdls_opt: Option<&CostMdls>,
     initial_budget: &ExBudget,
-) -> Result<Redeemer, Error> {
+) -> Result<(Redeemer, Vec<String>), Error> {
     let result = || {
         let purpose = get_script_purpose(
             redeemer,
@@ -737,7 +768,7 @@ pub fn eval_redeemer(
 
                         program.eval_as(&Language::PlutusV1, costs, Some(initial_budget))
                     } else {
-                        program.eval_v1()
+                        program.eval_v1_with_budget(*initial_budget)
                     };
 
                     match result {
@@ -755,7 +786,7 @@ pub fn eval_redeemer(
                         },
                     };
 
-                    Ok(new_redeemer)
+                    Ok((new_redeemer, logs))
                 }
                 ScriptVersion::V2(script) => {
                     let tx_info = get_tx_info_v2(tx, utxos, slot_config)?;
@@ -783,7 +814,7 @@ pub fn eval_redeemer(
 
                         program.eval_as(&Language::PlutusV2, costs, Some(initial_budget))
                     } else {
-                        program.eval(ExBudget::default())
+                        program.eval(*initial_budget)
                     };
 
                     match result {
@@ -801,7 +832,7 @@ pub fn eval_redeemer(
                         },
                     };
 
-                    Ok(new_redeemer)
+                    Ok((new_redeemer, logs))
                 }
                 ScriptVersion::Native(_) => Err(Error::NativeScriptPhaseTwo),
             },
@@ -831,7 +862,7 @@ pub fn eval_redeemer(
 
                         program.eval_as(&Language::PlutusV1, costs, Some(initial_budget))
                     } else {
-                        program.eval_v1()
+                        program.eval_v1_with_budget(*initial_budget)
                     };
 
                     match result {
@@ -849,7 +880,7 @@ pub fn eval_redeemer(
                         },
                     };
 
-                    Ok(new_redeemer)
+                    Ok((new_redeemer, logs))
                 }
                 ScriptVersion::V2(script) => {
                     let tx_info = get_tx_info_v2(tx, utxos, slot_config)?;
@@ -876,7 +907,7 @@ pub fn eval_redeemer(
 
                         program.eval_as(&Language::PlutusV2, costs, Some(initial_budget))
                     } else {
-                        program.eval(ExBudget::default())
+                        program.eval(*initial_budget)
                     };
 
                     match result {
@@ -894,7 +925,7 @@ pub fn eval_redeemer(
                         },
                     };
 
-                    Ok(new_redeemer)
+                    Ok((new_redeemer, logs))
                 }
                 ScriptVersion::Native(_) => Err(Error::NativeScriptPhaseTwo),
             },
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use pallas_addresses::{Address, ScriptHash, ShelleyPaymentPart, StakePayload};
 use pallas_codec::utils::{KeyValuePairs, MaybeIndefArray};
@@ -29,16 +29,23 @@ pub fn eval_phase_one(
 ) -> Result<(), Error> {
     let scripts_needed = scripts_needed(tx, utxos)?;
 
-    validate_missing_scripts(&scripts_needed, lookup_table.scripts())?;
+    validate_missing_scripts(
+        &scripts_needed,
+        lookup_table.scripts(),
+        lookup_table.reference_scripts(),
+    )?;
 
     has_exact_set_of_redeemers(tx, &scripts_needed, lookup_table.scripts())?;
 
     Ok(())
 }
 
+/// Scripts provided by reference may be there for other purposes than this transaction's, so
+/// only those in the witness set count as extraneous when they aren't needed.
 pub fn validate_missing_scripts(
     needed: &AlonzoScriptsNeeded,
     txscripts: HashMap<ScriptHash, ScriptVersion>,
+    reference_scripts: &HashSet<ScriptHash>,
 ) -> Result<(), Error> {
     let received_hashes = txscripts.keys().copied().collect::<Vec<ScriptHash>>();
 
@@ -53,7 +60,7 @@ pub fn validate_missing_scripts(
 
     let extra: Vec<_> = received_hashes
         .into_iter()
-        .filter(|x| !needed_hashes.contains(x))
+        .filter(|x| !needed_hashes.contains(x) && !reference_scripts.contains(x))
         .map(|x| format!("[Extraneous (sh: {x:?})]"))
         .collect();
 
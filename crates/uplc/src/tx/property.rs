@@ -0,0 +1,220 @@
+use pallas_addresses::{Network, ShelleyDelegationPart};
+use pallas_codec::utils::{CborWrap, Nullable};
+use pallas_crypto::hash::Hash;
+use pallas_primitives::{
+    babbage::{
+        CostMdls, DatumOption, ExUnits, PlutusV2Script, PostAlonzoTransactionOutput, Redeemer,
+        RedeemerTag, TransactionBody, TransactionInput, TransactionOutput, Tx, Value, WitnessSet,
+    },
+    Fragment,
+};
+use pallas_traverse::{Era, MultiEraTx};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ast::{DeBruijn, Program},
+    machine::cost_model::ExBudget,
+    prng::Prng,
+    PlutusData,
+};
+
+use super::{error::Error, eval_phase_two, hex_bytes, script_context::ResolvedInput, SlotConfig};
+
+/// A generated transaction, along with the outcome expected from phase-two evaluation.
+/// Transactions and UTxOs are kept in their CBOR form so that a case can be written
+/// down as-is and replayed later, for example as a regression test.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Case {
+    #[serde(with = "hex_bytes")]
+    pub tx: Vec<u8>,
+    pub utxos: Vec<Utxo>,
+    pub expect_success: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Utxo {
+    #[serde(with = "hex_bytes")]
+    pub input: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub output: Vec<u8>,
+}
+
+/// A case for which the validator(s) didn't behave as expected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Counterexample {
+    pub seed: u64,
+    pub case: Case,
+    /// The evaluation error, when the transaction was unexpectedly rejected.
+    pub error: Option<String>,
+}
+
+/// Produces transactions to evaluate. Implementations are responsible for building
+/// transactions whose datums and redeemers respect the validator's schemas; the seed
+/// is the only source of randomness, so that any case can be reproduced.
+pub trait Generator {
+    fn generate(&mut self, seed: u64) -> Case;
+}
+
+/// Parameters of the evaluation, as given to [`eval_phase_two`].
+pub struct Settings<'a> {
+    pub cost_mdls: Option<&'a CostMdls>,
+    pub initial_budget: Option<&'a ExBudget>,
+    pub slot_config: &'a SlotConfig,
+}
+
+impl Case {
+    /// Run phase-one and phase-two evaluation of the case's transaction.
+    pub fn eval(&self, settings: &Settings) -> Result<Vec<Redeemer>, Error> {
+        let utxos = self
+            .utxos
+            .iter()
+            .map(|utxo| {
+                Ok(ResolvedInput {
+                    input: TransactionInput::decode_fragment(&utxo.input)?,
+                    output: TransactionOutput::decode_fragment(&utxo.output)?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        match MultiEraTx::decode(Era::Babbage, &self.tx)? {
+            MultiEraTx::Babbage(tx) => eval_phase_two(
+                &tx,
+                &utxos,
+                settings.cost_mdls,
+                settings.initial_budget,
+                settings.slot_config,
+                true,
+            ),
+            _ => unreachable!("decoded as a Babbage transaction"),
+        }
+    }
+
+    /// A transaction spending, with `redeemer`, an output locked by `validator` (as a Plutus
+    /// V2 script) with `datum` inlined. The transaction has no other input nor any output.
+    pub fn spend(
+        validator: &Program<DeBruijn>,
+        datum: PlutusData,
+        redeemer: PlutusData,
+        expect_success: bool,
+    ) -> Self {
+        let input = TransactionInput {
+            transaction_id: Hash::new([0; 32]),
+            index: 0,
+        };
+
+        let locked = TransactionOutput::PostAlonzo(PostAlonzoTransactionOutput {
+            address: validator
+                .address(Network::Testnet, ShelleyDelegationPart::Null)
+                .to_vec()
+                .into(),
+            value: Value::Coin(10_000_000),
+            datum_option: Some(DatumOption::Data(CborWrap(datum))),
+            script_ref: None,
+        });
+
+        let tx = Tx {
+            transaction_body: TransactionBody {
+                inputs: vec![input.clone()],
+                outputs: vec![],
+                fee: 0,
+                ttl: None,
+                certificates: None,
+                withdrawals: None,
+                update: None,
+                auxiliary_data_hash: None,
+                validity_interval_start: None,
+                mint: None,
+                script_data_hash: None,
+                collateral: None,
+                required_signers: None,
+                network_id: None,
+                collateral_return: None,
+                total_collateral: None,
+                reference_inputs: None,
+            },
+            transaction_witness_set: WitnessSet {
+                vkeywitness: None,
+                native_script: None,
+                bootstrap_witness: None,
+                plutus_v1_script: None,
+                plutus_data: None,
+                redeemer: Some(vec![Redeemer {
+                    tag: RedeemerTag::Spend,
+                    index: 0,
+                    data: redeemer,
+                    ex_units: ExUnits { mem: 0, steps: 0 },
+                }]),
+                plutus_v2_script: Some(vec![PlutusV2Script(validator.to_cbor().unwrap().into())]),
+            },
+            success: true,
+            auxiliary_data: Nullable::Null,
+        };
+
+        Case {
+            tx: tx.encode_fragment().unwrap(),
+            utxos: vec![Utxo {
+                input: input.encode_fragment().unwrap(),
+                output: locked.encode_fragment().unwrap(),
+            }],
+            expect_success,
+        }
+    }
+}
+
+/// Spending transactions for a validator (see [`Case::spend`]), with a datum and a redeemer
+/// drawn by `draw`, e.g. following the validator's schemas. `expect_success` tells whether
+/// the validator ought to accept a given datum and redeemer.
+pub struct Spend<'a, D, E> {
+    validator: &'a Program<DeBruijn>,
+    draw: D,
+    expect_success: E,
+}
+
+impl<'a, D, E> Spend<'a, D, E>
+where
+    D: FnMut(&mut Prng) -> (PlutusData, PlutusData),
+    E: Fn(&PlutusData, &PlutusData) -> bool,
+{
+    pub fn new(validator: &'a Program<DeBruijn>, draw: D, expect_success: E) -> Self {
+        Spend {
+            validator,
+            draw,
+            expect_success,
+        }
+    }
+}
+
+impl<'a, D, E> Generator for Spend<'a, D, E>
+where
+    D: FnMut(&mut Prng) -> (PlutusData, PlutusData),
+    E: Fn(&PlutusData, &PlutusData) -> bool,
+{
+    fn generate(&mut self, seed: u64) -> Case {
+        let (datum, redeemer) = (self.draw)(&mut Prng::new(seed));
+
+        let expect_success = (self.expect_success)(&datum, &redeemer);
+
+        Case::spend(self.validator, datum, redeemer, expect_success)
+    }
+}
+
+/// Evaluate `runs` generated transactions, starting from `seed`, and collect every
+/// unexpected acceptance or rejection.
+pub fn check<G>(generator: &mut G, seed: u64, runs: u64, settings: &Settings) -> Vec<Counterexample>
+where
+    G: Generator,
+{
+    (seed..seed.saturating_add(runs))
+        .filter_map(|seed| {
+            let case = generator.generate(seed);
+
+            let error = case.eval(settings).err().map(|e| e.to_string());
+
+            if error.is_none() == case.expect_success {
+                None
+            } else {
+                Some(Counterexample { seed, case, error })
+            }
+        })
+        .collect()
+}
@@ -1,13 +1,23 @@
 use pallas_addresses::Address;
-use pallas_codec::utils::KeyValuePairs;
+use pallas_codec::{
+    minicbor,
+    utils::{Bytes, CborWrap, KeyValuePairs},
+};
 use pallas_crypto::hash::Hash;
-use pallas_primitives::babbage::{
-    AddrKeyhash, Certificate, Coin, DatumHash, PlutusData, PolicyId, Redeemer, StakeCredential,
-    TransactionInput, TransactionOutput, Value,
+use pallas_primitives::{
+    babbage::{
+        AddrKeyhash, Certificate, Coin, DatumHash, DatumOption, PlutusData, PlutusV1Script,
+        PlutusV2Script, PolicyId, PostAlonzoTransactionOutput, Redeemer, Script, StakeCredential,
+        TransactionInput, TransactionOutput, Value,
+    },
+    Fragment,
 };
 use serde::Deserialize;
 
-use super::to_plutus_data::MintValue;
+use super::{
+    error::Error,
+    to_plutus_data::{self, MintValue, ToPlutusData},
+};
 
 #[derive(Debug, PartialEq, Clone, Deserialize)]
 pub struct ResolvedInput {
@@ -15,6 +25,170 @@ pub struct ResolvedInput {
     pub output: TransactionOutput,
 }
 
+impl ResolvedInput {
+    /// Read the UTxOs written by `cardano-cli query utxo --out-file`: an object mapping each
+    /// `<tx hash>#<index>` to its address, value, datum and reference script. Inline datums may
+    /// be given as raw CBOR (`inlineDatumRaw`) or in the detailed JSON schema (`inlineDatum`).
+    pub fn from_cli_json(json: &serde_json::Value) -> Result<Vec<ResolvedInput>, Error> {
+        let utxos = json.as_object().ok_or_else(|| {
+            Error::MalformedUtxoJson(format!("expected an object of UTxOs in {json}"))
+        })?;
+
+        utxos
+            .iter()
+            .map(|(out_ref, utxo)| {
+                let malformed =
+                    |reason: &str| Error::MalformedUtxoJson(format!("{reason} for {out_ref}"));
+
+                let field = |key: &str| utxo.get(key).filter(|value| !value.is_null());
+
+                let hex_field = |key: &str| {
+                    field(key)
+                        .map(|value| {
+                            value
+                                .as_str()
+                                .and_then(|value| hex::decode(value).ok())
+                                .ok_or_else(|| malformed(&format!("expected base16 '{key}'")))
+                        })
+                        .transpose()
+                };
+
+                let input = out_ref
+                    .split_once('#')
+                    .and_then(|(hash, index)| {
+                        Some(TransactionInput {
+                            transaction_id: hash.parse().ok()?,
+                            index: index.parse().ok()?,
+                        })
+                    })
+                    .ok_or_else(|| {
+                        malformed("expected an output reference as <tx hash>#<index>")
+                    })?;
+
+                let address = field("address")
+                    .and_then(serde_json::Value::as_str)
+                    .ok_or_else(|| malformed("expected a bech32 'address'"))?;
+
+                let value = cli_value(
+                    field("value").ok_or_else(|| malformed("expected a 'value'"))?,
+                    &malformed,
+                )?;
+
+                let datum_option = if let Some(cbor) = hex_field("inlineDatumRaw")? {
+                    let data = PlutusData::decode_fragment(&cbor)
+                        .map_err(|_| malformed("expected CBOR data in 'inlineDatumRaw'"))?;
+
+                    Some(DatumOption::Data(CborWrap(data)))
+                } else if let Some(json) = field("inlineDatum") {
+                    Some(DatumOption::Data(CborWrap(to_plutus_data::from_json(
+                        json,
+                    )?)))
+                } else if let Some(hash) = hex_field("datumhash")? {
+                    let hash: [u8; 32] = hash
+                        .try_into()
+                        .map_err(|_| malformed("expected a 32-byte 'datumhash'"))?;
+
+                    Some(DatumOption::Hash(hash.into()))
+                } else {
+                    None
+                };
+
+                let script_ref = field("referenceScript")
+                    .map(|script| cli_script(script, &malformed))
+                    .transpose()?;
+
+                Ok(ResolvedInput {
+                    input,
+                    output: TransactionOutput::PostAlonzo(PostAlonzoTransactionOutput {
+                        address: Address::from_bech32(address)?.to_vec().into(),
+                        value,
+                        datum_option,
+                        script_ref: script_ref.map(CborWrap),
+                    }),
+                })
+            })
+            .collect()
+    }
+}
+
+/// A value as written by cardano-cli: `{"lovelace": 42, "<policy id>": {"<asset name>": 1}}`.
+fn cli_value(json: &serde_json::Value, malformed: &dyn Fn(&str) -> Error) -> Result<Value, Error> {
+    let object = json
+        .as_object()
+        .ok_or_else(|| malformed("expected an object as 'value'"))?;
+
+    let lovelace = object
+        .get("lovelace")
+        .and_then(serde_json::Value::as_u64)
+        .ok_or_else(|| malformed("expected a 'lovelace' amount"))?;
+
+    let assets = object
+        .iter()
+        .filter(|(key, _)| key.as_str() != "lovelace")
+        .map(|(policy_id, assets)| {
+            let policy_id: PolicyId = policy_id
+                .parse()
+                .map_err(|_| malformed(&format!("expected a policy id, got '{policy_id}'")))?;
+
+            let assets = assets
+                .as_object()
+                .ok_or_else(|| malformed("expected an object of assets under each policy"))?
+                .iter()
+                .map(|(asset_name, quantity)| {
+                    let asset_name = hex::decode(asset_name).map_err(|_| {
+                        malformed(&format!("expected a base16 asset name, got '{asset_name}'"))
+                    })?;
+
+                    let quantity = quantity
+                        .as_u64()
+                        .ok_or_else(|| malformed("expected a non-negative asset quantity"))?;
+
+                    Ok((asset_name.into(), quantity))
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            Ok((policy_id, KeyValuePairs::Def(assets)))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(if assets.is_empty() {
+        Value::Coin(lovelace)
+    } else {
+        Value::Multiasset(lovelace, KeyValuePairs::Def(assets))
+    })
+}
+
+/// A reference script as written by cardano-cli, in a text envelope:
+/// `{"script": {"type": "PlutusScriptV2", "cborHex": ".."}}`.
+fn cli_script(
+    json: &serde_json::Value,
+    malformed: &dyn Fn(&str) -> Error,
+) -> Result<Script, Error> {
+    let envelope = json.get("script").unwrap_or(json);
+
+    let cbor = envelope
+        .get("cborHex")
+        .and_then(serde_json::Value::as_str)
+        .and_then(|cbor| hex::decode(cbor).ok())
+        .ok_or_else(|| malformed("expected a base16 'cborHex' in 'referenceScript'"))?;
+
+    let bytes = || {
+        minicbor::decode::<Bytes>(&cbor)
+            .map_err(|_| malformed("expected a CBOR bytestring as 'cborHex'"))
+    };
+
+    match envelope.get("type").and_then(serde_json::Value::as_str) {
+        Some("PlutusScriptV1") => Ok(Script::PlutusV1Script(PlutusV1Script(bytes()?))),
+        Some("PlutusScriptV2") => Ok(Script::PlutusV2Script(PlutusV2Script(bytes()?))),
+        Some("SimpleScript" | "SimpleScriptV1" | "SimpleScriptV2") => Ok(Script::NativeScript(
+            minicbor::decode(&cbor).map_err(|_| malformed("expected a CBOR native script"))?,
+        )),
+        _ => Err(malformed(
+            "expected a 'type' of PlutusScriptV1, PlutusScriptV2 or SimpleScript",
+        )),
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct TxInInfo {
     pub out_ref: TransactionInput,
@@ -76,6 +250,108 @@ pub struct ScriptContext {
     pub purpose: ScriptPurpose,
 }
 
+/// Assembles a (V2) script context by hand, to run validators against made-up transactions
+/// in tests. Anything not explicitly given is left empty: no fee, no mint, no certificates, no
+/// withdrawals, no redeemers, no datums, an infinite validity range and a null transaction id.
+#[derive(Debug, Clone)]
+pub struct ScriptContextBuilder {
+    purpose: ScriptPurpose,
+    inputs: Vec<ResolvedInput>,
+    reference_inputs: Vec<ResolvedInput>,
+    outputs: Vec<TransactionOutput>,
+    signatories: Vec<AddrKeyhash>,
+    valid_range: TimeRange,
+}
+
+impl ScriptContextBuilder {
+    /// A context for spending the given output. The output itself must still be added as an
+    /// input, with [`ScriptContextBuilder::with_input`].
+    pub fn spend(out_ref: TransactionInput) -> Self {
+        ScriptContextBuilder {
+            purpose: ScriptPurpose::Spending(out_ref),
+            inputs: vec![],
+            reference_inputs: vec![],
+            outputs: vec![],
+            signatories: vec![],
+            valid_range: TimeRange {
+                lower_bound: None,
+                upper_bound: None,
+            },
+        }
+    }
+
+    pub fn with_input(mut self, input: ResolvedInput) -> Self {
+        self.inputs.push(input);
+        self
+    }
+
+    pub fn with_reference_input(mut self, input: ResolvedInput) -> Self {
+        self.reference_inputs.push(input);
+        self
+    }
+
+    pub fn with_output(mut self, output: TransactionOutput) -> Self {
+        self.outputs.push(output);
+        self
+    }
+
+    pub fn with_signatory(mut self, signatory: AddrKeyhash) -> Self {
+        self.signatories.push(signatory);
+        self
+    }
+
+    /// Bounds of the validity range, as POSIX times in milliseconds.
+    pub fn with_valid_range(mut self, valid_range: TimeRange) -> Self {
+        self.valid_range = valid_range;
+        self
+    }
+
+    /// The script context, with inputs and signatories sorted as the ledger would.
+    pub fn context(self) -> ScriptContext {
+        let tx_in_info = |inputs: Vec<ResolvedInput>| {
+            let mut inputs = inputs
+                .into_iter()
+                .map(|ResolvedInput { input, output }| TxInInfo {
+                    out_ref: input,
+                    resolved: TxOut::V2(output),
+                })
+                .collect::<Vec<_>>();
+
+            inputs.sort_by(|a, b| a.out_ref.cmp(&b.out_ref));
+
+            inputs
+        };
+
+        let mut signatories = self.signatories;
+        signatories.sort();
+
+        ScriptContext {
+            tx_info: TxInfo::V2(TxInfoV2 {
+                inputs: tx_in_info(self.inputs),
+                reference_inputs: tx_in_info(self.reference_inputs),
+                outputs: self.outputs.into_iter().map(TxOut::V2).collect(),
+                fee: Value::Coin(0),
+                mint: MintValue {
+                    mint_value: KeyValuePairs::Indef(vec![]),
+                },
+                dcert: vec![],
+                wdrl: KeyValuePairs::Indef(vec![]),
+                valid_range: self.valid_range,
+                signatories,
+                redeemers: KeyValuePairs::Indef(vec![]),
+                data: KeyValuePairs::Indef(vec![]),
+                id: Hash::new([0; 32]),
+            }),
+            purpose: self.purpose,
+        }
+    }
+
+    /// The script context as `Data`, ready to be applied to a validator.
+    pub fn build(self) -> PlutusData {
+        self.context().to_plutus_data()
+    }
+}
+
 //---- Time conversion: slot range => posix time range
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct TimeRange {
@@ -91,10 +367,47 @@ pub struct SlotConfig {
 
 impl Default for SlotConfig {
     fn default() -> Self {
+        Self::mainnet()
+    }
+}
+
+impl SlotConfig {
+    pub fn mainnet() -> Self {
         Self {
             slot_length: 1000,
             zero_slot: 4492800,
             zero_time: 1596059091000,
         }
     }
+
+    pub fn preprod() -> Self {
+        Self {
+            slot_length: 1000,
+            zero_slot: 86400,
+            zero_time: 1655769600000,
+        }
+    }
+
+    pub fn preview() -> Self {
+        Self {
+            slot_length: 1000,
+            zero_slot: 0,
+            zero_time: 1666656000000,
+        }
+    }
+
+    /// The POSIX time, in milliseconds, at which a slot begins. That's what validators see
+    /// as the bounds of a transaction's validity interval. Slots before `zero_slot` are
+    /// all considered to begin at `zero_time`.
+    pub fn slot_to_posix(&self, slot: u64) -> u64 {
+        let ms_after_begin = slot.saturating_sub(self.zero_slot) * self.slot_length as u64;
+        self.zero_time + ms_after_begin
+    }
+
+    /// The slot a POSIX time, in milliseconds, falls in. Times before `zero_time` are all
+    /// considered to fall in `zero_slot`.
+    pub fn posix_to_slot(&self, ms: u64) -> u64 {
+        let ms_after_begin = ms.saturating_sub(self.zero_time);
+        self.zero_slot + ms_after_begin / self.slot_length as u64
+    }
 }
@@ -1,13 +1,35 @@
-use pallas_codec::utils::MaybeIndefArray;
+use pallas_codec::utils::{CborWrap, MaybeIndefArray, Nullable};
+use pallas_crypto::hash::Hash;
 use pallas_primitives::{
-    babbage::{CostMdls, TransactionInput, TransactionOutput},
+    babbage::{
+        BigInt, Constr, CostMdls, DatumOption, ExUnits, Language, PlutusData, PlutusV2Script,
+        PostAlonzoTransactionOutput, Redeemer, RedeemerTag, Script, TransactionBody,
+        TransactionInput, TransactionOutput, Tx, Value, WitnessSet,
+    },
     Fragment,
 };
-use pallas_traverse::{Era, MultiEraTx};
+use pallas_traverse::{ComputeHash, Era, MultiEraTx};
 
-use crate::machine::cost_model::ExBudget;
+use crate::{
+    ast::{script_hash_from_bech32, DeBruijn, Program},
+    machine::cost_model::{parameter_names, ExBudget},
+    parser,
+};
+
+use super::{
+    check_budget_and_collateral,
+    error::Error,
+    eval::{eval_redeemer, get_script_and_datum_lookup_table},
+    eval_phase_two, eval_phase_two_raw_with_logs, eval_phase_two_with_logs,
+    property::{self, Case, Counterexample, Generator, Settings},
+    script_context::ScriptContextBuilder,
+    script_fee,
+    to_plutus_data::{from_json, to_diagnostic_cbor, to_json, ToPlutusData},
+    EvaluatedRedeemer, ResolvedInput, SlotConfig,
+};
 
-use super::{eval_phase_two, ResolvedInput, SlotConfig};
+/// A Plutus V2 minting transaction from the preview network, used by `test_eval`.
+const MINT_TX: &str = "84a80081825820975c17a4fed0051be622328efa548e206657d2b65a19224bf6ff8132571e6a5002018282581d60b6c8794e9a7a26599440a4d0fd79cd07644d15917ff13694f1f67235821a000f41f0a1581cc4f241450001af08f3ddbaf9335db79883cbcd81071b8e3508de3055a1400a82581d60b6c8794e9a7a26599440a4d0fd79cd07644d15917ff13694f1f672351a0084192f021a00053b6109a1581cc4f241450001af08f3ddbaf9335db79883cbcd81071b8e3508de3055a1400a0b5820b4f96b0acec8beff2adededa8ba317bcac92174f0f65ccefe569b9a6aac7375a0d818258206c732139de33e916342707de2aebef2252c781640326ff37b86ec99d97f1ba8d011082581d60b6c8794e9a7a26599440a4d0fd79cd07644d15917ff13694f1f672351b00000001af0cdfa2111a0007d912a3008182582031ae74f8058527afb305d7495b10a99422d9337fc199e1f28044f2c477a0f9465840b8b97b7c3b4e19ecfc2fcd9884ee53a35887ee6e4d36901b9ecbac3fe032d7e8a4358305afa573a86396e378255651ed03501906e9def450e588d4bb36f42a050581840100d87980821a000b68081a0cf3a5bf06815909b25909af010000323322323232323232323232323232323232323232332232323232323232323233223232223232533533223233025323233355300f1200135028502623500122333553012120013502b50292350012233350012330314800000488cc0c80080048cc0c400520000013355300e1200123500122335501c0023335001233553012120012350012233550200023550140010012233355500f0150020012335530121200123500122335502000235501300100133355500a01000200130105002300f5001533532350012222222222220045001102a2216135001220023333573466e1cd55ce9baa0044800080808c98c8080cd5ce01081000f1999ab9a3370e6aae7540092000233221233001003002323232323232323232323232323333573466e1cd55cea8062400046666666666664444444444442466666666666600201a01801601401201000e00c00a00800600466a03803a6ae854030cd4070074d5d0a80599a80e00f1aba1500a3335502075ca03e6ae854024ccd54081d7280f9aba1500833501c02835742a00e666aa040052eb4d5d0a8031919191999ab9a3370e6aae75400920002332212330010030023232323333573466e1cd55cea8012400046644246600200600466a066eb4d5d0a801181a1aba135744a004464c6406c66ae700dc0d80d04d55cf280089baa00135742a0046464646666ae68cdc39aab9d5002480008cc8848cc00400c008cd40cdd69aba150023034357426ae8940088c98c80d8cd5ce01b81b01a09aab9e5001137540026ae84d5d1280111931901919ab9c033032030135573ca00226ea8004d5d0a80299a80e3ae35742a008666aa04004a40026ae85400cccd54081d710009aba150023027357426ae8940088c98c80b8cd5ce01781701609aba25001135744a00226ae8940044d5d1280089aba25001135744a00226ae8940044d5d1280089aba25001135744a00226aae7940044dd50009aba150023017357426ae8940088c98c8080cd5ce01081000f080f89931900f99ab9c4901035054350001f135573ca00226ea8004444888ccd54c010480054040cd54c01c480048d400488cd54054008d54024004ccd54c0104800488d4008894cd4ccd54c03048004c8cd409c88ccd400c88008008004d40048800448cc004894cd400840b040040a48d400488cc028008014018400c4cd405001000d4044004cd54c01c480048d400488c8cd5405800cc004014c8004d540a4894cd40044d5402800c884d4008894cd4cc03000802044888cc0080280104c01800c008c8004d5408888448894cd40044008884cc014008ccd54c01c480040140100044484888c00c0104484888c004010c8004d5407c8844894cd400454038884cd403cc010008cd54c01848004010004c8004d5407888448894cd40044d400c88004884ccd401488008c010008ccd54c01c4800401401000488ccd5cd19b8f00200101e01d2350012222222222220091232230023758002640026aa038446666aae7c004940288cd4024c010d5d080118019aba2002015232323333573466e1cd55cea80124000466442466002006004601a6ae854008c014d5d09aba2500223263201533573802c02a02626aae7940044dd50009191919191999ab9a3370e6aae75401120002333322221233330010050040030023232323333573466e1cd55cea80124000466442466002006004602c6ae854008cd4040054d5d09aba2500223263201a33573803603403026aae7940044dd50009aba150043335500875ca00e6ae85400cc8c8c8cccd5cd19b875001480108c84888c008010d5d09aab9e500323333573466e1d4009200223212223001004375c6ae84d55cf280211999ab9a3370ea00690001091100191931900e19ab9c01d01c01a019018135573aa00226ea8004d5d0a80119a8063ae357426ae8940088c98c8058cd5ce00b80b00a09aba25001135744a00226aae7940044dd5000899aa800bae75a224464460046eac004c8004d5406488c8cccd55cf80112804119a80399aa80498031aab9d5002300535573ca00460086ae8800c04c4d5d08008891001091091198008020018891091980080180109119191999ab9a3370ea0029000119091180100198029aba135573ca00646666ae68cdc3a801240044244002464c6402066ae700440400380344d55cea80089baa001232323333573466e1d400520062321222230040053007357426aae79400c8cccd5cd19b875002480108c848888c008014c024d5d09aab9e500423333573466e1d400d20022321222230010053007357426aae7940148cccd5cd19b875004480008c848888c00c014dd71aba135573ca00c464c6402066ae7004404003803403002c4d55cea80089baa001232323333573466e1cd55cea80124000466442466002006004600a6ae854008dd69aba135744a004464c6401866ae700340300284d55cf280089baa0012323333573466e1cd55cea800a400046eb8d5d09aab9e500223263200a33573801601401026ea80048c8c8c8c8c8cccd5cd19b8750014803084888888800c8cccd5cd19b875002480288488888880108cccd5cd19b875003480208cc8848888888cc004024020dd71aba15005375a6ae84d5d1280291999ab9a3370ea00890031199109111111198010048041bae35742a00e6eb8d5d09aba2500723333573466e1d40152004233221222222233006009008300c35742a0126eb8d5d09aba2500923333573466e1d40192002232122222223007008300d357426aae79402c8cccd5cd19b875007480008c848888888c014020c038d5d09aab9e500c23263201333573802802602202001e01c01a01801626aae7540104d55cf280189aab9e5002135573ca00226ea80048c8c8c8c8cccd5cd19b875001480088ccc888488ccc00401401000cdd69aba15004375a6ae85400cdd69aba135744a00646666ae68cdc3a80124000464244600400660106ae84d55cf280311931900619ab9c00d00c00a009135573aa00626ae8940044d55cf280089baa001232323333573466e1d400520022321223001003375c6ae84d55cf280191999ab9a3370ea004900011909118010019bae357426aae7940108c98c8024cd5ce00500480380309aab9d50011375400224464646666ae68cdc3a800a40084244400246666ae68cdc3a8012400446424446006008600c6ae84d55cf280211999ab9a3370ea00690001091100111931900519ab9c00b00a008007006135573aa00226ea80048c8cccd5cd19b8750014800880348cccd5cd19b8750024800080348c98c8018cd5ce00380300200189aab9d37540029309000a4810350543100112330010020072253350021001100612335002223335003220020020013500122001122123300100300222333573466e1c00800401000c488008488004448c8c00400488cc00cc008008005f5f6";
 
 #[test]
 fn test_eval() {
@@ -23,7 +45,7 @@ fn test_eval() {
         txInfo :: Api.TxInfo
         txInfo = Api.scriptContextTxInfo ctx */
 
-    let tx_bytes = hex::decode("84a80081825820975c17a4fed0051be622328efa548e206657d2b65a19224bf6ff8132571e6a5002018282581d60b6c8794e9a7a26599440a4d0fd79cd07644d15917ff13694f1f67235821a000f41f0a1581cc4f241450001af08f3ddbaf9335db79883cbcd81071b8e3508de3055a1400a82581d60b6c8794e9a7a26599440a4d0fd79cd07644d15917ff13694f1f672351a0084192f021a00053b6109a1581cc4f241450001af08f3ddbaf9335db79883cbcd81071b8e3508de3055a1400a0b5820b4f96b0acec8beff2adededa8ba317bcac92174f0f65ccefe569b9a6aac7375a0d818258206c732139de33e916342707de2aebef2252c781640326ff37b86ec99d97f1ba8d011082581d60b6c8794e9a7a26599440a4d0fd79cd07644d15917ff13694f1f672351b00000001af0cdfa2111a0007d912a3008182582031ae74f8058527afb305d7495b10a99422d9337fc199e1f28044f2c477a0f9465840b8b97b7c3b4e19ecfc2fcd9884ee53a35887ee6e4d36901b9ecbac3fe032d7e8a4358305afa573a86396e378255651ed03501906e9def450e588d4bb36f42a050581840100d87980821a000b68081a0cf3a5bf06815909b25909af010000323322323232323232323232323232323232323232332232323232323232323233223232223232533533223233025323233355300f1200135028502623500122333553012120013502b50292350012233350012330314800000488cc0c80080048cc0c400520000013355300e1200123500122335501c0023335001233553012120012350012233550200023550140010012233355500f0150020012335530121200123500122335502000235501300100133355500a01000200130105002300f5001533532350012222222222220045001102a2216135001220023333573466e1cd55ce9baa0044800080808c98c8080cd5ce01081000f1999ab9a3370e6aae7540092000233221233001003002323232323232323232323232323333573466e1cd55cea8062400046666666666664444444444442466666666666600201a01801601401201000e00c00a00800600466a03803a6ae854030cd4070074d5d0a80599a80e00f1aba1500a3335502075ca03e6ae854024ccd54081d7280f9aba1500833501c02835742a00e666aa040052eb4d5d0a8031919191999ab9a3370e6aae75400920002332212330010030023232323333573466e1cd55cea8012400046644246600200600466a066eb4d5d0a801181a1aba135744a004464c6406c66ae700dc0d80d04d55cf280089baa00135742a0046464646666ae68cdc39aab9d5002480008cc8848cc00400c008cd40cdd69aba150023034357426ae8940088c98c80d8cd5ce01b81b01a09aab9e5001137540026ae84d5d1280111931901919ab9c033032030135573ca00226ea8004d5d0a80299a80e3ae35742a008666aa04004a40026ae85400cccd54081d710009aba150023027357426ae8940088c98c80b8cd5ce01781701609aba25001135744a00226ae8940044d5d1280089aba25001135744a00226ae8940044d5d1280089aba25001135744a00226aae7940044dd50009aba150023017357426ae8940088c98c8080cd5ce01081000f080f89931900f99ab9c4901035054350001f135573ca00226ea8004444888ccd54c010480054040cd54c01c480048d400488cd54054008d54024004ccd54c0104800488d4008894cd4ccd54c03048004c8cd409c88ccd400c88008008004d40048800448cc004894cd400840b040040a48d400488cc028008014018400c4cd405001000d4044004cd54c01c480048d400488c8cd5405800cc004014c8004d540a4894cd40044d5402800c884d4008894cd4cc03000802044888cc0080280104c01800c008c8004d5408888448894cd40044008884cc014008ccd54c01c480040140100044484888c00c0104484888c004010c8004d5407c8844894cd400454038884cd403cc010008cd54c01848004010004c8004d5407888448894cd40044d400c88004884ccd401488008c010008ccd54c01c4800401401000488ccd5cd19b8f00200101e01d2350012222222222220091232230023758002640026aa038446666aae7c004940288cd4024c010d5d080118019aba2002015232323333573466e1cd55cea80124000466442466002006004601a6ae854008c014d5d09aba2500223263201533573802c02a02626aae7940044dd50009191919191999ab9a3370e6aae75401120002333322221233330010050040030023232323333573466e1cd55cea80124000466442466002006004602c6ae854008cd4040054d5d09aba2500223263201a33573803603403026aae7940044dd50009aba150043335500875ca00e6ae85400cc8c8c8cccd5cd19b875001480108c84888c008010d5d09aab9e500323333573466e1d4009200223212223001004375c6ae84d55cf280211999ab9a3370ea00690001091100191931900e19ab9c01d01c01a019018135573aa00226ea8004d5d0a80119a8063ae357426ae8940088c98c8058cd5ce00b80b00a09aba25001135744a00226aae7940044dd5000899aa800bae75a224464460046eac004c8004d5406488c8cccd55cf80112804119a80399aa80498031aab9d5002300535573ca00460086ae8800c04c4d5d08008891001091091198008020018891091980080180109119191999ab9a3370ea0029000119091180100198029aba135573ca00646666ae68cdc3a801240044244002464c6402066ae700440400380344d55cea80089baa001232323333573466e1d400520062321222230040053007357426aae79400c8cccd5cd19b875002480108c848888c008014c024d5d09aab9e500423333573466e1d400d20022321222230010053007357426aae7940148cccd5cd19b875004480008c848888c00c014dd71aba135573ca00c464c6402066ae7004404003803403002c4d55cea80089baa001232323333573466e1cd55cea80124000466442466002006004600a6ae854008dd69aba135744a004464c6401866ae700340300284d55cf280089baa0012323333573466e1cd55cea800a400046eb8d5d09aab9e500223263200a33573801601401026ea80048c8c8c8c8c8cccd5cd19b8750014803084888888800c8cccd5cd19b875002480288488888880108cccd5cd19b875003480208cc8848888888cc004024020dd71aba15005375a6ae84d5d1280291999ab9a3370ea00890031199109111111198010048041bae35742a00e6eb8d5d09aba2500723333573466e1d40152004233221222222233006009008300c35742a0126eb8d5d09aba2500923333573466e1d40192002232122222223007008300d357426aae79402c8cccd5cd19b875007480008c848888888c014020c038d5d09aab9e500c23263201333573802802602202001e01c01a01801626aae7540104d55cf280189aab9e5002135573ca00226ea80048c8c8c8c8cccd5cd19b875001480088ccc888488ccc00401401000cdd69aba15004375a6ae85400cdd69aba135744a00646666ae68cdc3a80124000464244600400660106ae84d55cf280311931900619ab9c00d00c00a009135573aa00626ae8940044d55cf280089baa001232323333573466e1d400520022321223001003375c6ae84d55cf280191999ab9a3370ea004900011909118010019bae357426aae7940108c98c8024cd5ce00500480380309aab9d50011375400224464646666ae68cdc3a800a40084244400246666ae68cdc3a8012400446424446006008600c6ae84d55cf280211999ab9a3370ea00690001091100111931900519ab9c00b00a008007006135573aa00226ea80048c8cccd5cd19b8750014800880348cccd5cd19b8750024800080348c98c8018cd5ce00380300200189aab9d37540029309000a4810350543100112330010020072253350021001100612335002223335003220020020013500122001122123300100300222333573466e1c00800401000c488008488004448c8c00400488cc00cc008008005f5f6").unwrap();
+    let tx_bytes = hex::decode(MINT_TX).unwrap();
 
     let raw_inputs = hex::decode("84825820b16778c9cf065d9efeefe37ec269b4fc5107ecdbd0dd6bf3274b224165c2edd9008258206c732139de33e916342707de2aebef2252c781640326ff37b86ec99d97f1ba8d01825820975c17a4fed0051be622328efa548e206657d2b65a19224bf6ff8132571e6a500282582018f86700660fc88d0370a8f95ea58f75507e6b27a18a17925ad3b1777eb0d77600").unwrap();
     let raw_outputs = hex::decode("8482581d60b6c8794e9a7a26599440a4d0fd79cd07644d15917ff13694f1f67235821a000f8548a1581c15be994a64bdb79dde7fe080d8e7ff81b33a9e4860e9ee0d857a8e85a144576177610182581d60b6c8794e9a7a26599440a4d0fd79cd07644d15917ff13694f1f672351b00000001af14b8b482581d60b6c8794e9a7a26599440a4d0fd79cd07644d15917ff13694f1f672351a0098968082581d60b6c8794e9a7a26599440a4d0fd79cd07644d15917ff13694f1f672351a00acd8c6").unwrap();
@@ -1717,3 +1739,1000 @@ fn eval_extraneous_redeemer() {
         _ => unreachable!(),
     };
 }
+
+fn reference_script_output(address: Vec<u8>, script: Option<PlutusV2Script>) -> TransactionOutput {
+    TransactionOutput::PostAlonzo(PostAlonzoTransactionOutput {
+        address: address.into(),
+        value: Value::Coin(10_000_000),
+        datum_option: None,
+        script_ref: script.map(|script| CborWrap(Script::PlutusV2Script(script))),
+    })
+}
+
+fn plutus_v2_script(source: &str) -> PlutusV2Script {
+    let program: Program<DeBruijn> = parser::program(source).unwrap().try_into().unwrap();
+
+    PlutusV2Script(program.to_cbor().unwrap().into())
+}
+
+fn unit() -> PlutusData {
+    PlutusData::Constr(Constr {
+        tag: 121,
+        any_constructor: None,
+        fields: vec![],
+    })
+}
+
+/// Evaluate a transaction spending a single output locked by `validator`, which is only
+/// provided as a reference script, next to an unrelated reference script.
+fn eval_spend(
+    validator: &str,
+    datum_option: Option<DatumOption>,
+    witness_datums: Vec<PlutusData>,
+) -> Result<Vec<Redeemer>, Error> {
+    let redeemers = eval_spend_with_logs(validator, datum_option, witness_datums)?;
+
+    Ok(redeemers
+        .into_iter()
+        .map(|(redeemer, _logs)| redeemer)
+        .collect())
+}
+
+fn eval_spend_with_logs(
+    validator: &str,
+    datum_option: Option<DatumOption>,
+    witness_datums: Vec<PlutusData>,
+) -> Result<Vec<(Redeemer, Vec<String>)>, Error> {
+    let (tx_bytes, utxos) = spend_tx(validator, datum_option, witness_datums);
+
+    let multi_era_tx = MultiEraTx::decode(Era::Babbage, &tx_bytes).unwrap();
+
+    eval_phase_two_with_logs(
+        multi_era_tx.as_babbage().unwrap(),
+        &utxos,
+        None,
+        None,
+        &SlotConfig::default(),
+        true,
+    )
+}
+
+/// The transaction evaluated by [`eval_spend`], encoded, along with the UTxOs it needs.
+fn spend_tx(
+    validator: &str,
+    datum_option: Option<DatumOption>,
+    witness_datums: Vec<PlutusData>,
+) -> (Vec<u8>, Vec<ResolvedInput>) {
+    let validator = plutus_v2_script(validator);
+    let unrelated = plutus_v2_script("(program 1.0.0 (lam x x))");
+
+    let key_address = [vec![0x60], vec![0; 28]].concat();
+    let script_address = [vec![0x70], validator.compute_hash().to_vec()].concat();
+
+    let input = |index| TransactionInput {
+        transaction_id: Hash::from([0; 32]),
+        index,
+    };
+
+    let utxos = vec![
+        ResolvedInput {
+            input: input(0),
+            output: TransactionOutput::PostAlonzo(PostAlonzoTransactionOutput {
+                address: script_address.into(),
+                value: Value::Coin(10_000_000),
+                datum_option,
+                script_ref: None,
+            }),
+        },
+        ResolvedInput {
+            input: input(1),
+            output: reference_script_output(key_address.clone(), Some(validator)),
+        },
+        ResolvedInput {
+            input: input(2),
+            output: reference_script_output(key_address.clone(), Some(unrelated)),
+        },
+    ];
+
+    let tx = Tx {
+        transaction_body: TransactionBody {
+            inputs: vec![input(0)],
+            outputs: vec![reference_script_output(key_address, None)],
+            fee: 200_000,
+            ttl: None,
+            certificates: None,
+            withdrawals: None,
+            update: None,
+            auxiliary_data_hash: None,
+            validity_interval_start: None,
+            mint: None,
+            script_data_hash: None,
+            collateral: None,
+            required_signers: None,
+            network_id: None,
+            collateral_return: None,
+            total_collateral: None,
+            reference_inputs: Some(vec![input(1), input(2)]),
+        },
+        transaction_witness_set: WitnessSet {
+            vkeywitness: None,
+            native_script: None,
+            bootstrap_witness: None,
+            plutus_v1_script: None,
+            plutus_data: Some(witness_datums),
+            redeemer: Some(vec![Redeemer {
+                tag: RedeemerTag::Spend,
+                index: 0,
+                data: unit(),
+                ex_units: ExUnits { mem: 0, steps: 0 },
+            }]),
+            plutus_v2_script: None,
+        },
+        success: true,
+        auxiliary_data: Nullable::Null,
+    };
+
+    (tx.encode_fragment().unwrap(), utxos)
+}
+
+#[test]
+fn eval_reference_script() {
+    let redeemers = eval_spend(
+        "(program 1.0.0 (lam d (lam r (lam ctx (con unit ())))))",
+        Some(DatumOption::Data(CborWrap(unit()))),
+        vec![],
+    )
+    .unwrap();
+
+    assert_eq!(redeemers.len(), 1);
+    assert!(redeemers[0].ex_units.steps > 0);
+}
+
+#[test]
+fn eval_inline_datum() {
+    // Fails unless the datum is an integer.
+    let validator = "(program 1.0.0 (lam d (lam r (lam ctx [(builtin unIData) d]))))";
+
+    let inline = |datum| Some(DatumOption::Data(CborWrap(datum)));
+
+    // The inline datum is used, even when the witness set carries other datums.
+    assert!(eval_spend(
+        validator,
+        inline(PlutusData::BigInt(BigInt::Int(42.into()))),
+        vec![unit()]
+    )
+    .is_ok());
+
+    assert!(matches!(
+        eval_spend(validator, inline(unit()), vec![]),
+        Err(Error::RedeemerError { err, .. }) if matches!(*err, Error::Machine(..))
+    ));
+}
+
+#[test]
+fn eval_datum_hash() {
+    let validator = "(program 1.0.0 (lam d (lam r (lam ctx [(builtin unIData) d]))))";
+
+    let datum = PlutusData::BigInt(BigInt::Int(42.into()));
+    let hash = datum.compute_hash();
+
+    assert!(eval_spend(
+        validator,
+        Some(DatumOption::Hash(hash)),
+        vec![datum.clone()]
+    )
+    .is_ok());
+
+    assert!(matches!(
+        eval_spend(validator, Some(DatumOption::Hash(hash)), vec![]),
+        Err(Error::RedeemerError { err, .. }) if matches!(*err, Error::MissingRequiredDatum { .. })
+    ));
+
+    assert!(matches!(
+        eval_spend(validator, None, vec![datum]),
+        Err(Error::RedeemerError { err, .. }) if matches!(*err, Error::MissingRequiredInlineDatumOrHash)
+    ));
+}
+
+#[test]
+fn eval_with_logs() {
+    let redeemers = eval_spend_with_logs(
+        r#"(program 1.0.0
+            (lam d (lam r (lam ctx
+              [[(force (builtin trace)) (con string "hello")] (con unit ())]
+            )))
+        )"#,
+        Some(DatumOption::Data(CborWrap(unit()))),
+        vec![],
+    )
+    .unwrap();
+
+    assert_eq!(redeemers.len(), 1);
+
+    let (redeemer, logs) = &redeemers[0];
+
+    assert_eq!(logs, &vec!["hello".to_string()]);
+
+    let evaluated = EvaluatedRedeemer::new(redeemer, logs.clone());
+
+    assert_eq!(
+        serde_json::to_value(&evaluated).unwrap(),
+        serde_json::json!({
+            "redeemer": hex::encode(redeemer.encode_fragment().unwrap()),
+            "tag": "Spend",
+            "index": 0,
+            "cpu": redeemer.ex_units.steps,
+            "mem": redeemer.ex_units.mem,
+            "logs": ["hello"]
+        })
+    );
+}
+
+#[test]
+fn eval_raw_with_logs() {
+    let (tx_bytes, utxos) = spend_tx(
+        r#"(program 1.0.0
+            (lam d (lam r (lam ctx
+              [[(force (builtin trace)) (con string "hello")] (con unit ())]
+            )))
+        )"#,
+        Some(DatumOption::Data(CborWrap(unit()))),
+        vec![],
+    );
+
+    let utxos_bytes = utxos
+        .iter()
+        .map(|utxo| {
+            (
+                utxo.input.encode_fragment().unwrap(),
+                utxo.output.encode_fragment().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // Any cost model does, as long as both evaluations use the same.
+    let cost_mdls = CostMdls {
+        plutus_v1: None,
+        plutus_v2: Some(vec![1; parameter_names(&Language::PlutusV2).len()]),
+    };
+
+    let budget = ExBudget::default();
+
+    let slot_config = SlotConfig::default();
+
+    let evaluated = eval_phase_two_raw_with_logs(
+        &tx_bytes,
+        &utxos_bytes,
+        &cost_mdls.encode_fragment().unwrap(),
+        (budget.cpu as u64, budget.mem as u64),
+        (
+            slot_config.zero_time,
+            slot_config.zero_slot,
+            slot_config.slot_length,
+        ),
+        true,
+    )
+    .unwrap();
+
+    let multi_era_tx = MultiEraTx::decode(Era::Babbage, &tx_bytes).unwrap();
+
+    let redeemers = eval_phase_two_with_logs(
+        multi_era_tx.as_babbage().unwrap(),
+        &utxos,
+        Some(&cost_mdls),
+        Some(&budget),
+        &slot_config,
+        true,
+    )
+    .unwrap();
+
+    assert_eq!(evaluated.len(), 1);
+    assert_eq!(redeemers.len(), 1);
+
+    let (redeemer, logs) = &redeemers[0];
+
+    assert_eq!(
+        Redeemer::decode_fragment(&evaluated[0].redeemer).unwrap(),
+        *redeemer
+    );
+    assert_eq!(evaluated[0].logs, vec!["hello".to_string()]);
+    assert_eq!(evaluated[0], EvaluatedRedeemer::new(redeemer, logs.clone()));
+}
+
+struct Garbage {
+    expect_success: bool,
+}
+
+impl Generator for Garbage {
+    fn generate(&mut self, seed: u64) -> Case {
+        Case {
+            tx: seed.to_be_bytes().to_vec(),
+            utxos: vec![],
+            expect_success: self.expect_success,
+        }
+    }
+}
+
+#[test]
+fn property_check_reports_counterexamples() {
+    let settings = Settings {
+        cost_mdls: None,
+        initial_budget: None,
+        slot_config: &SlotConfig::default(),
+    };
+
+    let counterexamples = property::check(
+        &mut Garbage {
+            expect_success: false,
+        },
+        0,
+        10,
+        &settings,
+    );
+
+    assert!(counterexamples.is_empty());
+
+    let counterexamples = property::check(
+        &mut Garbage {
+            expect_success: true,
+        },
+        42,
+        3,
+        &settings,
+    );
+
+    assert_eq!(
+        counterexamples.iter().map(|c| c.seed).collect::<Vec<_>>(),
+        vec![42, 43, 44]
+    );
+
+    assert!(counterexamples.iter().all(|c| c.error.is_some()));
+}
+
+#[test]
+fn property_counterexample_roundtrip() {
+    let counterexample = Counterexample {
+        seed: 14,
+        case: Case {
+            tx: vec![0x84, 0xa0],
+            utxos: vec![property::Utxo {
+                input: vec![0x82, 0x00],
+                output: vec![0xff],
+            }],
+            expect_success: true,
+        },
+        error: Some("boom".to_string()),
+    };
+
+    let json = serde_json::to_value(&counterexample).unwrap();
+
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "seed": 14,
+            "case": {
+                "tx": "84a0",
+                "utxos": [{ "input": "8200", "output": "ff" }],
+                "expect_success": true
+            },
+            "error": "boom"
+        })
+    );
+
+    assert_eq!(
+        serde_json::from_value::<Counterexample>(json).unwrap(),
+        counterexample
+    );
+}
+
+#[test]
+fn diagnostic_cbor_of_nested_data() {
+    let constr = |index: u64, fields: Vec<PlutusData>| {
+        let tag = crate::machine::runtime::convert_constr_to_tag(index);
+
+        PlutusData::Constr(Constr {
+            tag: tag.unwrap_or(crate::machine::runtime::ANY_TAG),
+            any_constructor: tag.map_or(Some(index), |_| None),
+            fields,
+        })
+    };
+
+    let int = |i: i64| PlutusData::BigInt(BigInt::Int(i.into()));
+
+    let bytes = |b: &[u8]| PlutusData::BoundedBytes(b.to_vec().into());
+
+    assert_eq!(
+        to_diagnostic_cbor(&constr(0, vec![int(42), bytes(&[0xde, 0xad, 0xbe, 0xef])])),
+        "121([_ 42, h'deadbeef'])"
+    );
+
+    assert_eq!(
+        to_diagnostic_cbor(&constr(
+            1,
+            vec![PlutusData::Map(
+                vec![
+                    (bytes(&[0]), PlutusData::Array(vec![])),
+                    (int(1), constr(7, vec![])),
+                ]
+                .into()
+            )]
+        )),
+        "122([_ {h'00': [], 1: 1280([])}])"
+    );
+
+    assert_eq!(
+        to_diagnostic_cbor(&constr(
+            200,
+            vec![PlutusData::Array(vec![int(-1), constr(2, vec![])])]
+        )),
+        "102([200, [_ [_ -1, 123([])]]])"
+    );
+}
+
+#[test]
+fn plutus_data_json_roundtrip() {
+    let fixtures = [
+        serde_json::json!({ "int": -42 }),
+        serde_json::json!({ "int": u64::MAX }),
+        serde_json::json!({ "bytes": "deadbeef" }),
+        serde_json::json!({ "list": [{ "int": 1 }, { "bytes": "" }] }),
+        serde_json::json!({
+            "constructor": 1,
+            "fields": [
+                {
+                    "map": [
+                        { "k": { "bytes": "00" }, "v": { "list": [] } },
+                        { "k": { "int": 1 }, "v": { "constructor": 7, "fields": [] } }
+                    ]
+                },
+                {
+                    "constructor": 200,
+                    "fields": [{ "constructor": 0, "fields": [{ "int": 14 }] }]
+                }
+            ]
+        }),
+    ];
+
+    for json in fixtures {
+        let data = from_json(&json).unwrap();
+
+        assert_eq!(to_json(&data).unwrap(), json);
+
+        assert_eq!(
+            PlutusData::decode_fragment(&data.encode_fragment().unwrap()).unwrap(),
+            data
+        );
+    }
+
+    assert_eq!(
+        to_diagnostic_cbor(
+            &from_json(&serde_json::json!({
+                "constructor": 0,
+                "fields": [{ "int": 42 }, { "bytes": "deadbeef" }]
+            }))
+            .unwrap()
+        ),
+        "121([_ 42, h'deadbeef'])"
+    );
+
+    for malformed in [
+        serde_json::json!(42),
+        serde_json::json!({ "int": "42" }),
+        serde_json::json!({ "bytes": "xyz" }),
+        serde_json::json!({ "int": 1, "bytes": "00" }),
+        serde_json::json!({ "map": [{ "k": { "int": 1 } }] }),
+        serde_json::json!({ "constructor": -1, "fields": [] }),
+    ] {
+        assert!(matches!(
+            from_json(&malformed),
+            Err(Error::MalformedJsonData(..))
+        ));
+    }
+}
+
+#[test]
+fn resolved_inputs_from_cli_json() {
+    let address = pallas_addresses::Address::from_bytes(
+        &hex::decode("60b6c8794e9a7a26599440a4d0fd79cd07644d15917ff13694f1f67235").unwrap(),
+    )
+    .unwrap();
+
+    let policy_id = "c4f241450001af08f3ddbaf9335db79883cbcd81071b8e3508de3055";
+
+    let json = serde_json::json!({
+        format!("{}#1", "11".repeat(32)): {
+            "address": address.to_bech32().unwrap(),
+            "datum": null,
+            "datumhash": null,
+            "inlineDatum": { "constructor": 0, "fields": [{ "int": 42 }] },
+            "referenceScript": {
+                "script": {
+                    "cborHex": "4746010000222601",
+                    "description": "",
+                    "type": "PlutusScriptV2"
+                },
+                "scriptLanguage": "PlutusScriptLanguage PlutusScriptV2"
+            },
+            "value": { "lovelace": 2000000, policy_id: { "546f6b656e": 5 } }
+        },
+        format!("{}#0", "22".repeat(32)): {
+            "address": address.to_bech32().unwrap(),
+            "datumhash": "ff".repeat(32),
+            "inlineDatum": null,
+            "referenceScript": null,
+            "value": { "lovelace": 1000000 }
+        },
+        format!("{}#3", "33".repeat(32)): {
+            "address": address.to_bech32().unwrap(),
+            "inlineDatumRaw": "d8799f182aff",
+            "value": { "lovelace": 1000000 }
+        }
+    });
+
+    let utxos = ResolvedInput::from_cli_json(&json).unwrap();
+
+    let output = |address: &pallas_addresses::Address, value, datum_option, script_ref| {
+        TransactionOutput::PostAlonzo(PostAlonzoTransactionOutput {
+            address: address.to_vec().into(),
+            value,
+            datum_option,
+            script_ref,
+        })
+    };
+
+    let datum =
+        from_json(&serde_json::json!({ "constructor": 0, "fields": [{ "int": 42 }] })).unwrap();
+
+    assert_eq!(
+        utxos,
+        vec![
+            ResolvedInput {
+                input: TransactionInput {
+                    transaction_id: Hash::from([0x11; 32]),
+                    index: 1,
+                },
+                output: output(
+                    &address,
+                    Value::Multiasset(
+                        2000000,
+                        vec![(
+                            policy_id.parse().unwrap(),
+                            vec![(b"Token".to_vec().into(), 5)].into(),
+                        )]
+                        .into(),
+                    ),
+                    Some(DatumOption::Data(CborWrap(datum.clone()))),
+                    Some(CborWrap(Script::PlutusV2Script(PlutusV2Script(
+                        hex::decode("46010000222601").unwrap().into(),
+                    )))),
+                ),
+            },
+            ResolvedInput {
+                input: TransactionInput {
+                    transaction_id: Hash::from([0x22; 32]),
+                    index: 0,
+                },
+                output: output(
+                    &address,
+                    Value::Coin(1000000),
+                    Some(DatumOption::Hash(Hash::from([0xff; 32]))),
+                    None,
+                ),
+            },
+            ResolvedInput {
+                input: TransactionInput {
+                    transaction_id: Hash::from([0x33; 32]),
+                    index: 3,
+                },
+                output: output(
+                    &address,
+                    Value::Coin(1000000),
+                    Some(DatumOption::Data(CborWrap(datum))),
+                    None,
+                ),
+            },
+        ]
+    );
+
+    for malformed in [
+        serde_json::json!([]),
+        serde_json::json!({ "nothash#0": { "address": address.to_bech32().unwrap(), "value": { "lovelace": 1 } } }),
+        serde_json::json!({ format!("{}#0", "11".repeat(32)): { "address": address.to_bech32().unwrap(), "value": {} } }),
+    ] {
+        assert!(matches!(
+            ResolvedInput::from_cli_json(&malformed),
+            Err(Error::MalformedUtxoJson(..))
+        ));
+    }
+}
+
+#[test]
+fn script_context_builder_spend() {
+    let out_ref = TransactionInput {
+        transaction_id: Hash::from([1; 32]),
+        index: 0,
+    };
+
+    let context = ScriptContextBuilder::spend(out_ref.clone())
+        .with_input(ResolvedInput {
+            input: out_ref.clone(),
+            output: reference_script_output(vec![0x70; 29], None),
+        })
+        .with_output(reference_script_output(vec![0x60; 29], None))
+        .build();
+
+    match &context {
+        PlutusData::Constr(Constr { tag, fields, .. }) => {
+            assert_eq!(*tag, 121);
+            assert_eq!(
+                fields[1],
+                PlutusData::Constr(Constr {
+                    tag: 122,
+                    any_constructor: None,
+                    fields: vec![out_ref.to_plutus_data()],
+                })
+            );
+        }
+        _ => panic!("expected a constructor, got {context:?}"),
+    }
+
+    let program: Program<DeBruijn> =
+        parser::program("(program 1.0.0 (lam d (lam r (lam ctx (con unit ())))))")
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+    let (result, _, _) = program
+        .apply_data(unit())
+        .apply_data(unit())
+        .apply_data(context)
+        .eval(ExBudget::default());
+
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn script_hash_is_policy_id() {
+    let tx_bytes = hex::decode(MINT_TX).unwrap();
+
+    let tx = Tx::decode_fragment(&tx_bytes).unwrap();
+
+    let script = &tx.transaction_witness_set.plutus_v2_script.unwrap()[0];
+
+    let program = Program::<DeBruijn>::from_cbor(&script.0, &mut Vec::new()).unwrap();
+
+    assert_eq!(
+        program.hash().to_string(),
+        "c4f241450001af08f3ddbaf9335db79883cbcd81071b8e3508de3055"
+    );
+    assert_eq!(
+        program.hash_bech32(),
+        "script1cneyz3gqqxhs3u7ahtunxhdhnzpuhnvpqudcudggmcc92ctx0x3"
+    );
+    assert_eq!(
+        script_hash_from_bech32(&program.hash_bech32()),
+        Some(program.hash())
+    );
+    assert_eq!(script_hash_from_bech32("addr1vxzq"), None);
+}
+
+#[test]
+fn script_fee_of_known_tx() {
+    let tx_bytes = hex::decode(MINT_TX).unwrap();
+
+    let tx = Tx::decode_fragment(&tx_bytes).unwrap();
+
+    let redeemers = tx.transaction_witness_set.redeemer.unwrap();
+
+    // priceMem and priceSteps of the preview network, also used on mainnet.
+    let fee = script_fee(&redeemers, (577, 10_000), (721, 10_000_000));
+
+    assert_eq!(fee, 58_800);
+
+    // The transaction pays exactly the minimum fee, i.e. minFeeA * size + minFeeB + script fee.
+    assert_eq!(
+        44 * tx_bytes.len() as u64 + 155_381 + fee,
+        tx.transaction_body.fee
+    );
+}
+
+#[test]
+fn slot_posix_roundtrip() {
+    for slot_config in [
+        SlotConfig::mainnet(),
+        SlotConfig::preprod(),
+        SlotConfig::preview(),
+    ] {
+        for slot in [0, 1, 42, 86_400, 4_492_800, 100_000_000] {
+            let slot = slot_config.zero_slot + slot;
+            let posix = slot_config.slot_to_posix(slot);
+
+            assert_eq!(slot_config.posix_to_slot(posix), slot);
+            assert_eq!(slot_config.posix_to_slot(posix + 999), slot);
+            assert_eq!(slot_config.posix_to_slot(posix + 1000), slot + 1);
+        }
+
+        assert_eq!(
+            slot_config.slot_to_posix(slot_config.zero_slot),
+            slot_config.zero_time
+        );
+        assert_eq!(slot_config.posix_to_slot(0), slot_config.zero_slot);
+    }
+
+    // Mainnet slot 4924800 began on 2020-08-03 at 21:44:51 UTC, one epoch after Shelley.
+    assert_eq!(
+        SlotConfig::mainnet().slot_to_posix(4_924_800),
+        1_596_491_091_000
+    );
+    assert_eq!(SlotConfig::mainnet().slot_to_posix(0), 1_596_059_091_000);
+}
+
+/// A transaction spending one output locked by each of `validators`, provided as witnesses.
+fn spend_many(validators: &[&str]) -> (Vec<u8>, Vec<ResolvedInput>) {
+    let input = |index| TransactionInput {
+        transaction_id: Hash::from([0; 32]),
+        index,
+    };
+
+    let key_address = [vec![0x60], vec![0; 28]].concat();
+
+    let scripts: Vec<PlutusV2Script> = validators.iter().map(|v| plutus_v2_script(v)).collect();
+
+    let utxos: Vec<ResolvedInput> = scripts
+        .iter()
+        .enumerate()
+        .map(|(index, script)| ResolvedInput {
+            input: input(index as u64),
+            output: TransactionOutput::PostAlonzo(PostAlonzoTransactionOutput {
+                address: [vec![0x70], script.compute_hash().to_vec()].concat().into(),
+                value: Value::Coin(10_000_000),
+                datum_option: Some(DatumOption::Data(CborWrap(unit()))),
+                script_ref: None,
+            }),
+        })
+        .collect();
+
+    let tx = Tx {
+        transaction_body: TransactionBody {
+            inputs: utxos.iter().map(|utxo| utxo.input.clone()).collect(),
+            outputs: vec![reference_script_output(key_address, None)],
+            fee: 200_000,
+            ttl: None,
+            certificates: None,
+            withdrawals: None,
+            update: None,
+            auxiliary_data_hash: None,
+            validity_interval_start: None,
+            mint: None,
+            script_data_hash: None,
+            collateral: None,
+            required_signers: None,
+            network_id: None,
+            collateral_return: None,
+            total_collateral: None,
+            reference_inputs: None,
+        },
+        transaction_witness_set: WitnessSet {
+            vkeywitness: None,
+            native_script: None,
+            bootstrap_witness: None,
+            plutus_v1_script: None,
+            plutus_data: None,
+            redeemer: Some(
+                (0..validators.len())
+                    .map(|index| Redeemer {
+                        tag: RedeemerTag::Spend,
+                        index: index as u32,
+                        data: unit(),
+                        ex_units: ExUnits { mem: 0, steps: 0 },
+                    })
+                    .collect(),
+            ),
+            plutus_v2_script: Some(scripts),
+        },
+        success: true,
+        auxiliary_data: Nullable::Null,
+    };
+
+    (tx.encode_fragment().unwrap(), utxos)
+}
+
+#[test]
+fn eval_redeemers_in_parallel() {
+    let validator = |n| {
+        format!(
+            r#"(program 1.0.0
+                (lam d (lam r (lam ctx
+                  [(force (builtin trace)) (con string "{n}") [(builtin addInteger) (con integer {n}) (con integer {n})]]))))"#
+        )
+    };
+
+    let failing = |n| {
+        format!(
+            r#"(program 1.0.0 (lam d (lam r (lam ctx [(force (builtin trace)) (con string "{n}") (error)]))))"#
+        )
+    };
+
+    let eval = |validators: &[String], budget: &ExBudget| {
+        let validators: Vec<&str> = validators.iter().map(String::as_str).collect();
+
+        let (tx_bytes, utxos) = spend_many(&validators);
+
+        let multi_era_tx = MultiEraTx::decode(Era::Babbage, &tx_bytes).unwrap();
+
+        let tx = multi_era_tx.as_babbage().unwrap();
+
+        let parallel =
+            eval_phase_two_with_logs(tx, &utxos, None, Some(budget), &SlotConfig::default(), true);
+
+        let lookup_table = get_script_and_datum_lookup_table(tx, &utxos);
+
+        let mut remaining_budget = *budget;
+
+        let sequential = tx
+            .transaction_witness_set
+            .redeemer
+            .iter()
+            .flatten()
+            .map(|redeemer| {
+                let (redeemer, logs) = eval_redeemer(
+                    tx,
+                    &utxos,
+                    &SlotConfig::default(),
+                    redeemer,
+                    &lookup_table,
+                    None,
+                    &remaining_budget,
+                )?;
+
+                remaining_budget.cpu -= redeemer.ex_units.steps as i64;
+                remaining_budget.mem -= redeemer.ex_units.mem as i64;
+
+                Ok((redeemer, logs))
+            })
+            .collect::<Result<Vec<_>, Error>>();
+
+        (parallel, sequential)
+    };
+
+    let validators = vec![validator(1), validator(2), validator(3)];
+
+    let (parallel, sequential) = eval(&validators, &ExBudget::default());
+
+    let parallel = parallel.unwrap();
+
+    assert_eq!(parallel, sequential.unwrap());
+    assert_eq!(
+        parallel
+            .iter()
+            .map(|(redeemer, logs)| (redeemer.index, logs.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (0, vec!["1".to_string()]),
+            (1, vec!["2".to_string()]),
+            (2, vec!["3".to_string()]),
+        ]
+    );
+
+    // Only enough budget for the first two scripts.
+    let budget = ExBudget {
+        cpu: parallel[..2]
+            .iter()
+            .map(|(redeemer, _)| redeemer.ex_units.steps as i64)
+            .sum::<i64>(),
+        mem: ExBudget::default().mem,
+    };
+
+    let (parallel, sequential) = eval(&validators, &budget);
+
+    assert!(matches!(
+        parallel,
+        Err(Error::RedeemerError { index: 2, .. })
+    ));
+    assert_eq!(
+        parallel.unwrap_err().to_string(),
+        sequential.unwrap_err().to_string()
+    );
+
+    // The first failing script, in redeemers order, is the one reported.
+    let validators = vec![validator(1), failing(2), failing(3)];
+
+    let (parallel, sequential) = eval(&validators, &ExBudget::default());
+
+    assert!(matches!(
+        parallel,
+        Err(Error::RedeemerError { index: 1, .. })
+    ));
+    assert_eq!(
+        parallel.unwrap_err().to_string(),
+        sequential.unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn budget_and_collateral_checks() {
+    let validator = "(program 1.0.0 (lam d (lam r (lam ctx (con unit ())))))";
+
+    let (tx_bytes, mut utxos) = spend_many(&[validator, validator]);
+
+    let key_address = [vec![0x60], vec![0; 28]].concat();
+
+    let collateral = ResolvedInput {
+        input: TransactionInput {
+            transaction_id: Hash::from([1; 32]),
+            index: 0,
+        },
+        output: reference_script_output(key_address.clone(), None),
+    };
+
+    utxos.push(collateral.clone());
+
+    let check = |returned: u64, max_budget: &ExBudget| {
+        let mut tx = Tx::decode_fragment(&tx_bytes).unwrap();
+        tx.transaction_body.collateral = Some(vec![collateral.input.clone()]);
+        tx.transaction_body.collateral_return =
+            Some(TransactionOutput::PostAlonzo(PostAlonzoTransactionOutput {
+                address: key_address.clone().into(),
+                value: Value::Coin(returned),
+                datum_option: None,
+                script_ref: None,
+            }));
+
+        let tx_bytes = tx.encode_fragment().unwrap();
+        let multi_era_tx = MultiEraTx::decode(Era::Babbage, &tx_bytes).unwrap();
+        let tx = multi_era_tx.as_babbage().unwrap();
+
+        let redeemers =
+            eval_phase_two(tx, &utxos, None, None, &SlotConfig::default(), true).unwrap();
+
+        check_budget_and_collateral(tx, &utxos, &redeemers, max_budget, 150)
+    };
+
+    // The reference output holds 10 ADA. The transaction declares a fee of 0.2 ADA, so 0.3
+    // ADA of collateral is needed.
+    assert!(check(9_700_000, &ExBudget::default()).is_ok());
+
+    assert!(matches!(
+        check(9_700_001, &ExBudget::default()),
+        Err(Error::InsufficientCollateral {
+            provided: 299_999,
+            required: 300_000
+        })
+    ));
+
+    // That's the whole fee, not only the part paying for scripts, which 0.01 ADA would cover.
+    let tx = MultiEraTx::decode(Era::Babbage, &tx_bytes).unwrap();
+    let redeemers = eval_phase_two(
+        tx.as_babbage().unwrap(),
+        &utxos,
+        None,
+        None,
+        &SlotConfig::default(),
+        true,
+    )
+    .unwrap();
+    assert!(script_fee(&redeemers, (577, 10_000), (721, 10_000_000)) * 3 / 2 < 10_000);
+
+    assert!(matches!(
+        check(9_990_000, &ExBudget::default()),
+        Err(Error::InsufficientCollateral {
+            provided: 10_000,
+            ..
+        })
+    ));
+
+    assert!(matches!(
+        check(10_000_000, &ExBudget::default()),
+        Err(Error::InsufficientCollateral { provided: 0, required }) if required > 0
+    ));
+
+    let tight = ExBudget {
+        mem: 100,
+        cpu: 10_000_000,
+    };
+
+    assert!(matches!(
+        check(9_700_000, &tight),
+        Err(Error::OverBudget { spent, max }) if spent.mem > 100 && max == tight
+    ));
+}
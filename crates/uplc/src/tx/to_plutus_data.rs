@@ -8,8 +8,13 @@ use pallas_primitives::babbage::{
 };
 use pallas_traverse::ComputeHash;
 
-use crate::machine::runtime::{convert_constr_to_tag, ANY_TAG};
+pub use crate::ast::to_diagnostic_cbor;
+use crate::machine::{
+    from_pallas_bigint,
+    runtime::{convert_constr_to_tag, convert_tag_to_constr, ANY_TAG},
+};
 
+use super::error::Error;
 use super::script_context::{ScriptContext, ScriptPurpose, TimeRange, TxInInfo, TxInfo, TxOut};
 
 fn wrap_with_constr(index: u64, data: PlutusData) -> PlutusData {
@@ -620,3 +625,119 @@ impl ToPlutusData for bool {
         }
     }
 }
+
+/// Read some `Data` from the "detailed" JSON schema used by cardano-cli, e.g.
+/// `{"constructor": 0, "fields": [{"int": 42}, {"bytes": "deadbeef"}]}`. Maps are given as
+/// `{"map": [{"k": .., "v": ..}]}` and lists as `{"list": [..]}`.
+pub fn from_json(json: &serde_json::Value) -> Result<PlutusData, Error> {
+    let malformed = |reason: &str| Error::MalformedJsonData(format!("{reason} in {json}"));
+
+    let object = json
+        .as_object()
+        .ok_or_else(|| malformed("expected an object"))?;
+
+    let keys: Vec<&str> = object.keys().map(String::as_str).collect();
+
+    let list = |key: &str| {
+        object[key]
+            .as_array()
+            .ok_or_else(|| malformed("expected an array"))
+    };
+
+    match keys[..] {
+        ["int"] => {
+            let n = &object["int"];
+
+            let n: Int = match (n.as_i64(), n.as_u64()) {
+                (Some(n), _) => n.into(),
+                (None, Some(n)) => Int::try_from(i128::from(n))
+                    .map_err(|_| malformed("expected a 64-bit integer"))?,
+                _ => return Err(malformed("expected an integer")),
+            };
+
+            Ok(PlutusData::BigInt(BigInt::Int(n)))
+        }
+        ["bytes"] => {
+            let bytes = object["bytes"]
+                .as_str()
+                .and_then(|bytes| hex::decode(bytes).ok())
+                .ok_or_else(|| malformed("expected a base16-encoded bytestring"))?;
+
+            Ok(PlutusData::BoundedBytes(bytes.into()))
+        }
+        ["list"] => Ok(PlutusData::Array(
+            list("list")?
+                .iter()
+                .map(from_json)
+                .collect::<Result<_, _>>()?,
+        )),
+        ["map"] => {
+            let entries = list("map")?
+                .iter()
+                .map(|entry| match (entry.get("k"), entry.get("v")) {
+                    (Some(k), Some(v)) if entry.as_object().map(|o| o.len()) == Some(2) => {
+                        Ok((from_json(k)?, from_json(v)?))
+                    }
+                    _ => Err(malformed("expected map entries with a 'k' and a 'v'")),
+                })
+                .collect::<Result<_, _>>()?;
+
+            Ok(PlutusData::Map(KeyValuePairs::Def(entries)))
+        }
+        ["constructor", "fields"] | ["fields", "constructor"] => {
+            let index = object["constructor"]
+                .as_u64()
+                .ok_or_else(|| malformed("expected a non-negative constructor index"))?;
+
+            let fields = list("fields")?
+                .iter()
+                .map(from_json)
+                .collect::<Result<_, _>>()?;
+
+            Ok(wrap_multiple_with_constr(index, fields))
+        }
+        _ => Err(malformed(
+            "expected one of 'int', 'bytes', 'list', 'map' or 'constructor' and 'fields'",
+        )),
+    }
+}
+
+/// Write some `Data` in the "detailed" JSON schema used by cardano-cli; the inverse of
+/// [`from_json`]. Fails on integers beyond 64 bits, which JSON numbers can't hold here.
+pub fn to_json(data: &PlutusData) -> Result<serde_json::Value, Error> {
+    let list = |items: &[PlutusData]| items.iter().map(to_json).collect::<Result<Vec<_>, _>>();
+
+    Ok(match data {
+        PlutusData::Constr(Constr {
+            tag,
+            any_constructor,
+            fields,
+        }) => serde_json::json!({
+            "constructor": convert_tag_to_constr(*tag).or(*any_constructor),
+            "fields": list(fields)?,
+        }),
+        PlutusData::Map(entries) => serde_json::json!({
+            "map": entries
+                .iter()
+                .map(|(k, v)| Ok(serde_json::json!({ "k": to_json(k)?, "v": to_json(v)? })))
+                .collect::<Result<Vec<_>, Error>>()?,
+        }),
+        PlutusData::BigInt(n) => {
+            let n = from_pallas_bigint(n);
+
+            let n: serde_json::Number = match (i64::try_from(&n), u64::try_from(&n)) {
+                (Ok(n), _) => n.into(),
+                (_, Ok(n)) => n.into(),
+                _ => {
+                    return Err(Error::MalformedJsonData(format!(
+                        "integer {n} doesn't fit in 64 bits"
+                    )))
+                }
+            };
+
+            serde_json::json!({ "int": n })
+        }
+        PlutusData::BoundedBytes(bytes) => serde_json::json!({ "bytes": hex::encode(&**bytes) }),
+        PlutusData::Array(items) => serde_json::json!({ "list": list(items)? }),
+    })
+}